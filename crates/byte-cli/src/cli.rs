@@ -82,6 +82,21 @@ pub enum CollectionCommands {
         )]
         project_dir: Option<String>,
     },
+    #[clap(
+        action,
+        about = "Upgrades the configuration file to the current schema version, keeping a backup of the original"
+    )]
+    Migrate {
+        #[clap(help = "The name of the NFT collection")]
+        name: String,
+        #[clap(
+            short,
+            long,
+            action,
+            help = "The path to the project directory (defaults to the Home directory)"
+        )]
+        project_dir: Option<String>,
+    },
 }
 
 /// Enum representing specific Image Upload-related commands.
@@ -124,7 +139,7 @@ pub enum ClientCommands {
         #[clap(help = "The name of the NFT collection")]
         name: String,
         #[clap(
-            help = "Define the network environment: 'testnet' or 'mainnet'"
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
         )]
         network: String,
         #[clap(
@@ -141,6 +156,12 @@ pub enum ClientCommands {
         /// Gas budget for running module initializers
         #[clap(help = "Gas limit for the transaction in MIST")]
         gas_budget: Option<usize>,
+        #[clap(
+            long = "rpc-url",
+            action,
+            help = "Fallback RPC endpoint, tried in order after the active Sui environment. May be given several times"
+        )]
+        rpc_urls: Vec<String>,
     },
     #[clap(
         action,
@@ -150,7 +171,7 @@ pub enum ClientCommands {
         #[clap(help = "The name of the NFT collection")]
         name: String,
         #[clap(
-            help = "Define the network environment: 'testnet' or 'mainnet'"
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
         )]
         network: String,
         #[clap(
@@ -173,7 +194,7 @@ pub enum ClientCommands {
         #[clap(help = "The name of the NFT collection")]
         name: String,
         #[clap(
-            help = "Define the network environment: 'testnet' or 'mainnet'"
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
         )]
         network: String,
         #[clap(
@@ -213,6 +234,161 @@ pub enum ClientCommands {
             help = "Object ID of the MintCap object of the Collection"
         )]
         mint_cap_id: Option<String>,
+        #[clap(
+            long = "rpc-url",
+            action,
+            help = "Fallback RPC endpoint, tried in order after the active Sui environment. May be given several times"
+        )]
+        rpc_urls: Vec<String>,
+    },
+    #[clap(
+        action,
+        about = "Rebuilds the NFT contract and verifies it and its dependencies against the published package"
+    )]
+    VerifySource {
+        #[clap(help = "The name of the NFT collection")]
+        name: String,
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+        #[clap(
+            short,
+            long,
+            action,
+            help = "The path to the project directory (defaults to the Home directory)"
+        )]
+        project_dir: Option<String>,
+    },
+    #[clap(
+        action,
+        about = "Rebuilds the project state of a published collection from the objects on-chain"
+    )]
+    Sync {
+        #[clap(help = "The name of the NFT collection")]
+        name: String,
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+        #[clap(
+            long = "package",
+            action,
+            help = "Object ID of the published package (defaults to the one of the project state)"
+        )]
+        package_id: Option<String>,
+        #[clap(
+            short,
+            long,
+            action,
+            help = "The path to the project directory (defaults to the Home directory)"
+        )]
+        project_dir: Option<String>,
+        #[clap(
+            short,
+            long,
+            action,
+            help = "Overwrites the project state without asking for confirmation"
+        )]
+        yes: bool,
+        #[clap(
+            long = "rpc-url",
+            action,
+            help = "Fallback RPC endpoint, tried in order after the active Sui environment. May be given several times"
+        )]
+        rpc_urls: Vec<String>,
+    },
+    #[clap(
+        action,
+        about = "Hands over the admin objects of the NFT collection to another address, such as a multisig"
+    )]
+    TransferAdmin {
+        #[clap(help = "The name of the NFT collection")]
+        name: String,
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+        #[clap(long, action, help = "Address of the new custodian")]
+        to: String,
+        #[clap(
+            long = "cap",
+            action,
+            help = "Admin object to hand over: 'mint-cap', 'transfer-policy-cap', 'withdraw-policy-cap', 'borrow-policy-cap', 'publisher' or 'upgrade-cap'. May be given several times, defaults to all of them"
+        )]
+        caps: Vec<String>,
+        #[clap(
+            long,
+            action,
+            help = "Restricts the upgrade policy of the package beforehand: 'additive', 'dep-only' or 'immutable', which destroys the UpgradeCap"
+        )]
+        upgrade_policy: Option<String>,
+        #[clap(
+            short,
+            long,
+            action,
+            help = "The path to the project directory (defaults to the Home directory)"
+        )]
+        project_dir: Option<String>,
+        #[clap(
+            long,
+            action,
+            help = "Object ID of the Coin you would like to use to pay gas"
+        )]
+        gas_coin: Option<String>,
+        #[clap(long, action, help = "Gas limit for the transaction in MIST")]
+        gas_budget: Option<usize>,
+        #[clap(
+            long = "rpc-url",
+            action,
+            help = "Fallback RPC endpoint, tried in order after the active Sui environment. May be given several times"
+        )]
+        rpc_urls: Vec<String>,
+    },
+    #[clap(
+        action,
+        about = "Creates a listing of the NFT collection on the launchpad, along with its venues, from the launchpad config"
+    )]
+    CreateListing {
+        #[clap(help = "The name of the NFT collection")]
+        name: String,
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+        #[clap(
+            short,
+            long,
+            action,
+            help = "The path to the project directory (defaults to the Home directory)"
+        )]
+        project_dir: Option<String>,
+        #[clap(
+            long,
+            action,
+            help = "Object ID of the warehouse sold by the listing (defaults to the last one of the project state)"
+        )]
+        warehouse_id: Option<String>,
+        #[clap(
+            long,
+            action,
+            help = "Turns the sale on in every venue of the listing"
+        )]
+        start_sale: bool,
+        #[clap(
+            long,
+            action,
+            help = "Object ID of the Coin you would like to use to pay gas"
+        )]
+        gas_coin: Option<String>,
+        #[clap(long, action, help = "Gas limit for the transaction in MIST")]
+        gas_budget: Option<usize>,
+        #[clap(
+            long = "rpc-url",
+            action,
+            help = "Fallback RPC endpoint, tried in order after the active Sui environment. May be given several times"
+        )]
+        rpc_urls: Vec<String>,
     },
 }
 
@@ -272,7 +448,99 @@ pub enum MoveCommands {
         #[clap(help = "The name of the NFT collection")]
         name: String,
         #[clap(
-            help = "Define the network environment: 'testnet' or 'mainnet'"
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+        #[clap(
+            short,
+            long,
+            action,
+            help = "The path to the project directory (defaults to the Home directory)"
+        )]
+        project_dir: Option<String>,
+    },
+
+    #[clap(
+        action,
+        about = "Installs OriginByte and Sui dependencies and writes byte.lock"
+    )]
+    Install {
+        #[clap(help = "The name of the NFT collection")]
+        name: String,
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+        #[clap(
+            short,
+            long,
+            action,
+            help = "The path to the project directory (defaults to the Home directory)"
+        )]
+        project_dir: Option<String>,
+        #[clap(
+            long,
+            action,
+            help = "Restore the dependencies recorded in byte.lock instead of resolving them"
+        )]
+        locked: bool,
+    },
+
+    #[clap(
+        action,
+        about = "Adds an OriginByte package as a dependency along with its named address"
+    )]
+    Add {
+        #[clap(help = "The name of the NFT collection")]
+        name: String,
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+        #[clap(
+            help = "The package to add, optionally with a version, e.g. 'Launchpad' or 'Launchpad@1.2.0'"
+        )]
+        package: String,
+        #[clap(
+            short,
+            long,
+            action,
+            help = "The path to the project directory (defaults to the Home directory)"
+        )]
+        project_dir: Option<String>,
+    },
+
+    #[clap(
+        action,
+        about = "Removes a dependency along with its named address"
+    )]
+    Remove {
+        #[clap(help = "The name of the NFT collection")]
+        name: String,
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+        #[clap(help = "The package to remove, e.g. 'Launchpad'")]
+        package: String,
+        #[clap(
+            short,
+            long,
+            action,
+            help = "The path to the project directory (defaults to the Home directory)"
+        )]
+        project_dir: Option<String>,
+    },
+
+    #[clap(
+        action,
+        about = "Lists dependencies with their current, latest and allowed versions"
+    )]
+    List {
+        #[clap(help = "The name of the NFT collection")]
+        name: String,
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
         )]
         network: String,
         #[clap(
@@ -289,7 +557,7 @@ pub enum MoveCommands {
         #[clap(help = "The name of the NFT collection")]
         name: String,
         #[clap(
-            help = "Define the network environment: 'testnet' or 'mainnet'"
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
         )]
         network: String,
         #[clap(
@@ -303,11 +571,11 @@ pub enum MoveCommands {
 
     #[clap(
         action,
-        about = "Loads the dependencies for the Mainnet or Testnet environment"
+        about = "Switches Move.toml to the manifest of the Mainnet or Testnet environment"
     )]
     LoadEnv {
         #[clap(
-            help = "Define the network environment: 'testnet' or 'mainnet'"
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
         )]
         network: String,
         #[clap(
@@ -316,6 +584,114 @@ pub enum MoveCommands {
         name: Option<String>,
         #[clap(short, long, action)]
         project_dir: Option<String>,
+        #[clap(
+            long,
+            action,
+            help = "Discard changes of Move.toml that are not in the manifest of its network"
+        )]
+        force: bool,
+    },
+
+    #[clap(
+        action,
+        about = "Cross-checks Move.toml, Move.lock, BuildInfo.yaml and the program registry"
+    )]
+    Doctor {
+        #[clap(help = "The name of the NFT collection")]
+        name: String,
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+        #[clap(
+            short,
+            long,
+            action,
+            help = "The path to the project directory (defaults to the Home directory)"
+        )]
+        project_dir: Option<String>,
+    },
+
+    #[clap(
+        action,
+        about = "Checks the packages referenced by the program registry against their on-chain objects"
+    )]
+    VerifyRegistry {
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+    },
+
+    #[clap(about = "Manages the private program registry")]
+    Registry {
+        #[clap(subcommand)]
+        cmd: RegistryCommands,
+    },
+}
+
+/// Enum representing commands of the private program registry.
+#[derive(Parser)]
+pub enum RegistryCommands {
+    #[clap(
+        action,
+        about = "Creates an empty private registry for the network"
+    )]
+    Init {
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+    },
+
+    #[clap(
+        action,
+        about = "Registers the published version of a Move package in the private registry"
+    )]
+    Add {
+        #[clap(help = "The name of the project that published the package")]
+        name: String,
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+        #[clap(long, help = "The git repository of the package source")]
+        git: String,
+        #[clap(
+            long,
+            help = "The directory of the package within the git repository"
+        )]
+        subdir: Option<String>,
+        #[clap(long, help = "The git revision the package was published from")]
+        rev: String,
+        #[clap(
+            short,
+            long,
+            action,
+            help = "The path to the project directory (defaults to the Home directory)"
+        )]
+        project_dir: Option<String>,
+    },
+
+    #[clap(action, about = "Lists the packages of the private registry")]
+    List {
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+    },
+
+    #[clap(
+        action,
+        about = "Merges a registry file, such as a teammate's, into the private registry"
+    )]
+    Merge {
+        #[clap(
+            help = "Define the network environment: 'mainnet', 'testnet', 'devnet', 'localnet' or a custom network"
+        )]
+        network: String,
+        #[clap(help = "The path to the registry file to merge")]
+        path: String,
     },
 }
 
@@ -1,3 +1,4 @@
+use anyhow::{anyhow, Result};
 use console::Emoji;
 use package_manager::Network;
 
@@ -40,9 +41,68 @@ pub const DEFAULT_GAS_BUDGET: u64 = 50_000_000_000;
 
 pub const KIWI_EMOJI: Emoji<'_, '_> = Emoji("🥝 ", "");
 
-pub fn get_launchpad_id(network: &Network) -> &str {
+/// ID of the OriginByte launchpad deployed on the network.
+///
+/// # Returns
+/// Result containing the ID, or an error for networks without a launchpad,
+/// such as devnet, localnet and custom networks.
+pub fn get_launchpad_id(network: &Network) -> Result<&'static str> {
     match network {
-        Network::Mainnet => LAUNCHPAD_ID_MAIN,
-        Network::Testnet => LAUNCHPAD_ID_TEST,
+        Network::Mainnet => Ok(LAUNCHPAD_ID_MAIN),
+        Network::Testnet => Ok(LAUNCHPAD_ID_TEST),
+        Network::Devnet | Network::Localnet | Network::Custom { .. } => {
+            Err(no_launchpad(network))
+        }
+    }
+}
+
+/// Checks that the OriginByte launchpad is deployed on the network.
+///
+/// # Returns
+/// Result which is either empty (Ok) or contains an error for networks
+/// without a launchpad, such as devnet, localnet and custom networks.
+pub fn check_launchpad_network(network: &Network) -> Result<()> {
+    match network {
+        Network::Mainnet | Network::Testnet => Ok(()),
+        Network::Devnet | Network::Localnet | Network::Custom { .. } => {
+            Err(no_launchpad(network))
+        }
+    }
+}
+
+fn no_launchpad(network: &Network) -> anyhow::Error {
+    anyhow!(
+        "OriginByte launchpad is not deployed on {network}, use mainnet or \
+        testnet instead"
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use package_manager::package::Flavor;
+
+    #[test]
+    fn launchpad_is_only_on_mainnet_and_testnet() {
+        assert_eq!(
+            get_launchpad_id(&Network::Mainnet).unwrap(),
+            LAUNCHPAD_ID_MAIN
+        );
+        assert_eq!(
+            get_launchpad_id(&Network::Testnet).unwrap(),
+            LAUNCHPAD_ID_TEST
+        );
+
+        let custom = Network::Custom {
+            name: "staging".to_string(),
+            rpc_url: "http://127.0.0.1:9000".to_string(),
+            registry: Flavor::Testnet,
+        };
+        check_launchpad_network(&Network::Testnet).unwrap();
+        for network in [Network::Devnet, Network::Localnet, custom] {
+            let err = get_launchpad_id(&network).unwrap_err();
+            assert!(err.to_string().contains(&network.to_string()));
+            assert!(check_launchpad_network(&network).is_err());
+        }
     }
 }
@@ -0,0 +1,327 @@
+use anyhow::{anyhow, Result};
+use console::style;
+use gutenberg_types::models::address::Address;
+use gutenberg_types::models::launchpad::{listing::Listing, market::Market};
+use gutenberg_types::Schema;
+use package_manager::{package::PackageRegistry, Network};
+use rust_sdk::launchpad::{
+    self, LaunchpadPackage, ListingConfig, MarketKind, VenueConfig,
+};
+use rust_sdk::models::project::{CollectionObjects, Project};
+use rust_sdk::utils::{execute_tx_with_chain_client, get_context, MoveType};
+use std::str::FromStr;
+use sui_sdk::rpc_types::SuiTransactionBlockEffects;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+use terminal_link::Link;
+
+use super::{
+    check_network_match, explorer_link, get_failover_client, get_gas_budget,
+    get_gas_coin,
+};
+use crate::consts::check_launchpad_network;
+
+/// Asynchronously creates the listing of the collection on the launchpad,
+/// along with its venues, in a single transaction.
+///
+/// A collection is listed once, hence the launchpad config must declare a
+/// single listing.
+///
+/// # Arguments
+/// * `schema` - Reference to the Schema struct representing the NFT schema.
+/// * `state` - Mutable reference to the Project struct.
+/// * `registry` - Reference to the PackageRegistry of the network, which
+///   locates the launchpad package.
+/// * `warehouse_id` - Optional String representing the warehouse sold by the
+///   listing, which defaults to the last one of the project state.
+/// * `start_sale` - Whether to turn the sale on in every venue.
+/// * `gas_coin` - Optional String representing the gas coin.
+/// * `gas_budget` - Optional usize representing the gas budget.
+/// * `network` - Reference to the Network struct representing the blockchain
+///   network.
+/// * `rpc_urls` - Fallback RPC endpoints, tried after the active Sui
+///   environment.
+///
+/// # Returns
+/// Result type which is either empty (Ok) or contains an error (Err).
+#[allow(clippy::too_many_arguments)]
+pub async fn create_listing(
+    schema: &Schema,
+    state: &mut Project,
+    registry: &PackageRegistry,
+    warehouse_id: Option<String>,
+    start_sale: bool,
+    gas_coin: Option<String>,
+    gas_budget: Option<usize>,
+    network: &Network,
+    rpc_urls: &[String],
+) -> Result<()> {
+    let package_id = state.package_id.ok_or_else(|| {
+        anyhow!(
+            "Could not find contract ID in the project state. Make sure you \
+            run the command `deploy-contract`"
+        )
+    })?;
+
+    let listings = schema
+        .launchpad
+        .as_ref()
+        .map(|launchpad| launchpad.listings.0.as_slice())
+        .unwrap_or_default();
+    let listing = match listings {
+        [listing] => listing_config(listing)?,
+        [] => {
+            return Err(anyhow!(
+                "Could not find a listing in the launchpad config"
+            ))
+        }
+        _ => {
+            return Err(anyhow!(
+                "The launchpad config has {} listings, but a collection can \
+                only be listed once",
+                listings.len()
+            ))
+        }
+    };
+
+    let collection_objects = state
+        .collection_objects
+        .get_or_insert(CollectionObjects::empty());
+
+    if let Some(listing) = collection_objects.listing {
+        return Err(anyhow!("The collection is already listed on {listing}"));
+    }
+
+    let warehouse_id = match warehouse_id {
+        Some(warehouse_id) => ObjectID::from_str(warehouse_id.as_str())
+            .map_err(|err| anyhow!("Unable to parse warehouse ID: {err}"))?,
+        None => *collection_objects.warehouses.last().ok_or_else(|| {
+            anyhow!(
+                "Could not find a warehouse in the project state, call \
+                `byte client create-warehouse` first"
+            )
+        })?,
+    };
+
+    let nft_type = MoveType::new(
+        package_id.to_string(),
+        schema.package_name(),
+        String::from(schema.nft().type_name()),
+    );
+
+    check_launchpad_network(network)?;
+    let launchpad = launchpad_package(registry)?;
+
+    let wallet_ctx = get_context().await?;
+    let sender = wallet_ctx.config.active_address.unwrap();
+
+    check_network_match(&wallet_ctx, network)?;
+
+    let client = get_failover_client(&wallet_ctx, network, rpc_urls).await?;
+
+    println!("{} Creating listing", style("WIP").cyan().bold());
+
+    let gas_coin = get_gas_coin(&client, sender, gas_coin).await?;
+    let gas_budget = get_gas_budget(gas_coin.clone(), gas_budget)?;
+
+    let tx_data = launchpad::prepare_create_listing(
+        &client,
+        sender,
+        launchpad,
+        nft_type.write_type().as_str(),
+        &listing,
+        warehouse_id,
+        start_sale,
+        (gas_coin.coin_object_id, gas_coin.version, gas_coin.digest),
+        gas_budget as u64,
+    )
+    .await?;
+
+    let response =
+        execute_tx_with_chain_client(&client, &wallet_ctx, tx_data).await?;
+
+    let SuiTransactionBlockEffects::V1(effects) = response.effects.unwrap();
+
+    if effects.status.is_err() {
+        return Err(anyhow!("Transaction Failed: {:?}", effects.status));
+    }
+
+    let created: Vec<ObjectID> = effects
+        .created
+        .iter()
+        .map(|object| object.reference.object_id)
+        .collect();
+
+    let objects =
+        launchpad::listing_objects(&client, launchpad, &created).await?;
+
+    println!("{} Creating listing", style("DONE").green().bold());
+
+    println!("Listing object ID: {}", objects.listing);
+    for venue in objects.venues.iter() {
+        println!("Venue object ID: {venue}");
+    }
+
+    let explorer_link = explorer_link(network, &objects.listing);
+    let link = Link::new("Sui Explorer", explorer_link.as_str());
+
+    println!(
+        "You can now find your listing object on the {}",
+        style(link).blue().bold().underlined(),
+    );
+
+    // The warehouse is now the inventory of the listing
+    collection_objects
+        .warehouses
+        .retain(|id| *id != warehouse_id);
+    collection_objects.listing = Some(objects.listing);
+    collection_objects.venues.extend(objects.venues);
+
+    Ok(())
+}
+
+/// Looks up the addresses of the latest version of the launchpad package in
+/// the registry.
+///
+/// # Arguments
+/// * `registry` - Reference to the PackageRegistry of the network.
+///
+/// # Returns
+/// Result containing the LaunchpadPackage or an error.
+fn launchpad_package(registry: &PackageRegistry) -> Result<LaunchpadPackage> {
+    let (original_id, published_at) =
+        registry.get_latest_addresses("Launchpad")?;
+
+    Ok(LaunchpadPackage {
+        original_id: object_id(original_id)?,
+        published_at: object_id(published_at)?,
+    })
+}
+
+/// Converts the listing of the schema into the configuration of the
+/// transaction creating it.
+///
+/// # Arguments
+/// * `listing` - Reference to the Listing of the schema.
+///
+/// # Returns
+/// Result containing the ListingConfig or an error.
+fn listing_config(listing: &Listing) -> Result<ListingConfig> {
+    let venues = listing
+        .markets
+        .iter()
+        .map(|market| match market {
+            Market::FixedPrice {
+                token,
+                price,
+                is_whitelisted,
+            } => VenueConfig {
+                kind: MarketKind::FixedPrice,
+                token: token.clone(),
+                price: *price,
+                is_whitelisted: *is_whitelisted,
+            },
+            Market::DutchAuction {
+                token,
+                reserve_price,
+                is_whitelisted,
+            } => VenueConfig {
+                kind: MarketKind::DutchAuction,
+                token: token.clone(),
+                price: *reserve_price,
+                is_whitelisted: *is_whitelisted,
+            },
+        })
+        .collect();
+
+    Ok(ListingConfig {
+        admin: sui_address(&listing.admin)?,
+        receiver: sui_address(&listing.receiver)?,
+        venues,
+    })
+}
+
+/// Parses the address of a package of the registry.
+fn object_id(address: &Address) -> Result<ObjectID> {
+    ObjectID::from_str(address.to_string().as_str())
+        .map_err(|err| anyhow!("Unable to parse package ID {address}: {err}"))
+}
+
+/// Parses an address of the schema, which is padded to 32 bytes.
+fn sui_address(address: &Address) -> Result<SuiAddress> {
+    SuiAddress::from_str(address.to_string().as_str())
+        .map_err(|err| anyhow!("Unable to parse address {address}: {err}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use package_manager::package::Flavor;
+    use std::path::Path;
+
+    #[test]
+    fn finds_launchpad_package() -> Result<()> {
+        let registry = PackageRegistry::read(
+            Path::new("../package-manager/registry/registry-main.json"),
+            Flavor::Mainnet,
+        )?;
+
+        let launchpad = launchpad_package(&registry)?;
+
+        // Types keep the original address while calls go to the upgrade
+        assert_eq!(
+            launchpad,
+            LaunchpadPackage {
+                original_id: ObjectID::from_str(
+                    "0xc74531639fadfb02d30f05f37de4cf1e1149ed8d23658edd089004830068180b"
+                )?,
+                published_at: ObjectID::from_str(
+                    "0xc0c5ca1e59bbb0e7330c8f182cbad262717faf7d8d0d7f7da4b3146391ecbbe1"
+                )?,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn converts_listing() -> Result<()> {
+        let listing = Listing::new(
+            Address::new("0xa")?,
+            Address::new("0xb")?,
+            vec![
+                Market::FixedPrice {
+                    token: "0x2::sui::SUI".to_string(),
+                    price: 100,
+                    is_whitelisted: false,
+                },
+                Market::DutchAuction {
+                    token: "0x2::sui::SUI".to_string(),
+                    reserve_price: 50,
+                    is_whitelisted: true,
+                },
+            ],
+        );
+
+        let config = listing_config(&listing)?;
+
+        assert_eq!(
+            config.admin,
+            SuiAddress::from(ObjectID::from_single_byte(0xa))
+        );
+        assert_eq!(
+            config.receiver,
+            SuiAddress::from(ObjectID::from_single_byte(0xb))
+        );
+        assert_eq!(
+            config.venues[1],
+            VenueConfig {
+                kind: MarketKind::DutchAuction,
+                token: "0x2::sui::SUI".to_string(),
+                price: 50,
+                is_whitelisted: true,
+            }
+        );
+
+        Ok(())
+    }
+}
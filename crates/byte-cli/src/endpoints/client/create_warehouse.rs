@@ -1,6 +1,6 @@
 use crate::consts::get_launchpad_id;
 use crate::endpoints::client::{
-    check_network_match, get_gas_budget, get_gas_coin,
+    check_network_match, explorer_link, get_gas_budget, get_gas_coin,
 };
 use anyhow::Result;
 use console::style;
@@ -62,7 +62,7 @@ pub async fn create_warehouse(
     );
 
     // Gets the launchpad package ID.
-    let launchpad_pkg = ObjectID::from_str(get_launchpad_id(&network))?;
+    let launchpad_pkg = ObjectID::from_str(get_launchpad_id(&network)?)?;
 
     // Retrieves the gas coin.
     let gas_coin = get_gas_coin(&client, sender, gas_coin).await?;
@@ -89,11 +89,7 @@ pub async fn create_warehouse(
     println!("Warehouse object ID: {}", warehouse_object_id);
 
     // Creates a link to the Sui Explorer for the warehouse object.
-    let explorer_link = format!(
-        "https://explorer.sui.io/object/{}?network={}",
-        warehouse_object_id.clone(),
-        network
-    );
+    let explorer_link = explorer_link(network, &warehouse_object_id);
     let link = Link::new("Sui Explorer", explorer_link.as_str());
 
     // Prints the explorer link.
@@ -1,22 +1,21 @@
 use anyhow::{anyhow, Result};
 use console::style;
 use package_manager::Network;
-use rust_sdk::models::project::{
-    AdminObjects, CollectionObjects, MintCap, Project,
-};
-use rust_sdk::utils::execute_tx;
+use rust_sdk::chain::ChainClient;
+use rust_sdk::consts::VOLCANO_EMOJI;
+use rust_sdk::models::project::Project;
+use rust_sdk::utils::execute_tx_with_chain_client;
 use rust_sdk::{collection_state::ObjectType as OBObjectType, publish};
-use rust_sdk::{consts::VOLCANO_EMOJI, utils::get_context};
 use std::fs::File;
 use std::path::Path;
-use std::sync::mpsc::channel;
 use sui_sdk::rpc_types::{
-    SuiTransactionBlockEffects, SuiTransactionBlockResponse,
+    OwnedObjectRef, SuiTransactionBlockEffects, SuiTransactionBlockResponse,
 };
 use terminal_link::Link;
-use tokio::task::JoinSet;
 
 use super::check_network_match;
+use super::explorer_link;
+use super::get_failover_client;
 use super::get_gas_budget;
 use super::get_gas_coin;
 
@@ -49,6 +48,8 @@ Call `byte-cli init-collection-config` to initialize the configuration file."#,
 /// * `network` - The blockchain network.
 /// * `contract_dir` - A reference to a Path representing the contract
 ///   directory.
+/// * `rpc_urls` - Fallback RPC endpoints, tried after the active Sui
+///   environment.
 ///
 /// # Returns
 /// Result type which is either empty (Ok) or contains an error (Err).
@@ -58,13 +59,15 @@ pub async fn publish_contract(
     gas_budget: Option<usize>,
     network: Network,
     contract_dir: &Path,
+    rpc_urls: &[String],
 ) -> Result<()> {
     let wallet_ctx = rust_sdk::utils::get_context().await?;
     let sender = wallet_ctx.config.active_address.unwrap();
-    let sui_client = wallet_ctx.get_client().await.unwrap();
 
     check_network_match(&wallet_ctx, &network)?;
 
+    let client = get_failover_client(&wallet_ctx, &network, rpc_urls).await?;
+
     if let Some(pkg_id) = state.package_id {
         return Err(anyhow!(format!(
             "Collection has already been deploy: {}",
@@ -75,7 +78,7 @@ pub async fn publish_contract(
     // The project owner should be the publisher address
     state.project_owner = sender;
 
-    let gas_coin = get_gas_coin(&sui_client, sender, gas_coin).await?;
+    let gas_coin = get_gas_coin(&client, sender, gas_coin).await?;
     let gas_budget = get_gas_budget(gas_coin.clone(), gas_budget)?;
 
     let tx_data = publish::prepare_publish_contract(
@@ -87,9 +90,9 @@ pub async fn publish_contract(
     .await?;
 
     let response: SuiTransactionBlockResponse =
-        execute_tx(&wallet_ctx, tx_data).await?;
+        execute_tx_with_chain_client(&client, &wallet_ctx, tx_data).await?;
 
-    process_effects(state, response).await?;
+    process_effects(state, response, &client, &network).await?;
 
     Ok(())
 }
@@ -99,16 +102,20 @@ pub async fn publish_contract(
 /// # Arguments
 /// * `state` - Mutable reference to the Project struct.
 /// * `response` - SuiTransactionBlockResponse object.
+/// * `client` - The chain client used to read the created objects.
+/// * `network` - The blockchain network.
 ///
 /// # Returns
 /// Result type which is either empty (Ok) or contains an error (Err).
-pub async fn process_effects(
+pub async fn process_effects<C>(
     state: &mut Project,
     response: SuiTransactionBlockResponse,
-) -> Result<(), anyhow::Error> {
-    let context = get_context().await.unwrap();
-    let client = context.get_client().await?;
-
+    client: &C,
+    network: &Network,
+) -> Result<(), anyhow::Error>
+where
+    C: ChainClient + ?Sized,
+{
     println!(
         "{} {}",
         VOLCANO_EMOJI,
@@ -116,10 +123,6 @@ pub async fn process_effects(
             .green()
             .bold()
     );
-    let mut set = JoinSet::new();
-
-    // Creating a channel to send message with package ID
-    let (sender, receiver) = channel();
 
     let SuiTransactionBlockEffects::V1(effects) = response.effects.unwrap();
 
@@ -127,63 +130,10 @@ pub async fn process_effects(
         return Err(anyhow!("Transaction Failed: {:?}", effects));
     }
 
-    let objects_created = effects.created;
+    record_created_objects(state, client, &effects.created).await?;
 
-    objects_created
-        .iter()
-        .map(|object| {
-            // TODO: Remove this clone
-            let object_ = object.clone();
-            let client_ = client.clone();
-            let sender_ = sender.clone();
-            set.spawn(async move {
-                publish::print_object(&client_, &object_, sender_).await;
-            });
-        })
-        .for_each(drop);
-
-    let mut i = 1;
-    while let Some(res) = set.join_next().await {
-        res.unwrap();
-        i += 1;
-    }
-
-    println!("A total of {} object have been created.", i);
-
-    let mut j = 0;
-
-    // It's three as we are interest in the MintCap, Collection and Package
-    // We need to make sure we agree on the number of objects that are recorded
-    while j < 3 {
-        let object_type = receiver.recv().unwrap();
-        match object_type {
-            OBObjectType::Package(object_id) => {
-                state.package_id = Some(object_id);
-                j += 1;
-            }
-            OBObjectType::MintCap(object_id) => {
-                let admin_objs =
-                    state.admin_objects.get_or_insert(AdminObjects::empty());
-                admin_objs.mint_caps.push(MintCap::new(object_id));
-                j += 1;
-            }
-            OBObjectType::Collection(object_id) => {
-                let col_objs = state
-                    .collection_objects
-                    .get_or_insert(CollectionObjects::empty());
-
-                col_objs.collection = Some(object_id);
-
-                j += 1;
-            }
-            _ => {}
-        }
-    }
-
-    let explorer_link = format!(
-        "https://explorer.sui.io/object/{}?network=testnet",
-        state.package_id.as_ref().unwrap()
-    );
+    let explorer_link =
+        explorer_link(network, state.package_id.as_ref().unwrap());
 
     let link = Link::new("Sui Explorer", explorer_link.as_str());
 
@@ -194,3 +144,162 @@ pub async fn process_effects(
 
     Ok(())
 }
+
+/// Reads the objects created by the publish transaction and records them in
+/// the project state.
+///
+/// # Arguments
+/// * `state` - Mutable reference to the Project struct.
+/// * `client` - The chain client used to read the created objects.
+/// * `objects_created` - The objects created by the publish transaction.
+///
+/// # Returns
+/// Result type which is either empty (Ok) or contains an error (Err) if the
+/// package was not created.
+pub async fn record_created_objects<C>(
+    state: &mut Project,
+    client: &C,
+    objects_created: &[OwnedObjectRef],
+) -> Result<(), anyhow::Error>
+where
+    C: ChainClient + ?Sized,
+{
+    let objects =
+        publish::classify_created_objects(client, objects_created).await?;
+
+    for object in objects.iter() {
+        println!("{}: {}", object.name(), object.id());
+        state.record_object(object);
+    }
+
+    println!(
+        "A total of {} object have been created.",
+        objects_created.len()
+    );
+
+    if !objects
+        .iter()
+        .any(|object| matches!(object, OBObjectType::Package(_)))
+    {
+        return Err(anyhow!("The package was not created"));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_sdk::chain::fake::{object, struct_type, FakeChainClient};
+    use sui_sdk::rpc_types::SuiObjectRef;
+    use sui_sdk::types::{
+        base_types::{
+            ObjectDigest, ObjectID, ObjectType, SequenceNumber, SuiAddress,
+        },
+        object::Owner,
+    };
+
+    fn created(object_id: ObjectID, owner: Owner) -> OwnedObjectRef {
+        OwnedObjectRef {
+            owner,
+            reference: SuiObjectRef {
+                object_id,
+                version: SequenceNumber::from_u64(1),
+                digest: ObjectDigest::new([0; 32]),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn records_published_objects() -> Result<()> {
+        let owner = SuiAddress::from(ObjectID::from_single_byte(0xa));
+        let package = ObjectID::from_single_byte(1);
+        let ids: Vec<ObjectID> =
+            (2..=7).map(ObjectID::from_single_byte).collect();
+
+        let client = FakeChainClient::new()
+            .with_object(object(package, ObjectType::Package, Owner::Immutable))
+            .with_object(object(
+                ids[0],
+                struct_type("0x2::package::UpgradeCap"),
+                Owner::AddressOwner(owner),
+            ))
+            .with_object(object(
+                ids[1],
+                struct_type("0x2::package::Publisher"),
+                Owner::AddressOwner(owner),
+            ))
+            .with_object(object(
+                ids[2],
+                struct_type(
+                    "0x9::mint_cap::MintCap<0x1::suimarines::Suimarine>",
+                ),
+                Owner::AddressOwner(owner),
+            ))
+            .with_object(object(
+                ids[3],
+                struct_type(
+                    "0x9::collection::Collection<0x1::suimarines::Suimarine>",
+                ),
+                Owner::Shared {
+                    initial_shared_version: SequenceNumber::from_u64(1),
+                },
+            ))
+            .with_object(object(
+                ids[4],
+                struct_type(
+                    "0x2::display::Display<0x1::suimarines::Suimarine>",
+                ),
+                Owner::AddressOwner(owner),
+            ))
+            // Not a type of the published package
+            .with_object(object(
+                ids[5],
+                struct_type("0x9::mint_cap::MintCap<0x8::other::Other>"),
+                Owner::AddressOwner(owner),
+            ));
+
+        let mut created_objects = vec![created(package, Owner::Immutable)];
+        created_objects.extend(
+            ids.iter()
+                .map(|id| created(*id, Owner::AddressOwner(owner))),
+        );
+
+        let mut state = Project::new("Suimarines".to_string(), owner);
+        record_created_objects(&mut state, &client, &created_objects).await?;
+
+        assert_eq!(state.package_id, Some(package));
+        assert_eq!(state.upgrade_cap, Some(ids[0]));
+        assert_eq!(state.publisher, Some(ids[1]));
+
+        let admin_objects = state.admin_objects.unwrap();
+        assert_eq!(admin_objects.mint_caps.len(), 1);
+        assert_eq!(admin_objects.mint_caps[0].id, ids[2]);
+        assert_eq!(admin_objects.displays, vec![ids[4]]);
+        assert_eq!(state.collection_objects.unwrap().collection, Some(ids[3]));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_package() {
+        let owner = SuiAddress::from(ObjectID::from_single_byte(0xa));
+        let upgrade_cap = ObjectID::from_single_byte(2);
+
+        let client = FakeChainClient::new().with_object(object(
+            upgrade_cap,
+            struct_type("0x2::package::UpgradeCap"),
+            Owner::AddressOwner(owner),
+        ));
+
+        let mut state = Project::new("Suimarines".to_string(), owner);
+        let result = record_created_objects(
+            &mut state,
+            &client,
+            &[created(upgrade_cap, Owner::AddressOwner(owner))],
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+}
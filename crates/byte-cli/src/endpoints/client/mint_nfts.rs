@@ -1,5 +1,7 @@
 use crate::{
-    endpoints::client::check_network_match,
+    endpoints::client::{
+        check_network_match, explorer_link, get_failover_client,
+    },
     io::{LocalRead, LocalWrite},
     models::effects::{MintEffects, MintError, Minted},
 };
@@ -36,6 +38,8 @@ use terminal_link::Link;
 /// * `batches` - The number of batches to divide the minting process into.
 /// * `network` - Reference to the Network struct representing the blockchain
 ///   network.
+/// * `rpc_urls` - Fallback RPC endpoints, tried after the active Sui
+///   environment.
 ///
 /// # Returns
 /// Result containing the updated Project state or an error.
@@ -56,6 +60,7 @@ pub async fn mint_nfts(
     amount: u64,
     batches: u64,
     network: &Network,
+    rpc_urls: &[String],
 ) -> Result<Project> {
     let contract_id = Arc::new(state.package_id.as_ref().unwrap().to_string());
     println!("Initiliazing process on contract ID: {:?}", contract_id);
@@ -64,6 +69,9 @@ pub async fn mint_nfts(
 
     check_network_match(&wallet_ctx, network)?;
 
+    let client =
+        Arc::new(get_failover_client(&wallet_ctx, network, rpc_urls).await?);
+
     let active_address =
         get_active_address(&wallet_ctx.config.keystore).unwrap();
 
@@ -175,6 +183,7 @@ pub async fn mint_nfts(
 
         let effect = mint::mint_nfts_to_warehouse(
             batch,
+            client.clone(),
             wallet_ctx.clone(),
             contract_id.clone(),
             module_name.clone(),
@@ -209,12 +218,7 @@ pub async fn mint_nfts(
 
     println!("{} Minting NFTs on-chain", style("DONE").green().bold());
 
-    let network = wallet_ctx.config.active_env.as_ref().unwrap();
-
-    let explorer_link = format!(
-        "https://explorer.sui.io/object/{}?network={}",
-        warehouse, network
-    );
+    let explorer_link = explorer_link(network, &warehouse);
 
     let link = Link::new("Sui Explorer", explorer_link.as_str());
 
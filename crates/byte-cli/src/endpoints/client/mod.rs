@@ -1,18 +1,26 @@
+pub mod create_listing;
 pub mod create_warehouse;
 pub mod deploy_contract;
 pub mod mint_nfts;
+pub mod sync;
+pub mod transfer_admin;
+pub mod verify_registry;
+pub mod verify_source;
 
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 
 use anyhow::{anyhow, Result};
+use console::style;
 use package_manager::Network;
+use rust_sdk::chain::ChainClient;
 use rust_sdk::coin::{select_biggest_coin, select_coin};
+use rust_sdk::rpc::{FailoverClient, RetryPolicy};
 use sui_sdk::{
     rpc_types::Coin,
     types::base_types::{ObjectID, SuiAddress},
     wallet_context::WalletContext,
-    SuiClient,
 };
+use url::form_urlencoded;
 
 use crate::consts::DEFAULT_GAS_BUDGET;
 
@@ -38,11 +46,14 @@ pub fn get_gas_budget(
     Ok(gas_budget)
 }
 
-pub async fn get_gas_coin(
-    client: &SuiClient,
+pub async fn get_gas_coin<C>(
+    client: &C,
     sender: SuiAddress,
     gas_coin: Option<String>,
-) -> Result<Coin> {
+) -> Result<Coin>
+where
+    C: ChainClient + ?Sized,
+{
     let gas_coin = if let Some(gas_coin) = gas_coin {
         let gas_coin =
             ObjectID::from_str(gas_coin.as_str()).map_err(|err| {
@@ -64,16 +75,65 @@ pub fn check_network_match(
     wallet_ctx: &WalletContext,
     network: &Network,
 ) -> Result<()> {
-    let network_string = wallet_ctx.config.active_env.as_ref().unwrap();
-    let net = Network::from_str(network_string.as_str())
-        .map_err(|_| anyhow!("Invalid network string"))?;
+    let active_env = wallet_ctx.config.get_active_env()?;
 
-    if *network != net {
+    // Environments are matched by alias, or by RPC URL if they were named
+    // differently, e.g. when added with `sui client new-env`
+    let rpc_match = active_env.rpc.trim_end_matches('/')
+        == network.rpc_url().trim_end_matches('/');
+
+    if active_env.alias != network.to_string() && !rpc_match {
         return Err(anyhow!(format!(
             "Chosen network {} does not correspond to active Sui network {}",
-            network, net
+            network, active_env.alias
         )));
     }
 
     Ok(())
 }
+
+/// Connects to the RPC endpoint of the active Sui environment, failing over
+/// to the given endpoints and then to the public fullnode of the network.
+///
+/// # Arguments
+/// * `wallet_ctx` - The wallet context of the active Sui environment.
+/// * `network` - The blockchain network.
+/// * `rpc_urls` - Fallback RPC endpoints, in order of preference.
+pub async fn get_failover_client(
+    wallet_ctx: &WalletContext,
+    network: &Network,
+    rpc_urls: &[String],
+) -> Result<FailoverClient> {
+    let mut urls = vec![wallet_ctx.config.get_active_env()?.rpc.clone()];
+    urls.extend(rpc_urls.iter().cloned());
+    urls.push(network.rpc_url().to_string());
+
+    let client = FailoverClient::new(&urls, RetryPolicy::default()).await?;
+
+    for (url, checkpoint) in client.check_health().await {
+        if let Err(err) = checkpoint {
+            println!(
+                "{} RPC endpoint {url} is unhealthy: {err}",
+                style("WARNING").yellow().bold()
+            );
+        }
+    }
+
+    Ok(client)
+}
+
+/// Link to an object on the Sui Explorer of the network, which reads custom
+/// networks from their RPC endpoint.
+pub fn explorer_link(network: &Network, object_id: impl Display) -> String {
+    let explorer_network = match network {
+        Network::Localnet => "local".to_string(),
+        Network::Custom { rpc_url, .. } => {
+            form_urlencoded::byte_serialize(rpc_url.as_bytes()).collect()
+        }
+        _ => network.to_string(),
+    };
+
+    format!(
+        "https://explorer.sui.io/object/{object_id}?network={explorer_network}"
+    )
+}
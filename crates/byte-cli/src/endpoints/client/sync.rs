@@ -0,0 +1,272 @@
+use anyhow::{anyhow, Result};
+use console::style;
+use dialoguer::Confirm;
+use package_manager::Network;
+use rust_sdk::models::project::Project;
+use rust_sdk::utils::{get_active_address, get_context};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+
+use super::{check_network_match, get_failover_client};
+use crate::cli::get_dialoguer_theme;
+
+/// Asynchronously rebuilds the project state of a published collection from
+/// the chain.
+///
+/// # Arguments
+/// * `name` - The name of the NFT collection.
+/// * `state` - The local Project state of the network, if any.
+/// * `package_id` - Optional String representing the package ID, which
+///   defaults to the one of the local state.
+/// * `network` - Reference to the Network struct representing the blockchain
+///   network.
+/// * `rpc_urls` - Fallback RPC endpoints, tried after the active Sui
+///   environment.
+///
+/// # Returns
+/// Result containing the rebuilt Project state or an error.
+pub async fn sync_project(
+    name: &str,
+    state: Option<&Project>,
+    package_id: Option<String>,
+    network: &Network,
+    rpc_urls: &[String],
+) -> Result<Project> {
+    let package_id = match package_id {
+        Some(package_id) => ObjectID::from_str(package_id.as_str())
+            .map_err(|err| anyhow!("Unable to parse package ID: {err}"))?,
+        None => state.and_then(|state| state.package_id).ok_or_else(|| {
+            anyhow!(
+                "Could not find the package of '{name}' on {network}, pass \
+                it with `--package`"
+            )
+        })?,
+    };
+
+    let wallet_ctx = get_context().await?;
+
+    check_network_match(&wallet_ctx, network)?;
+
+    let client = get_failover_client(&wallet_ctx, network, rpc_urls).await?;
+
+    // Without a local state, the objects are looked up in the active wallet
+    let (name, owner) = match state {
+        Some(state) => (state.name.clone(), state.project_owner),
+        None => (
+            name.to_string(),
+            get_active_address(&wallet_ctx.config.keystore)?,
+        ),
+    };
+
+    println!(
+        "{} Reading the objects of package {package_id} owned by {owner}",
+        style("WIP").cyan().bold()
+    );
+
+    let mut custodians: Vec<SuiAddress> = state
+        .map(|state| state.custodians.values().copied().collect())
+        .unwrap_or_default();
+    custodians.sort();
+    custodians.dedup();
+
+    let mut project = rust_sdk::sync::sync_project(
+        &client,
+        name,
+        owner,
+        &custodians,
+        package_id,
+    )
+    .await?;
+
+    if let Some(state) = state {
+        rust_sdk::sync::carry_over_listing(&mut project, state);
+    }
+
+    Ok(project)
+}
+
+/// Lists the differences between the local project state and the one
+/// rebuilt from the chain, one line per changed field.
+///
+/// # Arguments
+/// * `old` - The local Project state, if any.
+/// * `new` - The Project state rebuilt from the chain.
+///
+/// # Returns
+/// Result containing the lines of the diff, empty if the states are equal.
+pub fn project_diff(
+    old: Option<&Project>,
+    new: &Project,
+) -> Result<Vec<String>> {
+    let old = match old {
+        Some(old) => flatten(&serde_json::to_value(old)?),
+        None => BTreeMap::new(),
+    };
+    let new = flatten(&serde_json::to_value(new)?);
+
+    let mut paths: Vec<&String> = old.keys().chain(new.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    let diff = paths
+        .into_iter()
+        .filter_map(|path| match (old.get(path), new.get(path)) {
+            (Some(old), None) => Some(format!("- {path}: {old}")),
+            (None, Some(new)) => Some(format!("+ {path}: {new}")),
+            (Some(old), Some(new)) if old != new => {
+                Some(format!("~ {path}: {old} -> {new}"))
+            }
+            _ => None,
+        })
+        .collect();
+
+    Ok(diff)
+}
+
+/// Prints the diff and asks whether to overwrite the project state with it.
+///
+/// # Arguments
+/// * `project_path` - Path of the project state of the network.
+/// * `diff` - The lines of the diff, as listed by `project_diff`.
+/// * `yes` - Whether to overwrite without asking.
+///
+/// # Returns
+/// Result containing whether the project state should be overwritten.
+pub fn confirm_overwrite(
+    project_path: &Path,
+    diff: &[String],
+    yes: bool,
+) -> Result<bool> {
+    if diff.is_empty() {
+        println!(
+            "{} {} is up to date",
+            style("DONE").green().bold(),
+            project_path.display()
+        );
+        return Ok(false);
+    }
+
+    println!("Changes to {}:", project_path.display());
+
+    for line in diff {
+        let line = match line.chars().next() {
+            Some('+') => style(line).green(),
+            Some('-') => style(line).red(),
+            _ => style(line).yellow(),
+        };
+        println!("  {line}");
+    }
+
+    if yes {
+        return Ok(true);
+    }
+
+    let overwrite = Confirm::with_theme(&get_dialoguer_theme())
+        .with_prompt(format!("Overwrite {}?", project_path.display()))
+        .default(false)
+        .interact()?;
+
+    Ok(overwrite)
+}
+
+/// Flattens a JSON value into its leaves, keyed by their path such as
+/// `admin_objects.mint_caps[0].id`. Null leaves are left out.
+fn flatten(value: &Value) -> BTreeMap<String, String> {
+    fn visit(
+        value: &Value,
+        path: String,
+        leaves: &mut BTreeMap<String, String>,
+    ) {
+        match value {
+            Value::Null => {}
+            Value::Object(fields) => {
+                for (key, value) in fields {
+                    let path = match path.is_empty() {
+                        true => key.clone(),
+                        false => format!("{path}.{key}"),
+                    };
+                    visit(value, path, leaves);
+                }
+            }
+            Value::Array(values) => {
+                for (index, value) in values.iter().enumerate() {
+                    visit(value, format!("{path}[{index}]"), leaves);
+                }
+            }
+            Value::String(value) => {
+                leaves.insert(path, value.clone());
+            }
+            value => {
+                leaves.insert(path, value.to_string());
+            }
+        }
+    }
+
+    let mut leaves = BTreeMap::new();
+    visit(value, String::new(), &mut leaves);
+    leaves
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rust_sdk::models::project::{AdminObjects, CollectionObjects, MintCap};
+
+    fn project() -> Project {
+        let owner = SuiAddress::from(ObjectID::from_single_byte(0xa));
+        let mut project = Project::new("Suimarines".to_string(), owner);
+        project.package_id = Some(ObjectID::from_single_byte(1));
+        project
+    }
+
+    #[test]
+    fn diffs_projects() -> Result<()> {
+        let old = project();
+
+        let mut new = project();
+        new.package_id = Some(ObjectID::from_single_byte(2));
+        new.upgrade_cap = Some(ObjectID::from_single_byte(3));
+        new.collection_objects = Some(CollectionObjects::empty());
+
+        let diff = project_diff(Some(&old), &new)?;
+
+        assert_eq!(
+            diff,
+            vec![
+                format!(
+                    "~ package_id: {} -> {}",
+                    ObjectID::from_single_byte(1),
+                    ObjectID::from_single_byte(2)
+                ),
+                format!("+ upgrade_cap: {}", ObjectID::from_single_byte(3)),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn diffs_missing_project() -> Result<()> {
+        let mut admin_objects = AdminObjects::empty();
+        admin_objects
+            .mint_caps
+            .push(MintCap::new(ObjectID::from_single_byte(4)));
+
+        let mut new = project();
+        new.admin_objects = Some(admin_objects);
+
+        let diff = project_diff(None, &new)?;
+
+        assert!(diff.contains(&format!(
+            "+ admin_objects.mint_caps[0].id: {}",
+            ObjectID::from_single_byte(4)
+        )));
+        assert!(diff.contains(&"+ name: Suimarines".to_string()));
+        assert!(project_diff(Some(&new), &new)?.is_empty());
+
+        Ok(())
+    }
+}
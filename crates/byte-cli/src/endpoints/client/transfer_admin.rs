@@ -0,0 +1,142 @@
+use anyhow::{anyhow, Result};
+use console::style;
+use package_manager::Network;
+use rust_sdk::custody::{self, AdminCap, UpgradePolicy};
+use rust_sdk::models::project::Project;
+use rust_sdk::utils::{execute_tx_with_chain_client, get_context};
+use std::str::FromStr;
+use sui_sdk::rpc_types::SuiTransactionBlockEffects;
+use sui_sdk::types::base_types::SuiAddress;
+
+use super::{
+    check_network_match, get_failover_client, get_gas_budget, get_gas_coin,
+};
+
+/// Asynchronously hands over the admin objects of a collection to a new
+/// custodian, such as a client or a multisig address.
+///
+/// # Arguments
+/// * `state` - Mutable reference to the Project struct.
+/// * `recipient` - String representing the address of the new custodian.
+/// * `caps` - The kinds of admin objects to hand over, all of them if empty.
+/// * `upgrade_policy` - Optional String representing the restriction applied
+///   to the upgrade policy of the package beforehand.
+/// * `gas_coin` - Optional String representing the gas coin.
+/// * `gas_budget` - Optional usize representing the gas budget.
+/// * `network` - Reference to the Network struct representing the blockchain
+///   network.
+/// * `rpc_urls` - Fallback RPC endpoints, tried after the active Sui
+///   environment.
+///
+/// # Returns
+/// Result type which is either empty (Ok) or contains an error (Err).
+#[allow(clippy::too_many_arguments)]
+pub async fn transfer_admin(
+    state: &mut Project,
+    recipient: String,
+    caps: Vec<String>,
+    upgrade_policy: Option<String>,
+    gas_coin: Option<String>,
+    gas_budget: Option<usize>,
+    network: &Network,
+    rpc_urls: &[String],
+) -> Result<()> {
+    let recipient = SuiAddress::from_str(recipient.as_str())
+        .map_err(|err| anyhow!("Unable to parse recipient address: {err}"))?;
+
+    let caps = match caps.is_empty() {
+        true => AdminCap::ALL.to_vec(),
+        false => caps
+            .iter()
+            .map(|cap| AdminCap::from_str(cap.as_str()))
+            .collect::<Result<Vec<AdminCap>, _>>()?,
+    };
+
+    let (objects, handed_over) = custody::held_objects(state, &caps);
+
+    for (id, custodian) in handed_over.iter() {
+        println!("Skipping {id}, already handed over to {custodian}");
+    }
+
+    let upgrade = match upgrade_policy {
+        Some(policy) => {
+            let policy = UpgradePolicy::from_str(policy.as_str())?;
+            let upgrade_cap = state.upgrade_cap.ok_or_else(|| {
+                anyhow!(
+                    "Could not find the UpgradeCap in the project state, \
+                    call `byte client sync` first"
+                )
+            })?;
+
+            if let Some(custodian) = state.custodians.get(&upgrade_cap) {
+                return Err(anyhow!(
+                    "The UpgradeCap was already handed over to {custodian}, \
+                    only its custodian can restrict the upgrade policy"
+                ));
+            }
+
+            Some((upgrade_cap, policy))
+        }
+        None => None,
+    };
+
+    let wallet_ctx = get_context().await?;
+    let sender = wallet_ctx.config.active_address.unwrap();
+
+    check_network_match(&wallet_ctx, network)?;
+
+    let client = get_failover_client(&wallet_ctx, network, rpc_urls).await?;
+
+    println!(
+        "{} Handing over admin objects to {recipient}",
+        style("WIP").cyan().bold()
+    );
+
+    let gas_coin = get_gas_coin(&client, sender, gas_coin).await?;
+    let gas_budget = get_gas_budget(gas_coin.clone(), gas_budget)?;
+
+    let tx_data = custody::prepare_transfer_admin(
+        &client,
+        sender,
+        recipient,
+        &objects,
+        upgrade,
+        (gas_coin.coin_object_id, gas_coin.version, gas_coin.digest),
+        gas_budget as u64,
+    )
+    .await?;
+
+    let response =
+        execute_tx_with_chain_client(&client, &wallet_ctx, tx_data).await?;
+
+    let SuiTransactionBlockEffects::V1(effects) = response.effects.unwrap();
+
+    if effects.status.is_err() {
+        return Err(anyhow!("Transaction Failed: {:?}", effects.status));
+    }
+
+    if let Some((upgrade_cap, policy)) = upgrade {
+        println!("Upgrade policy of the package: {policy}");
+
+        // The UpgradeCap was destroyed along with the ability to upgrade
+        if policy == UpgradePolicy::Immutable {
+            state.upgrade_cap = None;
+            state.custodians.remove(&upgrade_cap);
+        }
+    }
+
+    let transferred = custody::transferred_objects(&objects, upgrade);
+
+    for id in transferred.iter() {
+        println!("{id} -> {recipient}");
+    }
+
+    state.record_custodian(&transferred, recipient);
+
+    println!(
+        "{} Handing over admin objects",
+        style("DONE").green().bold()
+    );
+
+    Ok(())
+}
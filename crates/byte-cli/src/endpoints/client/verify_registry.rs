@@ -0,0 +1,65 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use gutenberg_types::models::address::Address;
+use package_manager::{
+    integrity::{
+        self, IntegrityIssue, OnChainObject, PackageObject, PackageReader,
+    },
+    package::PackageRegistry,
+    Network,
+};
+use rust_sdk::verify::{self, ObjectRead};
+use std::str::FromStr;
+use sui_sdk::{types::base_types::ObjectID, SuiClient};
+
+use super::check_network_match;
+
+/// Reads the packages referenced by the registry through the Sui client.
+struct ClientReader(SuiClient);
+
+#[async_trait]
+impl PackageReader for ClientReader {
+    async fn read_object(&self, id: &Address) -> Result<Option<OnChainObject>> {
+        let object_id = ObjectID::from_str(&id.to_string())
+            .map_err(|err| anyhow!("Invalid object ID {id}: {err}"))?;
+
+        let object =
+            verify::read_object(&self.0, object_id)
+                .await?
+                .map(|object| match object {
+                    ObjectRead::Package { version, modules } => {
+                        OnChainObject::Package(PackageObject {
+                            version,
+                            modules: modules.into_iter().collect(),
+                        })
+                    }
+                    ObjectRead::Other(object_type) => {
+                        OnChainObject::Other(object_type)
+                    }
+                });
+
+        Ok(object)
+    }
+}
+
+/// Asynchronously checks the package objects referenced by the program
+/// registry of the network.
+///
+/// # Arguments
+/// * `registry` - The registry to check, usually the public registry merged
+///   with the private one.
+/// * `network` - The blockchain network of the registry.
+///
+/// # Returns
+/// Result containing the issues found in the registry.
+pub async fn verify_registry(
+    registry: &PackageRegistry,
+    network: Network,
+) -> Result<Vec<IntegrityIssue>> {
+    let wallet_ctx = rust_sdk::utils::get_context().await?;
+    let sui_client = wallet_ctx.get_client().await?;
+
+    check_network_match(&wallet_ctx, &network)?;
+
+    integrity::verify_registry(registry, &ClientReader(sui_client)).await
+}
@@ -0,0 +1,79 @@
+use anyhow::{anyhow, Result};
+use package_manager::{info::MoveLock, Network};
+use rust_sdk::models::project::Project;
+use rust_sdk::verify;
+use std::path::Path;
+
+use super::check_network_match;
+use crate::models::verification::{DependencyReport, SourceReport};
+
+/// Asynchronously verifies a published contract against its on-chain
+/// bytecode.
+///
+/// # Arguments
+/// * `name` - The name of the NFT collection.
+/// * `state` - Reference to the Project struct of the network.
+/// * `network` - The blockchain network.
+/// * `contract_dir` - A reference to a Path representing the contract
+///   directory.
+///
+/// # Returns
+/// Result containing the verification report, which records a mismatch
+/// rather than failing on it.
+pub async fn verify_source(
+    name: &str,
+    state: &Project,
+    network: Network,
+    contract_dir: &Path,
+) -> Result<SourceReport> {
+    let wallet_ctx = rust_sdk::utils::get_context().await?;
+    let sui_client = wallet_ctx.get_client().await?;
+
+    check_network_match(&wallet_ctx, &network)?;
+
+    let package_id = state.package_id.ok_or_else(|| {
+        anyhow!(
+            "Collection '{name}' has not been published to {network}, call \
+            `byte client publish-collection` first"
+        )
+    })?;
+
+    let verification =
+        verify::verify_source(&sui_client, contract_dir, package_id).await?;
+
+    // Rebuilding resolves the dependencies into Move.lock
+    let move_lock_path = contract_dir.join("Move.lock");
+    let move_lock = match move_lock_path.exists() {
+        true => MoveLock::read(&move_lock_path)?,
+        false => MoveLock::default(),
+    };
+
+    let dependencies = verification
+        .dependencies
+        .iter()
+        .map(|(dep_name, dep_id)| {
+            let source =
+                move_lock.package(dep_name).map(|package| &package.source);
+
+            DependencyReport {
+                name: dep_name.clone(),
+                package_id: dep_id.to_string(),
+                git: source.and_then(|source| source.git.clone()),
+                subdir: source.and_then(|source| source.subdir.clone()),
+                rev: source.and_then(|source| source.rev.clone()),
+            }
+        })
+        .collect();
+
+    Ok(SourceReport {
+        collection: name.to_string(),
+        network: network.to_string(),
+        package_id: package_id.to_string(),
+        verified: verification.is_verified(),
+        package_name: verification.package_name,
+        package_digest: verification.package_digest,
+        modules: verification.modules,
+        dependencies,
+        error: verification.error,
+    })
+}
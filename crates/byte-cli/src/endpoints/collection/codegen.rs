@@ -1,11 +1,14 @@
 use anyhow::{anyhow, Result};
 use console::style;
-use gutenberg_types::Schema;
+use gutenberg_types::{migrate::migrate, Schema};
 use std::fs::File;
 use std::path::Path;
 
 /// Parses the configuration file to return a Schema object.
 ///
+/// Configuration files written in older schema versions are migrated in
+/// memory, while newer schema versions are refused.
+///
 /// # Arguments
 /// * `config_file` - A reference to a Path representing the configuration file.
 ///
@@ -20,12 +23,37 @@ Call `byte-cli init-collection-config` to initialize the configuration file."#,
         )
     })?;
 
-    serde_json::from_reader::<File, Schema>(file).map_err(|err|anyhow!(r#"Could not parse configuration file "{}": {err}
-Call `byte-cli init-collection-config to initialize the configuration file again."#, config_file.display()))
+    let migrated = serde_json::from_reader(file)
+        .map_err(anyhow::Error::from)
+        .and_then(|config| Ok(migrate(config)?))
+        .map_err(|err|anyhow!(r#"Could not parse configuration file "{}": {err}
+Call `byte-cli init-collection-config to initialize the configuration file again."#, config_file.display()))?;
+
+    if migrated.was_migrated() {
+        println!(
+            "{} Configuration uses schema version {}, call `byte collection migrate` to upgrade it",
+            style("WARNING").yellow().bold(),
+            migrated.from_version,
+        );
+    }
+
+    Ok(migrated.schema)
 }
 
 pub async fn gen_contract(contract_dir: &Path, schema: &Schema) -> Result<()> {
-    gutenberg::generate_project_with_flavors(schema, contract_dir)?;
+    let hand_edits = gutenberg::diff::find_hand_edits(contract_dir)?;
+    if !hand_edits.is_empty() {
+        println!(
+            "{} The following sources were edited by hand and will be overwritten:",
+            style("WARNING").yellow().bold(),
+        );
+        hand_edits
+            .iter()
+            .for_each(|path| println!("  {}", path.display()));
+        println!("Call `gutenberg diff` to review the changes beforehand.");
+    }
+
+    gutenberg::generate_project_with_flavors(schema, contract_dir, None)?;
 
     println!(
         "{} Contract successfully generated: {:?}",
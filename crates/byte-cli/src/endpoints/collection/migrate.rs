@@ -0,0 +1,73 @@
+use anyhow::{anyhow, Result};
+use console::style;
+use gutenberg_types::migrate::{self, Migrated, SCHEMA_VERSION};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Upgrades the configuration file in place to the current schema version.
+///
+/// # Arguments
+/// * `config_file` - A reference to a Path representing the configuration file.
+///
+/// # Returns
+/// The migrated schema, or `None` if the configuration file was already at the
+/// current schema version.
+///
+/// # Functionality
+/// - Migrates the configuration through every schema version in between.
+/// - Copies the original configuration file to `<config_file>.bak`.
+///
+/// The caller overwrites the configuration file with the migrated
+/// configuration, and only then reports it with `print_migrated`.
+pub fn migrate_config(config_file: &Path) -> Result<Option<Migrated>> {
+    let content = fs::read_to_string(config_file).map_err(|err| {
+        anyhow!(
+            r#"Could not find configuration file "{}": {err}"#,
+            config_file.display()
+        )
+    })?;
+
+    let migrated = migrate::migrate(serde_json::from_str(&content)?)?;
+    if !migrated.was_migrated() {
+        println!(
+            "{} Configuration is already at schema version {SCHEMA_VERSION}",
+            style("DONE").green().bold(),
+        );
+        return Ok(None);
+    }
+
+    let backup = backup_path(config_file);
+    fs::copy(config_file, &backup).map_err(|err| {
+        anyhow!(
+            r#"Could not back up configuration file to "{}": {err}"#,
+            backup.display()
+        )
+    })?;
+
+    Ok(Some(migrated))
+}
+
+/// Reports a migration once the migrated configuration has been written.
+///
+/// # Arguments
+/// * `config_file` - A reference to a Path representing the configuration file.
+/// * `migrated` - The migration returned by `migrate_config`.
+pub fn print_migrated(config_file: &Path, migrated: &Migrated) {
+    println!(
+        "{} Migrated configuration from schema version {} to {SCHEMA_VERSION}, \
+        original was saved to {:?}",
+        style("DONE").green().bold(),
+        migrated.from_version,
+        backup_path(config_file)
+    );
+    migrated.notes.iter().for_each(|note| {
+        println!("{} {note}", style("WARNING").yellow().bold())
+    });
+}
+
+/// Path the original configuration file is backed up to.
+fn backup_path(config_file: &Path) -> PathBuf {
+    config_file.with_extension("json.bak")
+}
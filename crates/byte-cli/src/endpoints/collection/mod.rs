@@ -1,3 +1,4 @@
 pub mod codegen;
 pub mod config;
 pub mod config_basic;
+pub mod migrate;
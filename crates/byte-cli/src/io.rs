@@ -2,6 +2,7 @@ use crate::{
     err::CliError,
     models::{
         effects::{MintEffects, Minted},
+        verification::SourceReport,
         Accounts,
     },
     SchemaBuilder,
@@ -10,7 +11,8 @@ use anyhow::{anyhow, Result};
 use gutenberg_types::Schema;
 use package_manager::Network;
 use package_manager::{
-    info::BuildInfo, package::PackageRegistry, toml::MoveToml,
+    info::BuildInfo, lock::LOCK_FILE_NAME, package::PackageRegistry,
+    toml::MoveToml,
 };
 use rust_sdk::{
     metadata::{GlobalMetadata, StorableMetadata},
@@ -23,7 +25,6 @@ use std::{
 };
 use uploader::writer::Storage;
 
-impl LocalRead for Schema {}
 impl LocalRead for Storage {}
 impl LocalRead for MoveToml {}
 impl LocalRead for PackageRegistry {}
@@ -42,6 +43,20 @@ impl LocalWrite for StorableMetadata {}
 impl LocalWrite for MintEffects {}
 impl LocalWrite for Minted {}
 impl LocalWrite for Accounts {}
+impl LocalWrite for SourceReport {}
+
+impl LocalRead for Schema {
+    /// Migrates configurations written in older schema versions, refusing
+    /// newer ones
+    fn read_json(path_buf: &PathBuf) -> Result<Self, CliError> {
+        let file = File::open(path_buf)?;
+        let config = serde_json::from_reader(file)?;
+        let migrated = gutenberg_types::migrate::migrate(config)
+            .map_err(anyhow::Error::from)?;
+
+        Ok(migrated.schema)
+    }
+}
 
 impl LocalRead for SchemaBuilder {
     fn read_json(path_buf: &PathBuf) -> Result<Self, CliError> {
@@ -151,26 +166,66 @@ pub trait LocalWrite: Serialize {
     }
 }
 
+/// Writes the JSON Schema of collection configuration files next to the
+/// configuration file at `schema_path`
+///
+/// # Returns
+/// Relative reference to the JSON Schema, to be embedded as `$schema` of the
+/// configuration file.
+pub fn write_json_schema(schema_path: &Path) -> Result<String, anyhow::Error> {
+    let path =
+        schema_path.with_file_name(gutenberg_types::JSON_SCHEMA_FILE_NAME);
+
+    // Create the parent directories if they don't exist
+    fs::create_dir_all(path.parent().unwrap())?;
+
+    fs::write(&path, gutenberg_types::json_schema_string()).map_err(|err| {
+        anyhow!(r#"Could not write file "{}": {err}"#, path.display())
+    })?;
+
+    Ok(format!("./{}", gutenberg_types::JSON_SCHEMA_FILE_NAME))
+}
+
+/// Path of the project state of the network, `project-<network>.json`.
+///
+/// Projects configured before networks other than mainnet and testnet were
+/// supported keep their state in `project.json` and `project-test.json`,
+/// which are used as long as they were not migrated.
 pub fn get_project_for_network(
     name: &str,
     path_opt: &Option<String>,
     network: &Network,
 ) -> PathBuf {
-    match network {
-        Network::Mainnet => get_project_filepath(name, path_opt),
-        Network::Testnet => get_project_test_filepath(name, path_opt),
-    }
-}
-
-pub fn get_project_filepath(name: &str, path_opt: &Option<String>) -> PathBuf {
-    get_file_path(name, path_opt, "configs", Some("project.json"))
+    let path = get_file_path(
+        name,
+        path_opt,
+        "configs",
+        Some(&format!("project-{network}.json")),
+    );
+
+    let legacy_file = match network {
+        Network::Mainnet => Some("project.json"),
+        Network::Testnet => Some("project-test.json"),
+        _ => None,
+    };
+
+    legacy_file
+        .map(|file| get_file_path(name, path_opt, "configs", Some(file)))
+        .filter(|legacy_path| !path.exists() && legacy_path.exists())
+        .unwrap_or(path)
 }
 
-pub fn get_project_test_filepath(
+pub fn get_source_report_path(
     name: &str,
     path_opt: &Option<String>,
+    network: &Network,
 ) -> PathBuf {
-    get_file_path(name, path_opt, "configs", Some("project-test.json"))
+    get_file_path(
+        name,
+        path_opt,
+        "reports",
+        Some(&format!("source-verification-{network}.json")),
+    )
 }
 
 pub fn get_schema_filepath(name: &str, path_opt: &Option<String>) -> PathBuf {
@@ -198,6 +253,21 @@ pub fn get_byte_path(path_opt: &Option<String>) -> PathBuf {
     filepath
 }
 
+/// Path of the custom networks, which map their names to their RPC URL and
+/// program registry.
+pub fn get_networks_path() -> PathBuf {
+    dirs::home_dir().unwrap().join(".byte/networks.json")
+}
+
+/// Parses the name of a built-in network or of a custom network.
+pub fn get_network(name: &str) -> Result<Network> {
+    Network::parse(name, &get_networks_path())
+}
+
+pub fn get_registry_dir() -> PathBuf {
+    dirs::home_dir().unwrap().join(".byte/registry")
+}
+
 pub fn get_metadata_path(name: &str, path_opt: &Option<String>) -> PathBuf {
     get_file_path(name, path_opt, "metadata", None)
 }
@@ -230,6 +300,31 @@ pub fn get_toml_path(name: &str, path_opt: &Option<String>) -> PathBuf {
     get_file_path(name, path_opt, "contract", Some("Move.toml"))
 }
 
+/// Reads and parses the `Move.toml` at `toml_path`.
+///
+/// # Returns
+/// The content of the manifest, which edits are applied to so that its
+/// formatting is kept, along with the parsed manifest.
+pub fn read_move_toml(toml_path: &Path) -> Result<(String, MoveToml)> {
+    let content = fs::read_to_string(toml_path).map_err(|err| {
+        anyhow!(r#"Could not read "{}": {err}"#, toml_path.display())
+    })?;
+
+    let move_toml = toml::from_str(&content).map_err(|err| {
+        anyhow!(r#"Could not parse "{}": {err}"#, toml_path.display())
+    })?;
+
+    Ok((content, move_toml))
+}
+
+pub fn get_lock_path(name: &str, path_opt: &Option<String>) -> PathBuf {
+    get_file_path(name, path_opt, "contract", Some(LOCK_FILE_NAME))
+}
+
+pub fn get_move_lock_path(name: &str, path_opt: &Option<String>) -> PathBuf {
+    get_file_path(name, path_opt, "contract", Some("Move.lock"))
+}
+
 pub fn get_build_info_path(
     name: &str,
     path_opt: &Option<String>,
@@ -238,7 +333,9 @@ pub fn get_build_info_path(
     // in the build folder, which is the case.PathBuf::from(Path::new(project_dir.as_str()));
     let mut build_info_path =
         get_file_path(name, path_opt, "contract/build", None);
-    let mut paths = fs::read_dir(&build_info_path).unwrap();
+    let mut paths = fs::read_dir(&build_info_path).map_err(|_| {
+        CliError::from(anyhow!("Could not find path to BuildInfo.yaml. Call `sui move build` to compile the Sui Move package"))
+    })?;
 
     if let Some(path) = paths.next() {
         build_info_path = path?.path();
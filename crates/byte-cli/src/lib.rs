@@ -4,15 +4,28 @@ pub mod err;
 pub mod io;
 pub mod models;
 
-use gutenberg_types::models::{collection::CollectionData, nft::NftData};
+use gutenberg_types::models::{
+    collection::CollectionData, launchpad::Launchpad, nft::NftData,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct SchemaBuilder {
-    #[serde(default)]
+    #[serde(
+        rename = "$schema",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub schema_url: Option<String>,
+    // Written as `package_name` by earlier versions
+    #[serde(default, alias = "package_name")]
     pub package_name: Option<String>,
     #[serde(default)]
     pub collection: Option<CollectionData>,
     #[serde(default)]
     pub nft: Option<NftData>,
+    // Kept as is when the configuration is rewritten
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launchpad: Option<Launchpad>,
 }
@@ -11,23 +11,30 @@ use byte::SchemaBuilder;
 use clap::Parser;
 use cli::{
     Cli, ClientCommands, CoinCommands, CollectionCommands, ImageCommands,
-    MoveCommands,
+    MoveCommands, RegistryCommands,
 };
+use console::style;
 use endpoints::collection::codegen;
 use endpoints::*;
+use gutenberg_types::models::address::Address;
 use io::LocalWrite;
-use package_manager::toml::{self as move_toml, MoveToml};
-use package_manager::{self, get_program_registry, Network};
+use package_manager::doctor::Doctor;
+use package_manager::document::MoveTomlDocument;
+use package_manager::info::{BuildInfo, MoveLock};
+use package_manager::lock::{Lockfile, LOCK_FILE_NAME};
+use package_manager::package::{Flavor, GitPath, PackageRegistry};
+use package_manager::registry;
+use package_manager::version::Version;
+use package_manager::{
+    self, get_merged_registries, get_merged_registry_at_head, Network,
+};
 use rust_sdk::coin;
+use rust_sdk::models::project::Project;
 use rust_sdk::utils::get_context;
 use std::env;
-use std::io::{Read, Write};
 use std::path::Path;
 use std::str::FromStr;
-use std::{
-    fs::{self, File},
-    path::PathBuf,
-};
+use std::{fs, path::PathBuf};
 use sui_sdk::types::base_types::ObjectID;
 use uploader::writer::Storage;
 
@@ -56,12 +63,16 @@ async fn run() -> Result<()> {
             match cmd {
                 CollectionCommands::ConfigBasic { name, project_dir } => {
                     // Input
-                    let project_path =
-                        io::get_project_filepath(name.as_str(), &project_dir);
+                    let project_path = io::get_project_for_network(
+                        name.as_str(),
+                        &project_dir,
+                        &Network::Mainnet,
+                    );
 
-                    let project_test_path = io::get_project_test_filepath(
+                    let project_test_path = io::get_project_for_network(
                         name.as_str(),
                         &project_dir,
+                        &Network::Testnet,
                     );
 
                     let schema_path =
@@ -69,18 +80,23 @@ async fn run() -> Result<()> {
 
                     // TODO: Add check to see if collection has already been created
 
-                    let (schema, project) =
+                    let (mut schema, project) =
                         collection::config_basic::init_schema(&name).await?;
 
                     // Output
+                    schema.schema_url =
+                        Some(io::write_json_schema(&schema_path)?);
                     schema.write_json(&schema_path)?;
                     project.write_json(&project_path)?;
                     project.write_json(&project_test_path)?;
                 }
                 CollectionCommands::Config { name, project_dir } => {
                     // Input
-                    let project_path =
-                        io::get_project_filepath(name.as_str(), &project_dir);
+                    let project_path = io::get_project_for_network(
+                        name.as_str(),
+                        &project_dir,
+                        &Network::Mainnet,
+                    );
                     let schema_path =
                         io::get_schema_filepath(name.as_str(), &project_dir);
 
@@ -93,6 +109,8 @@ async fn run() -> Result<()> {
                             .await?;
 
                     // Output
+                    builder.schema_url =
+                        Some(io::write_json_schema(&schema_path)?);
                     builder.write_json(&schema_path)?;
                     project.write_json(&project_path)?;
                 }
@@ -108,6 +126,27 @@ async fn run() -> Result<()> {
                     codegen::gen_contract(contract_dir.as_path(), &schema)
                         .await?;
                 }
+                CollectionCommands::Migrate { name, project_dir } => {
+                    // Input
+                    let schema_path =
+                        io::get_schema_filepath(name.as_str(), &project_dir);
+
+                    // Logic
+                    let migrated =
+                        collection::migrate::migrate_config(&schema_path)?;
+
+                    // Output
+                    if let Some(mut migrated) = migrated {
+                        migrated.schema.schema_url =
+                            Some(io::write_json_schema(&schema_path)?);
+                        migrated.schema.write_json(&schema_path)?;
+
+                        collection::migrate::print_migrated(
+                            &schema_path,
+                            &migrated,
+                        );
+                    }
+                }
             }
         }
         Cli::Images { cmd } => {
@@ -152,10 +191,10 @@ async fn run() -> Result<()> {
                 project_dir,
                 gas_coin,
                 gas_budget,
+                rpc_urls,
             } => {
                 // Input
-                let network = Network::from_str(network.as_str())
-                    .map_err(|err| anyhow!("Invalid network: {:?}", err))?;
+                let network = io::get_network(network.as_str())?;
 
                 let project_path = io::get_project_for_network(
                     name.as_str(),
@@ -166,10 +205,42 @@ async fn run() -> Result<()> {
                 let contract_dir =
                     io::get_contract_path(name.as_str(), &project_dir);
 
+                let toml_path = io::get_toml_path(name.as_str(), &project_dir);
+                let lock_path = io::get_lock_path(name.as_str(), &project_dir);
+
+                let (_, move_toml) = io::read_move_toml(&toml_path)?;
+
                 // Logic
-                let mut state = client::deploy_contract::parse_state(
-                    project_path.as_path(),
-                )?;
+                package_manager::env::check_flavor(&move_toml, &network)?;
+
+                if lock_path.exists() {
+                    let lockfile = Lockfile::read(&lock_path)?;
+
+                    lockfile.drift(&move_toml).iter().for_each(|drift| {
+                        println!(
+                            "WARNING: Move.toml has drifted from {LOCK_FILE_NAME}: {drift}"
+                        )
+                    });
+                }
+
+                let mut state = match project_path.exists() {
+                    true => client::deploy_contract::parse_state(
+                        project_path.as_path(),
+                    )?,
+                    // First release on the network, such as a rehearsal on
+                    // localnet, starts from the project of mainnet
+                    false => {
+                        let project = client::deploy_contract::parse_state(
+                            &io::get_project_for_network(
+                                name.as_str(),
+                                &project_dir,
+                                &Network::Mainnet,
+                            ),
+                        )?;
+
+                        Project::new(project.name, project.project_owner)
+                    }
+                };
 
                 client::deploy_contract::publish_contract(
                     &mut state,
@@ -177,11 +248,29 @@ async fn run() -> Result<()> {
                     gas_budget,
                     network,
                     &contract_dir,
+                    &rpc_urls,
                 )
                 .await?;
 
                 // IO Write
                 state.write_json(&project_path)?;
+
+                if let Some(package_id) = state.package_id {
+                    // Rehearsals must not overwrite the addresses of testnet
+                    match network.release_flavor() {
+                        Ok(flavor) => {
+                            let package_id =
+                                Address::new(&package_id.to_string())?;
+                            package_manager::env::record_publish(
+                                &contract_dir,
+                                flavor,
+                                &package_id,
+                                &package_id,
+                            )?;
+                        }
+                        Err(err) => println!("WARNING: {err}"),
+                    }
+                }
             }
             ClientCommands::CreateWarehouse {
                 name,
@@ -191,8 +280,7 @@ async fn run() -> Result<()> {
                 gas_budget,
             } => {
                 // Input
-                let network = Network::from_str(network.as_str())
-                    .map_err(|err| anyhow!("Invalid network: {:?}", err))?;
+                let network = io::get_network(network.as_str())?;
 
                 let project_path = io::get_project_for_network(
                     name.as_str(),
@@ -230,6 +318,7 @@ async fn run() -> Result<()> {
                 gas_budget,
                 warehouse_id,
                 mint_cap_id,
+                rpc_urls,
             } => {
                 // Input
                 let schema_path =
@@ -238,8 +327,7 @@ async fn run() -> Result<()> {
                 let (_, post_upload) =
                     io::get_upload_metadata(name.as_str(), &project_dir);
 
-                let network = Network::from_str(network.as_str())
-                    .map_err(|err| anyhow!("Invalid network: {:?}", err))?;
+                let network = io::get_network(network.as_str())?;
 
                 let project_path = io::get_project_for_network(
                     name.as_str(),
@@ -268,6 +356,199 @@ async fn run() -> Result<()> {
                     amount,
                     batches,
                     &network,
+                    &rpc_urls,
+                )
+                .await?;
+
+                // Output
+                state.write_json(&project_path)?;
+            }
+            ClientCommands::VerifySource {
+                name,
+                network,
+                project_dir,
+            } => {
+                // Input
+                let network = io::get_network(network.as_str())?;
+
+                let project_path = io::get_project_for_network(
+                    name.as_str(),
+                    &project_dir,
+                    &network,
+                );
+                let report_path = io::get_source_report_path(
+                    name.as_str(),
+                    &project_dir,
+                    &network,
+                );
+
+                let contract_dir =
+                    io::get_contract_path(name.as_str(), &project_dir);
+
+                let state = client::deploy_contract::parse_state(
+                    project_path.as_path(),
+                )?;
+
+                // Logic
+                let report = client::verify_source::verify_source(
+                    name.as_str(),
+                    &state,
+                    network,
+                    &contract_dir,
+                )
+                .await?;
+
+                // Output
+                report.write_json(&report_path)?;
+
+                match &report.error {
+                    None => println!(
+                        "{} Package {} matches its on-chain bytecode, report \
+                        written to {}",
+                        style("DONE").green().bold(),
+                        report.package_id,
+                        report_path.display()
+                    ),
+                    Some(err) => {
+                        return Err(anyhow!(
+                            "Package {} does not match its on-chain bytecode: \
+                            {err}\nReport written to {}",
+                            report.package_id,
+                            report_path.display()
+                        ))
+                    }
+                }
+            }
+            ClientCommands::Sync {
+                name,
+                network,
+                package_id,
+                project_dir,
+                yes,
+                rpc_urls,
+            } => {
+                // Input
+                let network = io::get_network(network.as_str())?;
+
+                let project_path = io::get_project_for_network(
+                    name.as_str(),
+                    &project_dir,
+                    &network,
+                );
+
+                let state = match project_path.exists() {
+                    true => Some(client::deploy_contract::parse_state(
+                        project_path.as_path(),
+                    )?),
+                    false => None,
+                };
+
+                // Logic
+                let synced = client::sync::sync_project(
+                    name.as_str(),
+                    state.as_ref(),
+                    package_id,
+                    &network,
+                    &rpc_urls,
+                )
+                .await?;
+
+                let diff = client::sync::project_diff(state.as_ref(), &synced)?;
+
+                // Output
+                if client::sync::confirm_overwrite(&project_path, &diff, yes)? {
+                    synced.write_json(&project_path)?;
+
+                    println!(
+                        "{} Project state written to {}",
+                        style("DONE").green().bold(),
+                        project_path.display()
+                    );
+                }
+            }
+            ClientCommands::TransferAdmin {
+                name,
+                network,
+                to,
+                caps,
+                upgrade_policy,
+                project_dir,
+                gas_coin,
+                gas_budget,
+                rpc_urls,
+            } => {
+                // Input
+                let network = io::get_network(network.as_str())?;
+
+                let project_path = io::get_project_for_network(
+                    name.as_str(),
+                    &project_dir,
+                    &network,
+                );
+
+                let mut state = client::deploy_contract::parse_state(
+                    project_path.as_path(),
+                )?;
+
+                // Logic
+                client::transfer_admin::transfer_admin(
+                    &mut state,
+                    to,
+                    caps,
+                    upgrade_policy,
+                    gas_coin,
+                    gas_budget,
+                    &network,
+                    &rpc_urls,
+                )
+                .await?;
+
+                // Output
+                state.write_json(&project_path)?;
+            }
+            ClientCommands::CreateListing {
+                name,
+                network,
+                project_dir,
+                warehouse_id,
+                start_sale,
+                gas_coin,
+                gas_budget,
+                rpc_urls,
+            } => {
+                // Input
+                let network = io::get_network(network.as_str())?;
+
+                let project_path = io::get_project_for_network(
+                    name.as_str(),
+                    &project_dir,
+                    &network,
+                );
+
+                let schema_path =
+                    io::get_schema_filepath(name.as_str(), &project_dir);
+
+                let schema = codegen::parse_config(schema_path.as_path())?;
+                let mut state = client::deploy_contract::parse_state(
+                    project_path.as_path(),
+                )?;
+
+                let (registry, _) = get_merged_registry_at_head(
+                    &network,
+                    &io::get_registry_dir(),
+                )?;
+
+                // Logic
+                client::create_listing::create_listing(
+                    &schema,
+                    &mut state,
+                    &registry,
+                    warehouse_id,
+                    start_sale,
+                    gas_coin,
+                    gas_budget,
+                    &network,
+                    &rpc_urls,
                 )
                 .await?;
 
@@ -396,80 +677,277 @@ async fn run() -> Result<()> {
                     let toml_path =
                         io::get_toml_path(name.as_str(), &project_dir);
 
-                    let network_ = network.as_str();
-
-                    let network = Network::from_str(network_)
-                        .map_err(|err| anyhow!("Invalid network: {:?}", err))?;
+                    let network = io::get_network(network.as_str())?;
 
-                    let registry = get_program_registry(&network)?;
+                    let (registry, commit) = get_merged_registry_at_head(
+                        &network,
+                        &io::get_registry_dir(),
+                    )?;
 
                     // Logic
-                    let toml_string: String =
-                        fs::read_to_string(toml_path.clone())?.parse()?;
+                    let (toml_string, mut move_toml) =
+                        io::read_move_toml(&toml_path)?;
+
+                    package_manager::env::check_flavor(&move_toml, &network)?;
+
+                    let resolution = move_toml.update_toml(&registry)?;
+                    let lockfile = Lockfile::new(
+                        &registry,
+                        commit,
+                        &resolution,
+                        &move_toml,
+                    );
+
+                    let mut document =
+                        MoveTomlDocument::from_str(toml_string.as_str())?;
+                    document.set_dependencies(&move_toml.dependencies)?;
+
+                    // Output
+                    package_manager::env::write_manifest(
+                        &io::get_contract_path(name.as_str(), &project_dir),
+                        &document,
+                    )?;
+
+                    lockfile.write(&io::get_lock_path(
+                        name.as_str(),
+                        &project_dir,
+                    ))?;
+                }
+                MoveCommands::Install {
+                    name,
+                    network,
+                    project_dir,
+                    locked,
+                } => {
+                    // Input
+                    let toml_path =
+                        io::get_toml_path(name.as_str(), &project_dir);
+                    let lock_path =
+                        io::get_lock_path(name.as_str(), &project_dir);
+
+                    let network = io::get_network(network.as_str())?;
 
-                    let mut move_toml: MoveToml =
-                        toml::from_str(toml_string.as_str()).unwrap();
+                    let (toml_string, mut move_toml) =
+                        io::read_move_toml(&toml_path)?;
 
-                    if let Some(flavor) = move_toml.package.flavor {
-                        if network_ != flavor.to_str() {
+                    package_manager::env::check_flavor(&move_toml, &network)?;
+
+                    if locked {
+                        if !lock_path.exists() {
                             return Err(anyhow!(
-                                "Network '{}' does not match flavor '{}'",
-                                network,
-                                flavor.to_str()
+                                "Could not find {LOCK_FILE_NAME}, run \
+                                `byte mpm update-dependencies` or \
+                                `byte mpm install` without `--locked` first"
                             ));
                         }
+
+                        // Logic
+                        let lockfile = Lockfile::read(&lock_path)?;
+                        lockfile.apply(&mut move_toml)?;
+
+                        let mut document =
+                            MoveTomlDocument::from_str(toml_string.as_str())?;
+                        document.set_dependencies(&move_toml.dependencies)?;
+
+                        // Output
+                        package_manager::env::write_manifest(
+                            &io::get_contract_path(name.as_str(), &project_dir),
+                            &document,
+                        )?;
+
+                        println!(
+                            "Restored dependencies from registry commit {}",
+                            lockfile.registry_commit
+                        );
+                    } else {
+                        // Logic
+                        let (registry, commit) = get_merged_registry_at_head(
+                            &network,
+                            &io::get_registry_dir(),
+                        )?;
+
+                        let resolution =
+                            move_toml.resolve_current(&registry)?;
+                        let lockfile = Lockfile::new(
+                            &registry,
+                            commit,
+                            &resolution,
+                            &move_toml,
+                        );
+
+                        // Output
+                        lockfile.write(&lock_path)?;
                     }
+                }
+                MoveCommands::Add {
+                    name,
+                    network,
+                    package,
+                    project_dir,
+                } => {
+                    // Input
+                    let toml_path =
+                        io::get_toml_path(name.as_str(), &project_dir);
 
-                    move_toml.update_toml(&registry);
+                    let network = io::get_network(network.as_str())?;
 
-                    let mut toml_string = toml::to_string_pretty(&move_toml)?;
+                    let (package, version) = match package.split_once('@') {
+                        Some((package, version)) => (
+                            package.to_string(),
+                            Some(Version::from_str(version)?),
+                        ),
+                        None => (package, None),
+                    };
+
+                    let (registry, commit) = get_merged_registry_at_head(
+                        &network,
+                        &io::get_registry_dir(),
+                    )?;
+
+                    // Logic
+                    let (toml_string, mut move_toml) =
+                        io::read_move_toml(&toml_path)?;
+
+                    package_manager::env::check_flavor(&move_toml, &network)?;
+
+                    let (added, resolution) = move_toml.add_dependency(
+                        &registry,
+                        package.as_str(),
+                        version.as_ref(),
+                    )?;
+                    let lockfile = Lockfile::new(
+                        &registry,
+                        commit,
+                        &resolution,
+                        &move_toml,
+                    );
 
-                    toml_string =
-                        move_toml::add_vertical_spacing(toml_string.as_str());
+                    let mut document =
+                        MoveTomlDocument::from_str(toml_string.as_str())?;
+                    document.set_dependencies(&move_toml.dependencies)?;
+                    document.set_addresses(&move_toml.addresses)?;
 
                     // Output
-                    let mut file = File::create(toml_path)?;
-                    file.write_all(toml_string.as_bytes())?;
+                    package_manager::env::write_manifest(
+                        &io::get_contract_path(name.as_str(), &project_dir),
+                        &document,
+                    )?;
+
+                    lockfile.write(&io::get_lock_path(
+                        name.as_str(),
+                        &project_dir,
+                    ))?;
+
+                    println!(
+                        "{}{package} version {added}",
+                        style("Added ").green().bold().on_bright(),
+                    );
                 }
-                MoveCommands::CheckDependencies {
+                MoveCommands::Remove {
                     name,
                     network,
+                    package,
                     project_dir,
                 } => {
                     // Input
                     let toml_path =
                         io::get_toml_path(name.as_str(), &project_dir);
 
-                    let network_ = network.as_str();
-
-                    let network = Network::from_str(network_)
-                        .map_err(|err| anyhow!("Invalid network: {:?}", err))?;
+                    let network = io::get_network(network.as_str())?;
 
-                    let registry = get_program_registry(&network)?;
+                    let (registry, commit) = get_merged_registry_at_head(
+                        &network,
+                        &io::get_registry_dir(),
+                    )?;
 
                     // Logic
-                    let toml_string: String =
-                        fs::read_to_string(toml_path.clone())?.parse()?;
+                    let (toml_string, mut move_toml) =
+                        io::read_move_toml(&toml_path)?;
 
-                    let move_toml: MoveToml =
-                        toml::from_str(toml_string.as_str()).unwrap();
+                    package_manager::env::check_flavor(&move_toml, &network)?;
 
-                    if let Some(flavor) = move_toml.package.flavor {
-                        if network_ != flavor.to_str() {
-                            return Err(anyhow!(
-                                "Network '{}' does not match flavor '{}'",
-                                network,
-                                flavor.to_str()
-                            ));
-                        }
-                    }
+                    move_toml.remove_dependency(package.as_str())?;
+
+                    let resolution = move_toml.resolve_current(&registry)?;
+                    let lockfile = Lockfile::new(
+                        &registry,
+                        commit,
+                        &resolution,
+                        &move_toml,
+                    );
+
+                    let mut document =
+                        MoveTomlDocument::from_str(toml_string.as_str())?;
+                    document.set_dependencies(&move_toml.dependencies)?;
+                    document.set_addresses(&move_toml.addresses)?;
+
+                    // Output
+                    package_manager::env::write_manifest(
+                        &io::get_contract_path(name.as_str(), &project_dir),
+                        &document,
+                    )?;
+
+                    lockfile.write(&io::get_lock_path(
+                        name.as_str(),
+                        &project_dir,
+                    ))?;
+
+                    println!(
+                        "{}{package}",
+                        style("Removed ").green().bold().on_bright(),
+                    );
+                }
+                MoveCommands::List {
+                    name,
+                    network,
+                    project_dir,
+                } => {
+                    // Input
+                    let toml_path =
+                        io::get_toml_path(name.as_str(), &project_dir);
+
+                    let network = io::get_network(network.as_str())?;
+
+                    let (registry, _) = get_merged_registry_at_head(
+                        &network,
+                        &io::get_registry_dir(),
+                    )?;
 
-                    move_toml.check_updates(&registry);
+                    let (_, move_toml) = io::read_move_toml(&toml_path)?;
+
+                    package_manager::env::check_flavor(&move_toml, &network)?;
+
+                    // Output
+                    move_toml.list_dependencies(&registry);
+                }
+                MoveCommands::CheckDependencies {
+                    name,
+                    network,
+                    project_dir,
+                } => {
+                    // Input
+                    let toml_path =
+                        io::get_toml_path(name.as_str(), &project_dir);
+
+                    let network = io::get_network(network.as_str())?;
+
+                    let (registry, _) = get_merged_registry_at_head(
+                        &network,
+                        &io::get_registry_dir(),
+                    )?;
+
+                    // Logic
+                    let (_, move_toml) = io::read_move_toml(&toml_path)?;
+
+                    package_manager::env::check_flavor(&move_toml, &network)?;
+
+                    move_toml.check_updates(&registry)?;
                 }
                 MoveCommands::LoadEnv {
                     name,
                     network,
                     project_dir,
+                    force,
                 } => {
                     let mut project_dir = match project_dir {
                         Some(pj_dir) => {
@@ -490,37 +968,251 @@ async fn run() -> Result<()> {
                         project_dir.push(format!("{}/contract", name));
                     }
 
-                    let network = Network::from_str(network.as_str())
-                        .map_err(|err| anyhow!("Invalid network: {:?}", err))?;
+                    let network = io::get_network(network.as_str())?;
 
-                    let flavours_path = match network {
-                        Network::Mainnet => {
-                            project_dir.join("flavours/Move-main.toml")
-                        }
-                        Network::Testnet => {
-                            project_dir.join("flavours/Move-test.toml")
-                        }
-                    };
+                    // Logic
+                    package_manager::env::load(
+                        &project_dir,
+                        network.flavor(),
+                        force,
+                    )?;
 
-                    println!("Project dir: {:?}", project_dir);
-                    println!("flavours_path: {:?}", flavours_path);
+                    println!("Loaded {network} manifest into Move.toml");
+                }
+                MoveCommands::Doctor {
+                    name,
+                    network,
+                    project_dir,
+                } => {
+                    // Input
+                    let network = io::get_network(network.as_str())?;
+
+                    let (_, move_toml) = io::read_move_toml(
+                        &io::get_toml_path(name.as_str(), &project_dir),
+                    )?;
+
+                    let move_lock_path =
+                        io::get_move_lock_path(name.as_str(), &project_dir);
+                    let move_lock = move_lock_path
+                        .exists()
+                        .then(|| MoveLock::read(&move_lock_path))
+                        .transpose()?;
+
+                    let build_info =
+                        io::get_build_info_path(name.as_str(), &project_dir)
+                            .ok()
+                            .filter(|path| path.exists())
+                            .map(|path| BuildInfo::read_yaml(&path))
+                            .transpose()?;
+
+                    let (main_registry, test_registry) =
+                        get_merged_registries(&io::get_registry_dir())?;
+                    let (registry, other_registry) = match network.flavor() {
+                        Flavor::Mainnet => (&main_registry, &test_registry),
+                        Flavor::Testnet => (&test_registry, &main_registry),
+                    };
 
                     // Logic
-                    // Open the source file for reading
-                    let mut source_file = fs::File::open(flavours_path)?;
+                    let issues = Doctor {
+                        flavor: network.flavor(),
+                        move_toml: &move_toml,
+                        move_lock: move_lock.as_ref(),
+                        build_info: build_info.as_ref(),
+                        registry,
+                        other_registry,
+                    }
+                    .diagnose();
 
-                    // Create or open the destination file for writing
-                    let mut destination_file =
-                        fs::File::create(project_dir.join("Move.toml"))?;
+                    // Output
+                    if issues.is_empty() {
+                        println!("No issues found");
+                    } else {
+                        for issue in issues.iter() {
+                            println!(
+                                "{} {issue}\n  {} {}",
+                                style("✗").red().bold(),
+                                style("fix:").bold(),
+                                issue.fix()
+                            );
+                        }
 
-                    // Read the contents of the source file
-                    let mut buffer = Vec::new();
-                    source_file.read_to_end(&mut buffer)?;
+                        return Err(anyhow!("Found {} issues", issues.len()));
+                    }
+                }
+                MoveCommands::VerifyRegistry { network } => {
+                    // Input
+                    let network = io::get_network(network.as_str())?;
+
+                    let (registry, _) = get_merged_registry_at_head(
+                        &network,
+                        &io::get_registry_dir(),
+                    )?;
+
+                    // Logic
+                    let issues = client::verify_registry::verify_registry(
+                        &registry, network,
+                    )
+                    .await?;
 
                     // Output
-                    // Write the contents to the destination file
-                    destination_file.write_all(&buffer)?;
+                    if issues.is_empty() {
+                        println!("No issues found");
+                    } else {
+                        for issue in issues.iter() {
+                            println!("{} {issue}", style("✗").red().bold());
+                        }
+
+                        return Err(anyhow!("Found {} issues", issues.len()));
+                    }
                 }
+                MoveCommands::Registry { cmd } => match cmd {
+                    RegistryCommands::Init { network } => {
+                        let network = io::get_network(network.as_str())?;
+
+                        let registry_dir = io::get_registry_dir();
+                        let registry_path = registry::registry_path(
+                            &registry_dir,
+                            network.release_flavor()?,
+                        );
+
+                        if registry_path.exists() {
+                            return Err(anyhow!(
+                                "Private registry already exists at {}",
+                                registry_path.display()
+                            ));
+                        }
+
+                        // Output
+                        fs::create_dir_all(&registry_dir)?;
+                        PackageRegistry(Default::default())
+                            .write(&registry_path)?;
+
+                        println!(
+                            "Created private registry at {}",
+                            registry_path.display()
+                        );
+                    }
+                    RegistryCommands::Add {
+                        name,
+                        network,
+                        git,
+                        subdir,
+                        rev,
+                        project_dir,
+                    } => {
+                        // Input
+                        let network = io::get_network(network.as_str())?;
+                        let flavor = network.release_flavor()?;
+
+                        let registry_path = registry::registry_path(
+                            &io::get_registry_dir(),
+                            flavor,
+                        );
+                        let mut private =
+                            PackageRegistry::read(&registry_path, flavor)
+                                .map_err(|err| {
+                                    anyhow!(
+                                        "{err}, call `byte mpm registry init \
+                                {network}` to create it"
+                                    )
+                                })?;
+
+                        let (registry, _) = get_merged_registry_at_head(
+                            &network,
+                            &io::get_registry_dir(),
+                        )?;
+
+                        let (_, move_toml) = io::read_move_toml(
+                            &io::get_toml_path(name.as_str(), &project_dir),
+                        )?;
+
+                        package_manager::env::check_flavor(
+                            &move_toml, &network,
+                        )?;
+
+                        let build_info =
+                            BuildInfo::read_yaml(&io::get_build_info_path(
+                                name.as_str(),
+                                &project_dir,
+                            )?)?;
+
+                        let project =
+                            Project::read_json(&io::get_project_for_network(
+                                name.as_str(),
+                                &project_dir,
+                                &network,
+                            ))?;
+
+                        let published_at =
+                            project.package_id.ok_or_else(|| {
+                                anyhow!(
+                                    "Project '{name}' has not been published \
+                                    to {network}"
+                                )
+                            })?;
+
+                        // Logic
+                        let entry = registry::package_entry(
+                            &registry,
+                            &move_toml,
+                            build_info,
+                            GitPath::new(git, subdir, rev),
+                            Address::new(&published_at.to_string())?,
+                        )?;
+
+                        let registered = format!(
+                            "{} {}",
+                            entry.package.name, entry.package.version
+                        );
+                        private.insert(entry)?;
+
+                        // Output
+                        private.write(&registry_path)?;
+                        println!("Registered {registered}");
+                    }
+                    RegistryCommands::List { network } => {
+                        let network = io::get_network(network.as_str())?;
+
+                        let registry = PackageRegistry::read(
+                            &registry::registry_path(
+                                &io::get_registry_dir(),
+                                network.flavor(),
+                            ),
+                            network.flavor(),
+                        )?;
+
+                        // Output
+                        for (name, versions) in registry.0.iter() {
+                            let versions = versions
+                                .keys()
+                                .map(ToString::to_string)
+                                .collect::<Vec<_>>()
+                                .join(", ");
+
+                            println!("{name}: {versions}");
+                        }
+                    }
+                    RegistryCommands::Merge { network, path } => {
+                        // Input
+                        let network = io::get_network(network.as_str())?;
+                        let flavor = network.release_flavor()?;
+
+                        let registry_path = registry::registry_path(
+                            &io::get_registry_dir(),
+                            flavor,
+                        );
+                        let mut private =
+                            PackageRegistry::read(&registry_path, flavor)?;
+                        let other =
+                            PackageRegistry::read(Path::new(&path), flavor)?;
+
+                        // Logic
+                        private.merge(other)?;
+
+                        // Output
+                        private.write(&registry_path)?;
+                    }
+                },
             }
         }
     }
@@ -2,6 +2,7 @@ pub mod collection;
 pub mod effects;
 pub mod nft;
 pub mod royalties;
+pub mod verification;
 
 use crate::{
     consts::{MAX_SYMBOL_LENGTH, TX_SENDER_ADDRESS},
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// `SourceReport` records the verification of a published collection against
+/// its on-chain bytecode, to be attached to audits and marketplace
+/// verification requests.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceReport {
+    /// The name of the NFT collection.
+    pub collection: String,
+    /// The network the package is published on.
+    pub network: String,
+    /// The ID the package is published at, as recorded in `project.json`.
+    pub package_id: String,
+    /// The name of the Move package.
+    pub package_name: String,
+    /// Hex digest of the rebuilt modules and the IDs of their dependencies.
+    pub package_digest: String,
+    /// Names of the rebuilt modules.
+    pub modules: Vec<String>,
+    /// Published dependencies the package was verified against.
+    pub dependencies: Vec<DependencyReport>,
+    /// Whether the package and its dependencies match the on-chain bytecode.
+    pub verified: bool,
+    /// Reason the verification failed, if it did.
+    pub error: Option<String>,
+}
+
+/// `DependencyReport` records a dependency of the verified package, along
+/// with the source revision it was rebuilt from.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyReport {
+    /// The name of the dependency.
+    pub name: String,
+    /// The on-chain ID of the dependency.
+    pub package_id: String,
+    /// The git repository of the dependency sources, if fetched from git.
+    pub git: Option<String>,
+    /// The directory of the dependency within the git repository.
+    pub subdir: Option<String>,
+    /// The git revision of the dependency sources.
+    pub rev: Option<String>,
+}
@@ -1,7 +1,18 @@
+pub mod migrate;
 pub mod models;
 mod schema;
 
-pub use schema::Schema;
+pub use schema::{
+    json_schema, json_schema_string, Schema, JSON_SCHEMA_FILE_NAME,
+};
+
+/// Reserved keywords of Move, which may not be used as identifiers.
+pub const MOVE_KEYWORDS: [&str; 28] = [
+    "abort", "acquires", "as", "break", "const", "continue", "copy", "else",
+    "enum", "false", "for", "friend", "fun", "has", "if", "let", "loop",
+    "match", "module", "move", "mut", "native", "public", "return", "struct",
+    "true", "use", "while",
+];
 
 /// Normalizes a given type name into a valid Move language type name.
 ///
@@ -0,0 +1,360 @@
+//! Upgrades configuration files written in older schema versions
+//!
+//! Configurations are migrated as untyped JSON, one version at a time, until
+//! they reach [`SCHEMA_VERSION`] and can be deserialized into a [`Schema`].
+//!
+//! Versions:
+//! 1. Legacy layout with `Collection`, `Nft` and `Settings` sections
+//! 2. Current layout, `schemaVersion` may be omitted
+use crate::{normalize_type, Schema};
+use serde_json::{json, Map, Value};
+
+/// Schema version of configurations understood by this release
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Migration from the version at index `i + 1` to the version `i + 2`
+type Migration = fn(Value, &mut Vec<String>) -> Result<Value, String>;
+
+const MIGRATIONS: [Migration; SCHEMA_VERSION as usize - 1] = [v1_to_v2];
+
+#[derive(thiserror::Error, Debug)]
+pub enum MigrationError {
+    #[error(
+        "Configuration has schema version {0}, but only versions up to \
+        {SCHEMA_VERSION} are supported, upgrade to a newer release"
+    )]
+    Unsupported(u32),
+    #[error(
+        "Could not migrate configuration from schema version {from}: {reason}"
+    )]
+    Invalid { from: u32, reason: String },
+    #[error(transparent)]
+    Deserialize(#[from] serde_json::Error),
+}
+
+/// Configuration upgraded to the current schema version
+#[derive(Debug)]
+pub struct Migrated {
+    pub schema: Schema,
+    /// Schema version the configuration was written in
+    pub from_version: u32,
+    /// Settings which could not be carried over to the current version
+    pub notes: Vec<String>,
+}
+
+impl Migrated {
+    /// Whether the configuration was written in an older schema version
+    pub fn was_migrated(&self) -> bool {
+        self.from_version != SCHEMA_VERSION
+    }
+}
+
+/// Schema version of the configuration
+///
+/// Configurations without `schemaVersion` are either in the legacy layout or
+/// predate the field being introduced in version 2.
+pub fn schema_version(config: &Value) -> Result<u32, MigrationError> {
+    if let Some(version) = config.get("schemaVersion") {
+        return version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version > 0)
+            .ok_or_else(|| MigrationError::Invalid {
+                from: 0,
+                reason: format!("Invalid `schemaVersion` {version}"),
+            });
+    }
+
+    let is_legacy = ["Collection", "Nft", "Settings"]
+        .iter()
+        .any(|section| config.get(section).is_some());
+
+    Ok(if is_legacy { 1 } else { 2 })
+}
+
+/// Upgrades the configuration to the current schema version and deserializes
+/// it
+pub fn migrate(mut config: Value) -> Result<Migrated, MigrationError> {
+    let from_version = schema_version(&config)?;
+    if from_version > SCHEMA_VERSION {
+        return Err(MigrationError::Unsupported(from_version));
+    }
+
+    let mut notes = Vec::new();
+    for (version, migration) in
+        (from_version..).zip(&MIGRATIONS[from_version as usize - 1..])
+    {
+        config = migration(config, &mut notes).map_err(|reason| {
+            MigrationError::Invalid {
+                from: version,
+                reason,
+            }
+        })?;
+    }
+
+    if let Value::Object(object) = &mut config {
+        object.insert("schemaVersion".to_string(), json!(SCHEMA_VERSION));
+    }
+
+    Ok(Migrated {
+        schema: serde_json::from_value(config)?,
+        from_version,
+        notes,
+    })
+}
+
+/// Moves the legacy `Collection`, `Nft` and `Settings` sections into
+/// `collection` and `nft`, and the listings of the legacy `Launchpad` into
+/// `launchpad`
+///
+/// Legacy configurations have no package or type name, so both are derived
+/// from the collection name.
+fn v1_to_v2(config: Value, notes: &mut Vec<String>) -> Result<Value, String> {
+    let mut config = into_object(config, "configuration")?;
+
+    let mut legacy_collection = into_object(
+        config.remove("Collection").unwrap_or_default(),
+        "Collection",
+    )?;
+    let legacy_nft =
+        into_object(config.remove("Nft").unwrap_or_default(), "Nft")?;
+    let mut settings =
+        into_object(config.remove("Settings").unwrap_or_default(), "Settings")?;
+    let mut legacy_launchpad = into_object(
+        config.remove("Launchpad").unwrap_or_default(),
+        "Launchpad",
+    )?;
+
+    let name = legacy_collection
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or("`Collection.name` is required")?
+        .to_string();
+
+    let mut collection = Map::new();
+    for key in ["name", "description", "symbol", "url", "creators"] {
+        if let Some(value) = legacy_collection.remove(key) {
+            collection.insert(key.to_string(), value);
+        }
+    }
+
+    let (supply, mint_cap) = match legacy_collection.remove("supply_policy") {
+        None => (json!("untracked"), json!("unlimited")),
+        Some(policy) => supply_policy(&policy)?,
+    };
+    collection.insert("supply".to_string(), supply);
+
+    if let Some(tags) = settings.remove("tags") {
+        collection.insert("tags".to_string(), tags);
+    }
+
+    if let Some(royalties) = settings.remove("royalties") {
+        collection.insert("royalties".to_string(), royalty_policy(royalties)?);
+    }
+
+    let mut nft = Map::new();
+    nft.insert("typeName".to_string(), json!(type_name(&name)));
+    nft.insert("mintCap".to_string(), mint_cap);
+    nft.insert("fields".to_string(), fields(&legacy_nft, notes));
+
+    if let Some(policies) = settings.remove("mint_policies") {
+        let mut policies = into_object(policies, "Settings.mint_policies")?;
+        if policies.remove("direct") == Some(Value::Bool(true)) {
+            notes.push("Direct minting is no longer supported".to_string());
+        }
+        nft.insert("mintPolicies".to_string(), Value::Object(policies));
+    }
+
+    // Remaining settings were never used for code generation
+    notes.extend(
+        settings
+            .into_iter()
+            .filter(|(_, value)| {
+                !matches!(value, Value::Null | Value::Bool(false))
+            })
+            .map(|(key, _)| format!("Dropped `Settings.{key}`")),
+    );
+    notes.extend(
+        legacy_launchpad
+            .iter()
+            .filter(|(key, value)| *key != "listings" && !value.is_null())
+            .map(|(key, _)| format!("Dropped `Launchpad.{key}`")),
+    );
+    notes.extend(config.keys().map(|key| {
+        format!(
+            "Dropped `{key}`, it is not part of the collection configuration"
+        )
+    }));
+
+    let mut migrated = json!({
+        "packageName": normalize_type(&name).to_lowercase(),
+        "collection": collection,
+        "nft": nft,
+    });
+
+    // Listings kept the same layout
+    if let Some(listings) = legacy_launchpad.remove("listings") {
+        migrated["launchpad"] = json!({ "listings": listings });
+    }
+
+    Ok(migrated)
+}
+
+/// Maps legacy `supply_policy` into collection supply and NFT mint cap
+fn supply_policy(policy: &Value) -> Result<(Value, Value), String> {
+    if policy == "Unlimited" {
+        return Ok((json!("untracked"), json!("unlimited")));
+    }
+
+    let max = policy
+        .get("Limited")
+        .and_then(|limited| limited.get("max").unwrap_or(limited).as_u64())
+        .ok_or_else(|| format!("Unsupported `supply_policy` {policy}"))?;
+
+    Ok((json!({ "enforced": max }), json!(max)))
+}
+
+/// Maps legacy `Proportional` royalties with `share` and `bps`
+fn royalty_policy(royalties: Value) -> Result<Value, String> {
+    let mut royalties = into_object(royalties, "Settings.royalties")?;
+    let proportional = royalties
+        .remove("Proportional")
+        .ok_or("Only `Proportional` royalties are supported")?;
+    let mut proportional = into_object(proportional, "Proportional")?;
+
+    let shares = match proportional.remove("shares") {
+        Some(Value::Array(shares)) => shares
+            .into_iter()
+            .map(|share| {
+                let share = into_object(share, "Proportional.shares")?;
+                Ok(json!({
+                    "address": share.get("address"),
+                    "shareBps": share.get("share"),
+                }))
+            })
+            .collect::<Result<Vec<_>, String>>()?,
+        _ => Vec::new(),
+    };
+
+    Ok(json!({
+        "proportional": {
+            "shares": shares,
+            "collectionRoyaltyBps": proportional.remove("bps").unwrap_or(json!(0)),
+        }
+    }))
+}
+
+/// Maps legacy NFT display flags into fields
+fn fields(legacy_nft: &Map<String, Value>, notes: &mut Vec<String>) -> Value {
+    let enabled = |key: &str| legacy_nft.get(key) == Some(&Value::Bool(true));
+
+    let mut fields = Vec::new();
+    if enabled("display") {
+        fields.push(json!(["name", "String"]));
+        fields.push(json!(["description", "String"]));
+    }
+    if enabled("url") {
+        fields.push(json!(["url", "Url"]));
+    }
+    if enabled("attributes") {
+        fields.push(json!(["attributes", "Attributes"]));
+    }
+    if enabled("tags") {
+        notes.push("NFT level tags are no longer supported".to_string());
+    }
+
+    Value::Array(fields)
+}
+
+/// Pascal case type name derived from the collection name
+fn type_name(name: &str) -> String {
+    normalize_type(name)
+        .split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect()
+}
+
+fn into_object(value: Value, name: &str) -> Result<Map<String, Value>, String> {
+    match value {
+        Value::Object(object) => Ok(object),
+        Value::Null => Ok(Map::new()),
+        _ => Err(format!("Expected `{name}` to be an object")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::launchpad::market::Market;
+
+    #[test]
+    fn migrates_legacy_example() {
+        let config: Value =
+            serde_json::from_str(include_str!("../../../example/config.json"))
+                .unwrap();
+
+        let migrated = migrate(config).unwrap();
+        assert_eq!(migrated.from_version, 1);
+
+        let schema = migrated.schema;
+        assert_eq!(schema.schema_version, SCHEMA_VERSION);
+        assert_eq!(schema.package_name(), "suimarines");
+        assert_eq!(schema.nft.type_name, "Suimarines");
+        assert_eq!(schema.collection.creators.len(), 2);
+        assert_eq!(schema.collection.tags.unwrap().0.len(), 2);
+        assert_eq!(schema.nft.fields.keys().count(), 4);
+        assert!(schema.nft.mint_policies.has_launchpad());
+
+        let listings = schema.launchpad.unwrap().listings.0;
+        assert_eq!(listings.len(), 1);
+        assert!(matches!(
+            listings[0].markets.as_slice(),
+            [
+                Market::FixedPrice { price: 100, .. },
+                Market::DutchAuction {
+                    reserve_price: 200,
+                    ..
+                }
+            ]
+        ));
+
+        assert!(migrated
+            .notes
+            .iter()
+            .any(|note| note.contains("`Launchpad.marketplace`")));
+        assert!(!migrated.notes.iter().any(|note| note.contains("listings")));
+    }
+
+    #[test]
+    fn current_version_is_unchanged() {
+        let config = json!({
+            "packageName": "gnomes",
+            "nft": { "typeName": "Gnome", "mintCap": 100 }
+        });
+
+        let migrated = migrate(config).unwrap();
+        assert!(!migrated.was_migrated());
+        assert!(migrated.notes.is_empty());
+        assert_eq!(migrated.schema.schema_version, SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn rejects_newer_version() {
+        let config = json!({
+            "schemaVersion": SCHEMA_VERSION + 1,
+            "packageName": "gnomes",
+        });
+
+        assert!(matches!(
+            migrate(config),
+            Err(MigrationError::Unsupported(version)) if version == SCHEMA_VERSION + 1
+        ));
+    }
+}
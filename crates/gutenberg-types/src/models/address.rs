@@ -1,3 +1,8 @@
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Metadata, Schema, SchemaObject, StringValidation},
+    JsonSchema,
+};
 use serde::{
     de::{self, Unexpected, Visitor},
     Deserialize, Deserializer, Serialize, Serializer,
@@ -112,6 +117,30 @@ impl Serialize for Address {
     }
 }
 
+/// Address is a hex-encoded string of up to 32 bytes, with optional `0x`
+/// prefix, matching `Deserialize` for `Address`.
+impl JsonSchema for Address {
+    fn schema_name() -> String {
+        "Address".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                pattern: Some("^(0x)?[0-9a-fA-F]{0,64}$".to_string()),
+                ..Default::default()
+            })),
+            metadata: Some(Box::new(Metadata {
+                description: Some("32 byte hex-encoded address".to_string()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
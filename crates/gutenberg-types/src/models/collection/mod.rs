@@ -10,12 +10,13 @@ mod tags;
 use super::address::Address;
 use crate::deunicode;
 pub use royalties::{RoyaltyPolicy, Share};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 pub use supply::Supply;
 pub use tags::{Tag, Tags};
 
 /// Contains the metadata fields of the collection
-#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CollectionData {
     /// The name of the collection
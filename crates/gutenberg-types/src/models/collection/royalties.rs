@@ -1,9 +1,10 @@
 use super::Address;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
 
 /// Enum representing different royalty policies.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum RoyaltyPolicy {
     /// Proportional royalty policy, represented by a set of shares and a base percentage.
@@ -40,6 +41,7 @@ impl RoyaltyPolicy {
     Default,
     Serialize,
     Deserialize,
+    JsonSchema,
     PartialEq,
     PartialOrd,
     Eq,
@@ -1,8 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// An enum representing different supply tracking options for an NFT collection.
 /// It provides flexibility in how the supply of NFTs is managed.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
 #[serde(rename_all = "camelCase")]
 // TODO: Add some kind of "optimistic" tracking that maintains supply tracking
 // without requiring a mutable `Collection` parameter
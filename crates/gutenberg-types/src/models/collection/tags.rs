@@ -1,3 +1,8 @@
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Metadata, Schema, SchemaObject},
+    JsonSchema,
+};
 use serde::{de::Visitor, Deserialize, Serialize};
 use std::fmt::{self, Display};
 
@@ -125,8 +130,44 @@ impl Serialize for Tag {
     }
 }
 
+/// Any string is a valid tag, the predefined tags are only listed as examples
+/// such that editors can suggest them.
+impl JsonSchema for Tag {
+    fn schema_name() -> String {
+        "Tag".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        let examples = [
+            Tag::Art,
+            Tag::ProfilePicture,
+            Tag::Collectible,
+            Tag::GameAsset,
+            Tag::TokenisedAsset,
+            Tag::DomainName,
+            Tag::Music,
+            Tag::Video,
+            Tag::Ticket,
+            Tag::License,
+        ]
+        .iter()
+        .map(|tag| tag.to_string().into())
+        .collect();
+
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            metadata: Some(Box::new(Metadata {
+                examples,
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
 /// Represents a collection of `Tag` instances.
-#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, Clone)]
 #[serde(transparent)]
 pub struct Tags(pub Vec<Tag>);
 
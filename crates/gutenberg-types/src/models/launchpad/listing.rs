@@ -1,9 +1,10 @@
 use super::market::Market;
 use super::Address;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Struct representing a collection of Listings.
-#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema, Clone)]
 #[serde(transparent)]
 pub struct Listings(pub Vec<Listing>);
 
@@ -11,7 +12,7 @@ pub struct Listings(pub Vec<Listing>);
 ///
 /// A Listing includes information about the admin and receiver addresses,
 /// as well as the associated markets.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct Listing {
     /// Address of the admin of the Listing.
     pub admin: Address,
@@ -39,14 +40,4 @@ impl Listing {
             markets,
         }
     }
-
-    /// Provides a snippet for sharing the listing object.
-    ///
-    /// # Returns
-    /// A string literal representing a code snippet for sharing the listing.
-    pub fn share(&self) -> &'static str {
-        "
-        transfer::share_object(listing);
-"
-    }
 }
@@ -1,8 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// An enum representing different types of markets for trading assets.
 /// It supports fixed price sales and Dutch auctions.
-#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Eq, PartialEq)]
 pub enum Market {
     /// Variant for a market with a fixed price.
     /// - `token`: Specifies the fully qualified fungible token in which the price is denominated.
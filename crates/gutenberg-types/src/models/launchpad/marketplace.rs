@@ -4,10 +4,11 @@
 //! the type of NFTs available or the type of Markets available on our
 //! OriginByte protocol.
 use super::Address;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Contains the market configurations of the marketplace
-#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default, Clone)]
 pub struct Marketplace {
     admin: Address,
     receiver: Address,
@@ -3,6 +3,7 @@ pub mod market;
 pub mod marketplace;
 
 use super::address::Address;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use self::{
@@ -10,13 +11,15 @@ use self::{
     marketplace::Marketplace,
 };
 
-/// Contains the market configurations of the marketplace
-#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+/// Contains the listings of the collection on the OriginByte launchpad, which
+/// are created at runtime by `byte client create-listing`
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Launchpad {
     /// In case a marketplace is creating the collection
     /// on behalf of the creator
     pub marketplace: Option<Marketplace>,
+    /// Listings selling the NFTs of the collection
     pub listings: Listings,
 }
 
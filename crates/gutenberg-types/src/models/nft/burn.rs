@@ -1,11 +1,15 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
 /// Enum representing different types of Burn policies.
-#[derive(Debug, Deserialize, Serialize, PartialEq, Copy, Clone)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize, PartialEq, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum Burn {
+    /// Generates `burn_nft`, `burn_nft_in_listing` and
+    /// `burn_nft_in_listing_with_id` functions
     Permissioned,
+    /// Generates `burn_own_nft` and `burn_own_nft_in_kiosk` functions
     Permissionless,
 }
 
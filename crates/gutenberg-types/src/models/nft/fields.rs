@@ -1,3 +1,5 @@
+use crate::{normalize_type, MOVE_KEYWORDS};
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::{
     de::{self, Visitor},
     ser::SerializeTuple,
@@ -5,7 +7,7 @@ use serde::{
 };
 
 /// A collection of `Field` instances.
-#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema, Serialize)]
 #[serde(transparent)]
 pub struct Fields(Vec<Field>);
 
@@ -29,16 +31,6 @@ impl Fields {
     pub fn params(&self) -> impl Iterator<Item = String> + '_ {
         self.iter().flat_map(Field::params)
     }
-
-    /// Returns an iterator over parameter types for each field.
-    pub fn param_types(&self) -> impl Iterator<Item = &'static str> + '_ {
-        self.iter().flat_map(Field::param_types)
-    }
-
-    /// Returns an iterator over test parameters for each field.
-    pub fn test_params(&self) -> impl Iterator<Item = &'static str> + '_ {
-        self.iter().flat_map(Field::test_params)
-    }
 }
 
 impl From<Vec<(&str, FieldType)>> for Fields {
@@ -56,7 +48,26 @@ pub struct Field {
 
 impl Field {
     /// Creates a new `Field` instance.
+    ///
+    /// Since field names can be deserialized from an untrusted source and
+    /// are written as Move identifiers, they are normalized on creation.
+    /// Names which are still not identifiers once normalized, such as `1st`,
+    /// `🦀` or the keyword `fun`, are prefixed with `field_`.
     pub fn new(name: String, field_type: FieldType) -> Self {
+        let name = normalize_type(&name);
+        let is_identifier = match name.chars().next() {
+            Some(char) if char.is_ascii_alphabetic() => true,
+            // A lone underscore is reserved as a wildcard
+            Some('_') => name.len() > 1,
+            _ => false,
+        } && !MOVE_KEYWORDS.contains(&name.as_str());
+
+        let name = if is_identifier {
+            name
+        } else {
+            format!("field_{name}")
+        };
+
         Self { name, field_type }
     }
 
@@ -83,31 +94,6 @@ impl Field {
         }
         .into_iter()
     }
-
-    /// Returns an iterator over the types of parameters associated with the field.
-    pub fn param_types(&self) -> impl Iterator<Item = &'static str> {
-        match self.field_type() {
-            FieldType::String => vec!["std::string::String"],
-            FieldType::Url => vec!["vector<u8>"],
-            FieldType::Attributes => {
-                vec!["vector<std::ascii::String>", "vector<std::ascii::String>"]
-            }
-        }
-        .into_iter()
-    }
-
-    /// Returns an iterator over test parameters for the field.
-    pub fn test_params(&self) -> impl Iterator<Item = &'static str> {
-        match self.field_type() {
-            FieldType::String => vec!["std::string::utf8(b\"TEST STRING\")"],
-            FieldType::Url => vec!["b\"https://originbyte.io\""],
-            FieldType::Attributes => vec![
-                "vector[std::ascii::string(b\"key\")]",
-                "vector[std::ascii::string(b\"attribute\")]",
-            ],
-        }
-        .into_iter()
-    }
 }
 
 impl From<(&str, FieldType)> for Field {
@@ -120,10 +106,14 @@ impl From<(&str, FieldType)> for Field {
 ///
 /// An explicit `FieldType` enum is defined as there is a limited set of
 /// acceptable fields.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum FieldType {
+    /// `std::string::String`
     String,
+    /// `sui::url::Url`
     Url,
+    /// `nft_protocol::attributes::Attributes`, initialized from vectors of
+    /// keys and values
     Attributes,
 }
 
@@ -156,7 +146,7 @@ impl<'de> Deserialize<'de> for Field {
                     .next_element()?
                     .ok_or_else(|| de::Error::invalid_length(1, &"2"))?;
 
-                Ok(Field { name, field_type })
+                Ok(Field::new(name, field_type))
             }
         }
 
@@ -177,3 +167,15 @@ impl Serialize for Field {
         element.end()
     }
 }
+
+/// Field is a tuple of field name and field type, matching `Deserialize` for
+/// `Field`.
+impl JsonSchema for Field {
+    fn schema_name() -> String {
+        "Field".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        <(String, FieldType)>::json_schema(gen)
+    }
+}
@@ -1,5 +1,10 @@
 use std::fmt;
 
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{Schema, SchemaObject, SubschemaValidation},
+    JsonSchema,
+};
 use serde::{
     de::{self, Deserialize, Visitor},
     Serialize,
@@ -114,3 +119,30 @@ impl Serialize for MintCap {
         }
     }
 }
+
+/// Either a `u64` supply or `"unlimited"`, matching `Deserialize` for
+/// `MintCap`.
+impl JsonSchema for MintCap {
+    fn schema_name() -> String {
+        "MintCap".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let unlimited = SchemaObject {
+            const_value: Some("unlimited".into()),
+            ..Default::default()
+        };
+
+        SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                one_of: Some(vec![
+                    gen.subschema_for::<u64>(),
+                    unlimited.into(),
+                ]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Struct representing different policies for minting tokens.
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize, Clone)]
 pub struct MintPolicies {
     /// Indicates whether the launchpad minting method is enabled.
     #[serde(default)]
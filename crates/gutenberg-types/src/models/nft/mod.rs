@@ -14,12 +14,13 @@ pub use mint_cap::MintCap;
 pub use minting::MintPolicies;
 pub use orderbook::Orderbook;
 pub use request::RequestPolicies;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 // TODO: Merge `cfg(feature = "full")` and `cfg(not(feature = "full"))` definitions, requires manually
 // implementing derives
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct NftData {
     /// Type name of the NFT
@@ -1,7 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Represents the policies regarding various requests in an NFT context.
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize, Clone)]
 pub struct RequestPolicies {
     /// Policy for transfer requests. `false` by default.
     #[serde(default)]
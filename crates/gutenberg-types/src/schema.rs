@@ -2,20 +2,37 @@
 //! struct `Schema`, acting as an intermediate data structure, to write
 //! the associated Move module and dump into a default or custom folder defined
 //! by the caller.
-use crate::models::{collection::CollectionData, nft::NftData};
-use crate::normalize_type;
+use crate::models::{
+    collection::CollectionData, launchpad::Launchpad, nft::NftData,
+};
+use crate::{migrate::SCHEMA_VERSION, normalize_type};
+use schemars::{schema::RootSchema, schema_for, JsonSchema};
 use serde::{Deserialize, Serialize};
 
 /// Struct that acts as an intermediate data structure representing the yaml
 /// configuration of the NFT collection.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Schema {
+    /// JSON Schema of the configuration file, used by editors for validation
+    /// and completion
+    #[serde(
+        rename = "$schema",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub schema_url: Option<String>,
+    /// Version of the configuration format, see [`crate::migrate`]
+    #[serde(default = "schema_version")]
+    pub schema_version: u32,
     /// The named address that the module is published under
     pub package_name: String,
     #[serde(default)]
     pub collection: CollectionData,
     pub nft: NftData,
+    /// Launchpad listings, created at runtime by `byte client create-listing`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launchpad: Option<Launchpad>,
 }
 
 impl Schema {
@@ -25,9 +42,12 @@ impl Schema {
         nft: NftData,
     ) -> Schema {
         Schema {
+            schema_url: None,
+            schema_version: SCHEMA_VERSION,
             package_name,
             collection,
             nft,
+            launchpad: None,
         }
     }
 
@@ -45,3 +65,51 @@ impl Schema {
         &self.nft
     }
 }
+
+fn schema_version() -> u32 {
+    SCHEMA_VERSION
+}
+
+/// Name of the file containing the JSON Schema returned by [`json_schema`]
+///
+/// Both this crate and projects created by `byte` keep the JSON Schema under
+/// this name, next to the configuration files referencing it.
+pub const JSON_SCHEMA_FILE_NAME: &str = "gutenberg.schema.json";
+
+/// JSON Schema describing the configuration files accepted by [`Schema`]
+pub fn json_schema() -> RootSchema {
+    schema_for!(Schema)
+}
+
+/// [`json_schema`] as written to [`JSON_SCHEMA_FILE_NAME`]
+pub fn json_schema_string() -> String {
+    let mut schema = serde_json::to_string_pretty(&json_schema())
+        .expect("JSON Schema must be serializable");
+    schema.push('\n');
+    schema
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{fs, path::Path};
+
+    /// Checked in JSON Schema is referenced by `template.json`, set
+    /// `GUTENBERG_BLESS=1` to update it
+    #[test]
+    fn json_schema_is_up_to_date() {
+        let path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join(JSON_SCHEMA_FILE_NAME);
+        let expected = json_schema_string();
+
+        if std::env::var_os("GUTENBERG_BLESS").is_some() {
+            fs::write(&path, &expected).unwrap();
+        }
+
+        assert!(
+            fs::read_to_string(&path).unwrap() == expected,
+            "{JSON_SCHEMA_FILE_NAME} is out of date, run with \
+            GUTENBERG_BLESS=1 to update it"
+        );
+    }
+}
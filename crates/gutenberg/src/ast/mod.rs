@@ -0,0 +1,591 @@
+//! Typed Move AST used to build generated contracts
+//!
+//! Models compose the nodes below instead of formatting Move source directly,
+//! which is then rendered by [`printer`] such that generated code is
+//! consistently formatted regardless of how it was composed.
+//!
+//! Identifiers and paths are trusted to be produced by the generator itself,
+//! whereas every user supplied value must pass through [`crate::literal`],
+//! either via [`Exp::bytes`] and friends or as a validated [`MoveType`].
+
+mod printer;
+
+use crate::literal::{self, MoveType};
+use gutenberg_types::models::address::Address;
+pub use printer::MAX_WIDTH;
+
+/// Move module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Module {
+    pub address: String,
+    pub name: String,
+    pub members: Vec<Member>,
+}
+
+impl Module {
+    pub fn new(address: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            name: name.into(),
+            members: Vec::new(),
+        }
+    }
+
+    pub fn member(mut self, member: impl Into<Member>) -> Self {
+        self.members.push(member.into());
+        self
+    }
+
+    pub fn members(
+        mut self,
+        members: impl IntoIterator<Item = Member>,
+    ) -> Self {
+        self.members.extend(members);
+        self
+    }
+
+    /// Returns function declared in module with the given name
+    pub fn fun(&self, name: &str) -> Option<&Fun> {
+        self.members.iter().find_map(|member| match member {
+            Member::Fun(fun) if fun.name == name => Some(fun),
+            _ => None,
+        })
+    }
+}
+
+/// Module level declaration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Member {
+    Struct(Struct),
+    Fun(Fun),
+    Const(Const),
+}
+
+impl From<Struct> for Member {
+    fn from(value: Struct) -> Self {
+        Member::Struct(value)
+    }
+}
+
+impl From<Fun> for Member {
+    fn from(value: Fun) -> Self {
+        Member::Fun(value)
+    }
+}
+
+impl From<Const> for Member {
+    fn from(value: Const) -> Self {
+        Member::Const(value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ability {
+    Copy,
+    Drop,
+    Store,
+    Key,
+}
+
+impl Ability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Ability::Copy => "copy",
+            Ability::Drop => "drop",
+            Ability::Store => "store",
+            Ability::Key => "key",
+        }
+    }
+}
+
+/// Struct declaration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Struct {
+    pub doc: Vec<String>,
+    pub name: String,
+    pub abilities: Vec<Ability>,
+    pub fields: Vec<(String, Type)>,
+}
+
+impl Struct {
+    pub fn new(name: impl Into<String>, abilities: &[Ability]) -> Self {
+        Self {
+            doc: Vec::new(),
+            name: name.into(),
+            abilities: abilities.to_vec(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Adds a line of documentation
+    pub fn doc(mut self, line: impl Into<String>) -> Self {
+        self.doc.push(line.into());
+        self
+    }
+
+    pub fn field(mut self, name: impl Into<String>, field_type: Type) -> Self {
+        self.fields.push((name.into(), field_type));
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Visibility {
+    #[default]
+    Private,
+    Public,
+}
+
+/// Function declaration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fun {
+    pub attributes: Vec<String>,
+    pub visibility: Visibility,
+    pub is_entry: bool,
+    pub name: String,
+    pub params: Vec<(String, Type)>,
+    pub returns: Option<Type>,
+    pub body: Block,
+}
+
+impl Fun {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            attributes: Vec::new(),
+            visibility: Visibility::Private,
+            is_entry: false,
+            name: name.into(),
+            params: Vec::new(),
+            returns: None,
+            body: Block::default(),
+        }
+    }
+
+    /// Declares a `#[test]` function
+    pub fn test(name: impl Into<String>) -> Self {
+        Self::new(name).attribute("test")
+    }
+
+    pub fn attribute(mut self, attribute: impl Into<String>) -> Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+
+    pub fn public(mut self) -> Self {
+        self.visibility = Visibility::Public;
+        self
+    }
+
+    pub fn entry(mut self) -> Self {
+        self.is_entry = true;
+        self
+    }
+
+    pub fn param(mut self, name: impl Into<String>, param_type: Type) -> Self {
+        self.params.push((name.into(), param_type));
+        self
+    }
+
+    pub fn params(
+        mut self,
+        params: impl IntoIterator<Item = (String, Type)>,
+    ) -> Self {
+        self.params.extend(params);
+        self
+    }
+
+    pub fn returns(mut self, returns: Type) -> Self {
+        self.returns = Some(returns);
+        self
+    }
+
+    pub fn body(mut self, body: impl Into<Block>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Returns parameter names in declaration order
+    pub fn param_names(&self) -> impl Iterator<Item = &str> {
+        self.params.iter().map(|(name, _)| name.as_str())
+    }
+}
+
+/// Constant declaration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Const {
+    pub attributes: Vec<String>,
+    pub name: String,
+    pub const_type: Type,
+    pub value: Exp,
+}
+
+impl Const {
+    pub fn new(name: impl Into<String>, const_type: Type, value: Exp) -> Self {
+        Self {
+            attributes: Vec::new(),
+            name: name.into(),
+            const_type,
+            value,
+        }
+    }
+
+    pub fn attribute(mut self, attribute: impl Into<String>) -> Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+}
+
+/// Sequence of statements with an optional trailing expression
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Block {
+    pub stmts: Vec<Stmt>,
+    pub result: Option<Exp>,
+}
+
+impl Block {
+    pub fn new(stmts: Vec<Stmt>) -> Self {
+        Self {
+            stmts,
+            result: None,
+        }
+    }
+
+    pub fn result(mut self, result: Exp) -> Self {
+        self.result = Some(result);
+        self
+    }
+}
+
+impl From<Vec<Stmt>> for Block {
+    fn from(value: Vec<Stmt>) -> Self {
+        Block::new(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stmt {
+    Let {
+        pattern: Pattern,
+        let_type: Option<Type>,
+        value: Exp,
+    },
+    Assign {
+        target: Exp,
+        value: Exp,
+    },
+    Exp(Exp),
+    Comment(String),
+    /// Empty line separating groups of statements
+    ///
+    /// Consecutive blank lines are collapsed and blank lines at the start or
+    /// end of a block are omitted.
+    Blank,
+}
+
+impl Stmt {
+    /// `let {name} = {value};`
+    pub fn bind(name: impl Into<String>, value: Exp) -> Self {
+        Stmt::Let {
+            pattern: Pattern::Var(name.into()),
+            let_type: None,
+            value,
+        }
+    }
+
+    /// `let {name}: {let_type} = {value};`
+    pub fn bind_typed(
+        name: impl Into<String>,
+        let_type: Type,
+        value: Exp,
+    ) -> Self {
+        Stmt::Let {
+            pattern: Pattern::Var(name.into()),
+            let_type: Some(let_type),
+            value,
+        }
+    }
+
+    /// `let {pattern} = {value};`
+    pub fn destructure(pattern: Pattern, value: Exp) -> Self {
+        Stmt::Let {
+            pattern,
+            let_type: None,
+            value,
+        }
+    }
+
+    pub fn assign(target: Exp, value: Exp) -> Self {
+        Stmt::Assign { target, value }
+    }
+
+    pub fn comment(comment: impl Into<String>) -> Self {
+        Stmt::Comment(comment.into())
+    }
+}
+
+impl From<Exp> for Stmt {
+    fn from(value: Exp) -> Self {
+        Stmt::Exp(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    Var(String),
+    Wildcard,
+    Tuple(Vec<Pattern>),
+    Unpack {
+        name: String,
+        fields: Vec<(String, Pattern)>,
+    },
+}
+
+impl Pattern {
+    pub fn var(name: impl Into<String>) -> Self {
+        Pattern::Var(name.into())
+    }
+
+    pub fn tuple(patterns: impl IntoIterator<Item = Pattern>) -> Self {
+        Pattern::Tuple(patterns.into_iter().collect())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Exp {
+    /// Pre-rendered literal such as a number, byte string or address
+    ///
+    /// Only constructed by the literal helpers below, never from user input.
+    Value(String),
+    Var(String),
+    Call {
+        function: String,
+        type_args: Vec<Type>,
+        args: Vec<Exp>,
+    },
+    Borrow {
+        mutable: bool,
+        exp: Box<Exp>,
+    },
+    Pack {
+        name: String,
+        fields: Vec<(String, Exp)>,
+    },
+    Field(Box<Exp>, String),
+    Vector(Vec<Exp>),
+}
+
+impl Exp {
+    pub fn var(name: impl Into<String>) -> Self {
+        Exp::Var(name.into())
+    }
+
+    pub fn call(function: impl Into<String>, args: Vec<Exp>) -> Self {
+        Exp::Call {
+            function: function.into(),
+            type_args: Vec::new(),
+            args,
+        }
+    }
+
+    pub fn call_generic(
+        function: impl Into<String>,
+        type_args: Vec<Type>,
+        args: Vec<Exp>,
+    ) -> Self {
+        Exp::Call {
+            function: function.into(),
+            type_args,
+            args,
+        }
+    }
+
+    /// `&{exp}`
+    pub fn borrow(exp: Exp) -> Self {
+        Exp::Borrow {
+            mutable: false,
+            exp: Box::new(exp),
+        }
+    }
+
+    /// `&mut {exp}`
+    pub fn borrow_mut(exp: Exp) -> Self {
+        Exp::Borrow {
+            mutable: true,
+            exp: Box::new(exp),
+        }
+    }
+
+    /// `{name} { .. }`
+    pub fn pack(
+        name: impl Into<String>,
+        fields: impl IntoIterator<Item = (String, Exp)>,
+    ) -> Self {
+        Exp::Pack {
+            name: name.into(),
+            fields: fields.into_iter().collect(),
+        }
+    }
+
+    /// `{exp}.{field}`
+    pub fn field(exp: Exp, field: impl Into<String>) -> Self {
+        Exp::Field(Box::new(exp), field.into())
+    }
+
+    pub fn vector(exps: Vec<Exp>) -> Self {
+        Exp::Vector(exps)
+    }
+
+    pub fn u64(value: u64) -> Self {
+        Exp::Value(value.to_string())
+    }
+
+    pub fn bool(value: bool) -> Self {
+        Exp::Value(value.to_string())
+    }
+
+    /// Byte string literal, see [`literal::byte_string`]
+    pub fn bytes(value: &str) -> Self {
+        Exp::Value(literal::byte_string(value))
+    }
+
+    /// `std::string::utf8({bytes})`
+    pub fn utf8(value: &str) -> Self {
+        Exp::call("std::string::utf8", vec![Exp::bytes(value)])
+    }
+
+    /// `std::ascii::string({bytes})`
+    pub fn ascii(value: &str) -> Self {
+        Exp::call("std::ascii::string", vec![Exp::bytes(value)])
+    }
+
+    pub fn address(address: &Address) -> Self {
+        Exp::Value(literal::address(address))
+    }
+
+    /// Numerical address literal, such as `@0xA1C04`
+    ///
+    /// Panics if the value is not hexadecimal.
+    pub fn address_hex(hex: &str) -> Self {
+        assert!(
+            !hex.is_empty() && hex.chars().all(|char| char.is_ascii_hexdigit()),
+            "Invalid address literal `{hex}`"
+        );
+        Exp::Value(format!("@0x{hex}"))
+    }
+
+    /// Returns all calls made within the expression, including nested calls
+    pub fn calls(&self) -> Vec<&str> {
+        let mut calls = Vec::new();
+        self.visit_calls(&mut calls);
+        calls
+    }
+
+    fn visit_calls<'a>(&'a self, calls: &mut Vec<&'a str>) {
+        match self {
+            Exp::Call { function, args, .. } => {
+                calls.push(function.as_str());
+                args.iter().for_each(|arg| arg.visit_calls(calls));
+            }
+            Exp::Borrow { exp, .. } | Exp::Field(exp, _) => {
+                exp.visit_calls(calls)
+            }
+            Exp::Pack { fields, .. } => {
+                fields.iter().for_each(|(_, exp)| exp.visit_calls(calls))
+            }
+            Exp::Vector(exps) => {
+                exps.iter().for_each(|exp| exp.visit_calls(calls))
+            }
+            Exp::Value(_) | Exp::Var(_) => (),
+        }
+    }
+}
+
+impl Block {
+    /// Returns all calls made within the block, in order
+    pub fn calls(&self) -> Vec<&str> {
+        self.stmts
+            .iter()
+            .flat_map(|stmt| match stmt {
+                Stmt::Let { value, .. } | Stmt::Exp(value) => value.calls(),
+                Stmt::Assign { target, value } => {
+                    let mut calls = target.calls();
+                    calls.extend(value.calls());
+                    calls
+                }
+                Stmt::Comment(_) | Stmt::Blank => Vec::new(),
+            })
+            .chain(self.result.iter().flat_map(Exp::calls))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    /// Validated type such as a primitive or a user supplied struct type
+    Move(MoveType),
+    /// Struct type declared in or referenced by the generated module
+    Named {
+        path: String,
+        type_args: Vec<Type>,
+    },
+    Vector(Box<Type>),
+    Ref {
+        mutable: bool,
+        inner: Box<Type>,
+    },
+}
+
+impl Type {
+    pub fn named(path: impl Into<String>) -> Self {
+        Type::Named {
+            path: path.into(),
+            type_args: Vec::new(),
+        }
+    }
+
+    pub fn generic(path: impl Into<String>, type_args: Vec<Type>) -> Self {
+        Type::Named {
+            path: path.into(),
+            type_args,
+        }
+    }
+
+    pub fn vector(inner: Type) -> Self {
+        Type::Vector(Box::new(inner))
+    }
+
+    /// `&{inner}`
+    pub fn borrow(inner: Type) -> Self {
+        Type::Ref {
+            mutable: false,
+            inner: Box::new(inner),
+        }
+    }
+
+    /// `&mut {inner}`
+    pub fn borrow_mut(inner: Type) -> Self {
+        Type::Ref {
+            mutable: true,
+            inner: Box::new(inner),
+        }
+    }
+
+    pub fn address() -> Self {
+        Type::Move(MoveType::Address)
+    }
+
+    pub fn u8() -> Self {
+        Type::Move(MoveType::U8)
+    }
+
+    /// `&mut sui::tx_context::TxContext`
+    pub fn tx_context() -> Self {
+        Type::borrow_mut(Type::named("sui::tx_context::TxContext"))
+    }
+}
+
+impl From<MoveType> for Type {
+    fn from(value: MoveType) -> Self {
+        Type::Move(value)
+    }
+}
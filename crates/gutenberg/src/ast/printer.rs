@@ -0,0 +1,611 @@
+//! Pretty-printer for the Move AST
+//!
+//! Nodes are printed on a single line whenever they fit within
+//! [`MAX_WIDTH`], otherwise calls, packs and vectors are broken up with one
+//! argument per line and a trailing comma, recursively.
+
+use super::{
+    Block, Const, Exp, Fun, Member, Module, Pattern, Stmt, Struct, Type,
+    Visibility,
+};
+use std::fmt::{self, Write};
+
+/// Maximum line width that the printer attempts to respect
+pub const MAX_WIDTH: usize = 100;
+
+const INDENT: usize = 4;
+
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "module {}::{} {{", self.address, self.name)?;
+
+        for (i, member) in self.members.iter().enumerate() {
+            if i > 0 {
+                f.write_char('\n')?;
+            }
+            f.write_str(&render_member(member, INDENT))?;
+        }
+
+        f.write_str("}\n")
+    }
+}
+
+impl fmt::Display for Member {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&render_member(self, 0))
+    }
+}
+
+impl fmt::Display for Fun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&render_fun(self, 0))
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Move(move_type) => write!(f, "{move_type}"),
+            Type::Named { path, type_args } => {
+                write!(f, "{path}{}", render_type_args(type_args))
+            }
+            Type::Vector(inner) => write!(f, "vector<{inner}>"),
+            Type::Ref { mutable, inner } => match mutable {
+                true => write!(f, "&mut {inner}"),
+                false => write!(f, "&{inner}"),
+            },
+        }
+    }
+}
+
+/// Renders expression on a single line
+impl fmt::Display for Exp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Exp::Value(value) | Exp::Var(value) => f.write_str(value),
+            Exp::Call {
+                function,
+                type_args,
+                args,
+            } => write!(
+                f,
+                "{function}{}({})",
+                render_type_args(type_args),
+                join(args)
+            ),
+            Exp::Borrow { mutable, exp } => match mutable {
+                true => write!(f, "&mut {exp}"),
+                false => write!(f, "&{exp}"),
+            },
+            Exp::Pack { name, fields } if fields.is_empty() => {
+                write!(f, "{name} {{}}")
+            }
+            Exp::Pack { name, fields } => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(n, e)| render_pack_field(n, e))
+                    .collect();
+                write!(f, "{name} {{ {} }}", fields.join(", "))
+            }
+            Exp::Field(exp, field) => write!(f, "{exp}.{field}"),
+            Exp::Vector(exps) => write!(f, "vector[{}]", join(exps)),
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pattern::Var(name) => f.write_str(name),
+            Pattern::Wildcard => f.write_char('_'),
+            Pattern::Tuple(patterns) => write!(f, "({})", join(patterns)),
+            Pattern::Unpack { name, fields } => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(field, pattern)| match pattern {
+                        Pattern::Var(var) if var == field => field.clone(),
+                        pattern => format!("{field}: {pattern}"),
+                    })
+                    .collect();
+                write!(f, "{name} {{ {} }}", fields.join(", "))
+            }
+        }
+    }
+}
+
+fn join<T: fmt::Display>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_type_args(type_args: &[Type]) -> String {
+    match type_args.is_empty() {
+        true => String::new(),
+        false => format!("<{}>", join(type_args)),
+    }
+}
+
+fn render_pack_field(name: &str, exp: &Exp) -> String {
+    match exp {
+        Exp::Var(var) if var == name => name.to_string(),
+        exp => format!("{name}: {exp}"),
+    }
+}
+
+fn pad(indent: usize) -> String {
+    " ".repeat(indent)
+}
+
+fn render_member(member: &Member, indent: usize) -> String {
+    match member {
+        Member::Struct(decl) => render_struct(decl, indent),
+        Member::Fun(fun) => render_fun(fun, indent),
+        Member::Const(decl) => render_const(decl, indent),
+    }
+}
+
+fn render_attributes(attributes: &[String], indent: usize) -> String {
+    attributes
+        .iter()
+        .map(|attribute| format!("{}#[{attribute}]\n", pad(indent)))
+        .collect()
+}
+
+fn render_struct(decl: &Struct, indent: usize) -> String {
+    let mut code: String = decl
+        .doc
+        .iter()
+        .map(|line| match line.is_empty() {
+            true => format!("{}///\n", pad(indent)),
+            false => format!("{}/// {line}\n", pad(indent)),
+        })
+        .collect();
+
+    let abilities: Vec<&str> = decl
+        .abilities
+        .iter()
+        .map(|ability| ability.as_str())
+        .collect();
+    let abilities = match abilities.is_empty() {
+        true => String::new(),
+        false => format!(" has {}", abilities.join(", ")),
+    };
+
+    write!(code, "{}struct {}{abilities} {{", pad(indent), decl.name).unwrap();
+
+    if decl.fields.is_empty() {
+        code.push_str("}\n");
+        return code;
+    }
+
+    code.push('\n');
+    for (name, field_type) in decl.fields.iter() {
+        writeln!(code, "{}{name}: {field_type},", pad(indent + INDENT))
+            .unwrap();
+    }
+    writeln!(code, "{}}}", pad(indent)).unwrap();
+
+    code
+}
+
+fn render_const(decl: &Const, indent: usize) -> String {
+    let mut code = render_attributes(&decl.attributes, indent);
+    let prefix = format!("const {}: {} = ", decl.name, decl.const_type);
+    code.push_str(&render_assignment(&prefix, &decl.value, indent));
+    code.push('\n');
+    code
+}
+
+fn render_fun(fun: &Fun, indent: usize) -> String {
+    let mut code = render_attributes(&fun.attributes, indent);
+
+    let visibility = match fun.visibility {
+        Visibility::Public => "public ",
+        Visibility::Private => "",
+    };
+    let entry = match fun.is_entry {
+        true => "entry ",
+        false => "",
+    };
+    let returns = fun
+        .returns
+        .as_ref()
+        .map(|returns| format!(": {returns}"))
+        .unwrap_or_default();
+
+    let params: Vec<String> = fun
+        .params
+        .iter()
+        .map(|(name, param_type)| format!("{name}: {param_type}"))
+        .collect();
+
+    let header = format!(
+        "{}{visibility}{entry}fun {}({}){returns} {{",
+        pad(indent),
+        fun.name,
+        params.join(", ")
+    );
+
+    if header.len() <= MAX_WIDTH {
+        code.push_str(&header);
+    } else {
+        write!(code, "{}{visibility}{entry}fun {}(", pad(indent), fun.name)
+            .unwrap();
+        for param in params {
+            write!(code, "\n{}{param},", pad(indent + INDENT)).unwrap();
+        }
+        write!(code, "\n{}){returns} {{", pad(indent)).unwrap();
+    }
+
+    code.push_str(&render_block(&fun.body, indent + INDENT));
+    writeln!(code, "{}}}", pad(indent)).unwrap();
+
+    code
+}
+
+/// Renders block contents, each line terminated by a newline, starting with
+/// a newline such that it can follow an opening brace
+fn render_block(block: &Block, indent: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut pending_blank = false;
+
+    for stmt in block.stmts.iter() {
+        if let Stmt::Blank = stmt {
+            pending_blank = !lines.is_empty();
+            continue;
+        }
+
+        if pending_blank {
+            lines.push(String::new());
+            pending_blank = false;
+        }
+
+        lines.push(render_stmt(stmt, indent));
+    }
+
+    if let Some(result) = block.result.as_ref() {
+        if pending_blank {
+            lines.push(String::new());
+        }
+
+        lines.push(format!(
+            "{}{}",
+            pad(indent),
+            render_exp(result, indent, indent, 0)
+        ));
+    }
+
+    let mut code = String::from("\n");
+    for line in lines {
+        code.push_str(&line);
+        code.push('\n');
+    }
+    code
+}
+
+fn render_stmt(stmt: &Stmt, indent: usize) -> String {
+    match stmt {
+        Stmt::Let {
+            pattern,
+            let_type,
+            value,
+        } => {
+            let let_type = let_type
+                .as_ref()
+                .map(|let_type| format!(": {let_type}"))
+                .unwrap_or_default();
+            let prefix = format!("let {pattern}{let_type} = ");
+            render_assignment(&prefix, value, indent)
+        }
+        Stmt::Assign { target, value } => {
+            render_assignment(&format!("{target} = "), value, indent)
+        }
+        Stmt::Exp(exp) => {
+            format!("{}{};", pad(indent), render_exp(exp, indent, indent, 1))
+        }
+        Stmt::Comment(comment) => format!("{}// {comment}", pad(indent)),
+        Stmt::Blank => String::new(),
+    }
+}
+
+/// Renders `{prefix}{value};`, moving the value onto its own line if the
+/// prefix leaves no room for it
+fn render_assignment(prefix: &str, value: &Exp, indent: usize) -> String {
+    let used = indent + prefix.len();
+    let flat = value.to_string();
+
+    // Account for the trailing semicolon
+    if used + flat.len() < MAX_WIDTH {
+        return format!("{}{prefix}{flat};", pad(indent));
+    }
+
+    if used + head_len(value) <= MAX_WIDTH {
+        let value = render_exp(value, indent, used, 1);
+        return format!("{}{prefix}{value};", pad(indent));
+    }
+
+    let inner = indent + INDENT;
+    format!(
+        "{}{}\n{}{};",
+        pad(indent),
+        prefix.trim_end(),
+        pad(inner),
+        render_exp(value, inner, inner, 1)
+    )
+}
+
+/// Length of the first line of an expression if it were to be broken up
+fn head_len(exp: &Exp) -> usize {
+    match exp {
+        Exp::Call {
+            function,
+            type_args,
+            args,
+        } if !args.is_empty() => {
+            function.len() + render_type_args(type_args).len() + 1
+        }
+        Exp::Pack { name, fields } if !fields.is_empty() => name.len() + 2,
+        Exp::Vector(exps) if !exps.is_empty() => "vector[".len(),
+        Exp::Borrow { mutable, exp } => {
+            let prefix = if *mutable { "&mut ".len() } else { 1 };
+            prefix + head_len(exp)
+        }
+        exp => exp.to_string().len(),
+    }
+}
+
+/// Renders expression such that it fits within `MAX_WIDTH`
+///
+/// `used` is the width already occupied on the first line and `trailing` the
+/// width of what follows the expression on its last line.
+fn render_exp(
+    exp: &Exp,
+    indent: usize,
+    used: usize,
+    trailing: usize,
+) -> String {
+    let flat = exp.to_string();
+    if used + flat.len() + trailing <= MAX_WIDTH {
+        return flat;
+    }
+
+    let inner = indent + INDENT;
+    let render_items = |open: String, items: Vec<String>, close: &str| {
+        let mut code = open;
+        for item in items {
+            write!(code, "\n{}{item},", pad(inner)).unwrap();
+        }
+        write!(code, "\n{}{close}", pad(indent)).unwrap();
+        code
+    };
+
+    match exp {
+        Exp::Call {
+            function,
+            type_args,
+            args,
+        } if !args.is_empty() => render_items(
+            format!("{function}{}(", render_type_args(type_args)),
+            args.iter()
+                .map(|arg| render_exp(arg, inner, inner, 1))
+                .collect(),
+            ")",
+        ),
+        Exp::Vector(exps) if !exps.is_empty() => render_items(
+            String::from("vector["),
+            exps.iter()
+                .map(|exp| render_exp(exp, inner, inner, 1))
+                .collect(),
+            "]",
+        ),
+        Exp::Pack { name, fields } if !fields.is_empty() => render_items(
+            format!("{name} {{"),
+            fields
+                .iter()
+                .map(|(name, exp)| match exp {
+                    Exp::Var(var) if var == name => name.clone(),
+                    exp => {
+                        let prefix = format!("{name}: ");
+                        let used = inner + prefix.len();
+                        format!("{prefix}{}", render_exp(exp, inner, used, 1))
+                    }
+                })
+                .collect(),
+            "}",
+        ),
+        Exp::Borrow { mutable, exp } => {
+            let prefix = if *mutable { "&mut " } else { "&" };
+            let used = used + prefix.len();
+            format!("{prefix}{}", render_exp(exp, indent, used, trailing))
+        }
+        _ => flat,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::{Ability, Block};
+
+    fn long_call(arity: usize) -> Exp {
+        Exp::call(
+            "nft_protocol::collection::add_domain",
+            (0..arity)
+                .map(|i| Exp::var(format!("some_rather_long_argument_{i}")))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn prints_short_calls_inline() {
+        let exp = Exp::call_generic(
+            "sui::display::new",
+            vec![Type::named("Joystick")],
+            vec![Exp::borrow(Exp::var("publisher")), Exp::var("ctx")],
+        );
+
+        assert_eq!(
+            render_stmt(&Stmt::bind("display", exp), 8),
+            "        let display = sui::display::new<Joystick>(&publisher, ctx);"
+        );
+    }
+
+    #[test]
+    fn breaks_long_calls() {
+        let stmt = Stmt::Exp(Exp::call(
+            "sui::transfer::public_transfer",
+            vec![long_call(3), Exp::var("receiver")],
+        ));
+
+        pretty_assertions::assert_eq!(
+            render_stmt(&stmt, 4),
+            "    sui::transfer::public_transfer(
+        nft_protocol::collection::add_domain(
+            some_rather_long_argument_0,
+            some_rather_long_argument_1,
+            some_rather_long_argument_2,
+        ),
+        receiver,
+    );"
+        );
+    }
+
+    #[test]
+    fn breaks_after_long_let_prefix() {
+        let long_type = Type::generic(
+            "ob_request::request::Policy",
+            vec![Type::generic(
+                "ob_request::request::WithNft",
+                vec![
+                    Type::named("Joystick"),
+                    Type::named("ob_request::borrow_request::BORROW_REQ"),
+                ],
+            )],
+        );
+        let stmt = Stmt::bind_typed(
+            "borrow_policy",
+            long_type,
+            Exp::call(
+                "sui::test_scenario::take_shared",
+                vec![Exp::borrow_mut(Exp::var("scenario"))],
+            ),
+        );
+
+        pretty_assertions::assert_eq!(
+            render_stmt(&stmt, 8),
+            "        let borrow_policy: ob_request::request::Policy<ob_request::request::WithNft<Joystick, ob_request::borrow_request::BORROW_REQ>> =
+            sui::test_scenario::take_shared(&mut scenario);"
+        );
+    }
+
+    #[test]
+    fn prints_pack_shorthand() {
+        let exp = Exp::pack(
+            "Joystick",
+            [
+                (
+                    "id".to_string(),
+                    Exp::call("sui::object::new", vec![Exp::var("ctx")]),
+                ),
+                ("name".to_string(), Exp::var("name")),
+            ],
+        );
+
+        assert_eq!(
+            exp.to_string(),
+            "Joystick { id: sui::object::new(ctx), name }"
+        );
+        assert_eq!(Exp::pack("Witness", []).to_string(), "Witness {}");
+    }
+
+    #[test]
+    fn collapses_blank_lines() {
+        let block = Block::new(vec![
+            Stmt::Blank,
+            Stmt::Exp(Exp::call("a", vec![])),
+            Stmt::Blank,
+            Stmt::Blank,
+            Stmt::comment("Comment"),
+            Stmt::Blank,
+        ])
+        .result(Exp::var("nft"));
+
+        assert_eq!(
+            render_block(&block, 4),
+            "\n    a();\n\n    // Comment\n\n    nft\n"
+        );
+    }
+
+    #[test]
+    fn prints_module() {
+        let module = Module::new("joysticks", "joystick")
+            .member(Struct::new("JOYSTICK", &[Ability::Drop]).doc(
+                "One time witness is only instantiated in the init method",
+            ))
+            .member(
+                Struct::new("Joystick", &[Ability::Key, Ability::Store])
+                    .field("id", Type::named("sui::object::UID")),
+            )
+            .member(
+                Fun::new("burn")
+                    .public()
+                    .entry()
+                    .param("nft", Type::named("Joystick"))
+                    .body(vec![Stmt::destructure(
+                        Pattern::Unpack {
+                            name: "Joystick".to_string(),
+                            fields: vec![(
+                                "id".to_string(),
+                                Pattern::var("id"),
+                            )],
+                        },
+                        Exp::var("nft"),
+                    )]),
+            );
+
+        pretty_assertions::assert_eq!(
+            module.to_string(),
+            "module joysticks::joystick {
+    /// One time witness is only instantiated in the init method
+    struct JOYSTICK has drop {}
+
+    struct Joystick has key, store {
+        id: sui::object::UID,
+    }
+
+    public entry fun burn(nft: Joystick) {
+        let Joystick { id } = nft;
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn breaks_long_function_headers() {
+        let fun = Fun::new("mint_nft_to_warehouse")
+            .public()
+            .entry()
+            .param(
+                "mint_cap",
+                Type::borrow_mut(Type::generic(
+                    "nft_protocol::mint_cap::MintCap",
+                    vec![Type::named("Joystick")],
+                )),
+            )
+            .param("ctx", Type::tx_context());
+
+        pretty_assertions::assert_eq!(
+            fun.to_string(),
+            "public entry fun mint_nft_to_warehouse(
+    mint_cap: &mut nft_protocol::mint_cap::MintCap<Joystick>,
+    ctx: &mut sui::tx_context::TxContext,
+) {
+}
+"
+        );
+    }
+}
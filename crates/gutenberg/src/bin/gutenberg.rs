@@ -1,39 +1,224 @@
-use clap::Parser;
-use gutenberg::generate_project;
-use package_manager::package::Flavor;
-use std::path::Path;
+use anyhow::Result;
+use clap::{Parser, Subcommand, ValueEnum};
+use gutenberg::{
+    compat::DEFAULT_PROTOCOL_VERSION,
+    diff::diff_project,
+    generate_contract, generate_contract_dir, generate_project,
+    generate_project_with_flavors, read_schema,
+    scenarios::{read_scenarios, write_packages},
+};
+use package_manager::{package::Flavor, version::Version};
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 
 /// A struct representing command-line arguments.
 #[derive(Parser)]
 #[clap(author, version, about)]
 pub struct Cli {
-    /// Path to the input configuration file.
-    input_config_path: String,
-    /// Path to the output directory.
-    output_dir: String,
+    #[clap(subcommand)]
+    cmd: Commands,
+}
+
+#[derive(Subcommand)]
+pub enum Commands {
+    /// Generates a contract package from a configuration file
+    Generate {
+        /// Path to the input configuration file.
+        input_config_path: PathBuf,
+        /// Path to the output directory.
+        output_dir: PathBuf,
+        /// Network flavor of the package manifest.
+        #[clap(long, value_enum, default_value_t = FlavorArg::Mainnet)]
+        flavor: FlavorArg,
+        /// NftProtocol version to depend on, defaults to the latest version
+        /// in the program registry.
+        #[clap(long, value_parser = clap::value_parser!(Version))]
+        protocol_version: Option<Version>,
+        /// Writes manifests for both flavors into `flavours/`, using the
+        /// mainnet manifest as `Move.toml`.
+        #[clap(long, conflicts_with = "flavor")]
+        with_flavours: bool,
+    },
+    /// Checks that configuration files can be generated into contracts
+    Validate {
+        /// Paths to the input configuration files.
+        #[clap(required = true)]
+        input_config_paths: Vec<PathBuf>,
+        /// NftProtocol version the contracts must be supported on.
+        #[clap(
+            long,
+            value_parser = clap::value_parser!(Version),
+            default_value_t = DEFAULT_PROTOCOL_VERSION
+        )]
+        protocol_version: Version,
+    },
+    /// Prints the JSON Schema of the configuration file
+    PrintSchema,
+    /// Regenerates the sources of the expected test packages from the test
+    /// scenarios
+    Scenarios {
+        /// Directory containing the scenario configuration files.
+        #[clap(long, default_value = "tests/scenarios")]
+        scenarios_dir: PathBuf,
+        /// Directory containing the expected `full` and `demo` packages.
+        #[clap(long, default_value = "tests/packages")]
+        packages_dir: PathBuf,
+    },
+    /// Shows how regenerating the contract would change an existing package
+    Diff {
+        /// Path to the input configuration file.
+        input_config_path: PathBuf,
+        /// Path to the existing contract package.
+        contract_dir: PathBuf,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum FlavorArg {
+    Mainnet,
+    Testnet,
+}
+
+impl From<FlavorArg> for Flavor {
+    fn from(flavor: FlavorArg) -> Self {
+        match flavor {
+            FlavorArg::Mainnet => Flavor::Mainnet,
+            FlavorArg::Testnet => Flavor::Testnet,
+        }
+    }
 }
 
 /// The main entry point of the application.
-fn main() {
-    // Parsing command-line arguments into the Cli struct
-    let Cli {
-        input_config_path,
-        output_dir,
-    } = Cli::parse();
-
-    // Parsing the input and output paths from the command-line arguments
-    let config_path_parsed = Path::new(&input_config_path);
-    let output_dir_parsed = Path::new(&output_dir);
-
-    // Attempt to generate a project based on the provided arguments
-    // and handle potential errors.
-    if let Err(err) = generate_project(
-        &config_path_parsed,
-        Flavor::Mainnet,
-        &output_dir_parsed,
-        Some(String::from("1.3.0")), /* TODO: It should not be a fixed
-                                      * version string */
-    ) {
-        eprintln!("{err}");
+///
+/// Exits with code `1` if the command failed, or in the case of `diff`, if
+/// the package differs, and with code `2` on invalid usage.
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.cmd {
+        Commands::Generate {
+            input_config_path,
+            output_dir,
+            flavor,
+            protocol_version,
+            with_flavours,
+        } => generate(
+            &input_config_path,
+            &output_dir,
+            flavor.into(),
+            protocol_version,
+            with_flavours,
+        ),
+        Commands::Validate {
+            input_config_paths,
+            protocol_version,
+        } => return validate(&input_config_paths, &protocol_version),
+        Commands::PrintSchema => print_schema(),
+        Commands::Scenarios {
+            scenarios_dir,
+            packages_dir,
+        } => scenarios(&scenarios_dir, &packages_dir),
+        Commands::Diff {
+            input_config_path,
+            contract_dir,
+        } => return diff(&input_config_path, &contract_dir),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn generate(
+    input_config_path: &Path,
+    output_dir: &Path,
+    flavor: Flavor,
+    version: Option<Version>,
+    with_flavours: bool,
+) -> Result<()> {
+    let contract_dir = if with_flavours {
+        let schema = read_schema(input_config_path)?;
+        let contract_dir = generate_contract_dir(&schema, output_dir)?;
+        generate_project_with_flavors(&schema, &contract_dir, version)?;
+        contract_dir
+    } else {
+        generate_project(input_config_path, flavor, output_dir, version)?
+    };
+
+    println!("Contract generated: {}", contract_dir.display());
+    Ok(())
+}
+
+/// Reports every configuration file which could not be read, exiting with a
+/// non-zero code if any of them were invalid
+fn validate(
+    input_config_paths: &[PathBuf],
+    protocol_version: &Version,
+) -> ExitCode {
+    let mut code = ExitCode::SUCCESS;
+
+    for path in input_config_paths {
+        match validate_one(path, protocol_version) {
+            Ok(()) => println!("{}: OK", path.display()),
+            Err(err) => {
+                eprintln!("{}: {err:#}", path.display());
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    code
+}
+
+fn validate_one(
+    input_config_path: &Path,
+    protocol_version: &Version,
+) -> Result<()> {
+    let schema = read_schema(input_config_path)?;
+
+    #[cfg_attr(not(feature = "move-check"), allow(unused_variables))]
+    let files = generate_contract(&schema, protocol_version)?;
+    #[cfg(feature = "move-check")]
+    gutenberg::check::check_syntax(&files)?;
+
+    Ok(())
+}
+
+fn print_schema() -> Result<()> {
+    print!("{}", gutenberg_types::json_schema_string());
+    Ok(())
+}
+
+fn scenarios(scenarios_dir: &Path, packages_dir: &Path) -> Result<()> {
+    let scenarios = read_scenarios(scenarios_dir)?;
+    write_packages(&scenarios, packages_dir)?;
+
+    println!(
+        "Regenerated {} scenarios in {}",
+        scenarios.len(),
+        packages_dir.display()
+    );
+    Ok(())
+}
+
+/// Prints a unified diff for every file that would change, exiting with
+/// code `1` if there are any differences and `2` on error, like `diff` does
+fn diff(input_config_path: &Path, contract_dir: &Path) -> ExitCode {
+    match diff_project(input_config_path, contract_dir) {
+        Ok(diffs) if diffs.is_empty() => ExitCode::SUCCESS,
+        Ok(diffs) => {
+            diffs.iter().for_each(|diff| print!("{diff}"));
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("{err:#}");
+            ExitCode::from(2)
+        }
     }
 }
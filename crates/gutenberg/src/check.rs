@@ -0,0 +1,94 @@
+//! Offline syntax check of generated contracts
+//!
+//! Parses generated Move sources with the Move compiler's parser such that
+//! malformed output is caught without having to fetch the contract's git
+//! dependencies and running `sui move build`.
+//!
+//! Requires the `move-check` feature.
+
+use crate::ContractFile;
+use anyhow::{anyhow, Context, Result};
+use move_compiler::{
+    diagnostics::report_diagnostics_to_buffer,
+    shared::{Flags, NumericalAddress},
+    Compiler, PASS_PARSER,
+};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Parses every Move source among the given contract files
+///
+/// # Returns
+/// Error containing the rendered compiler diagnostics if any of the sources
+/// failed to parse.
+pub fn check_syntax(files: &[ContractFile]) -> Result<()> {
+    let dir = ScratchDir::new()?;
+
+    let mut targets = Vec::new();
+    for file in files {
+        if file.path().extension().map_or(true, |ext| ext != "move") {
+            continue;
+        }
+
+        let path = dir.path().join(file.path());
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, file.content())
+            .with_context(|| format!("Could not write `{}`", path.display()))?;
+
+        targets.push(path.to_string_lossy().to_string());
+    }
+
+    let (sources, result) = Compiler::from_files(
+        targets,
+        Vec::new(),
+        BTreeMap::<String, NumericalAddress>::new(),
+    )
+    .set_flags(Flags::testing())
+    .run::<PASS_PARSER>()?;
+
+    result.map(|_| ()).map_err(|diagnostics| {
+        let report = report_diagnostics_to_buffer(&sources, diagnostics);
+        anyhow!(
+            "Generated contract is not valid Move:\n{}",
+            String::from_utf8_lossy(&report)
+        )
+    })
+}
+
+/// Temporary directory which is removed once dropped
+///
+/// The compiler reads sources from disk, so generated files must be written
+/// out before being parsed.
+struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    fn new() -> Result<Self> {
+        // Tests check many contracts in parallel within the same process
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let path = std::env::temp_dir().join(format!(
+            "gutenberg-check-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&path)?;
+
+        Ok(Self(path))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,419 @@
+//! Compatibility of template features with NftProtocol versions
+//!
+//! Generated contracts call into NftProtocol and its sibling packages, whose
+//! APIs change between releases. The matrix below records which NftProtocol
+//! versions every template feature was verified against, and which Move
+//! function generated code must call for a given NftProtocol version.
+//!
+//! Versions listed in `versions/versions.json` must always be covered by the
+//! matrix, this is enforced by tests.
+
+use anyhow::{anyhow, Result};
+use gutenberg_types::{
+    models::{collection::Supply, nft::Orderbook},
+    Schema,
+};
+use package_manager::version::Version;
+use std::fmt;
+
+/// Version of the templates, reported when refusing an NftProtocol version
+pub const TEMPLATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// NftProtocol version assumed when none was requested
+///
+/// Must be listed in `versions/versions.json`, this is enforced by tests.
+pub const DEFAULT_PROTOCOL_VERSION: Version = V1_0;
+
+/// Template features whose generated code depends on the NftProtocol API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    /// Collection, display and minting, generated for every contract
+    Core,
+    Royalties,
+    EnforcedSupply,
+    TransferPolicy,
+    WithdrawPolicy,
+    BorrowPolicy,
+    UnprotectedOrderbook,
+    ProtectedOrderbook,
+    Launchpad,
+    Dynamic,
+    Burn,
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Feature::Core => "core",
+            Feature::Royalties => "royalties",
+            Feature::EnforcedSupply => "enforced supply",
+            Feature::TransferPolicy => "transfer policy",
+            Feature::WithdrawPolicy => "withdraw policy",
+            Feature::BorrowPolicy => "borrow policy",
+            Feature::UnprotectedOrderbook => "unprotected orderbook",
+            Feature::ProtectedOrderbook => "protected orderbook",
+            Feature::Launchpad => "launchpad",
+            Feature::Dynamic => "dynamic fields",
+            Feature::Burn => "burn",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Move functions called by generated code whose path depends on the
+/// NftProtocol version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Api {
+    InitTransferPolicy,
+    InitWithdrawPolicy,
+    InitBorrowPolicy,
+    CreateUnprotectedOrderbook,
+    NewProtectedOrderbook,
+    CustomProtection,
+    ShareOrderbook,
+}
+
+/// Range of NftProtocol versions a feature is supported on, `until` is
+/// exclusive
+///
+/// `since` is the first NftProtocol release publishing every package the
+/// generated code of the feature calls into.
+struct Support {
+    feature: Feature,
+    since: Version,
+    until: Version,
+}
+
+/// Path of the Move function to call since the given NftProtocol version
+struct Shape {
+    api: Api,
+    since: Version,
+    path: &'static str,
+}
+
+const V1_0: Version = Version::new(1, 0, 0);
+const V2: Version = Version::new(2, 0, 0);
+
+static SUPPORT: [Support; 11] = [
+    Support {
+        feature: Feature::Core,
+        since: V1_0,
+        until: V2,
+    },
+    Support {
+        feature: Feature::Royalties,
+        since: V1_0,
+        until: V2,
+    },
+    Support {
+        feature: Feature::EnforcedSupply,
+        since: V1_0,
+        until: V2,
+    },
+    Support {
+        feature: Feature::TransferPolicy,
+        since: V1_0,
+        until: V2,
+    },
+    Support {
+        feature: Feature::WithdrawPolicy,
+        since: V1_0,
+        until: V2,
+    },
+    Support {
+        feature: Feature::BorrowPolicy,
+        since: V1_0,
+        until: V2,
+    },
+    Support {
+        feature: Feature::UnprotectedOrderbook,
+        since: V1_0,
+        until: V2,
+    },
+    Support {
+        feature: Feature::ProtectedOrderbook,
+        since: V1_0,
+        until: V2,
+    },
+    Support {
+        feature: Feature::Launchpad,
+        since: V1_0,
+        until: V2,
+    },
+    Support {
+        feature: Feature::Dynamic,
+        since: V1_0,
+        until: V2,
+    },
+    Support {
+        feature: Feature::Burn,
+        since: V1_0,
+        until: V2,
+    },
+];
+
+static SHAPES: [Shape; 7] = [
+    Shape {
+        api: Api::InitTransferPolicy,
+        since: V1_0,
+        path: "ob_request::transfer_request::init_policy",
+    },
+    Shape {
+        api: Api::InitWithdrawPolicy,
+        since: V1_0,
+        path: "ob_request::withdraw_request::init_policy",
+    },
+    Shape {
+        api: Api::InitBorrowPolicy,
+        since: V1_0,
+        path: "ob_request::borrow_request::init_policy",
+    },
+    Shape {
+        api: Api::CreateUnprotectedOrderbook,
+        since: V1_0,
+        path: "liquidity_layer_v1::orderbook::create_unprotected",
+    },
+    Shape {
+        api: Api::NewProtectedOrderbook,
+        since: V1_0,
+        path: "liquidity_layer_v1::orderbook::new_with_protected_actions",
+    },
+    Shape {
+        api: Api::CustomProtection,
+        since: V1_0,
+        path: "liquidity_layer_v1::orderbook::custom_protection",
+    },
+    Shape {
+        api: Api::ShareOrderbook,
+        since: V1_0,
+        path: "liquidity_layer_v1::orderbook::share",
+    },
+];
+
+/// Template features used by the contract generated for the schema
+pub fn features(schema: &Schema) -> Vec<Feature> {
+    let collection = schema.collection();
+    let nft = schema.nft();
+
+    let mut features = vec![Feature::Core];
+    if collection.has_royalties() {
+        features.push(Feature::Royalties);
+    }
+    if matches!(collection.supply(), Supply::Enforced(_)) {
+        features.push(Feature::EnforcedSupply);
+    }
+    if nft.requires_transfer() {
+        features.push(Feature::TransferPolicy);
+    }
+    if nft.requires_withdraw() {
+        features.push(Feature::WithdrawPolicy);
+    }
+    if nft.requires_borrow() {
+        features.push(Feature::BorrowPolicy);
+    }
+    match nft.orderbook {
+        Some(Orderbook::Unprotected) => {
+            features.push(Feature::UnprotectedOrderbook)
+        }
+        Some(Orderbook::Protected) => {
+            features.push(Feature::ProtectedOrderbook)
+        }
+        None => (),
+    }
+    if nft.mint_policies.has_launchpad() {
+        features.push(Feature::Launchpad);
+    }
+    if nft.dynamic.is_dynamic() {
+        features.push(Feature::Dynamic);
+    }
+    if nft.burn.is_some() {
+        features.push(Feature::Burn);
+    }
+
+    features
+}
+
+/// Whether the template feature is supported on the NftProtocol version
+pub fn is_supported(feature: Feature, version: &Version) -> bool {
+    SUPPORT.iter().any(|support| {
+        support.feature == feature
+            && support.since <= *version
+            && *version < support.until
+    })
+}
+
+/// Fails if any template feature used by the schema is not supported on the
+/// NftProtocol version
+pub fn check(schema: &Schema, version: &Version) -> Result<()> {
+    let unsupported: Vec<String> = features(schema)
+        .into_iter()
+        .filter(|feature| !is_supported(*feature, version))
+        .map(|feature| feature.to_string())
+        .collect();
+
+    if unsupported.is_empty() {
+        return Ok(());
+    }
+
+    Err(anyhow!(
+        "NftProtocol {version} is not supported by Gutenberg \
+        {TEMPLATE_VERSION} for: {}",
+        unsupported.join(", ")
+    ))
+}
+
+/// Path of the Move function generated code must call for the NftProtocol
+/// version
+///
+/// Picks the most recent shape introduced at or before the version, versions
+/// predating every shape fall back to the oldest one as they are refused by
+/// [`check`] anyway.
+pub fn path(api: Api, version: &Version) -> &'static str {
+    let shapes = SHAPES.iter().filter(|shape| shape.api == api);
+    let oldest = shapes.clone().min_by_key(|shape| &shape.since);
+
+    shapes
+        .filter(|shape| shape.since <= *version)
+        .max_by_key(|shape| &shape.since)
+        .or(oldest)
+        .map(|shape| shape.path)
+        .expect("Every `Api` must have a shape")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::Value;
+
+    const ALL_FEATURES: [Feature; 11] = [
+        Feature::Core,
+        Feature::Royalties,
+        Feature::EnforcedSupply,
+        Feature::TransferPolicy,
+        Feature::WithdrawPolicy,
+        Feature::BorrowPolicy,
+        Feature::UnprotectedOrderbook,
+        Feature::ProtectedOrderbook,
+        Feature::Launchpad,
+        Feature::Dynamic,
+        Feature::Burn,
+    ];
+
+    const ALL_APIS: [Api; 7] = [
+        Api::InitTransferPolicy,
+        Api::InitWithdrawPolicy,
+        Api::InitBorrowPolicy,
+        Api::CreateUnprotectedOrderbook,
+        Api::NewProtectedOrderbook,
+        Api::CustomProtection,
+        Api::ShareOrderbook,
+    ];
+
+    fn registry_versions() -> Vec<Version> {
+        let registry: Value = serde_json::from_str(include_str!(
+            "../../../versions/versions.json"
+        ))
+        .unwrap();
+
+        registry["NftProtocol"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|version| version.parse().unwrap())
+            .collect()
+    }
+
+    /// Registry packages the generated code of the feature calls into
+    fn packages(feature: Feature) -> &'static [&'static str] {
+        match feature {
+            Feature::Core => &["NftProtocol", "Kiosk", "Permissions"],
+            Feature::Royalties | Feature::EnforcedSupply => &["NftProtocol"],
+            Feature::TransferPolicy => &["Request", "Allowlist"],
+            Feature::WithdrawPolicy | Feature::BorrowPolicy => &["Request"],
+            Feature::UnprotectedOrderbook | Feature::ProtectedOrderbook => {
+                &["LiquidityLayerV1"]
+            }
+            Feature::Launchpad => &["Launchpad"],
+            Feature::Dynamic | Feature::Burn => &["NftProtocol", "Kiosk"],
+        }
+    }
+
+    #[test]
+    fn covers_registry_versions() {
+        let versions = registry_versions();
+        assert!(!versions.is_empty());
+        assert!(versions.contains(&DEFAULT_PROTOCOL_VERSION));
+
+        for version in versions.iter() {
+            for feature in ALL_FEATURES {
+                assert!(
+                    is_supported(feature, version),
+                    "{feature} is not supported on NftProtocol {version}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn features_are_supported_since_a_registry_release() {
+        let registry: Value = serde_json::from_str(include_str!(
+            "../../package-manager/registry/registry-main.json"
+        ))
+        .unwrap();
+
+        for support in SUPPORT.iter() {
+            let since = support.since.to_string();
+            let protocol = &registry["NftProtocol"][&since];
+            assert!(
+                protocol.is_object(),
+                "NftProtocol {since} is not published"
+            );
+
+            for package in packages(support.feature) {
+                let published = *package == "NftProtocol"
+                    || !protocol["dependencies"][package].is_null()
+                    || !registry[package][&since].is_null();
+                assert!(
+                    published,
+                    "{package} is not published with NftProtocol {since}, \
+                    required by {}",
+                    support.feature
+                );
+            }
+        }
+
+        for shape in SHAPES.iter() {
+            assert!(
+                registry["NftProtocol"][shape.since.to_string()].is_object()
+            );
+        }
+    }
+
+    #[test]
+    fn every_api_has_a_shape() {
+        for api in ALL_APIS {
+            for version in registry_versions() {
+                assert!(!path(api, &version).is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn refuses_unsupported_versions() {
+        let schema: Schema = serde_json::from_value(serde_json::json!({
+            "packageName": "gnomes",
+            "nft": {
+                "typeName": "Gnome",
+                "mintCap": 100,
+                "orderbook": "protected"
+            }
+        }))
+        .unwrap();
+
+        assert!(check(&schema, &DEFAULT_PROTOCOL_VERSION).is_ok());
+
+        let err = check(&schema, &Version::new(2, 0, 0)).unwrap_err();
+        assert!(err.to_string().contains("protected orderbook"));
+        assert!(check(&schema, &Version::new(0, 9, 0)).is_err());
+    }
+}
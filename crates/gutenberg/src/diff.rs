@@ -0,0 +1,173 @@
+//! Compares generated contracts against packages on disk
+//!
+//! Contracts are regenerated in memory and diffed against an existing
+//! package, which powers both `gutenberg diff` and the golden file tests.
+
+use crate::{generate_contract_with_schema, header, read_schema, ContractFile};
+use anyhow::{anyhow, Result};
+use gutenberg_types::Schema;
+use similar::TextDiff;
+use std::{
+    collections::BTreeSet,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Generated file which differs from its counterpart on disk
+#[derive(Debug)]
+pub struct FileDiff {
+    pub path: PathBuf,
+    pub status: DiffStatus,
+    /// Unified diff from the file on disk to the generated file
+    pub unified: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffStatus {
+    /// File would be created
+    Added,
+    /// File would be overwritten
+    Modified,
+    /// File would be removed as it is not generated
+    Removed,
+}
+
+impl fmt::Display for FileDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.unified)
+    }
+}
+
+/// Regenerates the contract for the given schema and diffs it against the
+/// package in `contract_dir`
+pub fn diff_contract(
+    schema: &Schema,
+    contract_dir: &Path,
+) -> Result<Vec<FileDiff>> {
+    diff_files(&generate_contract_with_schema(schema), contract_dir)
+}
+
+/// Regenerates the contract for the configuration file at `config_path` and
+/// diffs it against the package in `contract_dir`
+pub fn diff_project(
+    config_path: &Path,
+    contract_dir: &Path,
+) -> Result<Vec<FileDiff>> {
+    diff_contract(&read_schema(config_path)?, contract_dir)
+}
+
+/// Diffs generated files against the package in `contract_dir`
+///
+/// Move sources present in the package but not among the generated files are
+/// reported as removed, since generating the contract replaces the `sources`
+/// directory.
+pub fn diff_files(
+    files: &[ContractFile],
+    contract_dir: &Path,
+) -> Result<Vec<FileDiff>> {
+    let mut diffs = Vec::new();
+
+    for file in files {
+        let existing = read_optional(&contract_dir.join(file.path()))?;
+        let status = match existing {
+            Some(ref existing) if existing == file.content() => continue,
+            Some(_) => DiffStatus::Modified,
+            None => DiffStatus::Added,
+        };
+
+        diffs.push(FileDiff {
+            path: file.path().to_path_buf(),
+            status,
+            unified: unified_diff(
+                file.path(),
+                existing.as_deref().unwrap_or_default(),
+                file.content(),
+            ),
+        });
+    }
+
+    let generated: BTreeSet<&Path> =
+        files.iter().map(ContractFile::path).collect();
+
+    for path in existing_sources(contract_dir)? {
+        if generated.contains(path.as_path()) {
+            continue;
+        }
+
+        let existing = fs::read_to_string(contract_dir.join(&path))?;
+        diffs.push(FileDiff {
+            unified: unified_diff(&path, &existing, ""),
+            path,
+            status: DiffStatus::Removed,
+        });
+    }
+
+    Ok(diffs)
+}
+
+/// Move sources in the package which were either edited since they were
+/// generated or were not generated by Gutenberg at all
+///
+/// These would be lost if the contract was generated again.
+pub fn find_hand_edits(contract_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut edited = Vec::new();
+
+    for path in existing_sources(contract_dir)? {
+        let content = fs::read_to_string(contract_dir.join(&path))?;
+        if header::is_unmodified(&content) != Some(true) {
+            edited.push(path);
+        }
+    }
+
+    Ok(edited)
+}
+
+fn unified_diff(path: &Path, old: &str, new: &str) -> String {
+    let path = path.display();
+
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{path}"), &format!("b/{path}"))
+        .to_string()
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => {
+            Err(anyhow!(r#"Could not read "{}": {err}"#, path.display()))
+        }
+    }
+}
+
+/// Paths of Move sources in the package, relative to `contract_dir`
+fn existing_sources(contract_dir: &Path) -> Result<Vec<PathBuf>> {
+    let sources_dir = contract_dir.join("sources");
+
+    let entries = match fs::read_dir(&sources_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Ok(Vec::new())
+        }
+        Err(err) => {
+            return Err(anyhow!(
+                r#"Could not read directory "{}": {err}"#,
+                sources_dir.display()
+            ))
+        }
+    };
+
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "move") {
+            paths
+                .push(PathBuf::from("sources").join(path.file_name().unwrap()));
+        }
+    }
+    paths.sort();
+
+    Ok(paths)
+}
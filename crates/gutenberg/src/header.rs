@@ -0,0 +1,106 @@
+//! Header stamped onto every generated Move source
+//!
+//! The header records what the source was generated from, the template and
+//! NftProtocol versions and a digest of the configuration, and carries a
+//! digest of the code below it, such that sources which were edited by hand
+//! since they were generated can be detected before being overwritten.
+
+use crate::compat::TEMPLATE_VERSION;
+use gutenberg_types::Schema;
+use package_manager::version::Version;
+use std::fmt::Write;
+
+const MARKER: &str =
+    "// @generated by Gutenberg, manual changes will be overwritten";
+const DIGEST_PREFIX: &str = "// digest: ";
+
+/// Describes the inputs the source was generated from
+///
+/// The `$schema` reference only matters to editors, so it does not
+/// contribute to the configuration digest.
+pub fn provenance(schema: &Schema, protocol_version: &Version) -> String {
+    let mut schema = schema.clone();
+    schema.schema_url = None;
+    let config = serde_json::to_string(&schema)
+        .expect("Schema must always be serializable");
+
+    format!(
+        "// template: {TEMPLATE_VERSION}, NftProtocol: {protocol_version}, \
+        schema: {:016x}",
+        digest(&config)
+    )
+}
+
+/// Prepends the header to generated Move source
+pub fn stamp(provenance: &str, body: &str) -> String {
+    let mut content = String::with_capacity(body.len() + 192);
+    writeln!(content, "{MARKER}").unwrap();
+    writeln!(content, "{provenance}").unwrap();
+    writeln!(content, "{DIGEST_PREFIX}{:016x}", digest(body)).unwrap();
+    content.push_str(body);
+    content
+}
+
+/// Whether the content still matches the digest stamped in its header
+///
+/// Returns `None` if the content was not generated by Gutenberg.
+pub fn is_unmodified(content: &str) -> Option<bool> {
+    let mut rest = content.strip_prefix(MARKER)?.strip_prefix('\n')?;
+
+    // Skip other header lines until reaching the digest
+    loop {
+        let (line, body) = rest.split_once('\n')?;
+        if let Some(expected) = line.strip_prefix(DIGEST_PREFIX) {
+            let expected = u64::from_str_radix(expected, 16).ok()?;
+            return Some(digest(body) == expected);
+        }
+
+        line.strip_prefix("// ")?;
+        rest = body;
+    }
+}
+
+/// 64-bit FNV-1a
+///
+/// Digest must be stable across Rust versions and platforms, which is not
+/// guaranteed by `std::hash`.
+fn digest(body: &str) -> u64 {
+    body.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn detects_modification() {
+        let content = stamp("// NftProtocol: 1.0.0", "module a::b {}\n");
+        assert_eq!(is_unmodified(&content), Some(true));
+
+        let edited = content.replace("{}", "{ }");
+        assert_eq!(is_unmodified(&edited), Some(false));
+
+        assert_eq!(is_unmodified("module a::b {}\n"), None);
+    }
+
+    #[test]
+    fn provenance_ignores_schema_reference() {
+        let mut schema: Schema = serde_json::from_str(
+            r#"{ "packageName": "gnomes", "nft": { "typeName": "Gnome", "mintCap": 1 } }"#,
+        )
+        .unwrap();
+        let version = Version::new(1, 0, 0);
+
+        let expected = provenance(&schema, &version);
+        assert!(expected.contains(&format!("template: {TEMPLATE_VERSION}")));
+        assert!(expected.contains("NftProtocol: 1.0.0"));
+
+        schema.schema_url = Some("./gutenberg.schema.json".to_string());
+        assert_eq!(provenance(&schema, &version), expected);
+
+        schema.nft.type_name = "Elf".to_string();
+        assert_ne!(provenance(&schema, &version), expected);
+    }
+}
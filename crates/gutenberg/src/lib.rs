@@ -1,10 +1,23 @@
+pub mod ast;
+#[cfg(feature = "move-check")]
+pub mod check;
+pub mod compat;
+pub mod diff;
+mod header;
+pub mod literal;
 mod manifest;
 mod models;
+pub mod scenarios;
 mod schema;
 
 use anyhow::{anyhow, Result};
+use ast::{Member, Stmt, Type};
 use gutenberg_types::{
-    models::{collection::CollectionData, nft::Fields},
+    migrate::migrate,
+    models::{
+        collection::CollectionData,
+        nft::{Fields, NftData},
+    },
     Schema,
 };
 pub use manifest::write_manifest;
@@ -12,6 +25,7 @@ use manifest::write_manifest_with_flavours;
 use package_manager::{
     get_program_registry, package::Flavor, version::Version, Network,
 };
+pub use schema::{write_move_module, write_move_module_for};
 use std::{
     ffi::OsStr,
     fs::{self, File},
@@ -21,98 +35,108 @@ use std::{
 
 /// Trait for writing Move language contract's `init` function.
 pub trait MoveInit {
-    fn write_move_init(&self, args: InitArgs) -> String;
+    fn write_move_init(&self, context: &Context) -> Vec<Stmt>;
+}
+
+/// Trait for writing Move language contract's `init` function from models
+/// carrying free-form Move types, which are validated as they are written.
+pub trait TryMoveInit {
+    fn try_write_move_init(&self, context: &Context) -> Result<Vec<Stmt>>;
 }
 
 /// Trait for writing Move language contract's function definitions
 pub trait MoveDefs {
-    fn write_move_defs(&self, args: DefArgs) -> String;
+    fn write_move_defs(&self, context: &Context) -> Vec<Member>;
 }
 
 /// Trait for writing Move language contract's tests
 pub trait MoveTests {
-    fn write_move_tests(&self, args: TestArgs) -> String;
+    fn write_move_tests(&self, context: &Context) -> Vec<Member>;
 }
 
 /// Trait for writing Move language contract. It serves as a wrapper trait as it
 /// orchestrates the high-level codegen
 pub trait WriteMove {
-    fn write_move(&self) -> ContractFile;
+    fn write_move(&self, protocol_version: &Version) -> ContractFile;
 }
 
-// Enums for arguments passed to the `MoveInit` trait...
-pub enum InitArgs<'a> {
-    MintCap {
-        witness: &'a str,
-        type_name: &'a str,
-    },
-    NftData {
-        collection_data: &'a CollectionData,
-    },
-    CollectionData {
-        type_name: &'a str,
-    },
-    Orderbook {
-        type_name: &'a str,
-    },
-    None,
+/// Properties of the contract being generated that are shared by the
+/// `MoveInit`, `MoveDefs` and `MoveTests` implementations of every model
+pub struct Context<'a> {
+    schema: &'a Schema,
+    protocol_version: Version,
+    type_name: String,
+    witness_name: String,
 }
 
-// Enums for arguments passed to the `MoveDefs` trait...
-pub enum DefArgs<'a> {
-    Burn {
-        fields: &'a Fields,
-        type_name: &'a str,
-        requires_collection: bool,
-        requires_listing: bool,
-        requires_confirm: bool,
-    },
-    Dynamic {
-        fields: &'a Fields,
-        type_name: &'a str,
-    },
-    MintPolicies {
-        fields: &'a Fields,
-        type_name: &'a str,
-        requires_collection: bool,
-    },
-    NftData {
-        collection_data: &'a CollectionData,
-    },
-    None,
-}
+impl<'a> Context<'a> {
+    /// Context targeting [`compat::DEFAULT_PROTOCOL_VERSION`]
+    pub fn new(schema: &'a Schema) -> Self {
+        Self::with_protocol_version(schema, compat::DEFAULT_PROTOCOL_VERSION)
+    }
 
-// Enums for arguments passed to the `MoveTests` trait...
-pub enum TestArgs<'a> {
-    Burn {
-        fields: &'a Fields,
-        type_name: &'a str,
-        witness_name: &'a str,
-        requires_collection: bool,
-    },
-    Dynamic {
-        fields: &'a Fields,
-        type_name: &'a str,
-        witness_name: &'a str,
-        requires_collection: bool,
-    },
-    MintPolicies {
-        fields: &'a Fields,
-        type_name: &'a str,
-        witness_name: &'a str,
-        requires_collection: bool,
-    },
-    Orderbook {
-        fields: &'a Fields,
-        type_name: &'a str,
-        witness_name: &'a str,
-        requires_collection: bool,
-        requires_royalties: bool,
-    },
-    NftData {
-        collection_data: &'a CollectionData,
-    },
-    None,
+    pub fn with_protocol_version(
+        schema: &'a Schema,
+        protocol_version: Version,
+    ) -> Self {
+        Self {
+            schema,
+            protocol_version,
+            type_name: schema.nft().type_name(),
+            witness_name: schema.nft().witness_name(),
+        }
+    }
+
+    pub fn schema(&self) -> &'a Schema {
+        self.schema
+    }
+
+    pub fn collection(&self) -> &'a CollectionData {
+        self.schema.collection()
+    }
+
+    pub fn nft(&self) -> &'a NftData {
+        self.schema.nft()
+    }
+
+    pub fn fields(&self) -> &'a Fields {
+        self.nft().fields()
+    }
+
+    /// NftProtocol version the contract is generated for
+    pub fn protocol_version(&self) -> &Version {
+        &self.protocol_version
+    }
+
+    /// Path of the Move function to call for the NftProtocol version
+    pub fn api(&self, api: compat::Api) -> &'static str {
+        compat::path(api, &self.protocol_version)
+    }
+
+    /// Name of the NFT type
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// Name of the one-time witness
+    pub fn witness_name(&self) -> &str {
+        &self.witness_name
+    }
+
+    /// NFT type declared by the contract
+    pub fn nft_type(&self) -> Type {
+        Type::named(&self.type_name)
+    }
+
+    /// Whether `&mut Collection` needs to be passed into mint methods
+    pub fn requires_collection(&self) -> bool {
+        self.collection().requires_collection()
+    }
+
+    /// Whether collection has royalty policy defined
+    pub fn has_royalties(&self) -> bool {
+        self.collection().has_royalties()
+    }
 }
 
 /// Used to return all files for loading contract
@@ -149,45 +173,67 @@ impl ContractFile {
     }
 }
 
-/// Functions for generating and managing Move language contracts and projects.
+/// Creates the contract directory for the given schema under `output_dir`
 ///
-/// Includes functions for generating contract directories, writing contracts,
-/// asserting schema, normalizing type names, and generating projects with
-/// flavors.
-pub fn generate_contract_dir(schema: &Schema, output_dir: &Path) -> PathBuf {
+/// # Returns
+/// Path to the contract directory, named after the package.
+pub fn generate_contract_dir(
+    schema: &Schema,
+    output_dir: &Path,
+) -> Result<PathBuf> {
     // Create main contract directory
     let package_name = schema.package_name();
     let contract_dir = output_dir.join(&package_name);
     let sources_dir = contract_dir.join("sources");
 
     // Create directories
-    std::fs::create_dir_all(&sources_dir).unwrap();
+    fs::create_dir_all(&sources_dir).map_err(|err| {
+        anyhow!(
+            r#"Could not create directory "{}": {err}"#,
+            sources_dir.display()
+        )
+    })?;
 
-    contract_dir
+    Ok(contract_dir)
 }
 
-/// Generates a contract with the given schema and optionally enforces demo
-/// constraints.
+/// Generates a contract with the given schema for
+/// [`compat::DEFAULT_PROTOCOL_VERSION`].
 ///
 /// # Arguments
-/// * `schema` - A mutable reference to a Schema, representing the contract's
+/// * `schema` - A reference to a Schema, representing the contract's
 ///   structure.
-/// * `is_demo` - A boolean indicating if demo constraints should be enforced.
 ///
 /// # Returns
 /// A vector of `ContractFile` objects representing the generated contract
 /// files.
-///
-/// # Functionality
-/// - If `is_demo` is true, enforces demo constraints on the schema.
-/// - Generates contract files based on the schema.
 pub fn generate_contract_with_schema(schema: &Schema) -> Vec<ContractFile> {
     let mut files = Vec::new();
-    files.push(schema.write_move());
+    files.push(schema.write_move(&compat::DEFAULT_PROTOCOL_VERSION));
 
     files
 }
 
+/// Generates a contract with the given schema for an NftProtocol version.
+///
+/// # Arguments
+/// * `schema` - A reference to a Schema, representing the contract's
+///   structure.
+/// * `protocol_version` - NftProtocol version the contract will depend on.
+///
+/// # Returns
+/// A vector of `ContractFile` objects representing the generated contract
+/// files, or an error if the schema uses template features which are not
+/// supported on the NftProtocol version.
+pub fn generate_contract(
+    schema: &Schema,
+    protocol_version: &Version,
+) -> Result<Vec<ContractFile>> {
+    compat::check(schema, protocol_version)?;
+
+    Ok(vec![schema.write_move(protocol_version)])
+}
+
 /// Generates a project with the given configuration and writes it to the
 /// specified directory.
 ///
@@ -195,44 +241,56 @@ pub fn generate_contract_with_schema(schema: &Schema) -> Vec<ContractFile> {
 /// * `config_path` - Path to the configuration file.
 /// * `flavor` - The network flavor, whether `Mainnet` or `Testnet`.
 /// * `output_dir` - Path to the output directory for writing the project.
-/// * `version` - Optional string representing the version of the project.
+/// * `version` - Optional NftProtocol version to depend on, defaults to the
+///   latest version in the program registry of the flavor.
 ///
 /// # Returns
-/// Result indicating success or error.
+/// Path to the generated contract directory.
 ///
 /// # Functionality
-/// - Asserts the schema from the configuration file.
+/// - Reads the schema from the configuration file.
+/// - Checks that the schema is supported on the NftProtocol version.
 /// - Generates the contract directory and writes the project's manifest.
 /// - Generates and writes the contract files based on the schema.
 pub fn generate_project(
     config_path: &Path,
     flavor: Flavor,
     output_dir: &Path,
-    version: Option<String>,
-) -> Result<()> {
-    let schema = assert_schema(config_path);
-    let contract_dir = generate_contract_dir(&schema, output_dir);
+    version: Option<Version>,
+) -> Result<PathBuf> {
+    let schema = read_schema(config_path)?;
 
-    let registry = get_program_registry(&Network::Mainnet)?;
+    let network = match flavor {
+        Flavor::Mainnet => Network::Mainnet,
+        Flavor::Testnet => Network::Testnet,
+    };
+    let registry = get_program_registry(&network)?;
 
-    let version: Option<Version> = version.map(|s| s.parse().ok()).flatten();
+    let version = match version {
+        Some(version) => version,
+        None => registry.get_latest_version("NftProtocol")?.clone(),
+    };
+    // Refuse unsupported combinations before writing anything
+    let files = generate_contract(&schema, &version)?;
+
+    let contract_dir = generate_contract_dir(&schema, output_dir)?;
 
     write_manifest(
         schema.package_name(),
         flavor,
         &contract_dir,
         &registry,
-        version,
+        Some(version),
     )?;
 
-    generate_contract_with_schema(&schema)
+    files
         .into_iter()
         .try_for_each(|file| file.write_to_file(&contract_dir))?;
 
-    Ok(())
+    Ok(contract_dir)
 }
 
-/// Asserts that the given configuration file has a correct schema.
+/// Reads the schema from the given configuration file.
 ///
 /// # Arguments
 /// * `path` - Path to the configuration file.
@@ -242,66 +300,72 @@ pub fn generate_project(
 ///
 /// # Functionality
 /// - Opens and reads the configuration file.
-/// - Parses the file based on its extension (either YAML or JSON) to a
-///   `Schema`.
-fn assert_schema(path: &Path) -> Schema {
-    let config = File::open(path).unwrap();
+/// - Parses the file based on its extension (either YAML or JSON).
+/// - Migrates configurations written in older schema versions to a `Schema`,
+///   failing on schema versions newer than supported.
+/// - Checks that the market tokens of the launchpad are Move struct types.
+pub fn read_schema(path: &Path) -> Result<Schema> {
+    let config = File::open(path).map_err(|err| {
+        anyhow!(r#"Could not open "{}": {err}"#, path.display())
+    })?;
     let extension =
         path.extension().and_then(OsStr::to_str).unwrap_or_default();
 
-    match extension {
-        "yaml" => match serde_yaml::from_reader::<_, Schema>(config) {
-            Ok(schema) => schema,
-            Err(err) => {
-                eprintln!(
-                    "Could not parse `{path}` due to {err}",
-                    path = path.display()
-                );
-                std::process::exit(1);
-            }
-        },
-        "json" => match serde_json::from_reader::<_, Schema>(config) {
-            Ok(schema) => schema,
-            Err(err) => {
-                eprintln!(
-                    "Could not parse `{path}` due to {err}",
-                    path = path.display()
-                );
-                std::process::exit(1);
-            }
-        },
-        _ => {
-            eprintln!("Extension {extension} not supported");
-            std::process::exit(1);
-        }
+    let config: serde_json::Value = match extension {
+        "yaml" => serde_yaml::from_reader(config).map_err(|err| {
+            anyhow!("Could not parse `{}` due to {err}", path.display())
+        })?,
+        "json" => serde_json::from_reader(config).map_err(|err| {
+            anyhow!("Could not parse `{}` due to {err}", path.display())
+        })?,
+        _ => return Err(anyhow!("Extension {extension} not supported")),
+    };
+
+    let migrated = migrate(config).map_err(|err| {
+        anyhow!("Could not parse `{}` due to {err}", path.display())
+    })?;
+
+    if let Some(launchpad) = migrated.schema.launchpad.as_ref() {
+        models::launchpad::check_tokens(launchpad).map_err(|err| {
+            anyhow!("Could not parse `{}` due to {err}", path.display())
+        })?;
     }
+
+    Ok(migrated.schema)
 }
 
 /// Generates a project with flavors, setting up the project structure and
 /// writing the contract.
 ///
 /// # Arguments
-/// * `is_demo` - A boolean indicating if the project is a demo.
-/// * `schema` - A mutable reference to the Schema.
+/// * `schema` - A reference to the Schema.
 /// * `contract_dir` - Path to the directory where the contract should be
 ///   written.
-/// * `version` - Optional string representing the version of the project.
+/// * `version` - Optional NftProtocol version to depend on, defaults to the
+///   latest version in the main program registry.
 ///
 /// # Returns
 /// Result indicating success or error.
 ///
 /// # Functionality
+/// - Checks that the schema is supported on the NftProtocol version.
 /// - Sets up the project structure, including source directories.
 /// - Writes the project's manifest with flavors.
 /// - Generates and writes the contract files based on the schema.
 pub fn generate_project_with_flavors(
     schema: &Schema,
     contract_dir: &Path,
+    version: Option<Version>,
 ) -> Result<()> {
     let (main_registry, test_registry) =
         package_manager::get_program_registries()?;
 
-    let version = main_registry.get_latest_version("NftProtocol")?;
+    let version = match version {
+        Some(version) => version,
+        None => main_registry.get_latest_version("NftProtocol")?.clone(),
+    };
+    // Refuse unsupported combinations before removing existing sources
+    let files = generate_contract(schema, &version)?;
 
     let sources_dir = &contract_dir.join("sources");
     let _ = fs::remove_dir_all(sources_dir);
@@ -317,11 +381,11 @@ pub fn generate_project_with_flavors(
         &contract_dir,
         &main_registry,
         &test_registry,
-        Some(*version),
+        Some(version),
     )?;
 
     // Write Move contract
-    generate_contract_with_schema(schema)
+    files
         .into_iter()
         .try_for_each(|file| file.write_to_file(&contract_dir))?;
 
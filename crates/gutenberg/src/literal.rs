@@ -0,0 +1,370 @@
+//! Encoding layer for every user supplied value that ends up in generated
+//! Move source.
+//!
+//! Schemas can be deserialized from untrusted sources, therefore no model
+//! should interpolate raw strings into Move code. Byte strings are rendered
+//! with [`byte_string`], which either escapes the value or falls back to a
+//! hex literal, and type arguments are parsed into a [`MoveType`] before
+//! being rendered.
+
+use anyhow::{anyhow, bail, Result};
+use gutenberg_types::{models::address::Address, MOVE_KEYWORDS};
+use std::{fmt, str::FromStr};
+
+/// Renders a value as a Move byte string literal
+///
+/// Printable ASCII values are emitted as `b"..."` with quotes and backslashes
+/// escaped. Any other value is emitted as a `x"..."` hex literal such that
+/// newlines, control characters and multi-byte characters are preserved
+/// without ever being interpreted by the Move lexer.
+pub fn byte_string(value: &str) -> String {
+    let is_printable = value.bytes().all(|byte| (0x20..0x7f).contains(&byte));
+
+    if is_printable {
+        let mut literal = String::with_capacity(value.len() + 3);
+        literal.push_str("b\"");
+        for char in value.chars() {
+            match char {
+                '"' => literal.push_str("\\\""),
+                '\\' => literal.push_str("\\\\"),
+                char => literal.push(char),
+            }
+        }
+        literal.push('"');
+        literal
+    } else {
+        let hex: String =
+            value.bytes().map(|byte| format!("{byte:02x}")).collect();
+        format!("x\"{hex}\"")
+    }
+}
+
+/// Renders a value as a Move `std::string::String` expression
+pub fn utf8_string(value: &str) -> String {
+    format!("std::string::utf8({})", byte_string(value))
+}
+
+/// Renders an address as a Move address literal
+pub fn address(address: &Address) -> String {
+    format!("@{address}")
+}
+
+/// Whether the value is a valid Move identifier, which excludes keywords
+pub fn is_identifier(value: &str) -> bool {
+    if MOVE_KEYWORDS.contains(&value) {
+        return false;
+    }
+
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(char) if char.is_ascii_alphabetic() => (),
+        // A lone underscore is reserved as a wildcard
+        Some('_') if value.len() > 1 => (),
+        _ => return false,
+    }
+
+    chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+/// Address component of a fully qualified Move struct type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveAddress {
+    /// Numerical address, such as `0x2`
+    Numerical(String),
+    /// Named address, such as `sui`
+    Named(String),
+}
+
+impl fmt::Display for MoveAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveAddress::Numerical(hex) => write!(f, "0x{hex}"),
+            MoveAddress::Named(name) => f.write_str(name),
+        }
+    }
+}
+
+/// Validated Move type
+///
+/// Used to render type arguments, such as the fungible token in which a
+/// market is denominated, which are otherwise provided as free-form strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Signer,
+    Vector(Box<MoveType>),
+    Struct {
+        address: MoveAddress,
+        module: String,
+        name: String,
+        type_params: Vec<MoveType>,
+    },
+}
+
+impl MoveType {
+    /// Whether the type is a struct type, as is required for coin types
+    pub fn is_struct(&self) -> bool {
+        matches!(self, MoveType::Struct { .. })
+    }
+}
+
+impl fmt::Display for MoveType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveType::Bool => f.write_str("bool"),
+            MoveType::U8 => f.write_str("u8"),
+            MoveType::U16 => f.write_str("u16"),
+            MoveType::U32 => f.write_str("u32"),
+            MoveType::U64 => f.write_str("u64"),
+            MoveType::U128 => f.write_str("u128"),
+            MoveType::U256 => f.write_str("u256"),
+            MoveType::Address => f.write_str("address"),
+            MoveType::Signer => f.write_str("signer"),
+            MoveType::Vector(inner) => write!(f, "vector<{inner}>"),
+            MoveType::Struct {
+                address,
+                module,
+                name,
+                type_params,
+            } => {
+                write!(f, "{address}::{module}::{name}")?;
+
+                if !type_params.is_empty() {
+                    let params: Vec<String> =
+                        type_params.iter().map(ToString::to_string).collect();
+                    write!(f, "<{}>", params.join(", "))?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for MoveType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = TypeParser::new(s)?;
+        let move_type = parser.parse_type()?;
+
+        match parser.next() {
+            None => Ok(move_type),
+            Some(token) => {
+                bail!("Unexpected `{token}` after type `{move_type}` in `{s}`")
+            }
+        }
+    }
+}
+
+/// Lexes a type string into identifiers and punctuation and parses it
+/// recursively
+struct TypeParser<'a> {
+    tokens: Vec<&'a str>,
+    position: usize,
+}
+
+impl<'a> TypeParser<'a> {
+    fn new(s: &'a str) -> Result<Self> {
+        let mut tokens = Vec::new();
+        let mut rest = s;
+
+        while let Some(char) = rest.chars().next() {
+            if char == ' ' {
+                rest = &rest[1..];
+            } else if rest.starts_with("::") {
+                tokens.push(&rest[..2]);
+                rest = &rest[2..];
+            } else if matches!(char, '<' | '>' | ',') {
+                tokens.push(&rest[..1]);
+                rest = &rest[1..];
+            } else if char.is_ascii_alphanumeric() || char == '_' {
+                let end = rest
+                    .find(|char: char| {
+                        !(char.is_ascii_alphanumeric() || char == '_')
+                    })
+                    .unwrap_or(rest.len());
+                tokens.push(&rest[..end]);
+                rest = &rest[end..];
+            } else {
+                bail!("Invalid character {char:?} in type `{s}`")
+            }
+        }
+
+        if tokens.is_empty() {
+            bail!("Type must not be empty")
+        }
+
+        Ok(Self {
+            tokens,
+            position: 0,
+        })
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let token = self.tokens.get(self.position).copied();
+        self.position += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<()> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => bail!("Expected `{expected}` but found `{token}`"),
+            None => bail!("Expected `{expected}` but type ended"),
+        }
+    }
+
+    fn identifier(&mut self) -> Result<String> {
+        match self.next() {
+            Some(token) if is_identifier(token) => Ok(token.to_string()),
+            Some(token) => bail!("`{token}` is not a valid identifier"),
+            None => bail!("Expected identifier but type ended"),
+        }
+    }
+
+    fn parse_type(&mut self) -> Result<MoveType> {
+        let token = self
+            .next()
+            .ok_or_else(|| anyhow!("Expected type but type ended"))?;
+
+        let move_type = match token {
+            "bool" => MoveType::Bool,
+            "u8" => MoveType::U8,
+            "u16" => MoveType::U16,
+            "u32" => MoveType::U32,
+            "u64" => MoveType::U64,
+            "u128" => MoveType::U128,
+            "u256" => MoveType::U256,
+            "address" => MoveType::Address,
+            "signer" => MoveType::Signer,
+            "vector" => {
+                self.expect("<")?;
+                let inner = self.parse_type()?;
+                self.expect(">")?;
+                MoveType::Vector(Box::new(inner))
+            }
+            token => {
+                let address = parse_address(token)?;
+                self.expect("::")?;
+                let module = self.identifier()?;
+                self.expect("::")?;
+                let name = self.identifier()?;
+
+                let mut type_params = Vec::new();
+                if self.peek() == Some("<") {
+                    self.next();
+                    loop {
+                        type_params.push(self.parse_type()?);
+                        match self.next() {
+                            Some(",") => continue,
+                            Some(">") => break,
+                            Some(token) => {
+                                bail!("Expected `,` or `>` but found `{token}`")
+                            }
+                            None => bail!("Unterminated type parameters"),
+                        }
+                    }
+                }
+
+                MoveType::Struct {
+                    address,
+                    module,
+                    name,
+                    type_params,
+                }
+            }
+        };
+
+        Ok(move_type)
+    }
+}
+
+fn parse_address(token: &str) -> Result<MoveAddress> {
+    if let Some(hex) = token.strip_prefix("0x") {
+        if hex.is_empty()
+            || hex.len() > 64
+            || !hex.chars().all(|char| char.is_ascii_hexdigit())
+        {
+            bail!("`{token}` is not a valid address")
+        }
+
+        Ok(MoveAddress::Numerical(hex.to_lowercase()))
+    } else if is_identifier(token) {
+        Ok(MoveAddress::Named(token.to_string()))
+    } else {
+        Err(anyhow!("`{token}` is not a valid address"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn renders_printable_byte_strings() {
+        assert_eq!(byte_string("Suimarines"), r#"b"Suimarines""#);
+        assert_eq!(byte_string(""), r#"b"""#);
+        assert_eq!(byte_string(r#"a"b\c"#), r#"b"a\"b\\c""#);
+        assert_eq!(byte_string("{name}"), r#"b"{name}""#);
+    }
+
+    #[test]
+    fn renders_non_printable_byte_strings_as_hex() {
+        assert_eq!(byte_string("a\nb"), r#"x"610a62""#);
+        assert_eq!(byte_string("é"), r#"x"c3a9""#);
+    }
+
+    #[test]
+    fn parses_move_types() {
+        let move_type: MoveType = "sui::sui::SUI".parse().unwrap();
+        assert_eq!(move_type.to_string(), "sui::sui::SUI");
+        assert!(move_type.is_struct());
+
+        let move_type: MoveType = "0x2::coin::Coin< 0x2::sui::SUI,vector<u8> >"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            move_type.to_string(),
+            "0x2::coin::Coin<0x2::sui::SUI, vector<u8>>"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_move_types() {
+        for token in [
+            "",
+            "SUI",
+            "sui::sui",
+            "sui::sui::SUI>",
+            "sui::sui::SUI<",
+            "sui::sui::SUI<>",
+            "0x::sui::SUI",
+            "0xzz::sui::SUI",
+            "0X2::sui::SUI",
+            "sui::sui::SUI(ctx)",
+            "sui::sui::SUI>(); abort 0; foo<u8",
+            "sui::sui::SUI\n",
+            "vector<u8",
+            "_::sui::SUI",
+            "sui::fun::SUI",
+            "sui::sui::let",
+        ] {
+            assert!(
+                token.parse::<MoveType>().is_err(),
+                "`{token}` should have been rejected"
+            );
+        }
+    }
+}
@@ -7,132 +7,127 @@ pub mod royalties;
 pub mod supply;
 pub mod tags;
 
-use crate::{InitArgs, MoveInit};
+use crate::{
+    ast::{Exp, Stmt},
+    models::share_object,
+    Context, MoveInit,
+};
 use gutenberg_types::models::collection::CollectionData;
 
 impl MoveInit for CollectionData {
-    fn write_move_init(&self, args: InitArgs) -> String {
-        let type_name = init_args(args);
-
-        let mut domains_str = String::new();
-
-        domains_str.push_str(write_move_creators(self).as_str());
-        domains_str.push_str(
-            write_move_collection_display_info(self)
-                .unwrap_or_default()
-                .as_str(),
-        );
-        domains_str.push_str(
-            write_move_collection_symbol(self)
-                .unwrap_or_default()
-                .as_str(),
-        );
-        domains_str.push_str(
-            write_move_collection_url(self).unwrap_or_default().as_str(),
-        );
-        domains_str.push_str(&self.supply.write_move_init(InitArgs::None));
-        domains_str.push_str(
-            self.royalties
-                .as_ref()
-                .map(|royalties| royalties.write_move_init(InitArgs::None))
-                .unwrap_or_default()
-                .as_str(),
-        );
-
+    fn write_move_init(&self, context: &Context) -> Vec<Stmt> {
         // Opt for `collection::create` over `collection::create_from_otw` in
         // order to statically assert `DelegatedWitness` gets created for the
         // `Collection<T>` type `T`.
-        format!("
+        let mut stmts = vec![
+            Stmt::Blank,
+            Stmt::bind(
+                "collection",
+                Exp::call_generic(
+                    "nft_protocol::collection::create",
+                    vec![context.nft_type()],
+                    vec![Exp::var("delegated_witness"), Exp::var("ctx")],
+                ),
+            ),
+            Stmt::bind(
+                "collection_id",
+                Exp::call(
+                    "sui::object::id",
+                    vec![Exp::borrow(Exp::var("collection"))],
+                ),
+            ),
+        ];
+
+        stmts.extend(write_move_creators(self));
+        stmts.extend(write_move_collection_display_info(self));
+        stmts.extend(write_move_collection_symbol(self));
+        stmts.extend(write_move_collection_url(self));
+        stmts.extend(self.supply.write_move_init(context));
+        stmts.extend(
+            self.royalties
+                .iter()
+                .flat_map(|royalties| royalties.write_move_init(context)),
+        );
 
-        let collection = nft_protocol::collection::create<{type_name}>(delegated_witness, ctx);
-        let collection_id = sui::object::id(&collection);{domains_str}
+        stmts.push(Stmt::Blank);
+        stmts.push(share_object("collection"));
 
-        sui::transfer::public_share_object(collection);"
-        )
+        stmts
     }
 }
 
-fn write_move_collection_display_info(data: &CollectionData) -> Option<String> {
-    data.name().map(|name| {
-        let description = data.description().unwrap_or_default();
-
-        format!(
-            "
-
-    nft_protocol::collection::add_domain(
-        delegated_witness,
-        &mut collection,
-        nft_protocol::display_info::new(
-            std::string::utf8(b\"{name}\"),
-            std::string::utf8(b\"{description}\"),
-        ),
-    );"
-        )
-    })
+/// `nft_protocol::collection::add_domain(delegated_witness, &mut collection, {domain});`
+pub fn add_domain(domain: Exp) -> Vec<Stmt> {
+    vec![
+        Stmt::Blank,
+        Stmt::Exp(Exp::call(
+            "nft_protocol::collection::add_domain",
+            vec![
+                Exp::var("delegated_witness"),
+                Exp::borrow_mut(Exp::var("collection")),
+                domain,
+            ],
+        )),
+    ]
+}
+
+fn write_move_collection_display_info(data: &CollectionData) -> Vec<Stmt> {
+    data.name()
+        .map(|name| {
+            let description = data.description().unwrap_or_default();
+
+            add_domain(Exp::call(
+                "nft_protocol::display_info::new",
+                vec![Exp::utf8(&name), Exp::utf8(&description)],
+            ))
+        })
+        .unwrap_or_default()
 }
 
-fn write_move_collection_url(data: &CollectionData) -> Option<String> {
-    data.url().as_ref().map(|url| {
-        format!(
-            "
-
-    nft_protocol::collection::add_domain(
-        delegated_witness,
-        &mut collection,
-        sui::url::new_unsafe_from_bytes(b\"{url}\"),
-    );"
-        )
-    })
+fn write_move_collection_url(data: &CollectionData) -> Vec<Stmt> {
+    data.url()
+        .map(|url| {
+            add_domain(Exp::call(
+                "sui::url::new_unsafe_from_bytes",
+                vec![Exp::bytes(&url)],
+            ))
+        })
+        .unwrap_or_default()
 }
 
-fn write_move_collection_symbol(data: &CollectionData) -> Option<String> {
-    data.symbol().as_ref().map(|symbol| {
-        format!(
-            "
-
-    nft_protocol::collection::add_domain(
-        delegated_witness,
-        &mut collection,
-        nft_protocol::symbol::new(std::string::utf8(b\"{symbol}\")),
-    );",
-        )
-    })
+fn write_move_collection_symbol(data: &CollectionData) -> Vec<Stmt> {
+    data.symbol()
+        .map(|symbol| {
+            add_domain(Exp::call(
+                "nft_protocol::symbol::new",
+                vec![Exp::utf8(&symbol)],
+            ))
+        })
+        .unwrap_or_default()
 }
 
 // TODO: Separate out into `creators` module
-fn write_move_creators(data: &CollectionData) -> String {
-    let mut code = String::new();
+fn write_move_creators(data: &CollectionData) -> Vec<Stmt> {
+    if data.creators.is_empty() {
+        return Vec::new();
+    }
 
-    if !data.creators.is_empty() {
-        code.push_str(
-            "
+    let mut stmts = vec![
+        Stmt::Blank,
+        Stmt::bind("creators", Exp::call("sui::vec_set::empty", vec![])),
+    ];
 
-    let creators = sui::vec_set::empty();",
-        );
-        for address in data.creators.iter() {
-            code.push_str(&format!(
-                "
-    sui::vec_set::insert(&mut creators, @{address});"
-            ));
-        }
-
-        code.push_str(
-            "
-
-    nft_protocol::collection::add_domain(
-        delegated_witness,
-        &mut collection,
-        nft_protocol::creators::new(creators),
-    );",
-        );
-    };
+    stmts.extend(data.creators.iter().map(|address| {
+        Stmt::Exp(Exp::call(
+            "sui::vec_set::insert",
+            vec![Exp::borrow_mut(Exp::var("creators")), Exp::address(address)],
+        ))
+    }));
 
-    code
-}
+    stmts.extend(add_domain(Exp::call(
+        "nft_protocol::creators::new",
+        vec![Exp::var("creators")],
+    )));
 
-fn init_args(args: InitArgs) -> &str {
-    match args {
-        InitArgs::CollectionData { type_name } => type_name,
-        _ => panic!("Incorrect InitArgs variant"),
-    }
+    stmts
 }
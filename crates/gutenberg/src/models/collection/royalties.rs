@@ -1,40 +1,51 @@
-use crate::{InitArgs, MoveInit};
+use crate::{
+    ast::{Exp, Stmt},
+    Context, MoveInit,
+};
 use gutenberg_types::models::collection::RoyaltyPolicy;
 
 impl MoveInit for RoyaltyPolicy {
-    fn write_move_init(&self, _args: InitArgs) -> String {
+    fn write_move_init(&self, _context: &Context) -> Vec<Stmt> {
         match self {
             RoyaltyPolicy::Proportional {
                 shares,
                 collection_royalty_bps,
             } => {
-                let mut creators_str = "
+                let mut stmts = vec![
+                    Stmt::Blank,
+                    Stmt::bind(
+                        "royalty_map",
+                        Exp::call("sui::vec_map::empty", vec![]),
+                    ),
+                ];
 
-        let royalty_map = sui::vec_map::empty();"
-                    .to_string();
+                stmts.extend(shares.iter().map(|share| {
+                    Stmt::Exp(Exp::call(
+                        "sui::vec_map::insert",
+                        vec![
+                            Exp::borrow_mut(Exp::var("royalty_map")),
+                            Exp::address(&share.address),
+                            Exp::u64(share.share_bps),
+                        ],
+                    ))
+                }));
 
-                for share in shares {
-                    creators_str.push_str(&format!(
-                        "
-        sui::vec_map::insert(&mut royalty_map, @{address}, {share});",
-                        share = share.share_bps,
-                        address = share.address
-                    ));
-                }
+                stmts.push(Stmt::Blank);
+                stmts.push(Stmt::Exp(Exp::call(
+                    "nft_protocol::royalty_strategy_bps::create_domain_and_add_strategy",
+                    vec![
+                        Exp::var("delegated_witness"),
+                        Exp::borrow_mut(Exp::var("collection")),
+                        Exp::call(
+                            "nft_protocol::royalty::from_shares",
+                            vec![Exp::var("royalty_map"), Exp::var("ctx")],
+                        ),
+                        Exp::u64(*collection_royalty_bps),
+                        Exp::var("ctx"),
+                    ],
+                )));
 
-                let domain = format!(
-                    "
-
-        nft_protocol::royalty_strategy_bps::create_domain_and_add_strategy(
-            delegated_witness,
-            &mut collection,
-            nft_protocol::royalty::from_shares(royalty_map, ctx),
-            {collection_royalty_bps},
-            ctx,
-        );"
-                );
-                creators_str.push_str(&domain);
-                creators_str
+                stmts
             }
         }
     }
@@ -1,45 +1,67 @@
-use crate::{InitArgs, MoveInit};
+use super::add_domain;
+use crate::{
+    ast::{Exp, Stmt},
+    Context, MoveInit,
+};
 use gutenberg_types::models::collection::Supply;
 
 impl MoveInit for Supply {
-    fn write_move_init(&self, _args: InitArgs) -> String {
+    fn write_move_init(&self, _context: &Context) -> Vec<Stmt> {
         let supply = match self {
-            Supply::Untracked => return String::new(),
+            Supply::Untracked => return Vec::new(),
             Supply::Tracked => u64::MAX,
             Supply::Enforced(supply) => *supply,
         };
 
-        format!(
-            "
-
-        nft_protocol::collection::add_domain(
-            delegated_witness,
-            &mut collection,
-            nft_protocol::supply::new(
-                delegated_witness, {supply}, false,
-            )
-        );"
-        )
+        add_domain(Exp::call(
+            "nft_protocol::supply::new",
+            vec![
+                Exp::var("delegated_witness"),
+                Exp::u64(supply),
+                Exp::bool(false),
+            ],
+        ))
     }
 }
 
-pub fn write_move_increment() -> &'static str {
-    "
-
-    let supply = nft_protocol::supply::borrow_domain_mut(
-        nft_protocol::collection::borrow_uid_mut(delegated_witness, collection),
-    );
-
-    nft_protocol::supply::increment(delegated_witness, supply, 1);"
+/// Borrows the collection supply domain mutably as `supply`
+fn borrow_supply() -> Vec<Stmt> {
+    vec![
+        Stmt::Blank,
+        Stmt::bind(
+            "supply",
+            Exp::call(
+                "nft_protocol::supply::borrow_domain_mut",
+                vec![Exp::call(
+                    "nft_protocol::collection::borrow_uid_mut",
+                    vec![Exp::var("delegated_witness"), Exp::var("collection")],
+                )],
+            ),
+        ),
+        Stmt::Blank,
+    ]
 }
 
-pub fn write_move_decrement() -> &'static str {
-    "
+fn supply_call(function: &str) -> Stmt {
+    Stmt::Exp(Exp::call(
+        function,
+        vec![
+            Exp::var("delegated_witness"),
+            Exp::var("supply"),
+            Exp::u64(1),
+        ],
+    ))
+}
 
-    let supply = nft_protocol::supply::borrow_domain_mut(
-        nft_protocol::collection::borrow_uid_mut(delegated_witness, collection),
-    );
+pub fn write_move_increment() -> Vec<Stmt> {
+    let mut stmts = borrow_supply();
+    stmts.push(supply_call("nft_protocol::supply::increment"));
+    stmts
+}
 
-    nft_protocol::supply::decrement(delegated_witness, supply, 1);
-    nft_protocol::supply::decrease_supply_ceil(delegated_witness, supply, 1);"
+pub fn write_move_decrement() -> Vec<Stmt> {
+    let mut stmts = borrow_supply();
+    stmts.push(supply_call("nft_protocol::supply::decrement"));
+    stmts.push(supply_call("nft_protocol::supply::decrease_supply_ceil"));
+    stmts
 }
@@ -1,37 +1,38 @@
-use crate::{InitArgs, MoveInit};
+use crate::{
+    ast::{Exp, Stmt},
+    Context, MoveInit,
+};
 use gutenberg_types::models::collection::{Tag, Tags};
 
 impl MoveInit for Tag {
-    fn write_move_init(&self, _args: InitArgs) -> String {
-        match self {
-            Tag::Custom(tag) => format!(
-                "
-        std::vector::push_back(&mut tags, std::string::utf8(b\"{tag}\"));"
+    fn write_move_init(&self, _context: &Context) -> Vec<Stmt> {
+        let tag = match self {
+            Tag::Custom(tag) => Exp::utf8(tag),
+            tag => Exp::call(
+                format!("nft_protocol::tags::{}", tag.function_name()),
+                vec![],
             ),
-            tag => {
-                let function_name = tag.function_name();
-                format!(
-                    "
-        std::vector::push_back(&mut tags, nft_protocol::tags::{function_name}());"
-                )
-            }
-        }
+        };
+
+        vec![Stmt::Exp(Exp::call(
+            "std::vector::push_back",
+            vec![Exp::borrow_mut(Exp::var("tags")), tag],
+        ))]
     }
 }
 
 impl MoveInit for Tags {
     /// Generates Move code to push tags to a Move `vector` structure
-    fn write_move_init(&self, _args: InitArgs) -> String {
-        let mut code = String::from(
-            "
-
-        let tags = std::vector::empty();",
-        );
+    fn write_move_init(&self, context: &Context) -> Vec<Stmt> {
+        let mut stmts = vec![
+            Stmt::Blank,
+            Stmt::bind("tags", Exp::call("std::vector::empty", vec![])),
+        ];
 
         for tag in self.0.iter() {
-            code.push_str(&tag.write_move_init(InitArgs::None));
+            stmts.extend(tag.write_move_init(context));
         }
 
-        code
+        stmts
     }
 }
@@ -1,31 +1,43 @@
-use crate::{InitArgs, MoveInit};
+use crate::{
+    ast::{Exp, Stmt},
+    models::share_object,
+    Context, TryMoveInit,
+};
+use anyhow::Result;
 use gutenberg_types::models::launchpad::listing::Listing;
 
-impl MoveInit for Listing {
-    fn write_move_init(&self, _args: InitArgs) -> String {
-        let mut string = String::new();
-
-        string.push_str(&format!(
-            "
-        let listing = nft_protocol::listing::new(
-            @{admin},
-            @{receiver},
-            ctx,
-        );
-
-        let venue_id =
-            nft_protocol::listing::create_venue(&mut listing, ctx);
-",
-            admin = self.admin,
-            receiver = self.receiver,
-        ));
+impl TryMoveInit for Listing {
+    fn try_write_move_init(&self, context: &Context) -> Result<Vec<Stmt>> {
+        let mut stmts = vec![
+            Stmt::bind(
+                "listing",
+                Exp::call(
+                    "nft_protocol::listing::new",
+                    vec![
+                        Exp::address(&self.admin),
+                        Exp::address(&self.receiver),
+                        Exp::var("ctx"),
+                    ],
+                ),
+            ),
+            Stmt::Blank,
+            Stmt::bind(
+                "venue_id",
+                Exp::call(
+                    "nft_protocol::listing::create_venue",
+                    vec![Exp::borrow_mut(Exp::var("listing")), Exp::var("ctx")],
+                ),
+            ),
+        ];
 
         for market in self.markets.iter() {
-            string.push_str(&market.write_move_init(InitArgs::None));
+            stmts.push(Stmt::Blank);
+            stmts.extend(market.try_write_move_init(context)?);
         }
 
-        string.push_str(self.share());
+        stmts.push(Stmt::Blank);
+        stmts.push(share_object("listing"));
 
-        string
+        Ok(stmts)
     }
 }
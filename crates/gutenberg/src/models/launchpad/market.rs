@@ -1,39 +1,58 @@
-use crate::{InitArgs, MoveInit};
+use crate::{
+    ast::{Exp, Stmt},
+    literal::MoveType,
+    Context, TryMoveInit,
+};
+use anyhow::{anyhow, Result};
 use gutenberg_types::models::launchpad::market::Market;
 
-impl MoveInit for Market {
-    fn write_move_init(&self, _args: InitArgs) -> String {
-        match self {
+/// Parses the market token into a validated `MoveType`
+///
+/// Fails if the token is not a valid Move struct type, as it would otherwise
+/// be rendered verbatim as a type argument.
+pub fn token_type(market: &Market) -> Result<MoveType> {
+    let token = match market {
+        Market::FixedPrice { token, .. }
+        | Market::DutchAuction { token, .. } => token,
+    };
+
+    match token.parse::<MoveType>() {
+        Ok(move_type) if move_type.is_struct() => Ok(move_type),
+        Ok(move_type) => {
+            Err(anyhow!("Market token `{move_type}` must be a struct type"))
+        }
+        Err(err) => Err(anyhow!("Invalid market token `{token}`: {err}")),
+    }
+}
+
+impl TryMoveInit for Market {
+    fn try_write_move_init(&self, _context: &Context) -> Result<Vec<Stmt>> {
+        let (price, is_whitelisted) = match self {
             Market::FixedPrice {
-                token,
                 price,
                 is_whitelisted,
-            } => format!(
-                "
-        nft_protocol::fixed_price::create_market_on_listing<{token}>(
-            &mut listing,
-            venue_id,
-            {is_whitelisted},
-            {price},
-            ctx,
-        );
-",
-            ),
+                ..
+            } => (price, is_whitelisted),
             Market::DutchAuction {
-                token,
                 reserve_price,
                 is_whitelisted,
-            } => format!(
-                "
-        nft_protocol::dutch_auction::create_market_on_listing<{token}>(
-            &mut listing,
-            venue_id,
-            {is_whitelisted},
-            {reserve_price},
-            ctx,
-        );
-",
+                ..
+            } => (reserve_price, is_whitelisted),
+        };
+
+        Ok(vec![Stmt::Exp(Exp::call_generic(
+            format!(
+                "nft_protocol::{}::create_market_on_listing",
+                self.market_module()
             ),
-        }
+            vec![token_type(self)?.into()],
+            vec![
+                Exp::borrow_mut(Exp::var("listing")),
+                Exp::var("venue_id"),
+                Exp::bool(*is_whitelisted),
+                Exp::u64(*price),
+                Exp::var("ctx"),
+            ],
+        ))])
     }
 }
@@ -1,19 +1,38 @@
-use crate::{InitArgs, MoveInit};
+use crate::{ast::Stmt, Context, TryMoveInit};
+use anyhow::Result;
 use gutenberg_types::models::launchpad::Launchpad;
 pub mod listing;
 pub mod market;
 
-impl MoveInit for Launchpad {
-    // TODO: To deprecate. The creation of listins will be done at runtime
-    // in atomic transactions instead of being bundled up in the init funciton
-    fn write_move_init(&self, _args: InitArgs) -> String {
-        let code = self
-            .listings
-            .0
-            .iter()
-            .map(|listing| listing.write_move_init(InitArgs::None))
-            .collect::<Vec<_>>();
+impl TryMoveInit for Launchpad {
+    // TODO: To deprecate. Listings are created at runtime in atomic
+    // transactions by `byte client create-listing` instead of being bundled
+    // up in the init function
+    fn try_write_move_init(&self, context: &Context) -> Result<Vec<Stmt>> {
+        let mut stmts = Vec::new();
 
-        code.join("\n")
+        for listing in self.listings.0.iter() {
+            stmts.push(Stmt::Blank);
+            stmts.extend(listing.try_write_move_init(context)?);
+        }
+
+        Ok(stmts)
     }
 }
+
+/// Checks that the market tokens of every listing are valid Move struct
+/// types.
+///
+/// # Arguments
+/// * `launchpad` - The launchpad configuration of the schema.
+///
+/// # Returns
+/// An error naming the first invalid token, if any.
+pub fn check_tokens(launchpad: &Launchpad) -> Result<()> {
+    launchpad
+        .listings
+        .0
+        .iter()
+        .flat_map(|listing| listing.markets.iter())
+        .try_for_each(|market| market::token_type(market).map(|_| ()))
+}
@@ -2,49 +2,218 @@ pub mod collection;
 pub mod launchpad;
 pub mod nft;
 
-/// Generic method to write move functions
-///
-/// Use of this function is discouraged unless there is a wide range of
-/// function permutations.
-pub fn write_move_fn<F>(
-    name: &str,
-    params: &[&str],
-    param_types: &[&str],
-    is_public: bool,
-    is_entry: bool,
-    returns: Option<String>,
-    body_fn: F,
-) -> String
-where
-    F: FnOnce() -> String,
-{
-    let is_public_str = match is_public {
-        true => "public ",
-        false => "",
-    };
+use crate::{
+    ast::{Exp, Stmt, Type},
+    Context,
+};
 
-    let is_entry_str = match is_entry {
-        true => "entry ",
-        false => "",
-    };
+/// `nft_protocol::collection::Collection<T>`
+pub fn collection_type(context: &Context) -> Type {
+    Type::generic(
+        "nft_protocol::collection::Collection",
+        vec![context.nft_type()],
+    )
+}
+
+/// `nft_protocol::mint_cap::MintCap<T>`
+pub fn mint_cap_type(context: &Context) -> Type {
+    Type::generic("nft_protocol::mint_cap::MintCap", vec![context.nft_type()])
+}
 
-    let args_str = params
-        .iter()
-        .zip(param_types)
-        .map(|(param, param_type)| format!("        {param}: {param_type},\n"))
-        .collect::<Vec<String>>()
-        .join("");
+/// `ob_request::request::WithNft<T, {request}>`
+pub fn request_type(context: &Context, request: &str) -> Type {
+    Type::generic(
+        "ob_request::request::WithNft",
+        vec![context.nft_type(), Type::named(request)],
+    )
+}
+
+/// `ob_request::request::Policy<ob_request::request::WithNft<T, {request}>>`
+pub fn request_policy_type(context: &Context, request: &str) -> Type {
+    Type::generic(
+        "ob_request::request::Policy",
+        vec![request_type(context, request)],
+    )
+}
 
-    let returns_str = returns
-        .map(|returns| format!(": {returns}"))
-        .unwrap_or_default();
-    let body_str = body_fn();
+/// `ob_permissions::witness::Witness<T>`
+pub fn delegated_witness_type(context: &Context) -> Type {
+    Type::generic("ob_permissions::witness::Witness", vec![context.nft_type()])
+}
 
-    format!(
-        "
+/// `let delegated_witness = ob_permissions::witness::from_witness(Witness {});`
+pub fn delegated_witness_from_witness() -> Stmt {
+    Stmt::bind(
+        "delegated_witness",
+        Exp::call(
+            "ob_permissions::witness::from_witness",
+            vec![Exp::pack("Witness", [])],
+        ),
+    )
+}
 
-    {is_public_str}{is_entry_str}fun {name}(
-{args_str}    ){returns_str} {{{body_str}
-    }}"
+/// `let delegated_witness = ob_permissions::witness::from_publisher(publisher);`
+pub fn delegated_witness_from_publisher() -> Stmt {
+    Stmt::bind(
+        "delegated_witness",
+        Exp::call(
+            "ob_permissions::witness::from_publisher",
+            vec![Exp::var("publisher")],
+        ),
     )
 }
+
+/// `sui::tx_context::sender(ctx)`
+pub fn sender() -> Exp {
+    Exp::call("sui::tx_context::sender", vec![Exp::var("ctx")])
+}
+
+/// `sui::transfer::public_transfer({object}, sui::tx_context::sender(ctx));`
+pub fn transfer_to_sender(object: &str) -> Stmt {
+    Stmt::Exp(Exp::call(
+        "sui::transfer::public_transfer",
+        vec![Exp::var(object), sender()],
+    ))
+}
+
+/// `sui::transfer::public_share_object({object});`
+pub fn share_object(object: &str) -> Stmt {
+    Stmt::Exp(Exp::call(
+        "sui::transfer::public_share_object",
+        vec![Exp::var(object)],
+    ))
+}
+
+/// Helpers for writing `sui::test_scenario` based tests
+pub mod scenario {
+    use super::{collection_type, mint_cap_type};
+    use crate::{
+        ast::{Exp, Stmt, Type},
+        models::nft::fields,
+        Context,
+    };
+
+    /// `sui::test_scenario::ctx(&mut scenario)`
+    pub fn ctx() -> Exp {
+        Exp::call(
+            "sui::test_scenario::ctx",
+            vec![Exp::borrow_mut(Exp::var("scenario"))],
+        )
+    }
+
+    /// `sui::test_scenario::next_tx(&mut scenario, CREATOR);`
+    pub fn next_tx() -> Stmt {
+        Stmt::Exp(Exp::call(
+            "sui::test_scenario::next_tx",
+            vec![Exp::borrow_mut(Exp::var("scenario")), Exp::var("CREATOR")],
+        ))
+    }
+
+    /// Begins scenario and initializes the contract
+    pub fn begin(context: &Context) -> Vec<Stmt> {
+        vec![
+            Stmt::bind(
+                "scenario",
+                Exp::call(
+                    "sui::test_scenario::begin",
+                    vec![Exp::var("CREATOR")],
+                ),
+            ),
+            Stmt::Exp(Exp::call(
+                "init",
+                vec![Exp::pack(context.witness_name(), []), ctx()],
+            )),
+            next_tx(),
+        ]
+    }
+
+    /// `sui::test_scenario::end(scenario);`
+    pub fn end() -> Stmt {
+        Stmt::Exp(Exp::call(
+            "sui::test_scenario::end",
+            vec![Exp::var("scenario")],
+        ))
+    }
+
+    /// `let {name} = sui::test_scenario::take_from_address<{object_type}>(&scenario, CREATOR);`
+    pub fn take_from_creator(name: &str, object_type: Type) -> Stmt {
+        Stmt::bind(
+            name,
+            Exp::call_generic(
+                "sui::test_scenario::take_from_address",
+                vec![object_type],
+                vec![Exp::borrow(Exp::var("scenario")), Exp::var("CREATOR")],
+            ),
+        )
+    }
+
+    /// `sui::test_scenario::return_to_address(CREATOR, {name});`
+    pub fn return_to_creator(name: &str) -> Stmt {
+        Stmt::Exp(Exp::call(
+            "sui::test_scenario::return_to_address",
+            vec![Exp::var("CREATOR"), Exp::var(name)],
+        ))
+    }
+
+    /// `let {name} = sui::test_scenario::take_shared<{object_type}>(&scenario);`
+    pub fn take_shared(name: &str, object_type: Type) -> Stmt {
+        Stmt::bind(
+            name,
+            Exp::call_generic(
+                "sui::test_scenario::take_shared",
+                vec![object_type],
+                vec![Exp::borrow(Exp::var("scenario"))],
+            ),
+        )
+    }
+
+    /// `sui::test_scenario::return_shared({name});`
+    pub fn return_shared(name: &str) -> Stmt {
+        Stmt::Exp(Exp::call(
+            "sui::test_scenario::return_shared",
+            vec![Exp::var(name)],
+        ))
+    }
+
+    /// Takes `MintCap` and, if required by mint methods, `Collection`
+    pub fn take_mint_objects(context: &Context) -> Vec<Stmt> {
+        let mut stmts =
+            vec![take_from_creator("mint_cap", mint_cap_type(context))];
+
+        if context.requires_collection() {
+            stmts.push(Stmt::Blank);
+            stmts.push(take_shared("collection", collection_type(context)));
+        }
+
+        stmts
+    }
+
+    /// Returns objects taken by `take_mint_objects`
+    pub fn return_mint_objects(context: &Context) -> Vec<Stmt> {
+        let mut stmts = vec![return_to_creator("mint_cap")];
+
+        if context.requires_collection() {
+            stmts.push(return_shared("collection"));
+        }
+
+        stmts
+    }
+
+    /// Arguments for calling `mint` or any of its wrappers with test values,
+    /// followed by `extra_args`
+    pub fn mint_args(context: &Context, extra_args: Vec<Exp>) -> Vec<Exp> {
+        let mut args: Vec<Exp> = context
+            .fields()
+            .iter()
+            .flat_map(fields::test_args)
+            .collect();
+
+        args.push(Exp::borrow_mut(Exp::var("mint_cap")));
+        if context.requires_collection() {
+            args.push(Exp::borrow_mut(Exp::var("collection")));
+        }
+
+        args.extend(extra_args);
+        args
+    }
+}
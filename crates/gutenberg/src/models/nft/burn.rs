@@ -1,263 +1,340 @@
 use crate::{
-    models::collection::supply::write_move_decrement, DefArgs, MoveDefs,
-    MoveTests, TestArgs,
+    ast::{Exp, Fun, Member, Pattern, Stmt, Type},
+    models::{
+        collection::supply::write_move_decrement, collection_type,
+        delegated_witness_from_publisher, delegated_witness_from_witness,
+        delegated_witness_type, mint_cap_type, request_policy_type, scenario,
+        share_object,
+    },
+    Context, MoveDefs, MoveTests,
 };
-use gutenberg_types::models::nft::{Burn, Fields};
+use gutenberg_types::models::nft::Burn;
 
-impl MoveDefs for Burn {
-    fn write_move_defs(&self, args: DefArgs) -> String {
-        let (
-            fields,
-            type_name,
-            requires_collection,
-            requires_listing,
-            requires_confirm,
-        ) = def_args(args);
-
-        let mut code = String::new();
-
-        let mut_str = requires_collection.then_some("mut ").unwrap_or_default();
-
-        let collection_decrement_str = requires_collection
-            .then(write_move_decrement)
-            .unwrap_or_default();
-
-        let confirm_contract_str = requires_confirm
-            .then_some(
-                "
-        ob_request::withdraw_request::add_receipt(&mut withdraw_request, &Witness {});"
-            )
-            .unwrap_or_default();
+const WITHDRAW_REQ: &str = "ob_request::withdraw_request::WITHDRAW_REQ";
 
-        let burn_nft_call = self
-            .is_permissioned()
-            .then_some("delegated_witness, ")
-            .unwrap_or_default();
+/// `&Collection<T>` or `&mut Collection<T>` if supply must be decremented
+fn collection_param(context: &Context) -> (String, Type) {
+    let collection_type = match context.requires_collection() {
+        true => Type::borrow_mut(collection_type(context)),
+        false => Type::borrow(collection_type(context)),
+    };
 
-        let delegated_witness_init_str = self.is_permissionless()
-            .then_some(
-            "
-        let delegated_witness = ob_permissions::witness::from_witness(Witness {});"
-            )
-            .unwrap_or_default();
+    ("collection".to_string(), collection_type)
+}
 
-        let delegated_witness_publisher_init_str = self.is_permissioned()
-            .then_some(
-            "
-        let delegated_witness = ob_permissions::witness::from_publisher(publisher);"
-            )
-            .unwrap_or_default();
-
-        let delegated_witness_param_str = self
-            .is_permissioned()
-            .then(|| {
-                format!(
-                    "
-        delegated_witness: ob_permissions::witness::Witness<{type_name}>,"
-                )
-            })
-            .unwrap_or_default();
-
-        let publisher_param_str = self
-            .is_permissioned()
-            .then_some(
-                "
-        publisher: &sui::package::Publisher,",
-            )
-            .unwrap_or_default();
-
-        let fields_str: String =
-            fields.keys().map(|field| format!(", {field}: _")).collect();
-
-        code.push_str(&format!(
-            "
-
-    public fun burn_nft({delegated_witness_param_str}
-        collection: &{mut_str}nft_protocol::collection::Collection<{type_name}>,
-        nft: {type_name},
-    ) {{{delegated_witness_init_str}
-        let guard = nft_protocol::mint_event::start_burn(delegated_witness, &nft);
-        let {type_name} {{ id{fields_str} }} = nft;
-        nft_protocol::mint_event::emit_burn(guard, sui::object::id(collection), id);{collection_decrement_str}
-    }}
-
-    public entry fun burn_nft_in_kiosk({delegated_witness_param_str}
-        collection: &{mut_str}nft_protocol::collection::Collection<{type_name}>,
-        kiosk: &mut sui::kiosk::Kiosk,
-        nft_id: sui::object::ID,
-        policy: &ob_request::request::Policy<ob_request::request::WithNft<{type_name}, ob_request::withdraw_request::WITHDRAW_REQ>>,
-        ctx: &mut sui::tx_context::TxContext,
-    ) {{
-        let (nft, withdraw_request) = ob_kiosk::ob_kiosk::withdraw_nft_signed(kiosk, nft_id, ctx);{confirm_contract_str}
-        ob_request::withdraw_request::confirm(withdraw_request, policy);
-
-        burn_nft({burn_nft_call}collection, nft);
-    }}"
-        ));
+fn publisher_param() -> (String, Type) {
+    (
+        "publisher".to_string(),
+        Type::borrow(Type::named("sui::package::Publisher")),
+    )
+}
 
-        if self.is_permissioned() {
-            code.push_str(&format!("
-
-    public entry fun burn_nft_in_kiosk_as_publisher(
-        publisher: &sui::package::Publisher,
-        collection: &{mut_str}nft_protocol::collection::Collection<{type_name}>,
-        kiosk: &mut sui::kiosk::Kiosk,
-        nft_id: sui::object::ID,
-        policy: &ob_request::request::Policy<ob_request::request::WithNft<{type_name}, ob_request::withdraw_request::WITHDRAW_REQ>>,
-        ctx: &mut sui::tx_context::TxContext,
-    ) {{
-        let delegated_witness = ob_permissions::witness::from_publisher(publisher);
-        burn_nft_in_kiosk(delegated_witness, collection, kiosk, nft_id, policy, ctx);
-    }}"));
-        }
+/// `burn_nft({delegated_witness, }collection, nft);`
+fn call_burn_nft(burn: &Burn) -> Stmt {
+    let mut args = Vec::new();
+    if burn.is_permissioned() {
+        args.push(Exp::var("delegated_witness"));
+    }
+    args.extend([Exp::var("collection"), Exp::var("nft")]);
 
-        if requires_listing {
-            code.push_str(&format!(
-                "
-
-    public entry fun burn_nft_in_listing({publisher_param_str}
-        collection: &{mut_str}nft_protocol::collection::Collection<{type_name}>,
-        listing: &mut ob_launchpad::listing::Listing,
-        inventory_id: sui::object::ID,
-        ctx: &mut sui::tx_context::TxContext,
-    ) {{{delegated_witness_publisher_init_str}
-        let nft = ob_launchpad::listing::admin_redeem_nft<{type_name}>(listing, inventory_id, ctx);
-        burn_nft({burn_nft_call}collection, nft);
-    }}
-
-    public entry fun burn_nft_in_listing_with_id({publisher_param_str}
-        collection: &{mut_str}nft_protocol::collection::Collection<{type_name}>,
-        listing: &mut ob_launchpad::listing::Listing,
-        inventory_id: sui::object::ID,
-        nft_id: sui::object::ID,
-        ctx: &mut sui::tx_context::TxContext,
-    ) {{{delegated_witness_publisher_init_str}
-        let nft = ob_launchpad::listing::admin_redeem_nft_with_id(listing, inventory_id, nft_id, ctx);
-        burn_nft({burn_nft_call}collection, nft);
-    }}"
-            ));
-        }
+    Stmt::Exp(Exp::call("burn_nft", args))
+}
 
-        code
-    }
+/// Parameters for withdrawing NFT from a kiosk
+fn kiosk_params(context: &Context) -> Vec<(String, Type)> {
+    vec![
+        (
+            "kiosk".to_string(),
+            Type::borrow_mut(Type::named("sui::kiosk::Kiosk")),
+        ),
+        ("nft_id".to_string(), Type::named("sui::object::ID")),
+        (
+            "policy".to_string(),
+            Type::borrow(request_policy_type(context, WITHDRAW_REQ)),
+        ),
+        ("ctx".to_string(), Type::tx_context()),
+    ]
 }
 
-impl MoveTests for Burn {
-    fn write_move_tests(&self, args: TestArgs) -> String {
-        let (fields, type_name, witness_name, requires_collection) =
-            test_args(args);
-
-        let collection_param_str = requires_collection
-            .then_some(
-                "
-            &mut collection,",
-            )
-            .unwrap_or_default();
+fn write_move_burn_nft(burn: &Burn, context: &Context) -> Fun {
+    let mut fun = Fun::new("burn_nft").public();
+    if burn.is_permissioned() {
+        fun = fun.param("delegated_witness", delegated_witness_type(context));
+    }
 
-        let collection_mut_str =
-            requires_collection.then_some("mut ").unwrap_or_default();
+    let mut body = Vec::new();
+    if burn.is_permissionless() {
+        body.push(delegated_witness_from_witness());
+    }
 
-        let delegated_witness_init_param_str = self
-            .is_permissioned()
-            .then_some(
-                "
-                ob_permissions::witness::from_witness(Witness {}),",
+    let unpack = Pattern::Unpack {
+        name: context.type_name().to_string(),
+        fields: [("id".to_string(), Pattern::var("id"))]
+            .into_iter()
+            .chain(
+                context
+                    .fields()
+                    .keys()
+                    .map(|field| (field.to_string(), Pattern::Wildcard)),
             )
-            .unwrap_or_default();
-
-        let fields_str: String = fields
-            .test_params()
-            .map(|param| {
-                format!(
-                    "
-                {param},"
-                )
-            })
-            .collect();
-
-        format!("
-
-        #[test]
-        fun it_burns_nft() {{
-            let scenario = sui::test_scenario::begin(CREATOR);
-            init({witness_name} {{}}, sui::test_scenario::ctx(&mut scenario));
-
-            sui::test_scenario::next_tx(&mut scenario, CREATOR);
-
-            let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<{type_name}>>(
-                &scenario,
-                CREATOR,
-            );
+            .collect(),
+    };
+
+    body.extend([
+        Stmt::bind(
+            "guard",
+            Exp::call(
+                "nft_protocol::mint_event::start_burn",
+                vec![
+                    Exp::var("delegated_witness"),
+                    Exp::borrow(Exp::var("nft")),
+                ],
+            ),
+        ),
+        Stmt::destructure(unpack, Exp::var("nft")),
+        Stmt::Exp(Exp::call(
+            "nft_protocol::mint_event::emit_burn",
+            vec![
+                Exp::var("guard"),
+                Exp::call("sui::object::id", vec![Exp::var("collection")]),
+                Exp::var("id"),
+            ],
+        )),
+    ]);
+
+    if context.requires_collection() {
+        body.extend(write_move_decrement());
+    }
 
-            let publisher = sui::test_scenario::take_from_address<sui::package::Publisher>(
-                &scenario,
-                CREATOR,
-            );
+    let (name, collection_type) = collection_param(context);
+    fun.param(name, collection_type)
+        .param("nft", context.nft_type())
+        .body(body)
+}
 
-            let collection = sui::test_scenario::take_shared<nft_protocol::collection::Collection<{type_name}>>(
-                &scenario
-            );
+fn write_move_burn_nft_in_kiosk(burn: &Burn, context: &Context) -> Fun {
+    let mut fun = Fun::new("burn_nft_in_kiosk").public().entry();
+    if burn.is_permissioned() {
+        fun = fun.param("delegated_witness", delegated_witness_type(context));
+    }
 
-            let withdraw_policy = sui::test_scenario::take_shared<
-                ob_request::request::Policy<
-                    ob_request::request::WithNft<{type_name}, ob_request::withdraw_request::WITHDRAW_REQ>
-                >
-            >(&scenario);
+    let mut body = vec![Stmt::destructure(
+        Pattern::tuple([Pattern::var("nft"), Pattern::var("withdraw_request")]),
+        Exp::call(
+            "ob_kiosk::ob_kiosk::withdraw_nft_signed",
+            vec![Exp::var("kiosk"), Exp::var("nft_id"), Exp::var("ctx")],
+        ),
+    )];
+
+    // Contract must confirm withdrawal if no withdraw policy was requested
+    if !context.nft().request_policies.has_withdraw() {
+        body.push(Stmt::Exp(Exp::call(
+            "ob_request::withdraw_request::add_receipt",
+            vec![
+                Exp::borrow_mut(Exp::var("withdraw_request")),
+                Exp::borrow(Exp::pack("Witness", [])),
+            ],
+        )));
+    }
 
-            let nft = mint({fields_str}
-                &mut mint_cap,{collection_param_str}
-                sui::test_scenario::ctx(&mut scenario)
-            );
-            let nft_id = sui::object::id(&nft);
+    body.extend([
+        Stmt::Exp(Exp::call(
+            "ob_request::withdraw_request::confirm",
+            vec![Exp::var("withdraw_request"), Exp::var("policy")],
+        )),
+        Stmt::Blank,
+        call_burn_nft(burn),
+    ]);
+
+    fun.params([collection_param(context)])
+        .params(kiosk_params(context))
+        .body(body)
+}
 
-            let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
-            ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, sui::test_scenario::ctx(&mut scenario));
+fn write_move_burn_nft_in_kiosk_as_publisher(context: &Context) -> Fun {
+    Fun::new("burn_nft_in_kiosk_as_publisher")
+        .public()
+        .entry()
+        .params([publisher_param(), collection_param(context)])
+        .params(kiosk_params(context))
+        .body(vec![
+            delegated_witness_from_publisher(),
+            Stmt::Exp(Exp::call(
+                "burn_nft_in_kiosk",
+                [
+                    "delegated_witness",
+                    "collection",
+                    "kiosk",
+                    "nft_id",
+                    "policy",
+                    "ctx",
+                ]
+                .into_iter()
+                .map(Exp::var)
+                .collect(),
+            )),
+        ])
+}
 
-            burn_nft_in_kiosk({delegated_witness_init_param_str}
-                &{collection_mut_str}collection,
-                &mut kiosk,
-                nft_id,
-                &withdraw_policy,
-                sui::test_scenario::ctx(&mut scenario)
-            );
+/// `burn_nft_in_listing` or, if `with_id`, `burn_nft_in_listing_with_id`
+fn write_move_burn_nft_in_listing(
+    burn: &Burn,
+    context: &Context,
+    with_id: bool,
+) -> Fun {
+    let mut fun = match with_id {
+        true => Fun::new("burn_nft_in_listing_with_id"),
+        false => Fun::new("burn_nft_in_listing"),
+    }
+    .public()
+    .entry();
 
-            sui::test_scenario::return_to_address(CREATOR, mint_cap);
-            sui::test_scenario::return_to_address(CREATOR, publisher);
-            sui::test_scenario::return_shared(collection);
-            sui::test_scenario::return_shared(withdraw_policy);
-            sui::transfer::public_share_object(kiosk);
-            sui::test_scenario::end(scenario);
-        }}")
+    let mut body = Vec::new();
+    if burn.is_permissioned() {
+        fun = fun.params([publisher_param()]);
+        body.push(delegated_witness_from_publisher());
     }
-}
 
-fn def_args(args: DefArgs) -> (&Fields, &str, bool, bool, bool) {
-    match args {
-        DefArgs::Burn {
-            fields,
-            type_name,
-            requires_collection,
-            requires_listing,
-            requires_confirm,
-        } => (
-            fields,
-            type_name,
-            requires_collection,
-            requires_listing,
-            requires_confirm,
+    fun = fun.params([
+        collection_param(context),
+        (
+            "listing".to_string(),
+            Type::borrow_mut(Type::named("ob_launchpad::listing::Listing")),
         ),
-        _ => panic!("Incorrect DefArgs variant"),
+        ("inventory_id".to_string(), Type::named("sui::object::ID")),
+    ]);
+
+    let redeem = match with_id {
+        true => {
+            fun = fun.param("nft_id", Type::named("sui::object::ID"));
+            Exp::call(
+                "ob_launchpad::listing::admin_redeem_nft_with_id",
+                vec![
+                    Exp::var("listing"),
+                    Exp::var("inventory_id"),
+                    Exp::var("nft_id"),
+                    Exp::var("ctx"),
+                ],
+            )
+        }
+        false => Exp::call_generic(
+            "ob_launchpad::listing::admin_redeem_nft",
+            vec![context.nft_type()],
+            vec![
+                Exp::var("listing"),
+                Exp::var("inventory_id"),
+                Exp::var("ctx"),
+            ],
+        ),
+    };
+
+    body.extend([Stmt::bind("nft", redeem), call_burn_nft(burn)]);
+
+    fun.param("ctx", Type::tx_context()).body(body)
+}
+
+impl MoveDefs for Burn {
+    fn write_move_defs(&self, context: &Context) -> Vec<Member> {
+        let mut members = vec![
+            write_move_burn_nft(self, context).into(),
+            write_move_burn_nft_in_kiosk(self, context).into(),
+        ];
+
+        if self.is_permissioned() {
+            members.push(
+                write_move_burn_nft_in_kiosk_as_publisher(context).into(),
+            );
+        }
+
+        if context.nft().mint_policies.has_launchpad() {
+            members.extend([
+                write_move_burn_nft_in_listing(self, context, false).into(),
+                write_move_burn_nft_in_listing(self, context, true).into(),
+            ]);
+        }
+
+        members
     }
 }
 
-fn test_args(args: TestArgs) -> (&Fields, &str, &str, bool) {
-    match args {
-        TestArgs::Burn {
-            fields,
-            type_name,
-            witness_name,
-            requires_collection,
-        } => (fields, type_name, witness_name, requires_collection),
-        _ => panic!("Incorrect TestArgs variant"),
+impl MoveTests for Burn {
+    fn write_move_tests(&self, context: &Context) -> Vec<Member> {
+        let mut burn_args = Vec::new();
+        if self.is_permissioned() {
+            burn_args.push(Exp::call(
+                "ob_permissions::witness::from_witness",
+                vec![Exp::pack("Witness", [])],
+            ));
+        }
+
+        let collection = match context.requires_collection() {
+            true => Exp::borrow_mut(Exp::var("collection")),
+            false => Exp::borrow(Exp::var("collection")),
+        };
+
+        burn_args.extend([
+            collection,
+            Exp::borrow_mut(Exp::var("kiosk")),
+            Exp::var("nft_id"),
+            Exp::borrow(Exp::var("withdraw_policy")),
+            scenario::ctx(),
+        ]);
+
+        let mut body = scenario::begin(context);
+        body.extend([
+            Stmt::Blank,
+            scenario::take_from_creator("mint_cap", mint_cap_type(context)),
+            Stmt::Blank,
+            scenario::take_from_creator(
+                "publisher",
+                Type::named("sui::package::Publisher"),
+            ),
+            Stmt::Blank,
+            scenario::take_shared("collection", collection_type(context)),
+            Stmt::Blank,
+            scenario::take_shared(
+                "withdraw_policy",
+                request_policy_type(context, WITHDRAW_REQ),
+            ),
+            Stmt::Blank,
+            Stmt::bind(
+                "nft",
+                Exp::call(
+                    "mint",
+                    scenario::mint_args(context, vec![scenario::ctx()]),
+                ),
+            ),
+            Stmt::bind(
+                "nft_id",
+                Exp::call(
+                    "sui::object::id",
+                    vec![Exp::borrow(Exp::var("nft"))],
+                ),
+            ),
+            Stmt::Blank,
+            Stmt::destructure(
+                Pattern::tuple([Pattern::var("kiosk"), Pattern::Wildcard]),
+                Exp::call("ob_kiosk::ob_kiosk::new", vec![scenario::ctx()]),
+            ),
+            Stmt::Exp(Exp::call(
+                "ob_kiosk::ob_kiosk::deposit",
+                vec![
+                    Exp::borrow_mut(Exp::var("kiosk")),
+                    Exp::var("nft"),
+                    scenario::ctx(),
+                ],
+            )),
+            Stmt::Blank,
+            Stmt::Exp(Exp::call("burn_nft_in_kiosk", burn_args)),
+            Stmt::Blank,
+            scenario::return_to_creator("mint_cap"),
+            scenario::return_to_creator("publisher"),
+            scenario::return_shared("collection"),
+            scenario::return_shared("withdraw_policy"),
+            share_object("kiosk"),
+            scenario::end(),
+        ]);
+
+        vec![Fun::test("it_burns_nft").body(body).into()]
     }
 }
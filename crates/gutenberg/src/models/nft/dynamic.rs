@@ -1,296 +1,254 @@
-use crate::{models::write_move_fn, DefArgs, MoveDefs, MoveTests, TestArgs};
-use crate::{InitArgs, MoveInit};
-use gutenberg_types::models::nft::{Dynamic, Fields};
+use super::fields;
+use crate::{
+    ast::{Exp, Fun, Member, Pattern, Stmt, Type},
+    models::{
+        delegated_witness_from_publisher, delegated_witness_type,
+        request_policy_type, scenario, share_object,
+    },
+    Context, MoveDefs, MoveTests,
+};
+use gutenberg_types::models::nft::{Dynamic, Field};
+
+const BORROW_REQ: &str = "ob_request::borrow_request::BORROW_REQ";
+
+fn publisher_param() -> (String, Type) {
+    (
+        "publisher".to_string(),
+        Type::borrow(Type::named("sui::package::Publisher")),
+    )
+}
 
-impl MoveDefs for Dynamic {
-    fn write_move_defs(&self, args: DefArgs) -> String {
-        let (fields, type_name) = def_args(args);
+/// Parameters preceding the field parameters in kiosk setters
+fn kiosk_params() -> Vec<(String, Type)> {
+    vec![
+        (
+            "kiosk".to_string(),
+            Type::borrow_mut(Type::named("sui::kiosk::Kiosk")),
+        ),
+        ("nft_id".to_string(), Type::named("sui::object::ID")),
+    ]
+}
 
-        if !self.0 {
-            return String::new();
-        }
+/// Parameters following the field parameters in kiosk setters
+fn policy_params(context: &Context) -> Vec<(String, Type)> {
+    vec![
+        (
+            "policy".to_string(),
+            Type::borrow(request_policy_type(context, BORROW_REQ)),
+        ),
+        ("ctx".to_string(), Type::tx_context()),
+    ]
+}
 
-        fields
-            .iter()
-            .map(|field| {
-                let field_name = field.name();
-                write_move_field_setter(
-                    type_name,
-                    field_name,
-                    field.params().collect(),
-                    field.param_types().collect(),
-                    field.write_move_init(InitArgs::None), // .unwrap_or_else(|| field_name.to_string()), # TODO: Double check
-                )
-            })
-            .collect()
-    }
+fn vars<'a>(names: impl IntoIterator<Item = &'a str>) -> Vec<Exp> {
+    names.into_iter().map(Exp::var).collect()
 }
 
-impl MoveTests for Dynamic {
-    fn write_move_tests(&self, args: TestArgs) -> String {
-        let (fields, type_name, witness_name, requires_collection) =
-            test_args(args);
+/// Setter functions for a single field
+fn write_move_field_setters(field: &Field, context: &Context) -> Vec<Member> {
+    let field_name = field.name();
+    let params = fields::params(field);
+    let param_names: Vec<String> = field.params().collect();
+    let param_names = param_names.iter().map(String::as_str);
+
+    let set = Fun::new(format!("set_{field_name}"))
+        .public()
+        .param("_delegated_witness", delegated_witness_type(context))
+        .param("nft", Type::borrow_mut(context.nft_type()))
+        .params(params.clone())
+        .body(vec![Stmt::assign(
+            Exp::field(Exp::var("nft"), field_name),
+            fields::value(field),
+        )]);
+
+    let set_as_publisher = Fun::new(format!("set_{field_name}_as_publisher"))
+        .public()
+        .entry()
+        .params([publisher_param()])
+        .param("nft", Type::borrow_mut(context.nft_type()))
+        .params(params.clone())
+        .body(vec![
+            delegated_witness_from_publisher(),
+            Stmt::Exp(Exp::call(
+                format!("set_{field_name}"),
+                vars(
+                    ["delegated_witness", "nft"]
+                        .into_iter()
+                        .chain(param_names.clone()),
+                ),
+            )),
+        ]);
+
+    let set_in_kiosk = Fun::new(format!("set_{field_name}_in_kiosk"))
+        .public()
+        .param("delegated_witness", delegated_witness_type(context))
+        .params(kiosk_params())
+        .params(params.clone())
+        .params(policy_params(context))
+        .body(vec![
+            Stmt::bind(
+                "borrow",
+                Exp::call_generic(
+                    "ob_kiosk::ob_kiosk::borrow_nft_mut",
+                    vec![context.nft_type()],
+                    vec![
+                        Exp::var("kiosk"),
+                        Exp::var("nft_id"),
+                        Exp::call("std::option::none", Vec::new()),
+                        Exp::var("ctx"),
+                    ],
+                ),
+            ),
+            Stmt::Blank,
+            Stmt::bind_typed(
+                "nft",
+                Type::borrow_mut(context.nft_type()),
+                Exp::call(
+                    "ob_request::borrow_request::borrow_nft_ref_mut",
+                    vec![
+                        Exp::var("delegated_witness"),
+                        Exp::borrow_mut(Exp::var("borrow")),
+                    ],
+                ),
+            ),
+            Stmt::Exp(Exp::call(
+                format!("set_{field_name}"),
+                vars(
+                    ["delegated_witness", "nft"]
+                        .into_iter()
+                        .chain(param_names.clone()),
+                ),
+            )),
+            Stmt::Blank,
+            Stmt::Exp(Exp::call_generic(
+                "ob_kiosk::ob_kiosk::return_nft",
+                vec![Type::named("Witness"), context.nft_type()],
+                vars(["kiosk", "borrow", "policy"]),
+            )),
+        ]);
+
+    let set_in_kiosk_as_publisher =
+        Fun::new(format!("set_{field_name}_in_kiosk_as_publisher"))
+            .public()
+            .entry()
+            .params([publisher_param()])
+            .params(kiosk_params())
+            .params(params)
+            .params(policy_params(context))
+            .body(vec![
+                delegated_witness_from_publisher(),
+                Stmt::Exp(Exp::call(
+                    format!("set_{field_name}_in_kiosk"),
+                    vars(
+                        ["delegated_witness", "kiosk", "nft_id"]
+                            .into_iter()
+                            .chain(param_names)
+                            .chain(["policy", "ctx"]),
+                    ),
+                )),
+            ]);
+
+    vec![
+        set.into(),
+        set_as_publisher.into(),
+        set_in_kiosk.into(),
+        set_in_kiosk_as_publisher.into(),
+    ]
+}
 
+impl MoveDefs for Dynamic {
+    fn write_move_defs(&self, context: &Context) -> Vec<Member> {
         if !self.is_dynamic() {
-            return String::new();
+            return Vec::new();
         }
 
-        let collection_take_str = requires_collection.then(|| format!("
-
-        let collection = sui::test_scenario::take_shared<nft_protocol::collection::Collection<{type_name}>>(
-            &scenario,
-        );")).unwrap_or_default();
-
-        let collection_param_str = requires_collection
-            .then_some(
-                "
-            &mut collection,",
-            )
-            .unwrap_or_default();
-
-        let collection_return_str = requires_collection
-            .then_some(
-                "
-        sui::test_scenario::return_shared(collection);",
-            )
-            .unwrap_or_default();
-
-        let fields_str: String = fields
-            .test_params()
-            .map(|param| {
-                format!(
-                    "
-            {param},"
-                )
-            })
-            .collect();
-
-        let setters_str: String = fields
+        context
+            .fields()
             .iter()
-            .map(|field| {
-                let field_name = field.name();
-                let field_init: String = field
-                    .test_params()
-                    .map(|init| {
-                        format!(
-                            "
-            {init},"
-                        )
-                    })
-                    .collect();
-
-                format!(
-                    "
-
-        set_{field_name}_in_kiosk_as_publisher(
-            &publisher,
-            &mut kiosk,
-            nft_id,{field_init}
-            &borrow_policy,
-            sui::test_scenario::ctx(&mut scenario),
-        );"
-                )
-            })
-            .collect();
-
-        // Nothing to test if there are no setters
-        if setters_str.is_empty() {
-            return String::new();
-        }
-
-        format!("
-
-    #[test]
-    fun it_sets_metadata() {{
-        let scenario = sui::test_scenario::begin(CREATOR);
-        init({witness_name} {{}}, sui::test_scenario::ctx(&mut scenario));
-
-        sui::test_scenario::next_tx(&mut scenario, CREATOR);
-
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<{type_name}>>(
-            &scenario,
-            CREATOR,
-        );{collection_take_str}
-
-        let publisher = sui::test_scenario::take_from_address<sui::package::Publisher>(
-            &scenario,
-            CREATOR,
-        );
-
-        let borrow_policy: ob_request::request::Policy<ob_request::request::WithNft<{type_name}, ob_request::borrow_request::BORROW_REQ>> =
-            sui::test_scenario::take_shared(&mut scenario);
-
-        let nft = mint({fields_str}
-            &mut mint_cap,{collection_param_str}
-            sui::test_scenario::ctx(&mut scenario),
-        );
-        let nft_id = sui::object::id(&nft);
-
-        let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
-        ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, sui::test_scenario::ctx(&mut scenario));{setters_str}
-
-        sui::test_scenario::return_to_address(CREATOR, mint_cap);
-        sui::test_scenario::return_to_address(CREATOR, publisher);
-        sui::test_scenario::return_shared(borrow_policy);{collection_return_str}
-        sui::transfer::public_share_object(kiosk);
-        sui::test_scenario::end(scenario);
-    }}")
+            .flat_map(|field| write_move_field_setters(field, context))
+            .collect()
     }
 }
 
-fn write_move_field_setter(
-    type_name: &str,
-    field_name: &str,
-    params: Vec<String>,
-    param_types: Vec<&str>,
-    init_str: String,
-) -> String {
-    let params: Vec<_> = params.iter().map(String::as_str).collect();
-    let set_params_str = params.join(", ");
-
-    let delegated_witness_param_type_str =
-        format!("ob_permissions::witness::Witness<{type_name}>");
-    let nft_param_type_str = format!("&mut {type_name}");
-
-    let mut field_str = String::new();
-
-    let mut set_params = Vec::new();
-    set_params.extend_from_slice(&["_delegated_witness", "nft"]);
-    set_params.extend_from_slice(params.as_slice());
-
-    let mut set_param_types = Vec::new();
-    set_param_types.push(delegated_witness_param_type_str.as_str());
-    set_param_types.push(nft_param_type_str.as_str());
-    set_param_types.extend_from_slice(param_types.as_slice());
-
-    field_str.push_str(&write_move_fn(
-        &format!("set_{field_name}"),
-        &set_params,
-        &set_param_types,
-        true,
-        false,
-        None,
-        || {
-            format!(
-                "
-        nft.{field_name} = {init_str};"
-            )
-        },
-    ));
-
-    let mut set_params = Vec::new();
-    set_params.extend_from_slice(&["publisher", "nft"]);
-    set_params.extend_from_slice(params.as_slice());
-
-    let mut set_param_types = Vec::new();
-    set_param_types.extend_from_slice(&[
-        "&sui::package::Publisher",
-        nft_param_type_str.as_str(),
-    ]);
-    set_param_types.extend_from_slice(param_types.as_slice());
-
-    field_str.push_str(&write_move_fn(
-        &format!("set_{field_name}_as_publisher"),
-        &set_params,
-        &set_param_types,
-        true,
-        true,
-        None,
-        || {
-            format!("
-        let delegated_witness = ob_permissions::witness::from_publisher(publisher);
-        set_{field_name}(delegated_witness, nft, {set_params_str});")
-        },
-    ));
-
-    let mut set_params = Vec::new();
-    set_params.extend_from_slice(&["delegated_witness", "kiosk", "nft_id"]);
-    set_params.extend_from_slice(params.as_slice());
-    set_params.extend_from_slice(&["policy", "ctx"]);
-
-    let mut set_param_types = Vec::new();
-    set_param_types.extend_from_slice(&[
-        delegated_witness_param_type_str.as_str(),
-        "&mut sui::kiosk::Kiosk",
-        "sui::object::ID",
-    ]);
-    set_param_types.extend_from_slice(param_types.as_slice());
-    let policy_param_str =
-        format!("&ob_request::request::Policy<ob_request::request::WithNft<{type_name}, ob_request::borrow_request::BORROW_REQ>>");
-    set_param_types.extend_from_slice(&[
-        policy_param_str.as_str(),
-        "&mut sui::tx_context::TxContext",
-    ]);
-
-    field_str.push_str(&write_move_fn(
-        &format!("set_{field_name}_in_kiosk"),
-        &set_params,
-        &set_param_types,
-        true,
-        false,
-        None,
-        || {
-            format!("
-        let borrow = ob_kiosk::ob_kiosk::borrow_nft_mut<{type_name}>(kiosk, nft_id, std::option::none(), ctx);
-
-        let nft: &mut {type_name} = ob_request::borrow_request::borrow_nft_ref_mut(delegated_witness, &mut borrow);
-        set_{field_name}(delegated_witness, nft, {set_params_str});
-
-        ob_kiosk::ob_kiosk::return_nft<Witness, {type_name}>(kiosk, borrow, policy);")
-        },
-    ));
-
-    let mut set_params = Vec::new();
-    set_params.extend_from_slice(&["publisher", "kiosk", "nft_id"]);
-    set_params.extend(params);
-    set_params.extend_from_slice(&["policy", "ctx"]);
-
-    let mut set_param_types = Vec::new();
-    set_param_types.extend_from_slice(&[
-        "&sui::package::Publisher",
-        "&mut sui::kiosk::Kiosk",
-        "sui::object::ID",
-    ]);
-    set_param_types.extend_from_slice(param_types.as_slice());
-    let policy_param_str =
-        format!("&ob_request::request::Policy<ob_request::request::WithNft<{type_name}, ob_request::borrow_request::BORROW_REQ>>");
-    set_param_types.extend_from_slice(&[
-        policy_param_str.as_str(),
-        "&mut sui::tx_context::TxContext",
-    ]);
-
-    field_str.push_str(&write_move_fn(
-        &format!("set_{field_name}_in_kiosk_as_publisher"),
-        &set_params,
-        &set_param_types,
-        true,
-        true,
-        None,
-        || {
-            format!("
-        let delegated_witness = ob_permissions::witness::from_publisher(publisher);
-        set_{field_name}_in_kiosk(delegated_witness, kiosk, nft_id, {set_params_str}, policy, ctx);")
-        },
-    ));
+impl MoveTests for Dynamic {
+    fn write_move_tests(&self, context: &Context) -> Vec<Member> {
+        // Nothing to test if there are no setters
+        if !self.is_dynamic() || context.fields().iter().next().is_none() {
+            return Vec::new();
+        }
 
-    field_str
-}
+        let mut body = scenario::begin(context);
+        body.push(Stmt::Blank);
+        body.extend(scenario::take_mint_objects(context));
+        body.extend([
+            Stmt::Blank,
+            scenario::take_from_creator(
+                "publisher",
+                Type::named("sui::package::Publisher"),
+            ),
+            Stmt::Blank,
+            scenario::take_shared(
+                "borrow_policy",
+                request_policy_type(context, BORROW_REQ),
+            ),
+            Stmt::Blank,
+            Stmt::bind(
+                "nft",
+                Exp::call(
+                    "mint",
+                    scenario::mint_args(context, vec![scenario::ctx()]),
+                ),
+            ),
+            Stmt::bind(
+                "nft_id",
+                Exp::call(
+                    "sui::object::id",
+                    vec![Exp::borrow(Exp::var("nft"))],
+                ),
+            ),
+            Stmt::Blank,
+            Stmt::destructure(
+                Pattern::tuple([Pattern::var("kiosk"), Pattern::Wildcard]),
+                Exp::call("ob_kiosk::ob_kiosk::new", vec![scenario::ctx()]),
+            ),
+            Stmt::Exp(Exp::call(
+                "ob_kiosk::ob_kiosk::deposit",
+                vec![
+                    Exp::borrow_mut(Exp::var("kiosk")),
+                    Exp::var("nft"),
+                    scenario::ctx(),
+                ],
+            )),
+        ]);
+
+        for field in context.fields().iter() {
+            let mut args = vec![
+                Exp::borrow(Exp::var("publisher")),
+                Exp::borrow_mut(Exp::var("kiosk")),
+                Exp::var("nft_id"),
+            ];
+            args.extend(fields::test_args(field));
+            args.extend([
+                Exp::borrow(Exp::var("borrow_policy")),
+                scenario::ctx(),
+            ]);
+
+            body.push(Stmt::Blank);
+            body.push(Stmt::Exp(Exp::call(
+                format!("set_{}_in_kiosk_as_publisher", field.name()),
+                args,
+            )));
+        }
 
-fn def_args(args: DefArgs) -> (&Fields, &str) {
-    match args {
-        DefArgs::Dynamic { fields, type_name } => (fields, type_name),
-        _ => panic!("Incorrect DefArgs variant"),
-    }
-}
+        body.push(Stmt::Blank);
+        body.extend(scenario::return_mint_objects(context));
+        body.extend([
+            scenario::return_to_creator("publisher"),
+            scenario::return_shared("borrow_policy"),
+            share_object("kiosk"),
+            scenario::end(),
+        ]);
 
-fn test_args(args: TestArgs) -> (&Fields, &str, &str, bool) {
-    match args {
-        TestArgs::Dynamic {
-            fields,
-            type_name,
-            witness_name,
-            requires_collection,
-        } => (fields, type_name, witness_name, requires_collection),
-        _ => panic!("Incorrect TestArgs variant"),
+        vec![Fun::test("it_sets_metadata").body(body).into()]
     }
 }
@@ -1,24 +1,52 @@
-use crate::{InitArgs, MoveInit};
+use crate::ast::{Exp, Type};
 use gutenberg_types::models::nft::{Field, FieldType};
 
-impl MoveInit for Field {
-    fn write_move_init(&self, _args: InitArgs) -> String {
-        match self.field_type() {
-            FieldType::String => String::from(""), // TODO: This was changed from None, double check correctness
-            FieldType::Url => format!("sui::url::new_unsafe_from_bytes({field_name})", field_name = self.name),
-            FieldType::Attributes => format!("nft_protocol::attributes::from_vec({field_name}_keys, {field_name}_values)", field_name = self.name),
+/// Type of the field as declared on the NFT struct
+pub fn field_type(field_type: &FieldType) -> Type {
+    match field_type {
+        FieldType::String => Type::named("std::string::String"),
+        FieldType::Url => Type::named("sui::url::Url"),
+        FieldType::Attributes => {
+            Type::named("nft_protocol::attributes::Attributes")
         }
     }
 }
 
-impl MoveInit for FieldType {
-    fn write_move_init(&self, _args: InitArgs) -> String {
-        match self {
-            FieldType::String => String::from("std::string::String"),
-            FieldType::Url => String::from("sui::url::Url"),
-            FieldType::Attributes => {
-                String::from("nft_protocol::attributes::Attributes")
-            }
+/// Parameters from which the field is initialized
+pub fn params(field: &Field) -> Vec<(String, Type)> {
+    let param_types = match field.field_type() {
+        FieldType::String => vec![Type::named("std::string::String")],
+        FieldType::Url => vec![Type::vector(Type::u8())],
+        FieldType::Attributes => vec![
+            Type::vector(Type::named("std::ascii::String")),
+            Type::vector(Type::named("std::ascii::String")),
+        ],
+    };
+
+    field.params().zip(param_types).collect()
+}
+
+/// Expression initializing the field from its parameters
+pub fn value(field: &Field) -> Exp {
+    let params = field.params().map(Exp::Var).collect();
+
+    match field.field_type() {
+        FieldType::String => Exp::var(field.name()),
+        FieldType::Url => Exp::call("sui::url::new_unsafe_from_bytes", params),
+        FieldType::Attributes => {
+            Exp::call("nft_protocol::attributes::from_vec", params)
         }
     }
 }
+
+/// Test values for each of the field parameters
+pub fn test_args(field: &Field) -> Vec<Exp> {
+    match field.field_type() {
+        FieldType::String => vec![Exp::utf8("TEST STRING")],
+        FieldType::Url => vec![Exp::bytes("https://originbyte.io")],
+        FieldType::Attributes => vec![
+            Exp::vector(vec![Exp::ascii("key")]),
+            Exp::vector(vec![Exp::ascii("attribute")]),
+        ],
+    }
+}
@@ -1,35 +1,44 @@
-use crate::{InitArgs, MoveInit};
+use crate::{
+    ast::{Exp, Stmt, Type},
+    models::transfer_to_sender,
+    Context, MoveInit,
+};
 use gutenberg_types::models::nft::MintCap;
 
 // TODO: Reinstantiate write_move_demo_init when the time comes, but perhaps as
 // a wrapper method that fixes the supply param to 100
 impl MoveInit for MintCap {
     /// Write MintCap instantiation
-    fn write_move_init(&self, args: InitArgs) -> String {
-        let (witness, type_name) = init_args(args);
+    fn write_move_init(&self, context: &Context) -> Vec<Stmt> {
+        let type_args =
+            vec![Type::named(context.witness_name()), context.nft_type()];
 
-        let mint_cap_str = match self.supply {
-            Some(supply) => format!("
-
-        let mint_cap = nft_protocol::mint_cap::new_limited<{witness}, {type_name}>(
-            &witness, collection_id, {supply}, ctx
-        );"),
-            None =>
-            format!("
-
-        let mint_cap = nft_protocol::mint_cap::new_unlimited<{witness}, {type_name}>(
-            &witness, collection_id, ctx
-        );")
+        let mint_cap = match self.supply {
+            Some(supply) => Exp::call_generic(
+                "nft_protocol::mint_cap::new_limited",
+                type_args,
+                vec![
+                    Exp::borrow(Exp::var("witness")),
+                    Exp::var("collection_id"),
+                    Exp::u64(supply),
+                    Exp::var("ctx"),
+                ],
+            ),
+            None => Exp::call_generic(
+                "nft_protocol::mint_cap::new_unlimited",
+                type_args,
+                vec![
+                    Exp::borrow(Exp::var("witness")),
+                    Exp::var("collection_id"),
+                    Exp::var("ctx"),
+                ],
+            ),
         };
 
-        format!("{mint_cap_str}
-        sui::transfer::public_transfer(mint_cap, sui::tx_context::sender(ctx));")
-    }
-}
-
-fn init_args(args: InitArgs) -> (&str, &str) {
-    match args {
-        InitArgs::MintCap { witness, type_name } => (witness, type_name),
-        _ => panic!("Incorrect InitArgs variant"),
+        vec![
+            Stmt::Blank,
+            Stmt::bind("mint_cap", mint_cap),
+            transfer_to_sender("mint_cap"),
+        ]
     }
 }
@@ -1,305 +1,283 @@
-use crate::models::collection;
-use crate::{models::write_move_fn, DefArgs, MoveDefs, MoveTests, TestArgs};
-use crate::{InitArgs, MoveInit};
-use gutenberg_types::models::nft::{Fields, MintPolicies};
-
-impl MoveDefs for MintPolicies {
-    fn write_move_defs(&self, args: DefArgs) -> String {
-        let (fields, type_name, requires_collection) = def_args(args);
+use super::fields;
+use crate::{
+    ast::{Block, Exp, Fun, Member, Pattern, Stmt, Type},
+    models::{
+        collection::supply::write_move_increment, collection_type,
+        delegated_witness_from_witness, mint_cap_type, scenario, share_object,
+    },
+    Context, MoveDefs, MoveTests,
+};
+use gutenberg_types::models::nft::MintPolicies;
+
+/// Parameters shared by `mint` and all of its wrappers, excluding `ctx`
+fn base_params(context: &Context) -> Vec<(String, Type)> {
+    let mut params: Vec<(String, Type)> =
+        context.fields().iter().flat_map(fields::params).collect();
+
+    params.push((
+        "mint_cap".to_string(),
+        Type::borrow_mut(mint_cap_type(context)),
+    ));
+
+    if context.requires_collection() {
+        params.push((
+            "collection".to_string(),
+            Type::borrow_mut(collection_type(context)),
+        ));
+    }
 
-        let mut mint_fns = String::new();
+    params
+}
 
-        let params: Vec<String> = fields.params().collect();
-        let mut base_params: Vec<&str> =
-            params.iter().map(String::as_str).collect();
-        base_params.push("mint_cap");
+/// `let nft = mint(..);` forwarding the base parameters
+fn call_mint(context: &Context) -> Stmt {
+    let args = base_params(context)
+        .into_iter()
+        .map(|(name, _)| Exp::Var(name))
+        .chain([Exp::var("ctx")])
+        .collect();
 
-        if requires_collection {
-            base_params.push("collection");
-        }
+    Stmt::bind("nft", Exp::call("mint", args))
+}
 
-        let mut nft_params = base_params.clone();
-        nft_params.push("ctx");
+/// Declares a public entry function wrapping `mint`
+fn mint_wrapper(
+    context: &Context,
+    name: &str,
+    receiver: (&str, Type),
+    stmts: Vec<Stmt>,
+) -> Fun {
+    let mut body = vec![call_mint(context), Stmt::Blank];
+    body.extend(stmts);
+
+    Fun::new(name)
+        .public()
+        .entry()
+        .params(base_params(context))
+        .param(receiver.0, receiver.1)
+        .param("ctx", Type::tx_context())
+        .body(body)
+}
 
-        let mut base_param_types: Vec<&str> = fields.param_types().collect();
-        let mint_cap_param =
-            format!("&mut nft_protocol::mint_cap::MintCap<{type_name}>");
-        base_param_types.push(&mint_cap_param);
+fn write_move_mint(context: &Context) -> Fun {
+    let nft = Exp::pack(
+        context.type_name(),
+        [(
+            "id".to_string(),
+            Exp::call("sui::object::new", vec![Exp::var("ctx")]),
+        )]
+        .into_iter()
+        .chain(
+            context
+                .fields()
+                .iter()
+                .map(|field| (field.name().to_string(), fields::value(field))),
+        ),
+    );
+
+    let mut body = vec![
+        delegated_witness_from_witness(),
+        Stmt::Blank,
+        Stmt::bind("nft", nft),
+        Stmt::Blank,
+        Stmt::Exp(Exp::call(
+            "nft_protocol::mint_event::emit_mint",
+            vec![
+                Exp::var("delegated_witness"),
+                Exp::call(
+                    "nft_protocol::mint_cap::collection_id",
+                    vec![Exp::var("mint_cap")],
+                ),
+                Exp::borrow(Exp::var("nft")),
+            ],
+        )),
+    ];
+
+    if context.requires_collection() {
+        body.extend(write_move_increment());
+    }
 
-        let collection_param =
-            format!("&mut nft_protocol::collection::Collection<{type_name}>");
-        if requires_collection {
-            base_param_types.push(&collection_param);
-        }
+    body.push(Stmt::Blank);
+    body.push(Stmt::Exp(Exp::call(
+        "nft_protocol::mint_cap::increment_supply",
+        vec![Exp::var("mint_cap"), Exp::u64(1)],
+    )));
+    body.push(Stmt::Blank);
+
+    Fun::new("mint")
+        .params(base_params(context))
+        .param("ctx", Type::tx_context())
+        .returns(context.nft_type())
+        .body(Block::new(body).result(Exp::var("nft")))
+}
 
-        let mut nft_param_types = base_param_types.clone();
-        nft_param_types.push("&mut sui::tx_context::TxContext");
+impl MoveDefs for MintPolicies {
+    fn write_move_defs(&self, context: &Context) -> Vec<Member> {
+        let mut members = Vec::new();
 
         if self.launchpad {
-            let mut params = base_params.clone();
-            params.push("warehouse");
-            params.push("ctx");
-
-            let mut param_types = base_param_types.clone();
-            let warehouse_param =
-                format!("&mut ob_launchpad::warehouse::Warehouse<{type_name}>");
-            param_types.push(&warehouse_param);
-            param_types.push("&mut sui::tx_context::TxContext");
-
-            mint_fns.push_str(&write_move_fn(
-                "mint_nft_to_warehouse",
-                params.as_slice(),
-                param_types.as_slice(),
-                true,
-                true,
-                None,
-                || {
-                    format!(
-                        "
-        let nft = mint(
-            {params_str},
-        );
-
-        ob_launchpad::warehouse::deposit_nft(warehouse, nft);",
-                        params_str = nft_params.join(",\n            ")
-                    )
-                },
-            ));
+            members.push(
+                mint_wrapper(
+                    context,
+                    "mint_nft_to_warehouse",
+                    (
+                        "warehouse",
+                        Type::borrow_mut(Type::generic(
+                            "ob_launchpad::warehouse::Warehouse",
+                            vec![context.nft_type()],
+                        )),
+                    ),
+                    vec![Stmt::Exp(Exp::call(
+                        "ob_launchpad::warehouse::deposit_nft",
+                        vec![Exp::var("warehouse"), Exp::var("nft")],
+                    ))],
+                )
+                .into(),
+            );
         }
 
         // Airdrop NFT into Kiosks
         if self.airdrop {
-            // Write `mint_nft_to_kiosk`
-            let mut params = base_params.clone();
-            params.push("receiver");
-            params.push("ctx");
-
-            let mut param_types = base_param_types.clone();
-            param_types.push("&mut sui::kiosk::Kiosk");
-            param_types.push("&mut sui::tx_context::TxContext");
-
-            mint_fns.push_str(&write_move_fn(
-                "mint_nft_to_kiosk",
-                params.as_slice(),
-                param_types.as_slice(),
-                true,
-                true,
-                None,
-                || {
-                    format!(
-                        "
-        let nft = mint(
-            {params_str},
-        );
-
-        ob_kiosk::ob_kiosk::deposit(receiver, nft, ctx);",
-                        params_str = nft_params.join(",\n            "),
-                    )
-                },
-            ));
-
-            // Write `mint_nft_to_new_kiosk`
-            let mut params = base_params.clone();
-            params.push("receiver");
-            params.push("ctx");
-
-            let mut param_types = base_param_types.clone();
-            param_types.push("address");
-            param_types.push("&mut sui::tx_context::TxContext");
-
-            mint_fns.push_str(&write_move_fn(
-                "mint_nft_to_new_kiosk",
-                params.as_slice(),
-                param_types.as_slice(),
-                true,
-                true,
-                None,
-                || {
-                    format!(
-                        "
-        let nft = mint(
-            {params_str},
-        );
-
-        let (kiosk, _) = ob_kiosk::ob_kiosk::new_for_address(receiver, ctx);
-        ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, ctx);
-        sui::transfer::public_share_object(kiosk);",
-                        params_str = nft_params.join(",\n            ")
-                    )
-                },
-            ));
+            members.push(
+                mint_wrapper(
+                    context,
+                    "mint_nft_to_kiosk",
+                    (
+                        "receiver",
+                        Type::borrow_mut(Type::named("sui::kiosk::Kiosk")),
+                    ),
+                    vec![Stmt::Exp(Exp::call(
+                        "ob_kiosk::ob_kiosk::deposit",
+                        vec![
+                            Exp::var("receiver"),
+                            Exp::var("nft"),
+                            Exp::var("ctx"),
+                        ],
+                    ))],
+                )
+                .into(),
+            );
+
+            members.push(
+                mint_wrapper(
+                    context,
+                    "mint_nft_to_new_kiosk",
+                    ("receiver", Type::address()),
+                    vec![
+                        Stmt::destructure(
+                            Pattern::tuple([
+                                Pattern::var("kiosk"),
+                                Pattern::Wildcard,
+                            ]),
+                            Exp::call(
+                                "ob_kiosk::ob_kiosk::new_for_address",
+                                vec![Exp::var("receiver"), Exp::var("ctx")],
+                            ),
+                        ),
+                        Stmt::Exp(Exp::call(
+                            "ob_kiosk::ob_kiosk::deposit",
+                            vec![
+                                Exp::borrow_mut(Exp::var("kiosk")),
+                                Exp::var("nft"),
+                                Exp::var("ctx"),
+                            ],
+                        )),
+                        share_object("kiosk"),
+                    ],
+                )
+                .into(),
+            );
         }
 
-        mint_fns.push_str(&write_move_fn(
-            "mint",
-            nft_params.as_slice(),
-            nft_param_types.as_slice(),
-            false,
-            false,
-            Some(type_name.to_string()),
-            || {
-                let collection_increment_str = requires_collection
-                    .then(collection::supply::write_move_increment)
-                    .unwrap_or_default();
-
-                let fields_str: String = fields.iter().map(|field| {
-                    let field_name = field.name();
-                    let mut init = field
-                        .write_move_init(InitArgs::None);
-
-                    init =  if init != "" {
-                        format!("{field_name}: {init}")
-                    } else { field_name.to_string()};
-
-                    format!("
-            {init},")
-                }).collect();
-
-                format!(
-                    "
-        let delegated_witness = ob_permissions::witness::from_witness(Witness {{}});
-
-        let nft = {type_name} {{
-            id: sui::object::new(ctx),{fields_str}
-        }};
-
-        nft_protocol::mint_event::emit_mint(
-            delegated_witness,
-            nft_protocol::mint_cap::collection_id(mint_cap),
-            &nft,
-        );{collection_increment_str}
-
-        nft_protocol::mint_cap::increment_supply(mint_cap, 1);
+        members.push(write_move_mint(context).into());
 
-        nft",
-                )
-            },
-        ));
-
-        mint_fns
+        members
     }
 }
 
-impl MoveTests for MintPolicies {
-    fn write_move_tests(&self, args: TestArgs) -> String {
-        let (fields, type_name, witness_name, requires_collection) =
-            test_args(args);
-
-        let collection_take_str = requires_collection.then(|| format!("
-
-        let collection = sui::test_scenario::take_shared<nft_protocol::collection::Collection<{type_name}>>(
-            &scenario,
-        );")).unwrap_or_default();
-
-        let collection_param_str = requires_collection
-            .then_some(
-                "
-            &mut collection,",
-            )
-            .unwrap_or_default();
-
-        let collection_return_str = requires_collection
-            .then_some(
-                "
-        sui::test_scenario::return_shared(collection);",
-            )
-            .unwrap_or_default();
-
-        let fields_str: String = fields
-            .test_params()
-            .map(|param| {
-                format!(
-                    "
-            {param},"
-                )
-            })
-            .collect();
+/// Test minting through one of the `mint` wrappers into a receiver object
+fn write_move_mint_test(
+    context: &Context,
+    name: &str,
+    function: &str,
+    receiver_name: &str,
+    receiver: Stmt,
+    release_receiver: Stmt,
+) -> Fun {
+    let mut body = scenario::begin(context);
+    body.push(Stmt::Blank);
+    body.extend(scenario::take_mint_objects(context));
+    body.extend([
+        Stmt::Blank,
+        receiver,
+        Stmt::Blank,
+        Stmt::Exp(Exp::call(
+            function,
+            scenario::mint_args(
+                context,
+                vec![Exp::borrow_mut(Exp::var(receiver_name)), scenario::ctx()],
+            ),
+        )),
+        Stmt::Blank,
+        release_receiver,
+    ]);
+    body.extend(scenario::return_mint_objects(context));
+    body.push(scenario::end());
+
+    Fun::test(name).body(body)
+}
 
-        let mut test_str = String::new();
+impl MoveTests for MintPolicies {
+    fn write_move_tests(&self, context: &Context) -> Vec<Member> {
+        let mut members = Vec::new();
 
         if self.airdrop {
-            test_str.push_str(&format!(
-                "
-
-    #[test]
-    fun it_mints_nft_airdrop() {{
-        let scenario = sui::test_scenario::begin(CREATOR);
-        init({witness_name} {{}}, sui::test_scenario::ctx(&mut scenario));
-
-        sui::test_scenario::next_tx(&mut scenario, CREATOR);
-
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<{type_name}>>(
-            &scenario,
-            CREATOR,
-        );{collection_take_str}
-
-        let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
-
-        mint_nft_to_kiosk({fields_str}
-            &mut mint_cap,{collection_param_str}
-            &mut kiosk,
-            sui::test_scenario::ctx(&mut scenario)
-        );
-
-        sui::transfer::public_share_object(kiosk);
-        sui::test_scenario::return_to_address(CREATOR, mint_cap);{collection_return_str}
-        sui::test_scenario::end(scenario);
-    }}"));
+            members.push(
+                write_move_mint_test(
+                    context,
+                    "it_mints_nft_airdrop",
+                    "mint_nft_to_kiosk",
+                    "kiosk",
+                    Stmt::destructure(
+                        Pattern::tuple([
+                            Pattern::var("kiosk"),
+                            Pattern::Wildcard,
+                        ]),
+                        Exp::call(
+                            "ob_kiosk::ob_kiosk::new",
+                            vec![scenario::ctx()],
+                        ),
+                    ),
+                    share_object("kiosk"),
+                )
+                .into(),
+            );
         }
 
         if self.launchpad {
-            test_str.push_str(&format!(
-                "
-
-    #[test]
-    fun it_mints_nft_launchpad() {{
-        let scenario = sui::test_scenario::begin(CREATOR);
-        init({witness_name} {{}}, sui::test_scenario::ctx(&mut scenario));
-
-        sui::test_scenario::next_tx(&mut scenario, CREATOR);
-
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<{type_name}>>(
-            &scenario,
-            CREATOR,
-        );{collection_take_str}
-
-        let warehouse = ob_launchpad::warehouse::new<{type_name}>(sui::test_scenario::ctx(&mut scenario));
-
-        mint_nft_to_warehouse({fields_str}
-            &mut mint_cap,{collection_param_str}
-            &mut warehouse,
-            sui::test_scenario::ctx(&mut scenario)
-        );
-
-        sui::transfer::public_transfer(warehouse, CREATOR);
-        sui::test_scenario::return_to_address(CREATOR, mint_cap);{collection_return_str}
-        sui::test_scenario::end(scenario);
-    }}"));
+            members.push(
+                write_move_mint_test(
+                    context,
+                    "it_mints_nft_launchpad",
+                    "mint_nft_to_warehouse",
+                    "warehouse",
+                    Stmt::bind(
+                        "warehouse",
+                        Exp::call_generic(
+                            "ob_launchpad::warehouse::new",
+                            vec![context.nft_type()],
+                            vec![scenario::ctx()],
+                        ),
+                    ),
+                    Stmt::Exp(Exp::call(
+                        "sui::transfer::public_transfer",
+                        vec![Exp::var("warehouse"), Exp::var("CREATOR")],
+                    )),
+                )
+                .into(),
+            );
         }
 
-        test_str
-    }
-}
-
-fn def_args(args: DefArgs) -> (&Fields, &str, bool) {
-    match args {
-        DefArgs::MintPolicies {
-            fields,
-            type_name,
-            requires_collection,
-        } => (fields, type_name, requires_collection),
-        _ => panic!("Incorrect DefArgs variant"),
-    }
-}
-
-fn test_args(args: TestArgs) -> (&Fields, &str, &str, bool) {
-    match args {
-        TestArgs::MintPolicies {
-            fields,
-            type_name,
-            witness_name,
-            requires_collection,
-        } => (fields, type_name, witness_name, requires_collection),
-        _ => panic!("Incorrect TestArgs variant"),
+        members
     }
 }
@@ -1,199 +1,315 @@
-use crate::{InitArgs, MoveInit, MoveTests, TestArgs};
-use gutenberg_types::models::nft::{Fields, Orderbook};
-
-impl MoveInit for Orderbook {
-    fn write_move_init(&self, args: InitArgs) -> String {
-        let type_name = init_args(args);
-
-        match self {
-            Orderbook::Unprotected => format!(
-                "
-
-        liquidity_layer_v1::orderbook::create_unprotected<{type_name}, sui::sui::SUI>(
-            delegated_witness, &transfer_policy, ctx,
-        );"
-            ),
-            Orderbook::Protected => format!(
-                "
-
-        // Protected orderbook such that trading is not initially possible
-        let orderbook = liquidity_layer_v1::orderbook::new_with_protected_actions<{type_name}, sui::sui::SUI>(
-            delegated_witness, &transfer_policy, liquidity_layer_v1::orderbook::custom_protection(true, true, true), ctx,
-        );
-        liquidity_layer_v1::orderbook::share(orderbook);"
-            ),
-        }
-    }
-}
-
-impl MoveTests for Orderbook {
-    fn write_move_tests(&self, args: TestArgs) -> String {
-        let (
-            fields,
-            type_name,
-            witness_name,
-            requires_collection,
-            requires_royalties,
-        ) = test_args(args);
-        let collection_take_str = requires_collection.then(|| format!("
-
-        let collection = sui::test_scenario::take_shared<nft_protocol::collection::Collection<{type_name}>>(
-            &scenario,
-        );")).unwrap_or_default();
-
-        let collection_param_str = requires_collection
-            .then_some(
-                "
-            &mut collection,",
-            )
-            .unwrap_or_default();
-
-        let collection_return_str = requires_collection
-            .then_some(
-                "
-        sui::test_scenario::return_shared(collection);",
-            )
-            .unwrap_or_default();
-
-        let royalties_str = requires_royalties
-            .then(|| format!("
-
-        let royalty_strategy = sui::test_scenario::take_shared<nft_protocol::royalty_strategy_bps::BpsRoyaltyStrategy<{type_name}>>(&mut scenario);
-        nft_protocol::royalty_strategy_bps::confirm_transfer<{type_name}, sui::sui::SUI>(&mut royalty_strategy, &mut request);
-        sui::test_scenario::return_shared(royalty_strategy);"))
-            .unwrap_or_default();
-
-        let fields_str: String = fields
-            .test_params()
-            .map(|param| {
-                format!(
-                    "
-            {param},"
-                )
-            })
-            .collect();
-
-        format!("
-
-    #[test]
-    fun test_trade() {{
-        let scenario = sui::test_scenario::begin(CREATOR);
-
-        init({witness_name} {{}}, sui::test_scenario::ctx(&mut scenario));
-        sui::test_scenario::next_tx(&mut scenario, CREATOR);
-
-        // Setup allowlist
-        let (allowlist, allowlist_cap) = ob_allowlist::allowlist::new(sui::test_scenario::ctx(&mut scenario));
-        ob_allowlist::allowlist::insert_authority<liquidity_layer_v1::orderbook::Witness>(&allowlist_cap, &mut allowlist);
-
-        let publisher = sui::test_scenario::take_from_address<sui::package::Publisher>(
-            &scenario, CREATOR,
-        );
-
-        // Need to insert all tradeable types into collection
-        ob_allowlist::allowlist::insert_collection<{type_name}>(&mut allowlist, &publisher);
-        sui::transfer::public_share_object(allowlist);
-        sui::transfer::public_transfer(allowlist_cap, CREATOR);
-
-        // Setup orderbook
-        let orderbook = sui::test_scenario::take_shared<liquidity_layer_v1::orderbook::Orderbook<{type_name}, sui::sui::SUI>>(&scenario);
-        liquidity_layer_v1::orderbook::enable_orderbook(&publisher, &mut orderbook);
-
-        sui::test_scenario::return_to_address(CREATOR, publisher);
-
-        // Setup test NFT
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<{type_name}>>(
-            &mut scenario, CREATOR,
-        );{collection_take_str}
-
-        let nft = mint({fields_str}
-            &mut mint_cap,{collection_param_str}
-            sui::test_scenario::ctx(&mut scenario),
-        );
-        let nft_id = sui::object::id(&nft);
-
-        sui::test_scenario::return_to_address(CREATOR, mint_cap);{collection_return_str}
-
-        // Deposit NFT into Kiosk
-        let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
-        ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, sui::test_scenario::ctx(&mut scenario));
-        sui::transfer::public_share_object(kiosk);
-
-        sui::test_scenario::next_tx(&mut scenario, CREATOR);
-
-        // Test trade
-        let seller_kiosk = sui::test_scenario::take_shared<sui::kiosk::Kiosk>(&scenario);
-        let (buyer_kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
-
-        liquidity_layer_v1::orderbook::create_ask(
-            &mut orderbook,
-            &mut seller_kiosk,
-            100_000_000,
-            nft_id,
-            sui::test_scenario::ctx(&mut scenario),
-        );
-
-        let coin = sui::coin::mint_for_testing<sui::sui::SUI>(100_000_000, sui::test_scenario::ctx(&mut scenario));
-
-        let trade_opt = liquidity_layer_v1::orderbook::create_bid(
-            &mut orderbook,
-            &mut buyer_kiosk,
-            100_000_000,
-            &mut coin,
-            sui::test_scenario::ctx(&mut scenario),
-        );
-
-        sui::coin::burn_for_testing(coin);
-        let trade = std::option::destroy_some(trade_opt);
-
-        let request = liquidity_layer_v1::orderbook::finish_trade(
-            &mut orderbook,
-            liquidity_layer_v1::orderbook::trade_id(&trade),
-            &mut seller_kiosk,
-            &mut buyer_kiosk,
-            sui::test_scenario::ctx(&mut scenario),
-        );
-
-        let allowlist = sui::test_scenario::take_shared<ob_allowlist::allowlist::Allowlist>(&scenario);
-        nft_protocol::transfer_allowlist::confirm_transfer(&allowlist, &mut request);
-        sui::test_scenario::return_shared(allowlist);{royalties_str}
-
-        let transfer_policy = sui::test_scenario::take_shared<sui::transfer_policy::TransferPolicy<{type_name}>>(&scenario);
-        ob_request::transfer_request::confirm<{type_name}, sui::sui::SUI>(request, &transfer_policy, sui::test_scenario::ctx(&mut scenario));
-        sui::test_scenario::return_shared(transfer_policy);
-
-        ob_kiosk::ob_kiosk::assert_nft_type<{type_name}>(&buyer_kiosk, nft_id);
-
-        sui::transfer::public_share_object(buyer_kiosk);
-        sui::test_scenario::return_shared(seller_kiosk);
-        sui::test_scenario::return_shared(orderbook);
-        sui::test_scenario::end(scenario);
-    }}")
-    }
-}
-
-fn test_args(args: TestArgs) -> (&Fields, &str, &str, bool, bool) {
-    match args {
-        TestArgs::Orderbook {
-            fields,
-            type_name,
-            witness_name,
-            requires_collection,
-            requires_royalties,
-        } => (
-            fields,
-            type_name,
-            witness_name,
-            requires_collection,
-            requires_royalties,
-        ),
-        _ => panic!("Incorrect TestArgs variant"),
-    }
-}
-
-fn init_args(args: InitArgs) -> &str {
-    match args {
-        InitArgs::Orderbook { type_name } => type_name,
-        _ => panic!("Incorrect InitArgs variant"),
-    }
-}
+use crate::{
+    ast::{Exp, Fun, Member, Pattern, Stmt, Type},
+    compat::Api,
+    models::{scenario, share_object},
+    Context, MoveInit, MoveTests,
+};
+use gutenberg_types::models::nft::Orderbook;
+
+/// Price at which the test NFT is traded
+const TEST_PRICE: u64 = 100_000_000;
+
+/// `liquidity_layer_v1::orderbook::Orderbook<T, sui::sui::SUI>`
+fn orderbook_type(context: &Context) -> Type {
+    Type::generic(
+        "liquidity_layer_v1::orderbook::Orderbook",
+        vec![context.nft_type(), sui_type()],
+    )
+}
+
+fn sui_type() -> Type {
+    Type::named("sui::sui::SUI")
+}
+
+fn vars<'a>(names: impl IntoIterator<Item = &'a str>) -> Vec<Exp> {
+    names.into_iter().map(Exp::var).collect()
+}
+
+impl MoveInit for Orderbook {
+    fn write_move_init(&self, context: &Context) -> Vec<Stmt> {
+        let type_args = vec![context.nft_type(), sui_type()];
+
+        match self {
+            Orderbook::Unprotected => vec![
+                Stmt::Blank,
+                Stmt::Exp(Exp::call_generic(
+                    context.api(Api::CreateUnprotectedOrderbook),
+                    type_args,
+                    vec![
+                        Exp::var("delegated_witness"),
+                        Exp::borrow(Exp::var("transfer_policy")),
+                        Exp::var("ctx"),
+                    ],
+                )),
+            ],
+            Orderbook::Protected => vec![
+                Stmt::Blank,
+                Stmt::comment(
+                    "Protected orderbook such that trading is not initially possible",
+                ),
+                Stmt::bind(
+                    "orderbook",
+                    Exp::call_generic(
+                        context.api(Api::NewProtectedOrderbook),
+                        type_args,
+                        vec![
+                            Exp::var("delegated_witness"),
+                            Exp::borrow(Exp::var("transfer_policy")),
+                            Exp::call(
+                                context.api(Api::CustomProtection),
+                                vec![Exp::bool(true), Exp::bool(true), Exp::bool(true)],
+                            ),
+                            Exp::var("ctx"),
+                        ],
+                    ),
+                ),
+                Stmt::Exp(Exp::call(
+                    context.api(Api::ShareOrderbook),
+                    vec![Exp::var("orderbook")],
+                )),
+            ],
+        }
+    }
+}
+
+impl MoveTests for Orderbook {
+    fn write_move_tests(&self, context: &Context) -> Vec<Member> {
+        let mut body = scenario::begin(context);
+
+        body.extend([
+            Stmt::Blank,
+            Stmt::comment("Setup allowlist"),
+            Stmt::destructure(
+                Pattern::tuple([
+                    Pattern::var("allowlist"),
+                    Pattern::var("allowlist_cap"),
+                ]),
+                Exp::call(
+                    "ob_allowlist::allowlist::new",
+                    vec![scenario::ctx()],
+                ),
+            ),
+            Stmt::Exp(Exp::call_generic(
+                "ob_allowlist::allowlist::insert_authority",
+                vec![Type::named("liquidity_layer_v1::orderbook::Witness")],
+                vec![
+                    Exp::borrow(Exp::var("allowlist_cap")),
+                    Exp::borrow_mut(Exp::var("allowlist")),
+                ],
+            )),
+            Stmt::Blank,
+            scenario::take_from_creator(
+                "publisher",
+                Type::named("sui::package::Publisher"),
+            ),
+            Stmt::Blank,
+            Stmt::comment("Need to insert all tradeable types into collection"),
+            Stmt::Exp(Exp::call_generic(
+                "ob_allowlist::allowlist::insert_collection",
+                vec![context.nft_type()],
+                vec![
+                    Exp::borrow_mut(Exp::var("allowlist")),
+                    Exp::borrow(Exp::var("publisher")),
+                ],
+            )),
+            share_object("allowlist"),
+            Stmt::Exp(Exp::call(
+                "sui::transfer::public_transfer",
+                vars(["allowlist_cap", "CREATOR"]),
+            )),
+            Stmt::Blank,
+            Stmt::comment("Setup orderbook"),
+            scenario::take_shared("orderbook", orderbook_type(context)),
+            Stmt::Exp(Exp::call(
+                "liquidity_layer_v1::orderbook::enable_orderbook",
+                vec![
+                    Exp::borrow(Exp::var("publisher")),
+                    Exp::borrow_mut(Exp::var("orderbook")),
+                ],
+            )),
+            Stmt::Blank,
+            scenario::return_to_creator("publisher"),
+            Stmt::Blank,
+            Stmt::comment("Setup test NFT"),
+        ]);
+
+        body.extend(scenario::take_mint_objects(context));
+        body.extend([
+            Stmt::Blank,
+            Stmt::bind(
+                "nft",
+                Exp::call(
+                    "mint",
+                    scenario::mint_args(context, vec![scenario::ctx()]),
+                ),
+            ),
+            Stmt::bind(
+                "nft_id",
+                Exp::call(
+                    "sui::object::id",
+                    vec![Exp::borrow(Exp::var("nft"))],
+                ),
+            ),
+            Stmt::Blank,
+        ]);
+        body.extend(scenario::return_mint_objects(context));
+
+        body.extend([
+            Stmt::Blank,
+            Stmt::comment("Deposit NFT into Kiosk"),
+            Stmt::destructure(
+                Pattern::tuple([Pattern::var("kiosk"), Pattern::Wildcard]),
+                Exp::call("ob_kiosk::ob_kiosk::new", vec![scenario::ctx()]),
+            ),
+            Stmt::Exp(Exp::call(
+                "ob_kiosk::ob_kiosk::deposit",
+                vec![
+                    Exp::borrow_mut(Exp::var("kiosk")),
+                    Exp::var("nft"),
+                    scenario::ctx(),
+                ],
+            )),
+            share_object("kiosk"),
+            Stmt::Blank,
+            scenario::next_tx(),
+            Stmt::Blank,
+            Stmt::comment("Test trade"),
+            scenario::take_shared(
+                "seller_kiosk",
+                Type::named("sui::kiosk::Kiosk"),
+            ),
+            Stmt::destructure(
+                Pattern::tuple([
+                    Pattern::var("buyer_kiosk"),
+                    Pattern::Wildcard,
+                ]),
+                Exp::call("ob_kiosk::ob_kiosk::new", vec![scenario::ctx()]),
+            ),
+            Stmt::Blank,
+            Stmt::Exp(Exp::call(
+                "liquidity_layer_v1::orderbook::create_ask",
+                vec![
+                    Exp::borrow_mut(Exp::var("orderbook")),
+                    Exp::borrow_mut(Exp::var("seller_kiosk")),
+                    Exp::u64(TEST_PRICE),
+                    Exp::var("nft_id"),
+                    scenario::ctx(),
+                ],
+            )),
+            Stmt::Blank,
+            Stmt::bind(
+                "coin",
+                Exp::call_generic(
+                    "sui::coin::mint_for_testing",
+                    vec![sui_type()],
+                    vec![Exp::u64(TEST_PRICE), scenario::ctx()],
+                ),
+            ),
+            Stmt::Blank,
+            Stmt::bind(
+                "trade_opt",
+                Exp::call(
+                    "liquidity_layer_v1::orderbook::create_bid",
+                    vec![
+                        Exp::borrow_mut(Exp::var("orderbook")),
+                        Exp::borrow_mut(Exp::var("buyer_kiosk")),
+                        Exp::u64(TEST_PRICE),
+                        Exp::borrow_mut(Exp::var("coin")),
+                        scenario::ctx(),
+                    ],
+                ),
+            ),
+            Stmt::Blank,
+            Stmt::Exp(Exp::call("sui::coin::burn_for_testing", vars(["coin"]))),
+            Stmt::bind(
+                "trade",
+                Exp::call("std::option::destroy_some", vars(["trade_opt"])),
+            ),
+            Stmt::Blank,
+            Stmt::bind(
+                "request",
+                Exp::call(
+                    "liquidity_layer_v1::orderbook::finish_trade",
+                    vec![
+                        Exp::borrow_mut(Exp::var("orderbook")),
+                        Exp::call(
+                            "liquidity_layer_v1::orderbook::trade_id",
+                            vec![Exp::borrow(Exp::var("trade"))],
+                        ),
+                        Exp::borrow_mut(Exp::var("seller_kiosk")),
+                        Exp::borrow_mut(Exp::var("buyer_kiosk")),
+                        scenario::ctx(),
+                    ],
+                ),
+            ),
+            Stmt::Blank,
+            scenario::take_shared(
+                "allowlist",
+                Type::named("ob_allowlist::allowlist::Allowlist"),
+            ),
+            Stmt::Exp(Exp::call(
+                "nft_protocol::transfer_allowlist::confirm_transfer",
+                vec![
+                    Exp::borrow(Exp::var("allowlist")),
+                    Exp::borrow_mut(Exp::var("request")),
+                ],
+            )),
+            scenario::return_shared("allowlist"),
+        ]);
+
+        if context.has_royalties() {
+            let strategy_type = Type::generic(
+                "nft_protocol::royalty_strategy_bps::BpsRoyaltyStrategy",
+                vec![context.nft_type()],
+            );
+
+            body.extend([
+                Stmt::Blank,
+                scenario::take_shared("royalty_strategy", strategy_type),
+                Stmt::Exp(Exp::call_generic(
+                    "nft_protocol::royalty_strategy_bps::confirm_transfer",
+                    vec![context.nft_type(), sui_type()],
+                    vec![
+                        Exp::borrow_mut(Exp::var("royalty_strategy")),
+                        Exp::borrow_mut(Exp::var("request")),
+                    ],
+                )),
+                scenario::return_shared("royalty_strategy"),
+            ]);
+        }
+
+        body.extend([
+            Stmt::Blank,
+            scenario::take_shared(
+                "transfer_policy",
+                Type::generic(
+                    "sui::transfer_policy::TransferPolicy",
+                    vec![context.nft_type()],
+                ),
+            ),
+            Stmt::Exp(Exp::call_generic(
+                "ob_request::transfer_request::confirm",
+                vec![context.nft_type(), sui_type()],
+                vec![
+                    Exp::var("request"),
+                    Exp::borrow(Exp::var("transfer_policy")),
+                    scenario::ctx(),
+                ],
+            )),
+            scenario::return_shared("transfer_policy"),
+            Stmt::Blank,
+            Stmt::Exp(Exp::call_generic(
+                "ob_kiosk::ob_kiosk::assert_nft_type",
+                vec![context.nft_type()],
+                vec![Exp::borrow(Exp::var("buyer_kiosk")), Exp::var("nft_id")],
+            )),
+            Stmt::Blank,
+            share_object("buyer_kiosk"),
+            scenario::return_shared("seller_kiosk"),
+            scenario::return_shared("orderbook"),
+            scenario::end(),
+        ]);
+
+        vec![Fun::test("test_trade").body(body).into()]
+    }
+}
@@ -0,0 +1,133 @@
+//! Test scenarios from which the expected packages are generated
+//!
+//! Every scenario is a configuration file in `tests/scenarios` which is
+//! generated both as a full contract and as a demo contract into
+//! `tests/packages/{full,demo}`.
+
+use crate::{generate_contract_with_schema, read_schema, ContractFile};
+use anyhow::{anyhow, Result};
+use gutenberg_types::Schema;
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Configuration of a test scenario
+pub struct Scenario {
+    /// File stem of the configuration file
+    pub name: String,
+    pub schema: Schema,
+}
+
+/// Variant of the contract generated for a scenario
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// Contract generated as configured
+    Full,
+    /// Contract generated with demo constraints enforced
+    Demo,
+}
+
+impl Variant {
+    pub const ALL: [Variant; 2] = [Variant::Full, Variant::Demo];
+
+    /// Directory under `tests/packages` containing packages of the variant
+    pub fn dir_name(&self) -> &'static str {
+        match self {
+            Variant::Full => "full",
+            Variant::Demo => "demo",
+        }
+    }
+}
+
+impl Scenario {
+    /// Generates the contract files of the given variant
+    pub fn generate(&self, variant: Variant) -> Vec<ContractFile> {
+        match variant {
+            Variant::Full => generate_contract_with_schema(&self.schema),
+            Variant::Demo => {
+                let mut schema = self.schema.clone();
+                schema.collection.enforce_demo();
+                schema.nft.enforce_demo();
+
+                generate_contract_with_schema(&schema)
+            }
+        }
+    }
+
+    /// Directory of the expected package of the given variant
+    pub fn package_dir(
+        &self,
+        packages_dir: &Path,
+        variant: Variant,
+    ) -> PathBuf {
+        packages_dir.join(variant.dir_name()).join(&self.name)
+    }
+}
+
+/// Reads every JSON and YAML configuration file in `scenarios_dir`, sorted by
+/// name
+pub fn read_scenarios(scenarios_dir: &Path) -> Result<Vec<Scenario>> {
+    let entries = fs::read_dir(scenarios_dir).map_err(|err| {
+        anyhow!(
+            r#"Could not read directory "{}": {err}"#,
+            scenarios_dir.display()
+        )
+    })?;
+
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let extension = path.extension().and_then(OsStr::to_str);
+        if matches!(extension, Some("json" | "yaml")) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            Ok(Scenario {
+                name: path
+                    .file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string(),
+                schema: read_schema(&path)?,
+            })
+        })
+        .collect()
+}
+
+/// Regenerates the sources of the expected packages of every scenario
+///
+/// Only the `sources` directory of every package is replaced, manifests are
+/// left untouched as writing them requires fetching the program registry.
+pub fn write_packages(
+    scenarios: &[Scenario],
+    packages_dir: &Path,
+) -> Result<()> {
+    for scenario in scenarios {
+        for variant in Variant::ALL {
+            let package_dir = scenario.package_dir(packages_dir, variant);
+            let sources_dir = package_dir.join("sources");
+
+            let _ = fs::remove_dir_all(&sources_dir);
+            fs::create_dir_all(&sources_dir).map_err(|err| {
+                anyhow!(
+                    r#"Could not create directory "{}": {err}"#,
+                    sources_dir.display()
+                )
+            })?;
+
+            scenario
+                .generate(variant)
+                .iter()
+                .try_for_each(|file| file.write_to_file(&package_dir))?;
+        }
+    }
+
+    Ok(())
+}
@@ -2,62 +2,91 @@
 //! struct `Schema`, acting as an intermediate data structure, to write
 //! the associated Move module and dump into a default or custom folder defined
 //! by the caller.
-use crate::{ContractFile, DefArgs, MoveDefs, MoveTests, TestArgs, WriteMove};
+use crate::{
+    ast::{Ability, Const, Exp, Fun, Member, Module, Stmt, Struct, Type},
+    header,
+    models::{collection_type, mint_cap_type, scenario},
+    Context, ContractFile, MoveDefs, MoveTests, WriteMove,
+};
 use gutenberg_types::Schema;
+use package_manager::version::Version;
 use std::path::PathBuf;
 
 /// Implementation of `MoveDefs` trait for `Schema`.
 /// Provides functionality to generate Move definitions from a `Schema`.
 impl MoveDefs for Schema {
-    fn write_move_defs(&self, _args: DefArgs) -> String {
-        self.nft.write_move_defs(DefArgs::NftData {
-            collection_data: &self.collection,
-        })
+    fn write_move_defs(&self, context: &Context) -> Vec<Member> {
+        let otw = Struct::new(context.witness_name(), &[Ability::Drop])
+            .doc("One time witness is only instantiated in the init method");
+
+        let witness = Struct::new("Witness", &[Ability::Drop])
+            .doc("Can be used for authorization of other actions post-creation. It is")
+            .doc("vital that this struct is not freely given to any contract, because it")
+            .doc("serves as an auth token.");
+
+        let mut members = vec![otw.into(), witness.into()];
+        members.extend(self.nft.write_move_defs(context));
+        members
     }
 }
 
 /// Implementation of `MoveTests` trait for `Schema`.
 /// Enables writing of Move test cases based on the `Schema`.
 impl MoveTests for Schema {
-    /// Generates a string containing Move test cases.
-    fn write_move_tests(&self, _args: TestArgs) -> String {
-        let type_name = self.nft().type_name();
-        let witness_name = self.nft().witness_name();
-        let collection_data = self.collection();
-
-        let mut tests_str = format!(
-            "
-
-    #[test_only]
-    const CREATOR: address = @0xA1C04;
-
-    #[test]
-    fun it_inits_collection() {{
-        let scenario = sui::test_scenario::begin(CREATOR);
-
-        init({witness_name} {{}}, sui::test_scenario::ctx(&mut scenario));
-        sui::test_scenario::next_tx(&mut scenario, CREATOR);
-
-        assert!(sui::test_scenario::has_most_recent_shared<nft_protocol::collection::Collection<{type_name}>>(), 0);
-
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<{type_name}>>(
-            &scenario, CREATOR,
-        );
-
-        sui::test_scenario::return_to_address(CREATOR, mint_cap);
-        sui::test_scenario::next_tx(&mut scenario, CREATOR);
-
-        sui::test_scenario::end(scenario);
-    }}");
+    /// Generates Move test cases
+    fn write_move_tests(&self, context: &Context) -> Vec<Member> {
+        let creator =
+            Const::new("CREATOR", Type::address(), Exp::address_hex("A1C04"))
+                .attribute("test_only");
+
+        let mut body = scenario::begin(context);
+        body.extend([
+            Stmt::Blank,
+            Stmt::Exp(Exp::call(
+                "assert!",
+                vec![
+                    Exp::call_generic(
+                        "sui::test_scenario::has_most_recent_shared",
+                        vec![collection_type(context)],
+                        Vec::new(),
+                    ),
+                    Exp::u64(0),
+                ],
+            )),
+            Stmt::Blank,
+            scenario::take_from_creator("mint_cap", mint_cap_type(context)),
+            Stmt::Blank,
+            scenario::return_to_creator("mint_cap"),
+            scenario::next_tx(),
+            Stmt::Blank,
+            scenario::end(),
+        ]);
+
+        let mut members = vec![
+            creator.into(),
+            Fun::test("it_inits_collection").body(body).into(),
+        ];
+        members.extend(self.nft.write_move_tests(context));
+        members
+    }
+}
 
-        tests_str.push_str(
-            &self
-                .nft
-                .write_move_tests(TestArgs::NftData { collection_data }),
-        );
+/// Builds the Move module for the given `Schema`
+pub fn write_move_module(schema: &Schema) -> Module {
+    write_move_module_for(schema, &crate::compat::DEFAULT_PROTOCOL_VERSION)
+}
 
-        tests_str
-    }
+/// Builds the Move module for the given `Schema` and NftProtocol version
+pub fn write_move_module_for(
+    schema: &Schema,
+    protocol_version: &Version,
+) -> Module {
+    let context =
+        Context::with_protocol_version(schema, protocol_version.clone());
+
+    Module::new(schema.package_name(), schema.nft().module_name())
+        .members(schema.write_move_defs(&context))
+        .members(schema.write_move_tests(&context))
 }
 
 /// Implementation of `WriteMove` trait for `Schema`.
@@ -70,30 +99,16 @@ impl WriteMove for Schema {
     /// struct `Schema` and dump it into a default folder
     /// `../sources/examples/<module_name>.move` or custom folder defined by
     /// the caller.
-    fn write_move(&self) -> ContractFile {
-        let witness_name = self.nft().witness_name();
-        let module_name = self.nft().module_name();
-        let package_name = self.package_name();
-
-        let definitions = self.write_move_defs(DefArgs::None);
-        let tests = self.write_move_tests(TestArgs::None);
-
-        let content = format!(
-            "module {package_name}::{module_name} {{
-    /// One time witness is only instantiated in the init method
-    struct {witness_name} has drop {{}}
-
-    /// Can be used for authorization of other actions post-creation. It is
-    /// vital that this struct is not freely given to any contract, because it
-    /// serves as an auth token.
-    struct Witness has drop {{}}{definitions}{tests}
-}}
-"
-        );
+    fn write_move(&self, protocol_version: &Version) -> ContractFile {
+        let module = write_move_module_for(self, protocol_version);
 
         ContractFile {
-            path: PathBuf::from("sources").join(format!("{module_name}.move")),
-            content,
+            path: PathBuf::from("sources")
+                .join(format!("{}.move", module.name)),
+            content: header::stamp(
+                &header::provenance(self, protocol_version),
+                &module.to_string(),
+            ),
         }
     }
 }
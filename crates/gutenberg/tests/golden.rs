@@ -0,0 +1,131 @@
+//! Compares the contracts generated for every scenario against the expected
+//! packages in `tests/packages/{demo,full}`
+//!
+//! Set `GUTENBERG_BLESS=1` to overwrite the expected sources with the
+//! generated ones.
+//!
+//! The provenance line of the headers is not compared, such that releasing a
+//! new template version does not churn every expected source.
+use gutenberg::{
+    diff::{diff_files, find_hand_edits, DiffStatus},
+    scenarios::{read_scenarios, Scenario, Variant},
+    ContractFile,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+fn manifest_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn scenarios() -> Vec<Scenario> {
+    read_scenarios(&manifest_dir().join("tests/scenarios")).unwrap()
+}
+
+fn assert_golden(scenario: &Scenario, variant: Variant) {
+    let package_dir =
+        scenario.package_dir(&manifest_dir().join("tests/packages"), variant);
+    let files = scenario.generate(variant);
+
+    if std::env::var_os("GUTENBERG_BLESS").is_some() {
+        fs::create_dir_all(package_dir.join("sources")).unwrap();
+        files
+            .iter()
+            .for_each(|file| file.write_to_file(&package_dir).unwrap());
+    }
+
+    let files: Vec<ContractFile> = files
+        .iter()
+        .map(|file| with_expected_provenance(file, &package_dir))
+        .collect();
+
+    let diffs = diff_files(&files, &package_dir).unwrap();
+    if !diffs.is_empty() {
+        let unified: String = diffs.iter().map(ToString::to_string).collect();
+        panic!(
+            "Scenario {}/{} does not match its expected package, \
+            run with GUTENBERG_BLESS=1 to update it:\n{unified}",
+            variant.dir_name(),
+            scenario.name,
+        );
+    }
+}
+
+/// Replaces the provenance line of the generated file, second line of its
+/// header, with the one of the expected file
+fn with_expected_provenance(
+    file: &ContractFile,
+    package_dir: &Path,
+) -> ContractFile {
+    let content = file.content().to_string();
+    let expected = match fs::read_to_string(package_dir.join(file.path())) {
+        Ok(expected) => expected,
+        Err(_) => return ContractFile::new(file.path().to_path_buf(), content),
+    };
+
+    let provenance = |content: &str| content.lines().nth(1).map(str::to_string);
+
+    let content = match (provenance(&content), provenance(&expected)) {
+        (Some(generated), Some(expected))
+            if generated.starts_with("// template: ") =>
+        {
+            content.replacen(&generated, &expected, 1)
+        }
+        _ => content,
+    };
+
+    ContractFile::new(file.path().to_path_buf(), content)
+}
+
+#[test]
+fn full_scenarios_match_packages() {
+    for scenario in scenarios() {
+        assert_golden(&scenario, Variant::Full);
+    }
+}
+
+#[test]
+fn demo_scenarios_match_packages() {
+    for scenario in scenarios() {
+        assert_golden(&scenario, Variant::Demo);
+    }
+}
+
+#[test]
+fn detects_hand_edits() {
+    let files = scenarios()[0].generate(Variant::Full);
+
+    let dir = std::env::temp_dir()
+        .join(format!("gutenberg-golden-{}", std::process::id()));
+    fs::create_dir_all(dir.join("sources")).unwrap();
+    files
+        .iter()
+        .for_each(|file| file.write_to_file(&dir).unwrap());
+
+    assert!(diff_files(&files, &dir).unwrap().is_empty());
+    assert!(find_hand_edits(&dir).unwrap().is_empty());
+
+    // Edit generated source and add one which is not generated
+    let path = dir.join(files[0].path());
+    let edited = fs::read_to_string(&path).unwrap().replace("CREATOR", "ME");
+    fs::write(&path, edited).unwrap();
+    fs::write(dir.join("sources/extra.move"), "module a::b {}\n").unwrap();
+
+    let diffs = diff_files(&files, &dir).unwrap();
+    let hand_edits = find_hand_edits(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let statuses: Vec<_> = diffs.iter().map(|diff| diff.status).collect();
+    assert_eq!(statuses, [DiffStatus::Modified, DiffStatus::Removed]);
+    assert!(diffs[0].unified.contains("+    const CREATOR"));
+
+    assert_eq!(
+        hand_edits,
+        [
+            PathBuf::from("sources/extra.move"),
+            files[0].path().to_path_buf()
+        ]
+    );
+}
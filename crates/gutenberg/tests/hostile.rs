@@ -0,0 +1,311 @@
+//! Fuzz tests throwing hostile schemas at the code generator
+//!
+//! Every user supplied string must end up inside a Move literal, therefore
+//! the code generated for a hostile schema must have exactly the same tokens,
+//! once literals are masked, as the code generated for a benign schema.
+//! Layout is ignored as long literals may cause lines to break differently.
+use gutenberg::{
+    ast::Stmt, generate_contract_with_schema, literal::MoveType, read_schema,
+    Context, TryMoveInit,
+};
+use gutenberg_types::{
+    models::{
+        address::Address,
+        launchpad::{listing::Listing, market::Market},
+    },
+    Schema,
+};
+use serde_json::json;
+
+/// Characters that are meaningful to either the Move lexer or `format!`
+const HOSTILE_CHARS: &[char] = &[
+    '"', '\\', '{', '}', '(', ')', '<', '>', ';', ',', '/', '*', '\n', '\r',
+    '\t', '\0', '\u{7f}', 'é', '🦀', ' ', 'a', 'b', 'x', '0', '@', ':',
+];
+
+const HOSTILE_STRINGS: &[&str] = &[
+    "",
+    "\"",
+    "\\",
+    "\\\"",
+    "\"), abort 0, (b\"",
+    "\");\n    }\n\n    public fun pwn() {\n        abort 0",
+    "{name}",
+    "{{}}",
+    "// comment",
+    "/* unterminated comment",
+    "\\x41",
+    "x\"00\"",
+    "\u{0}\u{1}\u{1b}[31m",
+    "Ünïcödé",
+];
+
+/// Deterministic xorshift generator so failures are reproducible
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn string(&mut self) -> String {
+        let len = self.next() % 24;
+        (0..len)
+            .map(|_| {
+                HOSTILE_CHARS
+                    [(self.next() % HOSTILE_CHARS.len() as u64) as usize]
+            })
+            .collect()
+    }
+}
+
+fn schema(value: &str) -> Schema {
+    serde_json::from_value(json!({
+        "packageName": "hostile",
+        "collection": {
+            "name": value,
+            "description": value,
+            "symbol": value,
+            "url": value,
+            "tags": ["Art", value],
+            "creators": [
+                "0x61028a4c388514000a7de787c3f7b8ec1eb88d1bd2dbc0d3dfab37078e39630f"
+            ],
+        },
+        "nft": {
+            "typeName": "Joystick",
+            "mintCap": "unlimited",
+            "fields": [["name", "String"], ["url", "Url"]],
+        }
+    }))
+    .unwrap()
+}
+
+fn generate(value: &str) -> String {
+    generate_contract_with_schema(&schema(value))
+        .iter()
+        // Header digest differs between benign and hostile values
+        .map(|file| strip_header(file.content()))
+        .collect()
+}
+
+fn strip_header(content: &str) -> String {
+    content
+        .lines()
+        .skip_while(|line| line.starts_with("//"))
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+/// Replaces every byte string literal with a placeholder, returning the
+/// masked code and the decoded literal values
+fn mask_literals(code: &str) -> (String, Vec<Vec<u8>>) {
+    let mut masked = String::new();
+    let mut literals = Vec::new();
+    let mut chars = code.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        let is_literal = matches!(char, 'b' | 'x')
+            && chars.peek() == Some(&'"')
+            && !masked
+                .ends_with(|c: char| c.is_ascii_alphanumeric() || c == '_');
+
+        if !is_literal {
+            masked.push(char);
+            continue;
+        }
+
+        chars.next();
+        let mut raw = String::new();
+        loop {
+            match chars.next().expect("Unterminated literal") {
+                '"' => break,
+                '\\' => {
+                    assert_eq!(char, 'b', "Escape in hex literal");
+                    raw.push(chars.next().expect("Unterminated escape"));
+                }
+                other => {
+                    assert!(
+                        char == 'x' || (' '..='~').contains(&other),
+                        "Non printable character {other:?} in byte string",
+                    );
+                    raw.push(other);
+                }
+            }
+        }
+
+        let bytes = match char {
+            'x' => (0..raw.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&raw[i..i + 2], 16).unwrap())
+                .collect(),
+            _ => raw.into_bytes(),
+        };
+
+        masked.push_str("LITERAL");
+        literals.push(bytes);
+    }
+
+    (masked, literals)
+}
+
+/// Removes layout which depends on the width of literals
+fn strip_layout(code: &str) -> String {
+    code.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(", )", ")")
+        .replace(", ]", "]")
+        .replace("( ", "(")
+        .replace("[ ", "[")
+        .replace(" )", ")")
+        .replace(" ]", "]")
+}
+
+fn assert_contained(value: &str) {
+    let (benign, _) = mask_literals(&generate("benign"));
+    let (hostile, literals) = mask_literals(&generate(value));
+    let (benign, hostile) = (strip_layout(&benign), strip_layout(&hostile));
+
+    pretty_assertions::assert_eq!(benign, hostile, "Input {value:?} escaped");
+
+    // Description is deunicoded whereas URL is kept verbatim
+    assert!(
+        literals.iter().any(|literal| literal == value.as_bytes()),
+        "Input {value:?} was not preserved"
+    );
+}
+
+#[test]
+fn hostile_strings_stay_in_literals() {
+    for value in HOSTILE_STRINGS {
+        assert_contained(value);
+    }
+}
+
+#[test]
+fn random_strings_stay_in_literals() {
+    let mut rng = Rng(0x5eed_0b1e_c7ed_f00d);
+
+    for _ in 0..500 {
+        assert_contained(&rng.string());
+    }
+}
+
+#[test]
+fn hostile_field_names_are_normalized() {
+    let mut rng = Rng(0x00f1_e1d5);
+
+    for name in ["1st", "🦀", "", "_", "fun", "let", "-", "9 lives"]
+        .into_iter()
+        .map(String::from)
+        .chain((0..200).map(|_| rng.string()))
+    {
+        let schema: Schema = serde_json::from_value(json!({
+            "packageName": "hostile",
+            "nft": {
+                "typeName": "Joystick",
+                "mintCap": "unlimited",
+                "fields": [[name, "String"]],
+            }
+        }))
+        .unwrap();
+
+        let field = schema.nft().fields().keys().next().unwrap().to_string();
+        assert!(
+            gutenberg::literal::is_identifier(&field),
+            "Field {field:?} is not an identifier"
+        );
+    }
+}
+
+#[test]
+fn hostile_market_tokens_are_rejected() {
+    let mut rng = Rng(0x0070_4ee5);
+
+    for token in HOSTILE_STRINGS
+        .iter()
+        .map(|token| format!("sui::sui::SUI{token}"))
+        .chain((0..500).map(|_| format!("sui::sui::SUI{}", rng.string())))
+    {
+        if let Ok(move_type) = token.parse::<MoveType>() {
+            let rendered = move_type.to_string();
+            assert!(
+                rendered.chars().all(|char| char.is_ascii_alphanumeric()
+                    || matches!(char, '_' | ':' | '<' | '>' | ',' | ' ')),
+                "Token {token:?} rendered as {rendered:?}"
+            );
+            assert_eq!(rendered.parse::<MoveType>().unwrap(), move_type);
+        }
+    }
+}
+
+#[test]
+fn hostile_market_token_is_an_error() {
+    let token = "sui::sui::SUI>(&mut listing); abort 0; foo<u8";
+    let address = Address::new("0x2").unwrap();
+    let market = Market::FixedPrice {
+        token: token.to_string(),
+        price: 100,
+        is_whitelisted: false,
+    };
+
+    let schema = schema("benign");
+    let err = Listing::new(address.clone(), address, vec![market])
+        .try_write_move_init(&Context::new(&schema))
+        .unwrap_err();
+    assert!(err.to_string().contains("Invalid market token"));
+
+    // Refused as soon as the configuration is loaded
+    let mut config = serde_json::to_value(&schema).unwrap();
+    config["launchpad"] = json!({
+        "listings": [{
+            "admin": "0x2",
+            "receiver": "0x2",
+            "markets": [{
+                "FixedPrice": {
+                    "token": token,
+                    "price": 100,
+                    "is_whitelisted": false,
+                }
+            }],
+        }],
+    });
+
+    let path = std::env::temp_dir()
+        .join(format!("gutenberg-hostile-{}.json", std::process::id()));
+    std::fs::write(&path, config.to_string()).unwrap();
+    let err = read_schema(&path).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(err.to_string().contains("Invalid market token"));
+}
+
+#[test]
+fn market_token_is_rendered_canonically() {
+    let market = Market::DutchAuction {
+        token: "0x2::sui::SUI".to_string(),
+        reserve_price: 100,
+        is_whitelisted: false,
+    };
+
+    let schema = schema("benign");
+    let code: Vec<String> = market
+        .try_write_move_init(&Context::new(&schema))
+        .unwrap()
+        .iter()
+        .filter_map(|stmt| match stmt {
+            Stmt::Exp(exp) | Stmt::Let { value: exp, .. } => {
+                Some(exp.to_string())
+            }
+            _ => None,
+        })
+        .collect();
+
+    assert!(code
+        .iter()
+        .any(|code| code.contains("create_market_on_listing<0x2::sui::SUI>(")));
+}
@@ -3,12 +3,9 @@
 // TODO:
 // Input name: fail on non alpha-numeric
 // Input creators: non addresses, hexadecimals with wrong lenghts, lack of 0x,
-// empty vectors, etc. Listings > Markets > The token string field is currenly
-// not being validated Schema should not write Move if no mintpolicy is
+// empty vectors, etc. Schema should not write Move if no mintpolicy is
 // selected..
-// Input sanitation from the CLI >> avoid code injection
 // Input creators: empty vectors
-// Listings > Markets > The token string field is currenly not being validated
 
 use gutenberg_types::models::{
     address::Address,
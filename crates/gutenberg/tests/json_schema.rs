@@ -0,0 +1,106 @@
+//! Checks that the JSON Schema exported by `gutenberg-types` accepts exactly
+//! the configurations which deserialize into a `Schema`
+use gutenberg_types::{json_schema, Schema};
+use jsonschema::JSONSchema;
+use serde_json::{json, Value};
+use std::{fs, path::Path};
+
+fn compiled_schema() -> JSONSchema {
+    let schema = serde_json::to_value(json_schema()).unwrap();
+    JSONSchema::compile(&schema).unwrap()
+}
+
+fn assert_valid(schema: &JSONSchema, name: &str, config: &Value) {
+    if let Err(errors) = schema.validate(config) {
+        let errors: Vec<_> = errors.map(|err| err.to_string()).collect();
+        panic!("{name} does not match JSON Schema: {errors:#?}");
+    }
+}
+
+/// Minimal configuration with `nft` replaced by the given fields
+fn with_nft(nft: Value) -> Value {
+    let mut config = json!({
+        "packageName": "gnomes",
+        "nft": { "typeName": "Gnome", "mintCap": "unlimited" }
+    });
+    config["nft"]
+        .as_object_mut()
+        .unwrap()
+        .extend(nft.as_object().unwrap().clone());
+    config
+}
+
+#[test]
+fn template_matches_schema() {
+    let template: Value =
+        serde_json::from_str(include_str!("./../template.json")).unwrap();
+    assert_valid(&compiled_schema(), "template.json", &template);
+}
+
+#[test]
+fn scenarios_match_schema() {
+    let schema = compiled_schema();
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scenarios");
+
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let config: Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_valid(&schema, &path.display().to_string(), &config);
+    }
+}
+
+/// Hand-written `Deserialize` implementations must agree with the schema
+#[test]
+fn schema_agrees_with_deserialize() {
+    let schema = compiled_schema();
+
+    let cases = [
+        with_nft(json!({ "mintCap": 100 })),
+        with_nft(json!({ "mintCap": "unlimited" })),
+        with_nft(json!({ "mintCap": "limited" })),
+        with_nft(json!({ "mintCap": -1 })),
+        with_nft(json!({ "fields": [["name", "String"], ["url", "Url"]] })),
+        with_nft(json!({ "fields": [["name"]] })),
+        with_nft(json!({ "fields": [["name", "String", "Url"]] })),
+        with_nft(json!({ "fields": [["name", "Number"]] })),
+        with_nft(json!({ "burn": "permissionless", "orderbook": "protected" })),
+        with_nft(json!({ "burn": "Permissionless" })),
+        json!({
+            "$schema": "./gutenberg.schema.json",
+            "packageName": "gnomes",
+            "collection": {
+                "tags": ["Art", "Custom"],
+                "supply": { "enforced": 10 },
+                "creators": ["0x1", "1a4f2b04e99311b0ff8228cf12735402f6618d7be0f0b320364339baf03e49df"]
+            },
+            "nft": { "typeName": "Gnome", "mintCap": 1 }
+        }),
+        json!({
+            "packageName": "gnomes",
+            "collection": { "creators": ["0xg"] },
+            "nft": { "typeName": "Gnome", "mintCap": 1 }
+        }),
+        json!({
+            "packageName": "gnomes",
+            "collection": { "tags": [1] },
+            "nft": { "typeName": "Gnome", "mintCap": 1 }
+        }),
+        json!({
+            "packageName": "gnomes",
+            "collection": { "supply": "enforced" },
+            "nft": { "typeName": "Gnome", "mintCap": 1 }
+        }),
+        json!({ "packageName": "gnomes" }),
+    ];
+
+    for config in cases {
+        let deserializes = serde_json::from_value::<Schema>(config.clone());
+        assert_eq!(
+            schema.is_valid(&config),
+            deserializes.is_ok(),
+            "JSON Schema and `Deserialize` disagree on {config:#}: {:?}",
+            deserializes.err(),
+        );
+    }
+}
@@ -1,3 +1,6 @@
+// @generated by Gutenberg, manual changes will be overwritten
+// template: 0.4.0, NftProtocol: 1.0.0, schema: 4055bdc3819f2043
+// digest: 06ddbbc73ec74a8c
 module burn_permissioned::joystick {
     /// One time witness is only instantiated in the init method
     struct JOYSTICK has drop {}
@@ -24,7 +27,10 @@ module burn_permissioned::joystick {
         sui::transfer::public_share_object(collection);
 
         let mint_cap = nft_protocol::mint_cap::new_limited<JOYSTICK, Joystick>(
-            &witness, collection_id, 100, ctx
+            &witness,
+            collection_id,
+            100,
+            ctx,
         );
         sui::transfer::public_transfer(mint_cap, sui::tx_context::sender(ctx));
 
@@ -32,9 +38,21 @@ module burn_permissioned::joystick {
 
         let display = sui::display::new<Joystick>(&publisher, ctx);
         sui::display::add(&mut display, std::string::utf8(b"name"), std::string::utf8(b"{name}"));
-        sui::display::add(&mut display, std::string::utf8(b"description"), std::string::utf8(b"{description}"));
-        sui::display::add(&mut display, std::string::utf8(b"image_url"), std::string::utf8(b"{url}"));
-        sui::display::add(&mut display, std::string::utf8(b"attributes"), std::string::utf8(b"{attributes}"));
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"description"),
+            std::string::utf8(b"{description}"),
+        );
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"image_url"),
+            std::string::utf8(b"{url}"),
+        );
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"attributes"),
+            std::string::utf8(b"{attributes}"),
+        );
         sui::display::update_version(&mut display);
 
         sui::transfer::public_transfer(display, sui::tx_context::sender(ctx));
@@ -52,15 +70,7 @@ module burn_permissioned::joystick {
         receiver: &mut sui::kiosk::Kiosk,
         ctx: &mut sui::tx_context::TxContext,
     ) {
-        let nft = mint(
-            name,
-            description,
-            url,
-            attributes_keys,
-            attributes_values,
-            mint_cap,
-            ctx,
-        );
+        let nft = mint(name, description, url, attributes_keys, attributes_values, mint_cap, ctx);
 
         ob_kiosk::ob_kiosk::deposit(receiver, nft, ctx);
     }
@@ -75,15 +85,7 @@ module burn_permissioned::joystick {
         receiver: address,
         ctx: &mut sui::tx_context::TxContext,
     ) {
-        let nft = mint(
-            name,
-            description,
-            url,
-            attributes_keys,
-            attributes_values,
-            mint_cap,
-            ctx,
-        );
+        let nft = mint(name, description, url, attributes_keys, attributes_values, mint_cap, ctx);
 
         let (kiosk, _) = ob_kiosk::ob_kiosk::new_for_address(receiver, ctx);
         ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, ctx);
@@ -126,16 +128,20 @@ module burn_permissioned::joystick {
     #[test]
     fun it_inits_collection() {
         let scenario = sui::test_scenario::begin(CREATOR);
-
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-        assert!(sui::test_scenario::has_most_recent_shared<nft_protocol::collection::Collection<Joystick>>(), 0);
-
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &scenario, CREATOR,
+        assert!(
+            sui::test_scenario::has_most_recent_shared<nft_protocol::collection::Collection<Joystick>>(),
+            0,
         );
 
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
+
         sui::test_scenario::return_to_address(CREATOR, mint_cap);
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
@@ -146,13 +152,13 @@ module burn_permissioned::joystick {
     fun it_mints_nft_airdrop() {
         let scenario = sui::test_scenario::begin(CREATOR);
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
-
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &scenario,
-            CREATOR,
-        );
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
 
         let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
 
@@ -164,7 +170,7 @@ module burn_permissioned::joystick {
             vector[std::ascii::string(b"attribute")],
             &mut mint_cap,
             &mut kiosk,
-            sui::test_scenario::ctx(&mut scenario)
+            sui::test_scenario::ctx(&mut scenario),
         );
 
         sui::transfer::public_share_object(kiosk);
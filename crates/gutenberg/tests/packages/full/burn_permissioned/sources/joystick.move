@@ -1,3 +1,6 @@
+// @generated by Gutenberg, manual changes will be overwritten
+// template: 0.4.0, NftProtocol: 1.0.0, schema: 5195d8da45927453
+// digest: fadd52a5fb0bd829
 module burn_permissioned::joystick {
     /// One time witness is only instantiated in the init method
     struct JOYSTICK has drop {}
@@ -20,7 +23,9 @@ module burn_permissioned::joystick {
         sui::transfer::public_share_object(collection);
 
         let mint_cap = nft_protocol::mint_cap::new_unlimited<JOYSTICK, Joystick>(
-            &witness, collection_id, ctx
+            &witness,
+            collection_id,
+            ctx,
         );
         sui::transfer::public_transfer(mint_cap, sui::tx_context::sender(ctx));
 
@@ -28,18 +33,30 @@ module burn_permissioned::joystick {
 
         let display = sui::display::new<Joystick>(&publisher, ctx);
         sui::display::add(&mut display, std::string::utf8(b"name"), std::string::utf8(b"{name}"));
-        sui::display::add(&mut display, std::string::utf8(b"description"), std::string::utf8(b"{description}"));
-        sui::display::add(&mut display, std::string::utf8(b"image_url"), std::string::utf8(b"{url}"));
-        sui::display::add(&mut display, std::string::utf8(b"attributes"), std::string::utf8(b"{attributes}"));
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"description"),
+            std::string::utf8(b"{description}"),
+        );
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"image_url"),
+            std::string::utf8(b"{url}"),
+        );
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"attributes"),
+            std::string::utf8(b"{attributes}"),
+        );
         sui::display::update_version(&mut display);
 
         sui::transfer::public_transfer(display, sui::tx_context::sender(ctx));
 
-        let (withdraw_policy, withdraw_policy_cap) = ob_request::withdraw_request::init_policy<Joystick>(
-            &publisher, ctx,
-        );
+        let (withdraw_policy, withdraw_policy_cap) =
+            ob_request::withdraw_request::init_policy<Joystick>(&publisher, ctx);
         ob_request::request::enforce_rule_no_state<ob_request::request::WithNft<Joystick, ob_request::withdraw_request::WITHDRAW_REQ>, Witness>(
-            &mut withdraw_policy, &withdraw_policy_cap,
+            &mut withdraw_policy,
+            &withdraw_policy_cap,
         );
 
         sui::transfer::public_transfer(publisher, sui::tx_context::sender(ctx));
@@ -53,10 +70,7 @@ module burn_permissioned::joystick {
         receiver: &mut sui::kiosk::Kiosk,
         ctx: &mut sui::tx_context::TxContext,
     ) {
-        let nft = mint(
-            mint_cap,
-            ctx,
-        );
+        let nft = mint(mint_cap, ctx);
 
         ob_kiosk::ob_kiosk::deposit(receiver, nft, ctx);
     }
@@ -66,10 +80,7 @@ module burn_permissioned::joystick {
         receiver: address,
         ctx: &mut sui::tx_context::TxContext,
     ) {
-        let nft = mint(
-            mint_cap,
-            ctx,
-        );
+        let nft = mint(mint_cap, ctx);
 
         let (kiosk, _) = ob_kiosk::ob_kiosk::new_for_address(receiver, ctx);
         ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, ctx);
@@ -82,9 +93,7 @@ module burn_permissioned::joystick {
     ): Joystick {
         let delegated_witness = ob_permissions::witness::from_witness(Witness {});
 
-        let nft = Joystick {
-            id: sui::object::new(ctx),
-        };
+        let nft = Joystick { id: sui::object::new(ctx) };
 
         nft_protocol::mint_event::emit_mint(
             delegated_witness,
@@ -140,16 +149,20 @@ module burn_permissioned::joystick {
     #[test]
     fun it_inits_collection() {
         let scenario = sui::test_scenario::begin(CREATOR);
-
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-        assert!(sui::test_scenario::has_most_recent_shared<nft_protocol::collection::Collection<Joystick>>(), 0);
-
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &scenario, CREATOR,
+        assert!(
+            sui::test_scenario::has_most_recent_shared<nft_protocol::collection::Collection<Joystick>>(),
+            0,
         );
 
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
+
         sui::test_scenario::return_to_address(CREATOR, mint_cap);
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
@@ -160,77 +173,70 @@ module burn_permissioned::joystick {
     fun it_mints_nft_airdrop() {
         let scenario = sui::test_scenario::begin(CREATOR);
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
-
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &scenario,
-            CREATOR,
-        );
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
 
         let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
 
-        mint_nft_to_kiosk(
-            &mut mint_cap,
-            &mut kiosk,
-            sui::test_scenario::ctx(&mut scenario)
-        );
+        mint_nft_to_kiosk(&mut mint_cap, &mut kiosk, sui::test_scenario::ctx(&mut scenario));
 
         sui::transfer::public_share_object(kiosk);
         sui::test_scenario::return_to_address(CREATOR, mint_cap);
         sui::test_scenario::end(scenario);
     }
 
-        #[test]
-        fun it_burns_nft() {
-            let scenario = sui::test_scenario::begin(CREATOR);
-            init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
-
-            sui::test_scenario::next_tx(&mut scenario, CREATOR);
+    #[test]
+    fun it_burns_nft() {
+        let scenario = sui::test_scenario::begin(CREATOR);
+        init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
+        sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-            let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
                 &scenario,
                 CREATOR,
             );
 
-            let publisher = sui::test_scenario::take_from_address<sui::package::Publisher>(
+        let publisher = sui::test_scenario::take_from_address<sui::package::Publisher>(
+            &scenario,
+            CREATOR,
+        );
+
+        let collection =
+            sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
                 &scenario,
-                CREATOR,
             );
 
-            let collection = sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
-                &scenario
+        let withdraw_policy =
+            sui::test_scenario::take_shared<ob_request::request::Policy<ob_request::request::WithNft<Joystick, ob_request::withdraw_request::WITHDRAW_REQ>>>(
+                &scenario,
             );
 
-            let withdraw_policy = sui::test_scenario::take_shared<
-                ob_request::request::Policy<
-                    ob_request::request::WithNft<Joystick, ob_request::withdraw_request::WITHDRAW_REQ>
-                >
-            >(&scenario);
+        let nft = mint(&mut mint_cap, sui::test_scenario::ctx(&mut scenario));
+        let nft_id = sui::object::id(&nft);
 
-            let nft = mint(
-                &mut mint_cap,
-                sui::test_scenario::ctx(&mut scenario)
-            );
-            let nft_id = sui::object::id(&nft);
-
-            let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
-            ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, sui::test_scenario::ctx(&mut scenario));
-
-            burn_nft_in_kiosk(
-                ob_permissions::witness::from_witness(Witness {}),
-                &collection,
-                &mut kiosk,
-                nft_id,
-                &withdraw_policy,
-                sui::test_scenario::ctx(&mut scenario)
-            );
+        let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
+        ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, sui::test_scenario::ctx(&mut scenario));
 
-            sui::test_scenario::return_to_address(CREATOR, mint_cap);
-            sui::test_scenario::return_to_address(CREATOR, publisher);
-            sui::test_scenario::return_shared(collection);
-            sui::test_scenario::return_shared(withdraw_policy);
-            sui::transfer::public_share_object(kiosk);
-            sui::test_scenario::end(scenario);
-        }
+        burn_nft_in_kiosk(
+            ob_permissions::witness::from_witness(Witness {}),
+            &collection,
+            &mut kiosk,
+            nft_id,
+            &withdraw_policy,
+            sui::test_scenario::ctx(&mut scenario),
+        );
+
+        sui::test_scenario::return_to_address(CREATOR, mint_cap);
+        sui::test_scenario::return_to_address(CREATOR, publisher);
+        sui::test_scenario::return_shared(collection);
+        sui::test_scenario::return_shared(withdraw_policy);
+        sui::transfer::public_share_object(kiosk);
+        sui::test_scenario::end(scenario);
+    }
 }
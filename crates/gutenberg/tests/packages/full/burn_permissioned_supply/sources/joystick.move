@@ -1,3 +1,6 @@
+// @generated by Gutenberg, manual changes will be overwritten
+// template: 0.4.0, NftProtocol: 1.0.0, schema: 4817f6f0754b1926
+// digest: d55d0149845b476c
 module burn_permissioned_supply::joystick {
     /// One time witness is only instantiated in the init method
     struct JOYSTICK has drop {}
@@ -20,15 +23,15 @@ module burn_permissioned_supply::joystick {
         nft_protocol::collection::add_domain(
             delegated_witness,
             &mut collection,
-            nft_protocol::supply::new(
-                delegated_witness, 600, false,
-            )
+            nft_protocol::supply::new(delegated_witness, 600, false),
         );
 
         sui::transfer::public_share_object(collection);
 
         let mint_cap = nft_protocol::mint_cap::new_unlimited<JOYSTICK, Joystick>(
-            &witness, collection_id, ctx
+            &witness,
+            collection_id,
+            ctx,
         );
         sui::transfer::public_transfer(mint_cap, sui::tx_context::sender(ctx));
 
@@ -36,18 +39,30 @@ module burn_permissioned_supply::joystick {
 
         let display = sui::display::new<Joystick>(&publisher, ctx);
         sui::display::add(&mut display, std::string::utf8(b"name"), std::string::utf8(b"{name}"));
-        sui::display::add(&mut display, std::string::utf8(b"description"), std::string::utf8(b"{description}"));
-        sui::display::add(&mut display, std::string::utf8(b"image_url"), std::string::utf8(b"{url}"));
-        sui::display::add(&mut display, std::string::utf8(b"attributes"), std::string::utf8(b"{attributes}"));
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"description"),
+            std::string::utf8(b"{description}"),
+        );
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"image_url"),
+            std::string::utf8(b"{url}"),
+        );
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"attributes"),
+            std::string::utf8(b"{attributes}"),
+        );
         sui::display::update_version(&mut display);
 
         sui::transfer::public_transfer(display, sui::tx_context::sender(ctx));
 
-        let (withdraw_policy, withdraw_policy_cap) = ob_request::withdraw_request::init_policy<Joystick>(
-            &publisher, ctx,
-        );
+        let (withdraw_policy, withdraw_policy_cap) =
+            ob_request::withdraw_request::init_policy<Joystick>(&publisher, ctx);
         ob_request::request::enforce_rule_no_state<ob_request::request::WithNft<Joystick, ob_request::withdraw_request::WITHDRAW_REQ>, Witness>(
-            &mut withdraw_policy, &withdraw_policy_cap,
+            &mut withdraw_policy,
+            &withdraw_policy_cap,
         );
 
         sui::transfer::public_transfer(publisher, sui::tx_context::sender(ctx));
@@ -62,11 +77,7 @@ module burn_permissioned_supply::joystick {
         receiver: &mut sui::kiosk::Kiosk,
         ctx: &mut sui::tx_context::TxContext,
     ) {
-        let nft = mint(
-            mint_cap,
-            collection,
-            ctx,
-        );
+        let nft = mint(mint_cap, collection, ctx);
 
         ob_kiosk::ob_kiosk::deposit(receiver, nft, ctx);
     }
@@ -77,11 +88,7 @@ module burn_permissioned_supply::joystick {
         receiver: address,
         ctx: &mut sui::tx_context::TxContext,
     ) {
-        let nft = mint(
-            mint_cap,
-            collection,
-            ctx,
-        );
+        let nft = mint(mint_cap, collection, ctx);
 
         let (kiosk, _) = ob_kiosk::ob_kiosk::new_for_address(receiver, ctx);
         ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, ctx);
@@ -95,9 +102,7 @@ module burn_permissioned_supply::joystick {
     ): Joystick {
         let delegated_witness = ob_permissions::witness::from_witness(Witness {});
 
-        let nft = Joystick {
-            id: sui::object::new(ctx),
-        };
+        let nft = Joystick { id: sui::object::new(ctx) };
 
         nft_protocol::mint_event::emit_mint(
             delegated_witness,
@@ -166,16 +171,20 @@ module burn_permissioned_supply::joystick {
     #[test]
     fun it_inits_collection() {
         let scenario = sui::test_scenario::begin(CREATOR);
-
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-        assert!(sui::test_scenario::has_most_recent_shared<nft_protocol::collection::Collection<Joystick>>(), 0);
-
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &scenario, CREATOR,
+        assert!(
+            sui::test_scenario::has_most_recent_shared<nft_protocol::collection::Collection<Joystick>>(),
+            0,
         );
 
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
+
         sui::test_scenario::return_to_address(CREATOR, mint_cap);
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
@@ -186,17 +195,18 @@ module burn_permissioned_supply::joystick {
     fun it_mints_nft_airdrop() {
         let scenario = sui::test_scenario::begin(CREATOR);
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
-
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &scenario,
-            CREATOR,
-        );
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
 
-        let collection = sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
-            &scenario,
-        );
+        let collection =
+            sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
+                &scenario,
+            );
 
         let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
 
@@ -204,7 +214,7 @@ module burn_permissioned_supply::joystick {
             &mut mint_cap,
             &mut collection,
             &mut kiosk,
-            sui::test_scenario::ctx(&mut scenario)
+            sui::test_scenario::ctx(&mut scenario),
         );
 
         sui::transfer::public_share_object(kiosk);
@@ -213,57 +223,53 @@ module burn_permissioned_supply::joystick {
         sui::test_scenario::end(scenario);
     }
 
-        #[test]
-        fun it_burns_nft() {
-            let scenario = sui::test_scenario::begin(CREATOR);
-            init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
-
-            sui::test_scenario::next_tx(&mut scenario, CREATOR);
+    #[test]
+    fun it_burns_nft() {
+        let scenario = sui::test_scenario::begin(CREATOR);
+        init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
+        sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-            let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
                 &scenario,
                 CREATOR,
             );
 
-            let publisher = sui::test_scenario::take_from_address<sui::package::Publisher>(
+        let publisher = sui::test_scenario::take_from_address<sui::package::Publisher>(
+            &scenario,
+            CREATOR,
+        );
+
+        let collection =
+            sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
                 &scenario,
-                CREATOR,
             );
 
-            let collection = sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
-                &scenario
+        let withdraw_policy =
+            sui::test_scenario::take_shared<ob_request::request::Policy<ob_request::request::WithNft<Joystick, ob_request::withdraw_request::WITHDRAW_REQ>>>(
+                &scenario,
             );
 
-            let withdraw_policy = sui::test_scenario::take_shared<
-                ob_request::request::Policy<
-                    ob_request::request::WithNft<Joystick, ob_request::withdraw_request::WITHDRAW_REQ>
-                >
-            >(&scenario);
+        let nft = mint(&mut mint_cap, &mut collection, sui::test_scenario::ctx(&mut scenario));
+        let nft_id = sui::object::id(&nft);
+
+        let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
+        ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, sui::test_scenario::ctx(&mut scenario));
 
-            let nft = mint(
-                &mut mint_cap,
+        burn_nft_in_kiosk(
+            ob_permissions::witness::from_witness(Witness {}),
             &mut collection,
-                sui::test_scenario::ctx(&mut scenario)
-            );
-            let nft_id = sui::object::id(&nft);
-
-            let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
-            ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, sui::test_scenario::ctx(&mut scenario));
-
-            burn_nft_in_kiosk(
-                ob_permissions::witness::from_witness(Witness {}),
-                &mut collection,
-                &mut kiosk,
-                nft_id,
-                &withdraw_policy,
-                sui::test_scenario::ctx(&mut scenario)
-            );
+            &mut kiosk,
+            nft_id,
+            &withdraw_policy,
+            sui::test_scenario::ctx(&mut scenario),
+        );
 
-            sui::test_scenario::return_to_address(CREATOR, mint_cap);
-            sui::test_scenario::return_to_address(CREATOR, publisher);
-            sui::test_scenario::return_shared(collection);
-            sui::test_scenario::return_shared(withdraw_policy);
-            sui::transfer::public_share_object(kiosk);
-            sui::test_scenario::end(scenario);
-        }
+        sui::test_scenario::return_to_address(CREATOR, mint_cap);
+        sui::test_scenario::return_to_address(CREATOR, publisher);
+        sui::test_scenario::return_shared(collection);
+        sui::test_scenario::return_shared(withdraw_policy);
+        sui::transfer::public_share_object(kiosk);
+        sui::test_scenario::end(scenario);
+    }
 }
@@ -1,3 +1,6 @@
+// @generated by Gutenberg, manual changes will be overwritten
+// template: 0.4.0, NftProtocol: 1.0.0, schema: c3d2f9c613a17a2a
+// digest: 4bb97bcd3d3c9bdf
 module joysticks::joystick {
     /// One time witness is only instantiated in the init method
     struct JOYSTICK has drop {}
@@ -22,7 +25,10 @@ module joysticks::joystick {
         let collection_id = sui::object::id(&collection);
 
         let creators = sui::vec_set::empty();
-        sui::vec_set::insert(&mut creators, @0x61028a4c388514000a7de787c3f7b8ec1eb88d1bd2dbc0d3dfab37078e39630f);
+        sui::vec_set::insert(
+            &mut creators,
+            @0x61028a4c388514000a7de787c3f7b8ec1eb88d1bd2dbc0d3dfab37078e39630f,
+        );
 
         nft_protocol::collection::add_domain(
             delegated_witness,
@@ -54,14 +60,20 @@ module joysticks::joystick {
         nft_protocol::collection::add_domain(
             delegated_witness,
             &mut collection,
-            nft_protocol::supply::new(
-                delegated_witness, 600, false,
-            )
+            nft_protocol::supply::new(delegated_witness, 600, false),
         );
 
         let royalty_map = sui::vec_map::empty();
-        sui::vec_map::insert(&mut royalty_map, @0x61028a4c388514000a7de787c3f7b8ec1eb88d1bd2dbc0d3dfab37078e39630f, 500);
-        sui::vec_map::insert(&mut royalty_map, @0x8212bb78cc5c42f95766107573147d79b0954fe06e52f54f27e26677b43c88f5, 9500);
+        sui::vec_map::insert(
+            &mut royalty_map,
+            @0x61028a4c388514000a7de787c3f7b8ec1eb88d1bd2dbc0d3dfab37078e39630f,
+            500,
+        );
+        sui::vec_map::insert(
+            &mut royalty_map,
+            @0x8212bb78cc5c42f95766107573147d79b0954fe06e52f54f27e26677b43c88f5,
+            9500,
+        );
 
         nft_protocol::royalty_strategy_bps::create_domain_and_add_strategy(
             delegated_witness,
@@ -74,7 +86,10 @@ module joysticks::joystick {
         sui::transfer::public_share_object(collection);
 
         let mint_cap = nft_protocol::mint_cap::new_limited<JOYSTICK, Joystick>(
-            &witness, collection_id, 600, ctx
+            &witness,
+            collection_id,
+            600,
+            ctx,
         );
         sui::transfer::public_transfer(mint_cap, sui::tx_context::sender(ctx));
 
@@ -82,39 +97,59 @@ module joysticks::joystick {
 
         let display = sui::display::new<Joystick>(&publisher, ctx);
         sui::display::add(&mut display, std::string::utf8(b"name"), std::string::utf8(b"{name}"));
-        sui::display::add(&mut display, std::string::utf8(b"description"), std::string::utf8(b"{description}"));
-        sui::display::add(&mut display, std::string::utf8(b"image_url"), std::string::utf8(b"{url}"));
-        sui::display::add(&mut display, std::string::utf8(b"attributes"), std::string::utf8(b"{attributes}"));
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"description"),
+            std::string::utf8(b"{description}"),
+        );
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"image_url"),
+            std::string::utf8(b"{url}"),
+        );
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"attributes"),
+            std::string::utf8(b"{attributes}"),
+        );
 
         let tags = std::vector::empty();
         std::vector::push_back(&mut tags, std::string::utf8(b"Gaming"));
 
-        sui::display::add(&mut display, std::string::utf8(b"tags"), ob_utils::display::from_vec(tags));
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"tags"),
+            ob_utils::display::from_vec(tags),
+        );
         sui::display::update_version(&mut display);
 
         sui::transfer::public_transfer(display, sui::tx_context::sender(ctx));
 
-        let (transfer_policy, transfer_policy_cap) = ob_request::transfer_request::init_policy<Joystick>(
-            &publisher, ctx,
-        );
+        let (transfer_policy, transfer_policy_cap) =
+            ob_request::transfer_request::init_policy<Joystick>(&publisher, ctx);
         nft_protocol::royalty_strategy_bps::enforce(&mut transfer_policy, &transfer_policy_cap);
         nft_protocol::transfer_allowlist::enforce(&mut transfer_policy, &transfer_policy_cap);
 
         let (borrow_policy, borrow_policy_cap) = ob_request::borrow_request::init_policy<Joystick>(
-            &publisher, ctx,
+            &publisher,
+            ctx,
         );
 
-        let (withdraw_policy, withdraw_policy_cap) = ob_request::withdraw_request::init_policy<Joystick>(
-            &publisher, ctx,
-        );
+        let (withdraw_policy, withdraw_policy_cap) =
+            ob_request::withdraw_request::init_policy<Joystick>(&publisher, ctx);
         ob_request::request::enforce_rule_no_state<ob_request::request::WithNft<Joystick, ob_request::withdraw_request::WITHDRAW_REQ>, Witness>(
-            &mut withdraw_policy, &withdraw_policy_cap,
+            &mut withdraw_policy,
+            &withdraw_policy_cap,
         );
 
         // Protected orderbook such that trading is not initially possible
-        let orderbook = liquidity_layer_v1::orderbook::new_with_protected_actions<Joystick, sui::sui::SUI>(
-            delegated_witness, &transfer_policy, liquidity_layer_v1::orderbook::custom_protection(true, true, true), ctx,
-        );
+        let orderbook =
+            liquidity_layer_v1::orderbook::new_with_protected_actions<Joystick, sui::sui::SUI>(
+                delegated_witness,
+                &transfer_policy,
+                liquidity_layer_v1::orderbook::custom_protection(true, true, true),
+                ctx,
+            );
         liquidity_layer_v1::orderbook::share(orderbook);
 
         sui::transfer::public_transfer(publisher, sui::tx_context::sender(ctx));
@@ -268,9 +303,17 @@ module joysticks::joystick {
         policy: &ob_request::request::Policy<ob_request::request::WithNft<Joystick, ob_request::borrow_request::BORROW_REQ>>,
         ctx: &mut sui::tx_context::TxContext,
     ) {
-        let borrow = ob_kiosk::ob_kiosk::borrow_nft_mut<Joystick>(kiosk, nft_id, std::option::none(), ctx);
+        let borrow = ob_kiosk::ob_kiosk::borrow_nft_mut<Joystick>(
+            kiosk,
+            nft_id,
+            std::option::none(),
+            ctx,
+        );
 
-        let nft: &mut Joystick = ob_request::borrow_request::borrow_nft_ref_mut(delegated_witness, &mut borrow);
+        let nft: &mut Joystick = ob_request::borrow_request::borrow_nft_ref_mut(
+            delegated_witness,
+            &mut borrow,
+        );
         set_name(delegated_witness, nft, name);
 
         ob_kiosk::ob_kiosk::return_nft<Witness, Joystick>(kiosk, borrow, policy);
@@ -313,9 +356,17 @@ module joysticks::joystick {
         policy: &ob_request::request::Policy<ob_request::request::WithNft<Joystick, ob_request::borrow_request::BORROW_REQ>>,
         ctx: &mut sui::tx_context::TxContext,
     ) {
-        let borrow = ob_kiosk::ob_kiosk::borrow_nft_mut<Joystick>(kiosk, nft_id, std::option::none(), ctx);
+        let borrow = ob_kiosk::ob_kiosk::borrow_nft_mut<Joystick>(
+            kiosk,
+            nft_id,
+            std::option::none(),
+            ctx,
+        );
 
-        let nft: &mut Joystick = ob_request::borrow_request::borrow_nft_ref_mut(delegated_witness, &mut borrow);
+        let nft: &mut Joystick = ob_request::borrow_request::borrow_nft_ref_mut(
+            delegated_witness,
+            &mut borrow,
+        );
         set_description(delegated_witness, nft, description);
 
         ob_kiosk::ob_kiosk::return_nft<Witness, Joystick>(kiosk, borrow, policy);
@@ -358,9 +409,17 @@ module joysticks::joystick {
         policy: &ob_request::request::Policy<ob_request::request::WithNft<Joystick, ob_request::borrow_request::BORROW_REQ>>,
         ctx: &mut sui::tx_context::TxContext,
     ) {
-        let borrow = ob_kiosk::ob_kiosk::borrow_nft_mut<Joystick>(kiosk, nft_id, std::option::none(), ctx);
+        let borrow = ob_kiosk::ob_kiosk::borrow_nft_mut<Joystick>(
+            kiosk,
+            nft_id,
+            std::option::none(),
+            ctx,
+        );
 
-        let nft: &mut Joystick = ob_request::borrow_request::borrow_nft_ref_mut(delegated_witness, &mut borrow);
+        let nft: &mut Joystick = ob_request::borrow_request::borrow_nft_ref_mut(
+            delegated_witness,
+            &mut borrow,
+        );
         set_url(delegated_witness, nft, url);
 
         ob_kiosk::ob_kiosk::return_nft<Witness, Joystick>(kiosk, borrow, policy);
@@ -406,9 +465,17 @@ module joysticks::joystick {
         policy: &ob_request::request::Policy<ob_request::request::WithNft<Joystick, ob_request::borrow_request::BORROW_REQ>>,
         ctx: &mut sui::tx_context::TxContext,
     ) {
-        let borrow = ob_kiosk::ob_kiosk::borrow_nft_mut<Joystick>(kiosk, nft_id, std::option::none(), ctx);
+        let borrow = ob_kiosk::ob_kiosk::borrow_nft_mut<Joystick>(
+            kiosk,
+            nft_id,
+            std::option::none(),
+            ctx,
+        );
 
-        let nft: &mut Joystick = ob_request::borrow_request::borrow_nft_ref_mut(delegated_witness, &mut borrow);
+        let nft: &mut Joystick = ob_request::borrow_request::borrow_nft_ref_mut(
+            delegated_witness,
+            &mut borrow,
+        );
         set_attributes(delegated_witness, nft, attributes_keys, attributes_values);
 
         ob_kiosk::ob_kiosk::return_nft<Witness, Joystick>(kiosk, borrow, policy);
@@ -424,7 +491,15 @@ module joysticks::joystick {
         ctx: &mut sui::tx_context::TxContext,
     ) {
         let delegated_witness = ob_permissions::witness::from_publisher(publisher);
-        set_attributes_in_kiosk(delegated_witness, kiosk, nft_id, attributes_keys, attributes_values, policy, ctx);
+        set_attributes_in_kiosk(
+            delegated_witness,
+            kiosk,
+            nft_id,
+            attributes_keys,
+            attributes_values,
+            policy,
+            ctx,
+        );
     }
 
     public fun burn_nft(
@@ -475,7 +550,12 @@ module joysticks::joystick {
         nft_id: sui::object::ID,
         ctx: &mut sui::tx_context::TxContext,
     ) {
-        let nft = ob_launchpad::listing::admin_redeem_nft_with_id(listing, inventory_id, nft_id, ctx);
+        let nft = ob_launchpad::listing::admin_redeem_nft_with_id(
+            listing,
+            inventory_id,
+            nft_id,
+            ctx,
+        );
         burn_nft(collection, nft);
     }
 
@@ -485,16 +565,20 @@ module joysticks::joystick {
     #[test]
     fun it_inits_collection() {
         let scenario = sui::test_scenario::begin(CREATOR);
-
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-        assert!(sui::test_scenario::has_most_recent_shared<nft_protocol::collection::Collection<Joystick>>(), 0);
-
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &scenario, CREATOR,
+        assert!(
+            sui::test_scenario::has_most_recent_shared<nft_protocol::collection::Collection<Joystick>>(),
+            0,
         );
 
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
+
         sui::test_scenario::return_to_address(CREATOR, mint_cap);
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
@@ -505,17 +589,18 @@ module joysticks::joystick {
     fun it_mints_nft_airdrop() {
         let scenario = sui::test_scenario::begin(CREATOR);
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
-
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &scenario,
-            CREATOR,
-        );
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
 
-        let collection = sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
-            &scenario,
-        );
+        let collection =
+            sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
+                &scenario,
+            );
 
         let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
 
@@ -528,7 +613,7 @@ module joysticks::joystick {
             &mut mint_cap,
             &mut collection,
             &mut kiosk,
-            sui::test_scenario::ctx(&mut scenario)
+            sui::test_scenario::ctx(&mut scenario),
         );
 
         sui::transfer::public_share_object(kiosk);
@@ -541,19 +626,22 @@ module joysticks::joystick {
     fun it_mints_nft_launchpad() {
         let scenario = sui::test_scenario::begin(CREATOR);
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
-
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &scenario,
-            CREATOR,
-        );
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
 
-        let collection = sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
-            &scenario,
-        );
+        let collection =
+            sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
+                &scenario,
+            );
 
-        let warehouse = ob_launchpad::warehouse::new<Joystick>(sui::test_scenario::ctx(&mut scenario));
+        let warehouse = ob_launchpad::warehouse::new<Joystick>(
+            sui::test_scenario::ctx(&mut scenario),
+        );
 
         mint_nft_to_warehouse(
             std::string::utf8(b"TEST STRING"),
@@ -564,7 +652,7 @@ module joysticks::joystick {
             &mut mint_cap,
             &mut collection,
             &mut warehouse,
-            sui::test_scenario::ctx(&mut scenario)
+            sui::test_scenario::ctx(&mut scenario),
         );
 
         sui::transfer::public_transfer(warehouse, CREATOR);
@@ -577,25 +665,28 @@ module joysticks::joystick {
     fun it_sets_metadata() {
         let scenario = sui::test_scenario::begin(CREATOR);
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
-
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &scenario,
-            CREATOR,
-        );
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
 
-        let collection = sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
-            &scenario,
-        );
+        let collection =
+            sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
+                &scenario,
+            );
 
         let publisher = sui::test_scenario::take_from_address<sui::package::Publisher>(
             &scenario,
             CREATOR,
         );
 
-        let borrow_policy: ob_request::request::Policy<ob_request::request::WithNft<Joystick, ob_request::borrow_request::BORROW_REQ>> =
-            sui::test_scenario::take_shared(&mut scenario);
+        let borrow_policy =
+            sui::test_scenario::take_shared<ob_request::request::Policy<ob_request::request::WithNft<Joystick, ob_request::borrow_request::BORROW_REQ>>>(
+                &scenario,
+            );
 
         let nft = mint(
             std::string::utf8(b"TEST STRING"),
@@ -650,84 +741,89 @@ module joysticks::joystick {
         );
 
         sui::test_scenario::return_to_address(CREATOR, mint_cap);
+        sui::test_scenario::return_shared(collection);
         sui::test_scenario::return_to_address(CREATOR, publisher);
         sui::test_scenario::return_shared(borrow_policy);
-        sui::test_scenario::return_shared(collection);
         sui::transfer::public_share_object(kiosk);
         sui::test_scenario::end(scenario);
     }
 
-        #[test]
-        fun it_burns_nft() {
-            let scenario = sui::test_scenario::begin(CREATOR);
-            init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
-
-            sui::test_scenario::next_tx(&mut scenario, CREATOR);
+    #[test]
+    fun it_burns_nft() {
+        let scenario = sui::test_scenario::begin(CREATOR);
+        init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
+        sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-            let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
                 &scenario,
                 CREATOR,
             );
 
-            let publisher = sui::test_scenario::take_from_address<sui::package::Publisher>(
+        let publisher = sui::test_scenario::take_from_address<sui::package::Publisher>(
+            &scenario,
+            CREATOR,
+        );
+
+        let collection =
+            sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
                 &scenario,
-                CREATOR,
             );
 
-            let collection = sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
-                &scenario
+        let withdraw_policy =
+            sui::test_scenario::take_shared<ob_request::request::Policy<ob_request::request::WithNft<Joystick, ob_request::withdraw_request::WITHDRAW_REQ>>>(
+                &scenario,
             );
 
-            let withdraw_policy = sui::test_scenario::take_shared<
-                ob_request::request::Policy<
-                    ob_request::request::WithNft<Joystick, ob_request::withdraw_request::WITHDRAW_REQ>
-                >
-            >(&scenario);
-
-            let nft = mint(
-                std::string::utf8(b"TEST STRING"),
-                std::string::utf8(b"TEST STRING"),
-                b"https://originbyte.io",
-                vector[std::ascii::string(b"key")],
-                vector[std::ascii::string(b"attribute")],
-                &mut mint_cap,
+        let nft = mint(
+            std::string::utf8(b"TEST STRING"),
+            std::string::utf8(b"TEST STRING"),
+            b"https://originbyte.io",
+            vector[std::ascii::string(b"key")],
+            vector[std::ascii::string(b"attribute")],
+            &mut mint_cap,
             &mut collection,
-                sui::test_scenario::ctx(&mut scenario)
-            );
-            let nft_id = sui::object::id(&nft);
+            sui::test_scenario::ctx(&mut scenario),
+        );
+        let nft_id = sui::object::id(&nft);
 
-            let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
-            ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, sui::test_scenario::ctx(&mut scenario));
+        let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
+        ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, sui::test_scenario::ctx(&mut scenario));
 
-            burn_nft_in_kiosk(
-                &mut collection,
-                &mut kiosk,
-                nft_id,
-                &withdraw_policy,
-                sui::test_scenario::ctx(&mut scenario)
-            );
+        burn_nft_in_kiosk(
+            &mut collection,
+            &mut kiosk,
+            nft_id,
+            &withdraw_policy,
+            sui::test_scenario::ctx(&mut scenario),
+        );
 
-            sui::test_scenario::return_to_address(CREATOR, mint_cap);
-            sui::test_scenario::return_to_address(CREATOR, publisher);
-            sui::test_scenario::return_shared(collection);
-            sui::test_scenario::return_shared(withdraw_policy);
-            sui::transfer::public_share_object(kiosk);
-            sui::test_scenario::end(scenario);
-        }
+        sui::test_scenario::return_to_address(CREATOR, mint_cap);
+        sui::test_scenario::return_to_address(CREATOR, publisher);
+        sui::test_scenario::return_shared(collection);
+        sui::test_scenario::return_shared(withdraw_policy);
+        sui::transfer::public_share_object(kiosk);
+        sui::test_scenario::end(scenario);
+    }
 
     #[test]
     fun test_trade() {
         let scenario = sui::test_scenario::begin(CREATOR);
-
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
         // Setup allowlist
-        let (allowlist, allowlist_cap) = ob_allowlist::allowlist::new(sui::test_scenario::ctx(&mut scenario));
-        ob_allowlist::allowlist::insert_authority<liquidity_layer_v1::orderbook::Witness>(&allowlist_cap, &mut allowlist);
+        let (allowlist, allowlist_cap) = ob_allowlist::allowlist::new(
+            sui::test_scenario::ctx(&mut scenario),
+        );
+        ob_allowlist::allowlist::insert_authority<liquidity_layer_v1::orderbook::Witness>(
+            &allowlist_cap,
+            &mut allowlist,
+        );
 
         let publisher = sui::test_scenario::take_from_address<sui::package::Publisher>(
-            &scenario, CREATOR,
+            &scenario,
+            CREATOR,
         );
 
         // Need to insert all tradeable types into collection
@@ -736,19 +832,25 @@ module joysticks::joystick {
         sui::transfer::public_transfer(allowlist_cap, CREATOR);
 
         // Setup orderbook
-        let orderbook = sui::test_scenario::take_shared<liquidity_layer_v1::orderbook::Orderbook<Joystick, sui::sui::SUI>>(&scenario);
+        let orderbook =
+            sui::test_scenario::take_shared<liquidity_layer_v1::orderbook::Orderbook<Joystick, sui::sui::SUI>>(
+                &scenario,
+            );
         liquidity_layer_v1::orderbook::enable_orderbook(&publisher, &mut orderbook);
 
         sui::test_scenario::return_to_address(CREATOR, publisher);
 
         // Setup test NFT
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &mut scenario, CREATOR,
-        );
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
 
-        let collection = sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
-            &scenario,
-        );
+        let collection =
+            sui::test_scenario::take_shared<nft_protocol::collection::Collection<Joystick>>(
+                &scenario,
+            );
 
         let nft = mint(
             std::string::utf8(b"TEST STRING"),
@@ -779,17 +881,20 @@ module joysticks::joystick {
         liquidity_layer_v1::orderbook::create_ask(
             &mut orderbook,
             &mut seller_kiosk,
-            100_000_000,
+            100000000,
             nft_id,
             sui::test_scenario::ctx(&mut scenario),
         );
 
-        let coin = sui::coin::mint_for_testing<sui::sui::SUI>(100_000_000, sui::test_scenario::ctx(&mut scenario));
+        let coin = sui::coin::mint_for_testing<sui::sui::SUI>(
+            100000000,
+            sui::test_scenario::ctx(&mut scenario),
+        );
 
         let trade_opt = liquidity_layer_v1::orderbook::create_bid(
             &mut orderbook,
             &mut buyer_kiosk,
-            100_000_000,
+            100000000,
             &mut coin,
             sui::test_scenario::ctx(&mut scenario),
         );
@@ -805,16 +910,31 @@ module joysticks::joystick {
             sui::test_scenario::ctx(&mut scenario),
         );
 
-        let allowlist = sui::test_scenario::take_shared<ob_allowlist::allowlist::Allowlist>(&scenario);
+        let allowlist = sui::test_scenario::take_shared<ob_allowlist::allowlist::Allowlist>(
+            &scenario,
+        );
         nft_protocol::transfer_allowlist::confirm_transfer(&allowlist, &mut request);
         sui::test_scenario::return_shared(allowlist);
 
-        let royalty_strategy = sui::test_scenario::take_shared<nft_protocol::royalty_strategy_bps::BpsRoyaltyStrategy<Joystick>>(&mut scenario);
-        nft_protocol::royalty_strategy_bps::confirm_transfer<Joystick, sui::sui::SUI>(&mut royalty_strategy, &mut request);
+        let royalty_strategy =
+            sui::test_scenario::take_shared<nft_protocol::royalty_strategy_bps::BpsRoyaltyStrategy<Joystick>>(
+                &scenario,
+            );
+        nft_protocol::royalty_strategy_bps::confirm_transfer<Joystick, sui::sui::SUI>(
+            &mut royalty_strategy,
+            &mut request,
+        );
         sui::test_scenario::return_shared(royalty_strategy);
 
-        let transfer_policy = sui::test_scenario::take_shared<sui::transfer_policy::TransferPolicy<Joystick>>(&scenario);
-        ob_request::transfer_request::confirm<Joystick, sui::sui::SUI>(request, &transfer_policy, sui::test_scenario::ctx(&mut scenario));
+        let transfer_policy =
+            sui::test_scenario::take_shared<sui::transfer_policy::TransferPolicy<Joystick>>(
+                &scenario,
+            );
+        ob_request::transfer_request::confirm<Joystick, sui::sui::SUI>(
+            request,
+            &transfer_policy,
+            sui::test_scenario::ctx(&mut scenario),
+        );
         sui::test_scenario::return_shared(transfer_policy);
 
         ob_kiosk::ob_kiosk::assert_nft_type<Joystick>(&buyer_kiosk, nft_id);
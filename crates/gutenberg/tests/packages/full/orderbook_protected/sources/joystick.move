@@ -1,3 +1,6 @@
+// @generated by Gutenberg, manual changes will be overwritten
+// template: 0.4.0, NftProtocol: 1.0.0, schema: 6bfe9f99933116c3
+// digest: aa65035e3c4d2b0d
 module orderbook_protected::joystick {
     /// One time witness is only instantiated in the init method
     struct JOYSTICK has drop {}
@@ -20,7 +23,9 @@ module orderbook_protected::joystick {
         sui::transfer::public_share_object(collection);
 
         let mint_cap = nft_protocol::mint_cap::new_unlimited<JOYSTICK, Joystick>(
-            &witness, collection_id, ctx
+            &witness,
+            collection_id,
+            ctx,
         );
         sui::transfer::public_transfer(mint_cap, sui::tx_context::sender(ctx));
 
@@ -28,22 +33,37 @@ module orderbook_protected::joystick {
 
         let display = sui::display::new<Joystick>(&publisher, ctx);
         sui::display::add(&mut display, std::string::utf8(b"name"), std::string::utf8(b"{name}"));
-        sui::display::add(&mut display, std::string::utf8(b"description"), std::string::utf8(b"{description}"));
-        sui::display::add(&mut display, std::string::utf8(b"image_url"), std::string::utf8(b"{url}"));
-        sui::display::add(&mut display, std::string::utf8(b"attributes"), std::string::utf8(b"{attributes}"));
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"description"),
+            std::string::utf8(b"{description}"),
+        );
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"image_url"),
+            std::string::utf8(b"{url}"),
+        );
+        sui::display::add(
+            &mut display,
+            std::string::utf8(b"attributes"),
+            std::string::utf8(b"{attributes}"),
+        );
         sui::display::update_version(&mut display);
 
         sui::transfer::public_transfer(display, sui::tx_context::sender(ctx));
 
-        let (transfer_policy, transfer_policy_cap) = ob_request::transfer_request::init_policy<Joystick>(
-            &publisher, ctx,
-        );
+        let (transfer_policy, transfer_policy_cap) =
+            ob_request::transfer_request::init_policy<Joystick>(&publisher, ctx);
         nft_protocol::transfer_allowlist::enforce(&mut transfer_policy, &transfer_policy_cap);
 
         // Protected orderbook such that trading is not initially possible
-        let orderbook = liquidity_layer_v1::orderbook::new_with_protected_actions<Joystick, sui::sui::SUI>(
-            delegated_witness, &transfer_policy, liquidity_layer_v1::orderbook::custom_protection(true, true, true), ctx,
-        );
+        let orderbook =
+            liquidity_layer_v1::orderbook::new_with_protected_actions<Joystick, sui::sui::SUI>(
+                delegated_witness,
+                &transfer_policy,
+                liquidity_layer_v1::orderbook::custom_protection(true, true, true),
+                ctx,
+            );
         liquidity_layer_v1::orderbook::share(orderbook);
 
         sui::transfer::public_transfer(publisher, sui::tx_context::sender(ctx));
@@ -57,10 +77,7 @@ module orderbook_protected::joystick {
         receiver: &mut sui::kiosk::Kiosk,
         ctx: &mut sui::tx_context::TxContext,
     ) {
-        let nft = mint(
-            mint_cap,
-            ctx,
-        );
+        let nft = mint(mint_cap, ctx);
 
         ob_kiosk::ob_kiosk::deposit(receiver, nft, ctx);
     }
@@ -70,10 +87,7 @@ module orderbook_protected::joystick {
         receiver: address,
         ctx: &mut sui::tx_context::TxContext,
     ) {
-        let nft = mint(
-            mint_cap,
-            ctx,
-        );
+        let nft = mint(mint_cap, ctx);
 
         let (kiosk, _) = ob_kiosk::ob_kiosk::new_for_address(receiver, ctx);
         ob_kiosk::ob_kiosk::deposit(&mut kiosk, nft, ctx);
@@ -86,9 +100,7 @@ module orderbook_protected::joystick {
     ): Joystick {
         let delegated_witness = ob_permissions::witness::from_witness(Witness {});
 
-        let nft = Joystick {
-            id: sui::object::new(ctx),
-        };
+        let nft = Joystick { id: sui::object::new(ctx) };
 
         nft_protocol::mint_event::emit_mint(
             delegated_witness,
@@ -107,16 +119,20 @@ module orderbook_protected::joystick {
     #[test]
     fun it_inits_collection() {
         let scenario = sui::test_scenario::begin(CREATOR);
-
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-        assert!(sui::test_scenario::has_most_recent_shared<nft_protocol::collection::Collection<Joystick>>(), 0);
-
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &scenario, CREATOR,
+        assert!(
+            sui::test_scenario::has_most_recent_shared<nft_protocol::collection::Collection<Joystick>>(),
+            0,
         );
 
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
+
         sui::test_scenario::return_to_address(CREATOR, mint_cap);
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
@@ -127,21 +143,17 @@ module orderbook_protected::joystick {
     fun it_mints_nft_airdrop() {
         let scenario = sui::test_scenario::begin(CREATOR);
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
-
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &scenario,
-            CREATOR,
-        );
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
 
         let (kiosk, _) = ob_kiosk::ob_kiosk::new(sui::test_scenario::ctx(&mut scenario));
 
-        mint_nft_to_kiosk(
-            &mut mint_cap,
-            &mut kiosk,
-            sui::test_scenario::ctx(&mut scenario)
-        );
+        mint_nft_to_kiosk(&mut mint_cap, &mut kiosk, sui::test_scenario::ctx(&mut scenario));
 
         sui::transfer::public_share_object(kiosk);
         sui::test_scenario::return_to_address(CREATOR, mint_cap);
@@ -151,16 +163,21 @@ module orderbook_protected::joystick {
     #[test]
     fun test_trade() {
         let scenario = sui::test_scenario::begin(CREATOR);
-
         init(JOYSTICK {}, sui::test_scenario::ctx(&mut scenario));
         sui::test_scenario::next_tx(&mut scenario, CREATOR);
 
         // Setup allowlist
-        let (allowlist, allowlist_cap) = ob_allowlist::allowlist::new(sui::test_scenario::ctx(&mut scenario));
-        ob_allowlist::allowlist::insert_authority<liquidity_layer_v1::orderbook::Witness>(&allowlist_cap, &mut allowlist);
+        let (allowlist, allowlist_cap) = ob_allowlist::allowlist::new(
+            sui::test_scenario::ctx(&mut scenario),
+        );
+        ob_allowlist::allowlist::insert_authority<liquidity_layer_v1::orderbook::Witness>(
+            &allowlist_cap,
+            &mut allowlist,
+        );
 
         let publisher = sui::test_scenario::take_from_address<sui::package::Publisher>(
-            &scenario, CREATOR,
+            &scenario,
+            CREATOR,
         );
 
         // Need to insert all tradeable types into collection
@@ -169,20 +186,22 @@ module orderbook_protected::joystick {
         sui::transfer::public_transfer(allowlist_cap, CREATOR);
 
         // Setup orderbook
-        let orderbook = sui::test_scenario::take_shared<liquidity_layer_v1::orderbook::Orderbook<Joystick, sui::sui::SUI>>(&scenario);
+        let orderbook =
+            sui::test_scenario::take_shared<liquidity_layer_v1::orderbook::Orderbook<Joystick, sui::sui::SUI>>(
+                &scenario,
+            );
         liquidity_layer_v1::orderbook::enable_orderbook(&publisher, &mut orderbook);
 
         sui::test_scenario::return_to_address(CREATOR, publisher);
 
         // Setup test NFT
-        let mint_cap = sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
-            &mut scenario, CREATOR,
-        );
+        let mint_cap =
+            sui::test_scenario::take_from_address<nft_protocol::mint_cap::MintCap<Joystick>>(
+                &scenario,
+                CREATOR,
+            );
 
-        let nft = mint(
-            &mut mint_cap,
-            sui::test_scenario::ctx(&mut scenario),
-        );
+        let nft = mint(&mut mint_cap, sui::test_scenario::ctx(&mut scenario));
         let nft_id = sui::object::id(&nft);
 
         sui::test_scenario::return_to_address(CREATOR, mint_cap);
@@ -201,17 +220,20 @@ module orderbook_protected::joystick {
         liquidity_layer_v1::orderbook::create_ask(
             &mut orderbook,
             &mut seller_kiosk,
-            100_000_000,
+            100000000,
             nft_id,
             sui::test_scenario::ctx(&mut scenario),
         );
 
-        let coin = sui::coin::mint_for_testing<sui::sui::SUI>(100_000_000, sui::test_scenario::ctx(&mut scenario));
+        let coin = sui::coin::mint_for_testing<sui::sui::SUI>(
+            100000000,
+            sui::test_scenario::ctx(&mut scenario),
+        );
 
         let trade_opt = liquidity_layer_v1::orderbook::create_bid(
             &mut orderbook,
             &mut buyer_kiosk,
-            100_000_000,
+            100000000,
             &mut coin,
             sui::test_scenario::ctx(&mut scenario),
         );
@@ -227,12 +249,21 @@ module orderbook_protected::joystick {
             sui::test_scenario::ctx(&mut scenario),
         );
 
-        let allowlist = sui::test_scenario::take_shared<ob_allowlist::allowlist::Allowlist>(&scenario);
+        let allowlist = sui::test_scenario::take_shared<ob_allowlist::allowlist::Allowlist>(
+            &scenario,
+        );
         nft_protocol::transfer_allowlist::confirm_transfer(&allowlist, &mut request);
         sui::test_scenario::return_shared(allowlist);
 
-        let transfer_policy = sui::test_scenario::take_shared<sui::transfer_policy::TransferPolicy<Joystick>>(&scenario);
-        ob_request::transfer_request::confirm<Joystick, sui::sui::SUI>(request, &transfer_policy, sui::test_scenario::ctx(&mut scenario));
+        let transfer_policy =
+            sui::test_scenario::take_shared<sui::transfer_policy::TransferPolicy<Joystick>>(
+                &scenario,
+            );
+        ob_request::transfer_request::confirm<Joystick, sui::sui::SUI>(
+            request,
+            &transfer_policy,
+            sui::test_scenario::ctx(&mut scenario),
+        );
         sui::test_scenario::return_shared(transfer_policy);
 
         ob_kiosk::ob_kiosk::assert_nft_type<Joystick>(&buyer_kiosk, nft_id);
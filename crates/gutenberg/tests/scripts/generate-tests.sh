@@ -1,50 +1,23 @@
 #!/bin/bash
 
-cargo build --bin gutenberg --features="cli"
+cargo build --bin gutenberg
 if [[ ! $? -eq 0 ]]; then
     echo "Could not build Gutenberg"
     echo "FAIL"
     exit 1
 fi
 
-rm -rf ./tests/packages
+# Regenerate sources of the full and demo contracts of every scenario
+echo "Generating contracts..."
 
-# Generate and compare demo contracts
-echo "Generating demo contracts..."
+../../target/debug/gutenberg scenarios \
+    --scenarios-dir ./tests/scenarios \
+    --packages-dir ./tests/packages
 
-for file in ./tests/scenarios/*.json; do
-    echo "Generating $file"
-
-    filename=$(basename -- "$file")
-    filename="${filename%.*}"
-
-    rm -rf ./tests/packages/demo/$filename/*
-    ../../target/debug/gutenberg --demo $file ./tests/packages/demo
-
-    if [[ ! $? -eq 0 ]]; then
-        echo "Scenario $file did not generate valid contract"
-        echo "FAIL"
-        exit 1
-    fi
-done
-
-# Generate and compare full contracts
-echo "Generating full contracts..."
-
-for file in ./tests/scenarios/*.json; do
-    echo "Generating $file"
-
-    filename=$(basename -- "$file")
-    filename="${filename%.*}"
-
-    rm -rf ./tests/packages/full/$filename/*
-    ../../target/debug/gutenberg $file ./tests/packages/full
-
-    if [[ ! $? -eq 0 ]]; then
-        echo "Scenario $file did not generate valid full contract"
-        echo "FAIL"
-        exit 1
-    fi
-done
+if [[ ! $? -eq 0 ]]; then
+    echo "Scenarios did not generate valid contracts"
+    echo "FAIL"
+    exit 1
+fi
 
-echo "SUCCESS"
\ No newline at end of file
+echo "SUCCESS"
@@ -1,41 +1,22 @@
 #!/bin/bash
 
-cargo build --bin gutenberg --features="cli"
+# Compare generated contracts against the expected packages
+cargo test --test golden
 if [[ ! $? -eq 0 ]]; then
-    echo "Could not build Gutenberg"
+    echo "Scenarios did not generate matching contracts"
+    echo "Run './tests/scripts/generate-tests.sh' to update tests"
     echo "FAIL"
     exit 1
 fi
 
-tmp_dir=$(mktemp -d)
-
-# Generate and compare full contracts
+# Run Sui tests of the expected packages
 #
 # Test full contracts first since they're the ones most likely to fail
-echo "Testing generated full contracts..."
-
-for file in ./tests/scenarios/*.json; do
-    echo "Testing scenario $file"
-
-    rm -rf $tmp_dir/*
-    ../../target/debug/gutenberg $file $tmp_dir
-
-    filename=$(basename -- "$file")
-    filename="${filename%.*}"
-    diff -r ./tests/packages/full/$filename $tmp_dir/$filename
+for package in ./tests/packages/full/* ./tests/packages/demo/*; do
+    echo "Testing package $package"
 
-    if [ $? -eq 1 ]; then
-        echo "Scenario $file did not generate matching full contract"
-        echo "Run './tests/generate-tests.sh' to update tests"
-        echo "FAIL"
-        rm -rf $tmp_dir
-        exit 1
-    fi
-
-    # Run Sui tests
-    #
     # Output is silenced unless an error occurs
-    output=$(sui move test --path $tmp_dir/$filename 2>/dev/null)
+    output=$(sui move test --path $package 2>/dev/null)
 
     # `sui move test` always returns zero code
     #
@@ -47,46 +28,10 @@ for file in ./tests/scenarios/*.json; do
     # ¯\_(ツ)_/¯
     if [[ ! -z $(echo $output | grep -F "error") ]]; then
         echo "$output"
-        echo "Scenario $file did not pass Sui tests"
-        echo "FAIL"
-        rm -rf $tmp_dir
-        exit 1
-    fi
-done
-
-# Generate and compare demo contracts
-echo "Testing generated demo contracts..."
-
-for file in ./tests/scenarios/*.json; do
-    echo "Testing scenario $file"
-
-    rm -rf $tmp_dir/*
-    ../../target/debug/gutenberg --demo $file $tmp_dir
-
-    filename=$(basename -- "$file")
-    filename="${filename%.*}"
-    diff -r ./tests/packages/demo/$filename $tmp_dir/$filename
-
-    if [ $? -eq 1 ]; then
-        echo "Scenario $file did not generate matching demo contract"
-        echo "Run './tests/generate-tests.sh' to update tests"
-        echo "FAIL"
-        rm -rf $tmp_dir
-        exit 1
-    fi
-
-    # Run Sui tests
-    output=$(sui move test --path $tmp_dir/$filename 2>/dev/null)
-
-    if [[ ! -z $(echo $output | grep -F "error") ]]; then
-        echo "$output"
-        echo "Scenario $file did not pass Sui tests"
+        echo "Package $package did not pass Sui tests"
         echo "FAIL"
-        rm -rf $tmp_dir
         exit 1
     fi
 done
 
-rm -rf $tmp_dir
-
 echo "SUCCESS"
\ No newline at end of file
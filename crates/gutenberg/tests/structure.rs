@@ -0,0 +1,215 @@
+//! Structural tests asserting on the generated Move module rather than on its
+//! rendered source
+use gutenberg::{
+    ast::{Fun, Member, Module},
+    write_move_module,
+};
+use gutenberg_types::Schema;
+use serde_json::json;
+use std::{fs, path::Path};
+
+fn scenarios() -> impl Iterator<Item = (String, Schema)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scenarios");
+
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+
+    paths.into_iter().map(|path| {
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        let schema = serde_json::from_str(&fs::read_to_string(&path).unwrap())
+            .unwrap_or_else(|err| panic!("Invalid scenario {name}: {err}"));
+        (name, schema)
+    })
+}
+
+fn module(schema: serde_json::Value) -> Module {
+    write_move_module(&serde_json::from_value(schema).unwrap())
+}
+
+fn fun<'a>(module: &'a Module, name: &str) -> &'a Fun {
+    module
+        .fun(name)
+        .unwrap_or_else(|| panic!("Function `{name}` was not generated"))
+}
+
+fn param_names(fun: &Fun) -> Vec<&str> {
+    fun.param_names().collect()
+}
+
+#[test]
+fn local_calls_are_declared() {
+    for (name, schema) in scenarios() {
+        let module = write_move_module(&schema);
+
+        for member in module.members.iter() {
+            let Member::Fun(fun) = member else { continue };
+
+            for call in fun.body.calls() {
+                if call.contains("::") || call == "assert!" {
+                    continue;
+                }
+
+                assert!(
+                    module.fun(call).is_some(),
+                    "Scenario {name}: `{}` calls undeclared `{call}`",
+                    fun.name
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn every_scenario_declares_init_and_mint() {
+    for (name, schema) in scenarios() {
+        let module = write_move_module(&schema);
+
+        let init = fun(&module, "init");
+        assert_eq!(param_names(init), ["witness", "ctx"], "Scenario {name}");
+
+        let calls = init.body.calls();
+        assert!(calls.contains(&"sui::package::claim"), "Scenario {name}");
+        assert!(
+            calls.contains(&"nft_protocol::collection::create"),
+            "Scenario {name}"
+        );
+
+        assert_eq!(param_names(fun(&module, "mint")).last(), Some(&"ctx"));
+        assert!(module.fun("it_inits_collection").is_some());
+    }
+}
+
+#[test]
+fn mint_params_follow_fields() {
+    let module = module(json!({
+        "packageName": "fields",
+        "nft": {
+            "typeName": "Joystick",
+            "mintCap": "unlimited",
+            "fields": [
+                ["name", "String"],
+                ["url", "Url"],
+                ["attributes", "Attributes"]
+            ],
+        }
+    }));
+
+    assert_eq!(
+        param_names(fun(&module, "mint")),
+        [
+            "name",
+            "url",
+            "attributes_keys",
+            "attributes_values",
+            "mint_cap",
+            "ctx"
+        ]
+    );
+}
+
+#[test]
+fn supply_requires_mutable_collection() {
+    let module = module(json!({
+        "packageName": "supply",
+        "collection": { "supply": { "enforced": 100 } },
+        "nft": {
+            "typeName": "Joystick",
+            "mintCap": "unlimited",
+            "burn": "permissionless",
+        }
+    }));
+
+    let mint = fun(&module, "mint");
+    assert_eq!(
+        param_names(mint),
+        ["mint_cap", "collection", "ctx"],
+        "Mint must increment collection supply"
+    );
+    assert!(mint
+        .body
+        .calls()
+        .contains(&"nft_protocol::supply::increment"));
+
+    let burn = fun(&module, "burn_nft");
+    assert!(burn
+        .body
+        .calls()
+        .contains(&"nft_protocol::supply::decrement"));
+}
+
+#[test]
+fn permissioned_burn_requires_delegated_witness() {
+    for (burn, expected) in [
+        (
+            "permissioned",
+            ["delegated_witness", "collection", "nft"].as_slice(),
+        ),
+        ("permissionless", ["collection", "nft"].as_slice()),
+    ] {
+        let module = module(json!({
+            "packageName": "burn",
+            "nft": {
+                "typeName": "Joystick",
+                "mintCap": "unlimited",
+                "burn": burn,
+            }
+        }));
+
+        assert_eq!(param_names(fun(&module, "burn_nft")), expected);
+        assert_eq!(
+            module.fun("burn_nft_in_kiosk_as_publisher").is_some(),
+            burn == "permissioned"
+        );
+        assert!(module.fun("burn_nft_in_listing").is_none());
+    }
+}
+
+#[test]
+fn dynamic_fields_have_setters() {
+    let module = module(json!({
+        "packageName": "dynamic",
+        "nft": {
+            "typeName": "Joystick",
+            "mintCap": "unlimited",
+            "dynamic": true,
+            "fields": [["name", "String"], ["attributes", "Attributes"]],
+        }
+    }));
+
+    assert_eq!(
+        param_names(fun(&module, "set_name")),
+        ["_delegated_witness", "nft", "name"]
+    );
+    assert_eq!(
+        param_names(fun(&module, "set_attributes_in_kiosk")),
+        [
+            "delegated_witness",
+            "kiosk",
+            "nft_id",
+            "attributes_keys",
+            "attributes_values",
+            "policy",
+            "ctx"
+        ]
+    );
+    assert!(fun(&module, "set_name_as_publisher").is_entry);
+    assert!(module.fun("it_sets_metadata").is_some());
+}
+
+#[test]
+fn static_nft_has_no_setters() {
+    let module = module(json!({
+        "packageName": "static",
+        "nft": {
+            "typeName": "Joystick",
+            "mintCap": "unlimited",
+            "fields": [["name", "String"]],
+        }
+    }));
+
+    assert!(module.fun("set_name").is_none());
+    assert!(module.fun("it_sets_metadata").is_none());
+}
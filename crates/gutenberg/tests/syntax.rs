@@ -0,0 +1,44 @@
+//! Parses the contracts generated for every scenario with the Move parser
+//!
+//! Runs without network access, unlike `./tests/scripts/test-scenarios.sh`,
+//! but only when built with `--features move-check`.
+#![cfg(feature = "move-check")]
+
+use gutenberg::{
+    check::check_syntax,
+    scenarios::{read_scenarios, Scenario, Variant},
+};
+use std::path::Path;
+
+fn scenarios() -> Vec<Scenario> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scenarios");
+    read_scenarios(&dir).unwrap()
+}
+
+#[test]
+fn full_scenarios_parse() {
+    for scenario in scenarios() {
+        if let Err(err) = check_syntax(&scenario.generate(Variant::Full)) {
+            panic!("Scenario {}: {err}", scenario.name);
+        }
+    }
+}
+
+#[test]
+fn demo_scenarios_parse() {
+    for scenario in scenarios() {
+        if let Err(err) = check_syntax(&scenario.generate(Variant::Demo)) {
+            panic!("Scenario {}: {err}", scenario.name);
+        }
+    }
+}
+
+#[test]
+fn malformed_source_is_rejected() {
+    let file = gutenberg::ContractFile::new(
+        "sources/broken.move".into(),
+        "module broken::broken { fun f( }".to_string(),
+    );
+
+    assert!(check_syntax(&[file]).is_err());
+}
@@ -0,0 +1,679 @@
+//! Diagnosis of a Move package against its build and the Package Registry
+//!
+//! Move.toml declares the dependencies, `sui move build` resolves them into
+//! Move.lock and compiles the package against the named addresses recorded
+//! in BuildInfo.yaml, and the Package Registry records where every release
+//! was published. A package built from inconsistent sources compiles fine
+//! and only fails once published, hence [`Doctor`] cross-checks them.
+
+use gutenberg_types::models::address::Address;
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    info::{BuildInfo, MoveLock},
+    package::{Flavor, PackageInfo, PackageRegistry},
+    resolve::{Conflict, Step},
+    toml::{named_address, MoveToml},
+    version::Version,
+};
+
+/// Repository of the Sui framework packages
+const SUI_REPOSITORY: &str = "MystenLabs/sui";
+
+/// Sources of a Move package to cross-check.
+///
+/// # Fields
+/// * `flavor` - The network the package is diagnosed for.
+/// * `move_toml` - The Move.toml of the package.
+/// * `move_lock` - The Move.lock of the package, if it was built.
+/// * `build_info` - The BuildInfo.yaml of the package, if it was built.
+/// * `registry` - The registry of the network.
+/// * `other_registry` - The registry of the other network, to recognize
+///   releases made for the wrong network.
+pub struct Doctor<'a> {
+    pub flavor: Flavor,
+    pub move_toml: &'a MoveToml,
+    pub move_lock: Option<&'a MoveLock>,
+    pub build_info: Option<&'a BuildInfo>,
+    pub registry: &'a PackageRegistry,
+    pub other_registry: &'a PackageRegistry,
+}
+
+/// Problem found by [`Doctor::diagnose`], see [`Issue::fix`] for how to
+/// solve it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    /// Move.toml is loaded for another network
+    FlavorMismatch { manifest: Flavor, network: Flavor },
+    /// Dependency points at a release made for the other network
+    OtherNetworkRelease {
+        name: String,
+        rev: String,
+        network: Flavor,
+        release: Option<Step>,
+    },
+    /// Dependency points at a revision the registry does not know about
+    UnknownRevision {
+        name: String,
+        rev: String,
+        release: Option<Step>,
+    },
+    /// Registry release of a dependency has no `publishedAt` address
+    Unpublished { name: String, version: Version },
+    /// Named address of a dependency in Move.toml differs from the registry
+    NamedAddressMismatch {
+        name: String,
+        declared: Address,
+        expected: Address,
+    },
+    /// Dependencies require the same package at different revisions
+    Conflict(Conflict),
+    /// Build output is missing, such that it could not be checked
+    NotBuilt(&'static str),
+    /// Move.lock resolved a dependency to another revision than Move.toml
+    StaleMoveLock {
+        name: String,
+        declared: String,
+        locked: Option<String>,
+    },
+    /// Move.lock resolved the Sui framework packages at several revisions
+    DuplicateFramework(BTreeMap<String, String>),
+    /// Package was compiled against an unpublished dependency
+    UnpublishedAddress { name: String },
+    /// Package was compiled against another address than the registry's
+    AddressMismatch {
+        name: String,
+        compiled: Address,
+        expected: Address,
+    },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::FlavorMismatch { manifest, network } => write!(
+                f,
+                "Move.toml is loaded for {} but was checked against {}",
+                manifest.to_str(),
+                network.to_str()
+            ),
+            Issue::OtherNetworkRelease {
+                name, rev, network, ..
+            } => write!(
+                f,
+                "{name} is at rev {rev}, which is a {} release",
+                network.to_str()
+            ),
+            Issue::UnknownRevision { name, rev, .. } => write!(
+                f,
+                "{name} is at rev {rev}, which is not a release in the \
+                registry"
+            ),
+            Issue::Unpublished { name, version } => write!(
+                f,
+                "{name} {version} has no `publishedAt` address in the \
+                registry"
+            ),
+            Issue::NamedAddressMismatch {
+                name,
+                declared,
+                expected,
+            } => write!(
+                f,
+                "Named address `{name}` is {declared} in Move.toml but \
+                {expected} in the registry"
+            ),
+            Issue::Conflict(conflict) => write!(f, "{conflict}"),
+            Issue::NotBuilt(file) => {
+                write!(f, "{file} is missing, the build was not checked")
+            }
+            Issue::StaleMoveLock {
+                name,
+                declared,
+                locked,
+            } => match locked {
+                Some(locked) => write!(
+                    f,
+                    "{name} is at rev {declared} in Move.toml but at rev \
+                    {locked} in Move.lock"
+                ),
+                None => write!(f, "{name} is missing from Move.lock"),
+            },
+            Issue::DuplicateFramework(revs) => write!(
+                f,
+                "Move.lock resolves the Sui framework at several revisions: \
+                {}",
+                revs.iter()
+                    .map(|(name, rev)| format!("{name} @{rev}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Issue::UnpublishedAddress { name } => write!(
+                f,
+                "{name} was compiled against address 0x0, as if it were \
+                unpublished"
+            ),
+            Issue::AddressMismatch {
+                name,
+                compiled,
+                expected,
+            } => write!(
+                f,
+                "{name} was compiled against {compiled} but the registry \
+                has {expected}"
+            ),
+        }
+    }
+}
+
+impl Issue {
+    /// Concrete steps that solve the issue.
+    pub fn fix(&self) -> String {
+        match self {
+            Issue::FlavorMismatch { network, .. } => format!(
+                "Call `byte mpm load-env {}` to switch Move.toml to the \
+                manifest of the network",
+                network.to_str()
+            ),
+            Issue::OtherNetworkRelease { name, release, .. }
+            | Issue::UnknownRevision { name, release, .. } => match release {
+                Some(release) => format!(
+                    "Set the rev of {name} in Move.toml to {} ({release})",
+                    release.rev
+                ),
+                None => format!(
+                    "Remove {name} from Move.toml, it has no release on \
+                    this network"
+                ),
+            },
+            Issue::Unpublished { name, .. } => format!(
+                "Depend on a published version of {name}, or register its \
+                address with `byte mpm registry add`"
+            ),
+            Issue::NamedAddressMismatch { name, expected, .. } => format!(
+                "Set `{name} = \"{expected}\"` in the [addresses] of \
+                Move.toml"
+            ),
+            Issue::Conflict(_) => "Call `byte mpm update-dependencies` to \
+                move the dependencies to a consistent set of versions"
+                .to_string(),
+            Issue::NotBuilt(_) => {
+                "Call `sui move build` in the contract directory".to_string()
+            }
+            Issue::StaleMoveLock { .. } => {
+                "Call `sui move build` to resolve the dependencies again"
+                    .to_string()
+            }
+            Issue::DuplicateFramework(_) => "Pin the Sui dependency of \
+                Move.toml to the revision required by the other \
+                dependencies, add `override = true` to it, then call `sui \
+                move build`"
+                .to_string(),
+            Issue::UnpublishedAddress { name } => format!(
+                "Point {name} at a published release with `byte mpm \
+                update-dependencies`, then call `sui move build`"
+            ),
+            Issue::AddressMismatch { name, .. } => format!(
+                "Call `sui move build` to rebuild against the revision of \
+                {name} in Move.lock, and make sure it is a release of this \
+                network"
+            ),
+        }
+    }
+}
+
+impl<'a> Doctor<'a> {
+    /// Cross-checks the sources of the package.
+    ///
+    /// # Returns
+    /// Every issue found, or none if the package is consistent.
+    pub fn diagnose(&self) -> Vec<Issue> {
+        let mut issues = Vec::new();
+
+        self.check_flavor(&mut issues);
+        self.check_dependencies(&mut issues);
+        self.check_move_lock(&mut issues);
+        self.check_build_info(&mut issues);
+
+        issues
+    }
+
+    fn check_flavor(&self, issues: &mut Vec<Issue>) {
+        if let Some(manifest) = self.move_toml.package.flavor {
+            if manifest != self.flavor {
+                issues.push(Issue::FlavorMismatch {
+                    manifest,
+                    network: self.flavor,
+                });
+            }
+        }
+    }
+
+    /// Checks the dependencies of Move.toml against the registry
+    fn check_dependencies(&self, issues: &mut Vec<Issue>) {
+        let mut all_released = true;
+
+        for (name, path) in self.move_toml.dependencies.iter() {
+            let Some(versions) = self.registry.0.get(name) else {
+                continue;
+            };
+
+            let Some(info) = release(self.registry, name, &path.rev) else {
+                all_released = false;
+
+                let other = release(self.other_registry, name, &path.rev);
+                let same_version = other
+                    .and_then(|other| versions.get(&other.package.version));
+                let release = same_version
+                    .or_else(|| versions.values().next_back())
+                    .map(step);
+
+                issues.push(match other {
+                    Some(other) => Issue::OtherNetworkRelease {
+                        name: name.clone(),
+                        rev: path.rev.clone(),
+                        network: other.package.flavor.unwrap_or(self.flavor),
+                        release,
+                    },
+                    None => Issue::UnknownRevision {
+                        name: name.clone(),
+                        rev: path.rev.clone(),
+                        release,
+                    },
+                });
+                continue;
+            };
+
+            if info.package.published_at.is_none() {
+                issues.push(Issue::Unpublished {
+                    name: name.clone(),
+                    version: info.package.version.clone(),
+                });
+            }
+
+            let named = named_address(name, &path.git);
+            if let Some(declared) = self.move_toml.addresses.get(&named) {
+                if *declared != info.contract_ref.object_id {
+                    issues.push(Issue::NamedAddressMismatch {
+                        name: named,
+                        declared: declared.clone(),
+                        expected: info.contract_ref.object_id.clone(),
+                    });
+                }
+            }
+        }
+
+        // Revisions unknown to the registry cannot be resolved
+        if all_released {
+            if let Err(conflict) = self.move_toml.resolve_current(self.registry)
+            {
+                issues.push(Issue::Conflict(conflict));
+            }
+        }
+    }
+
+    /// Checks Move.lock against Move.toml
+    fn check_move_lock(&self, issues: &mut Vec<Issue>) {
+        let Some(move_lock) = self.move_lock else {
+            issues.push(Issue::NotBuilt("Move.lock"));
+            return;
+        };
+
+        for (name, path) in self.move_toml.dependencies.iter() {
+            let locked = move_lock
+                .package(name)
+                .and_then(|package| package.source.rev.clone());
+
+            if locked.as_ref() != Some(&path.rev) {
+                issues.push(Issue::StaleMoveLock {
+                    name: name.clone(),
+                    declared: path.rev.clone(),
+                    locked,
+                });
+            }
+        }
+
+        let framework: BTreeMap<String, String> = move_lock
+            .packages()
+            .iter()
+            .filter(|package| {
+                package
+                    .source
+                    .git
+                    .as_ref()
+                    .is_some_and(|git| git.contains(SUI_REPOSITORY))
+            })
+            .filter_map(|package| {
+                Some((package.name.clone(), package.source.rev.clone()?))
+            })
+            .collect();
+
+        let mut revs = framework.values().collect::<Vec<_>>();
+        revs.sort();
+        revs.dedup();
+        if revs.len() > 1 {
+            issues.push(Issue::DuplicateFramework(framework));
+        }
+    }
+
+    /// Checks the addresses the package was compiled against
+    fn check_build_info(&self, issues: &mut Vec<Issue>) {
+        let Some(build_info) = self.build_info else {
+            issues.push(Issue::NotBuilt("BuildInfo.yaml"));
+            return;
+        };
+
+        let mut compiled = build_info.packages.clone();
+        compiled.make_name_canonical();
+
+        let unpublished = Address::new("0x0").expect("Address is valid");
+        let package_name = self.move_toml.package.name();
+
+        for (name, address) in compiled.ob_packages.iter() {
+            if *name == package_name || !self.registry.0.contains_key(name) {
+                continue;
+            }
+
+            if *address == unpublished {
+                issues.push(Issue::UnpublishedAddress { name: name.clone() });
+                continue;
+            }
+
+            let rev = self
+                .move_lock
+                .and_then(|move_lock| move_lock.package(name))
+                .and_then(|package| package.source.rev.as_ref())
+                .or_else(|| {
+                    self.move_toml.dependencies.get(name).map(|path| &path.rev)
+                });
+
+            let Some(info) =
+                rev.and_then(|rev| release(self.registry, name, rev))
+            else {
+                continue;
+            };
+
+            if *address != info.contract_ref.object_id {
+                issues.push(Issue::AddressMismatch {
+                    name: name.clone(),
+                    compiled: address.clone(),
+                    expected: info.contract_ref.object_id.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// Release of the package at the revision, if any
+fn release<'a>(
+    registry: &'a PackageRegistry,
+    name: &str,
+    rev: &str,
+) -> Option<&'a PackageInfo> {
+    registry
+        .0
+        .get(name)?
+        .values()
+        .find(|info| info.contract_ref.path.rev == rev)
+}
+
+fn step(info: &PackageInfo) -> Step {
+    Step {
+        name: info.package.name.clone(),
+        version: Some(info.package.version.clone()),
+        rev: info.contract_ref.path.rev.clone(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        fixtures::{registry, test_registry},
+        info::{CompiledPackageInfo, MoveLockPackage, MoveLockSource},
+        package::GitPath,
+    };
+
+    fn info<'a>(
+        registry: &'a PackageRegistry,
+        name: &str,
+        version: &str,
+    ) -> &'a PackageInfo {
+        &registry.0[name][&version.parse::<Version>().unwrap()]
+    }
+
+    fn move_toml(flavor: &str, deps: &[&PackageInfo]) -> MoveToml {
+        let mut move_toml: MoveToml = toml::from_str(&format!(
+            r#"[package]
+name = "Suimarines"
+version = "1.0.0"
+flavor = "{flavor}"
+
+[dependencies]
+
+[addresses]
+suimarines = "0x0"
+"#
+        ))
+        .unwrap();
+
+        for dep in deps {
+            move_toml.dependencies.insert(
+                dep.package.name.clone(),
+                dep.contract_ref.path.clone(),
+            );
+        }
+
+        move_toml
+    }
+
+    fn locked(name: &str, path: &GitPath) -> MoveLockPackage {
+        MoveLockPackage {
+            name: name.to_string(),
+            source: MoveLockSource {
+                git: Some(path.git.clone()),
+                subdir: path.subdir.clone(),
+                rev: Some(path.rev.clone()),
+                local: None,
+            },
+        }
+    }
+
+    /// Named addresses declared by the Move.toml of the packages themselves,
+    /// spelled out rather than derived with `named_address`
+    fn declared_address(name: &str) -> &'static str {
+        match name {
+            "Permissions" => "ob_permissions",
+            "Utils" => "ob_utils",
+            "Kiosk" => "ob_kiosk",
+            "Sui" => "sui",
+            _ => panic!("No named address for {name}"),
+        }
+    }
+
+    /// Move.lock and BuildInfo.yaml of a package built against the releases
+    fn build(deps: &[&PackageInfo]) -> (MoveLock, BuildInfo) {
+        let mut move_lock = MoveLock::default();
+        let mut addresses = BTreeMap::new();
+
+        for dep in deps {
+            move_lock
+                .table
+                .packages
+                .push(locked(&dep.package.name, &dep.contract_ref.path));
+            addresses.insert(
+                declared_address(&dep.package.name).to_string(),
+                dep.contract_ref.object_id.clone(),
+            );
+        }
+
+        let build_info = BuildInfo {
+            packages: CompiledPackageInfo {
+                package_name: "Suimarines".to_string(),
+                ob_packages: addresses,
+            },
+        };
+
+        (move_lock, build_info)
+    }
+
+    #[test]
+    fn healthy_package_has_no_issues() {
+        let (main, test) = (registry(), test_registry());
+        let deps = [
+            info(&main, "Permissions", "1.2.0"),
+            info(&main, "Utils", "1.2.0"),
+            info(&main, "Sui", "1.2.0"),
+        ];
+
+        let move_toml = move_toml("Mainnet", &deps[..1]);
+        let (move_lock, build_info) = build(&deps);
+
+        let issues = Doctor {
+            flavor: Flavor::Mainnet,
+            move_toml: &move_toml,
+            move_lock: Some(&move_lock),
+            build_info: Some(&build_info),
+            registry: &main,
+            other_registry: &test,
+        }
+        .diagnose();
+
+        assert_eq!(issues, Vec::new());
+    }
+
+    #[test]
+    fn finds_inconsistent_sources() {
+        let (main, test) = (registry(), test_registry());
+        let permissions = info(&main, "Permissions", "1.2.0");
+        let utils = info(&main, "Utils", "1.2.0");
+        let sui = info(&main, "Sui", "1.2.0");
+        let test_kiosk = info(&test, "Kiosk", "1.0.0");
+
+        let move_toml = move_toml("Testnet", &[permissions, test_kiosk]);
+        let (mut move_lock, mut build_info) = build(&[permissions, utils, sui]);
+
+        // Framework packages resolved at different revisions
+        let mut std_path =
+            info(&main, "Sui", "1.0.0").contract_ref.path.clone();
+        std_path.subdir =
+            Some("crates/sui-framework/packages/move-stdlib".into());
+        move_lock
+            .table
+            .packages
+            .push(locked("MoveStdlib", &std_path));
+
+        let addresses = &mut build_info.packages.ob_packages;
+        addresses.insert("ob_utils".to_string(), Address::new("0x0").unwrap());
+        addresses.insert(
+            "ob_permissions".to_string(),
+            Address::new("0xa1").unwrap(),
+        );
+
+        let issues = Doctor {
+            flavor: Flavor::Mainnet,
+            move_toml: &move_toml,
+            move_lock: Some(&move_lock),
+            build_info: Some(&build_info),
+            registry: &main,
+            other_registry: &test,
+        }
+        .diagnose();
+
+        let main_kiosk = info(&main, "Kiosk", "1.0.0");
+        assert_eq!(
+            issues,
+            vec![
+                Issue::FlavorMismatch {
+                    manifest: Flavor::Testnet,
+                    network: Flavor::Mainnet,
+                },
+                Issue::OtherNetworkRelease {
+                    name: "Kiosk".to_string(),
+                    rev: test_kiosk.contract_ref.path.rev.clone(),
+                    network: Flavor::Testnet,
+                    release: Some(step(main_kiosk)),
+                },
+                Issue::StaleMoveLock {
+                    name: "Kiosk".to_string(),
+                    declared: test_kiosk.contract_ref.path.rev.clone(),
+                    locked: None,
+                },
+                Issue::DuplicateFramework(BTreeMap::from([
+                    ("MoveStdlib".to_string(), std_path.rev.clone()),
+                    ("Sui".to_string(), sui.contract_ref.path.rev.clone()),
+                ])),
+                Issue::AddressMismatch {
+                    name: "Permissions".to_string(),
+                    compiled: Address::new("0xa1").unwrap(),
+                    expected: permissions.contract_ref.object_id.clone(),
+                },
+                Issue::UnpublishedAddress {
+                    name: "Utils".to_string(),
+                },
+            ]
+        );
+
+        assert!(issues[1].fix().contains(&main_kiosk.contract_ref.path.rev));
+    }
+
+    #[test]
+    fn finds_named_address_mismatch() {
+        let (main, test) = (registry(), test_registry());
+        let kiosk = info(&main, "Kiosk", "1.2.0");
+        let permissions = info(&main, "Permissions", "1.2.0");
+
+        let mut move_toml = move_toml("Mainnet", &[kiosk, permissions]);
+        move_toml.addresses.insert(
+            "ob_permissions".to_string(),
+            permissions.contract_ref.object_id.clone(),
+        );
+        move_toml
+            .addresses
+            .insert("ob_kiosk".to_string(), Address::new("0xa1").unwrap());
+        let (move_lock, build_info) = build(&[kiosk, permissions]);
+
+        let issues = Doctor {
+            flavor: Flavor::Mainnet,
+            move_toml: &move_toml,
+            move_lock: Some(&move_lock),
+            build_info: Some(&build_info),
+            registry: &main,
+            other_registry: &test,
+        }
+        .diagnose();
+
+        assert_eq!(
+            issues,
+            vec![Issue::NamedAddressMismatch {
+                name: "ob_kiosk".to_string(),
+                declared: Address::new("0xa1").unwrap(),
+                expected: kiosk.contract_ref.object_id.clone(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_missing_build() {
+        let (main, test) = (registry(), test_registry());
+        let move_toml = move_toml("Mainnet", &[]);
+
+        let issues = Doctor {
+            flavor: Flavor::Mainnet,
+            move_toml: &move_toml,
+            move_lock: None,
+            build_info: None,
+            registry: &main,
+            other_registry: &test,
+        }
+        .diagnose();
+
+        assert_eq!(
+            issues,
+            vec![
+                Issue::NotBuilt("Move.lock"),
+                Issue::NotBuilt("BuildInfo.yaml")
+            ]
+        );
+    }
+}
@@ -0,0 +1,448 @@
+//! Format-preserving editing of Move.toml
+//!
+//! [`MoveToml`](crate::toml::MoveToml) only models the sections the package
+//! manager reasons about, such that serializing it back drops comments, key
+//! order and every other section. [`MoveTomlDocument`] instead edits the
+//! Move.toml as written on disk, and only rewrites the dependency entries
+//! whose location changed.
+
+use anyhow::{anyhow, Result};
+use gutenberg_types::models::address::Address;
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use toml_edit::{value, Document, Item, Table, TableLike, Value};
+
+use crate::{
+    package::{Flavor, GitPath},
+    toml::snake_case,
+};
+
+/// Move.toml as written on disk.
+#[derive(Debug, Clone)]
+pub struct MoveTomlDocument {
+    doc: Document,
+}
+
+impl FromStr for MoveTomlDocument {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let doc = s
+            .parse::<Document>()
+            .map_err(|err| anyhow!("Could not parse Move.toml: {err}"))?;
+
+        Ok(Self { doc })
+    }
+}
+
+impl fmt::Display for MoveTomlDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.doc.fmt(f)
+    }
+}
+
+impl MoveTomlDocument {
+    /// Reads the Move.toml at the given path.
+    pub fn read(path: &Path) -> Result<Self> {
+        fs::read_to_string(path)
+            .map_err(|err| {
+                anyhow!(r#"Could not read "{}": {err}"#, path.display())
+            })?
+            .parse()
+    }
+
+    /// Writes the Move.toml to the given path.
+    ///
+    /// The file is replaced atomically, such that an interrupted write never
+    /// leaves a truncated Move.toml behind.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        write_atomic(path, &self.to_string())
+    }
+
+    /// Name of the package declared in `[package]`.
+    pub fn package_name(&self) -> Option<&str> {
+        self.doc.get("package")?.get("name")?.as_str()
+    }
+
+    /// Named address of the package itself, as declared in `[addresses]`.
+    ///
+    /// OriginByte packages declare theirs with an `ob_` prefix, e.g. `Kiosk`
+    /// declares `ob_kiosk`, which is used when declared. Otherwise this is
+    /// the package name in snake case, whether declared yet or not.
+    pub fn package_address(&self) -> Option<String> {
+        let address = snake_case(self.package_name()?);
+        let prefixed = format!("ob_{address}");
+
+        let addresses = self.doc.get("addresses");
+        match addresses.and_then(|addresses| addresses.get(&prefixed)) {
+            Some(_) => Some(prefixed),
+            None => Some(address),
+        }
+    }
+
+    /// Network flavor declared in `[package]`.
+    pub fn flavor(&self) -> Option<Flavor> {
+        match self.doc.get("package")?.get("flavor")?.as_str()? {
+            "Mainnet" => Some(Flavor::Mainnet),
+            "Testnet" => Some(Flavor::Testnet),
+            _ => None,
+        }
+    }
+
+    /// Sets the `published-at` address of the package.
+    pub fn set_published_at(&mut self, address: &Address) -> Result<()> {
+        let table = self
+            .doc
+            .get_mut("package")
+            .and_then(Item::as_table_like_mut)
+            .ok_or_else(|| anyhow!("Move.toml has no `[package]` table"))?;
+
+        set_address(table, "published-at", address);
+
+        Ok(())
+    }
+
+    /// Assigns a single named address, leaving the other ones untouched.
+    pub fn set_address(&mut self, name: &str, address: &Address) -> Result<()> {
+        let table = self
+            .doc
+            .entry("addresses")
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut()
+            .ok_or_else(|| {
+                anyhow!("`addresses` of Move.toml is not a table")
+            })?;
+
+        set_address(table, name, address);
+
+        Ok(())
+    }
+
+    /// Sets the `[dependencies]` of the Move.toml to the given ones.
+    ///
+    /// Entries already pointing at the given location are left untouched,
+    /// and only the `git`, `subdir` and `rev` keys of the remaining entries
+    /// are rewritten, keeping their other keys and comments. New entries are
+    /// written in the style of the existing ones, as `[dependencies.Name]`
+    /// tables unless the existing ones are inline tables.
+    ///
+    /// # Arguments
+    /// * `dependencies` - The dependencies the Move.toml must declare.
+    pub fn set_dependencies(
+        &mut self,
+        dependencies: &BTreeMap<String, GitPath>,
+    ) -> Result<()> {
+        let table = self
+            .doc
+            .entry("dependencies")
+            .or_insert_with(implicit_table)
+            .as_table_like_mut()
+            .ok_or_else(|| {
+                anyhow!("`dependencies` of Move.toml is not a table")
+            })?;
+
+        let stale: Vec<String> = table
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .filter(|name| !dependencies.contains_key(name))
+            .collect();
+        stale.iter().for_each(|name| {
+            table.remove(name);
+        });
+
+        let use_inline = table.iter().any(|(_, item)| item.is_inline_table());
+
+        for (name, path) in dependencies.iter() {
+            match table.get_mut(name).and_then(Item::as_table_like_mut) {
+                Some(entry) => set_path(entry, path),
+                None if use_inline => {
+                    let mut entry = Table::new();
+                    set_path(&mut entry, path);
+                    table.insert(name, value(entry.into_inline_table()));
+                }
+                None => {
+                    let mut entry = Table::new();
+                    entry.decor_mut().set_prefix("\n");
+                    set_path(&mut entry, path);
+                    table.insert(name, Item::Table(entry));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the `[addresses]` of the Move.toml to the given ones.
+    ///
+    /// Entries already assigned to the given address are left untouched,
+    /// even if written with fewer leading zeros.
+    ///
+    /// # Arguments
+    /// * `addresses` - The named addresses the Move.toml must declare.
+    pub fn set_addresses(
+        &mut self,
+        addresses: &BTreeMap<String, Address>,
+    ) -> Result<()> {
+        let table = self
+            .doc
+            .entry("addresses")
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut()
+            .ok_or_else(|| {
+                anyhow!("`addresses` of Move.toml is not a table")
+            })?;
+
+        let stale: Vec<String> = table
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .filter(|name| !addresses.contains_key(name))
+            .collect();
+        stale.iter().for_each(|name| {
+            table.remove(name);
+        });
+
+        addresses
+            .iter()
+            .for_each(|(name, address)| set_address(table, name, address));
+
+        Ok(())
+    }
+}
+
+/// Replaces the file through a rename, which is atomic on the same file
+/// system
+pub(crate) fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    fs::write(&tmp_path, contents)
+        .and_then(|_| fs::rename(&tmp_path, path))
+        .map_err(|err| {
+            let _ = fs::remove_file(&tmp_path);
+            anyhow!(r#"Could not write "{}": {err}"#, path.display())
+        })
+}
+
+fn implicit_table() -> Item {
+    let mut table = Table::new();
+    table.set_implicit(true);
+    Item::Table(table)
+}
+
+/// Writes the location keys of a dependency entry that differ from the path
+fn set_path(entry: &mut dyn TableLike, path: &GitPath) {
+    set_str(entry, "git", Some(&path.git));
+    set_str(entry, "subdir", path.subdir.as_deref());
+    set_str(entry, "rev", Some(&path.rev));
+}
+
+fn set_address(entry: &mut dyn TableLike, key: &str, address: &Address) {
+    // Addresses are equal regardless of their leading zeros
+    let current = entry
+        .get(key)
+        .and_then(Item::as_str)
+        .and_then(|current| Address::new(current).ok());
+
+    if current.as_ref() != Some(address) {
+        set_str(entry, key, Some(&address.to_string()))
+    }
+}
+
+fn set_str(entry: &mut dyn TableLike, key: &str, new: Option<&str>) {
+    let Some(new) = new else {
+        entry.remove(key);
+        return;
+    };
+
+    match entry.get_mut(key).and_then(Item::as_value_mut) {
+        Some(current) if current.as_str() == Some(new) => (),
+        Some(current) => {
+            // Keep the whitespace and comments surrounding the value
+            let decor = current.decor().clone();
+            *current = Value::from(new);
+            *current.decor_mut() = decor;
+        }
+        None => {
+            entry.insert(key, value(new));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MOVE_TOML: &str = r#"# Suimarines collection
+[package]
+name = "Suimarines"
+version = "1.0.0"
+flavor = "Mainnet"
+
+[dependencies.NftProtocol]
+git = "https://github.com/Origin-Byte/nft-protocol.git"
+subdir = "contracts/nft_protocol"
+# Pinned until the launch
+rev = "95d16538dc7688dd4c4a5e7c3348bf3addf9c310" # v1.0.0
+
+[dependencies.Sui]
+git = "https://github.com/MystenLabs/sui.git"
+subdir = "crates/sui-framework/packages/sui-framework"
+rev = "ae1212baf8f0837e25926d941db3d26a61c1bea2"
+override = true
+
+[dev-dependencies]
+Tests = { local = "../tests" }
+
+[addresses]
+suimarines = "0x0"
+
+[dev-addresses]
+suimarines = "0x1"
+
+[byte.constraints]
+NftProtocol = "^1.0"
+"#;
+
+    fn dependencies(document: &MoveTomlDocument) -> BTreeMap<String, GitPath> {
+        let move_toml: crate::toml::MoveToml =
+            toml::from_str(&document.to_string()).unwrap();
+        move_toml.dependencies
+    }
+
+    #[test]
+    fn unchanged_dependencies_keep_document() -> Result<()> {
+        let mut document: MoveTomlDocument = MOVE_TOML.parse()?;
+        let deps = dependencies(&document);
+
+        document.set_dependencies(&deps)?;
+        assert_eq!(document.to_string(), MOVE_TOML);
+
+        Ok(())
+    }
+
+    #[test]
+    fn only_touches_changed_entries() -> Result<()> {
+        let mut document: MoveTomlDocument = MOVE_TOML.parse()?;
+        let mut deps = dependencies(&document);
+
+        deps.get_mut("NftProtocol").unwrap().rev =
+            "93f6cd0b8966354b1b00e7d798cbfddaa867a07b".to_string();
+        deps.insert(
+            "Launchpad".to_string(),
+            GitPath::new(
+                "https://github.com/Origin-Byte/nft-protocol.git".to_string(),
+                Some("contracts/launchpad".to_string()),
+                "93f6cd0b8966354b1b00e7d798cbfddaa867a07b".to_string(),
+            ),
+        );
+
+        document.set_dependencies(&deps)?;
+
+        let expected = MOVE_TOML
+            .replace(
+                r#"rev = "95d16538dc7688dd4c4a5e7c3348bf3addf9c310" # v1.0.0"#,
+                r#"rev = "93f6cd0b8966354b1b00e7d798cbfddaa867a07b" # v1.0.0"#,
+            )
+            .replace(
+                "override = true\n",
+                r#"override = true
+
+[dependencies.Launchpad]
+git = "https://github.com/Origin-Byte/nft-protocol.git"
+subdir = "contracts/launchpad"
+rev = "93f6cd0b8966354b1b00e7d798cbfddaa867a07b"
+"#,
+            );
+        assert_eq!(document.to_string(), expected);
+        assert_eq!(dependencies(&document), deps);
+
+        deps.remove("Launchpad");
+        document.set_dependencies(&deps)?;
+        assert!(!document.to_string().contains("Launchpad"));
+        assert!(document.to_string().contains("[dev-addresses]"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn reads_package_address() -> Result<()> {
+        let document: MoveTomlDocument = MOVE_TOML.parse()?;
+        assert_eq!(document.package_address().unwrap(), "suimarines");
+
+        let document: MoveTomlDocument = r#"[package]
+name = "Kiosk"
+
+[addresses]
+ob_kiosk = "0x0"
+"#
+        .parse()?;
+        assert_eq!(document.package_address().unwrap(), "ob_kiosk");
+
+        let document: MoveTomlDocument = r#"[package]
+name = "Kiosk"
+
+[addresses]
+kiosk = "0x0"
+"#
+        .parse()?;
+        assert_eq!(document.package_address().unwrap(), "kiosk");
+
+        Ok(())
+    }
+
+    #[test]
+    fn edits_addresses_in_place() -> Result<()> {
+        let mut document: MoveTomlDocument = MOVE_TOML.parse()?;
+        let move_toml: crate::toml::MoveToml =
+            toml::from_str(&document.to_string())?;
+
+        let mut addresses = move_toml.addresses;
+        document.set_addresses(&addresses)?;
+        assert_eq!(document.to_string(), MOVE_TOML);
+
+        addresses.insert("kiosk".to_string(), Address::new("0x95a4")?);
+        document.set_addresses(&addresses)?;
+        assert!(document
+            .to_string()
+            .contains("suimarines = \"0x0\"\nkiosk = \"0x0000"));
+
+        addresses.remove("kiosk");
+        document.set_addresses(&addresses)?;
+        assert_eq!(document.to_string(), MOVE_TOML);
+
+        Ok(())
+    }
+
+    #[test]
+    fn follows_inline_style() -> Result<()> {
+        let mut document: MoveTomlDocument = r#"[package]
+name = "Gnomes"
+version = "1.0.0"
+
+[dependencies]
+Sui = { git = "https://github.com/MystenLabs/sui.git", rev = "1" }
+
+[addresses]
+gnomes = "0x0"
+"#
+        .parse()?;
+
+        let mut deps = dependencies(&document);
+        deps.insert(
+            "Permissions".to_string(),
+            GitPath::new("https://example.com".to_string(), None, "2".into()),
+        );
+        document.set_dependencies(&deps)?;
+
+        assert!(document.to_string().contains(
+            r#"Permissions = { git = "https://example.com", rev = "2" }"#
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,325 @@
+//! Per-network manifests of a Move package
+//!
+//! A package is deployed to several networks, each with its own dependency
+//! revisions and its own published address. Every network therefore has its
+//! own manifest under `flavours/`, of which the active one is copied to
+//! Move.toml. Changes to the active Move.toml are written back to the
+//! manifest of its network, such that switching networks never loses them.
+
+use anyhow::{anyhow, Result};
+use gutenberg_types::models::address::Address;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    document::{write_atomic, MoveTomlDocument},
+    package::Flavor,
+    toml::MoveToml,
+    Network,
+};
+
+/// Directory of the per-network manifests, relative to the contract
+/// directory
+pub const FLAVOURS_DIR: &str = "flavours";
+
+/// Path of the manifest of the network.
+///
+/// # Arguments
+/// * `contract_dir` - The directory of the Move package.
+/// * `flavor` - The network flavor.
+pub fn manifest_path(contract_dir: &Path, flavor: Flavor) -> PathBuf {
+    let file_name = match flavor {
+        Flavor::Mainnet => "Move-main.toml",
+        Flavor::Testnet => "Move-test.toml",
+    };
+
+    contract_dir.join(FLAVOURS_DIR).join(file_name)
+}
+
+/// Checks that the active Move.toml is loaded for the network.
+///
+/// # Arguments
+/// * `move_toml` - The active Move.toml.
+/// * `network` - The network the command runs against.
+///
+/// # Returns
+/// An error if the Move.toml declares the flavor of another network.
+pub fn check_flavor(move_toml: &MoveToml, network: &Network) -> Result<()> {
+    match move_toml.package.flavor {
+        Some(flavor) if flavor != network.flavor() => Err(anyhow!(
+            "Move.toml is loaded for {}, but {network} builds against {}, \
+            call `byte mpm load-env {network}` first",
+            flavor.to_str(),
+            network.flavor().to_str()
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Writes the active Move.toml, along with the manifest of its network if
+/// the package keeps per-network manifests.
+///
+/// # Arguments
+/// * `contract_dir` - The directory of the Move package.
+/// * `document` - The contents of the active Move.toml.
+pub fn write_manifest(
+    contract_dir: &Path,
+    document: &MoveTomlDocument,
+) -> Result<()> {
+    if let Some(flavor) = document.flavor() {
+        let path = manifest_path(contract_dir, flavor);
+        if path.exists() {
+            document.write(&path)?;
+        }
+    }
+
+    document.write(&contract_dir.join("Move.toml"))
+}
+
+/// Switches the active Move.toml to the manifest of the network.
+///
+/// Switching is refused if the active Move.toml contains changes that are
+/// not in the manifest of its network, unless `force` is set, in which case
+/// they are discarded.
+///
+/// # Arguments
+/// * `contract_dir` - The directory of the Move package.
+/// * `flavor` - The network flavor to switch to.
+/// * `force` - Whether to discard changes of the active Move.toml.
+pub fn load(contract_dir: &Path, flavor: Flavor, force: bool) -> Result<()> {
+    let source_path = manifest_path(contract_dir, flavor);
+    let source = fs::read_to_string(&source_path).map_err(|err| {
+        anyhow!(r#"Could not read "{}": {err}"#, source_path.display())
+    })?;
+
+    let active_path = contract_dir.join("Move.toml");
+    if !force && active_path.exists() {
+        let active = fs::read_to_string(&active_path)?;
+        check_saved(contract_dir, &active, &source)?;
+    }
+
+    write_atomic(&active_path, &source)
+}
+
+/// Fails if the active Move.toml differs from both the manifest of its own
+/// network and the manifest being switched to
+fn check_saved(contract_dir: &Path, active: &str, target: &str) -> Result<()> {
+    if active == target {
+        return Ok(());
+    }
+
+    let saved_path = active
+        .parse::<MoveTomlDocument>()
+        .ok()
+        .and_then(|document| document.flavor())
+        .map(|flavor| manifest_path(contract_dir, flavor));
+
+    if let Some(saved_path) = saved_path {
+        match fs::read_to_string(&saved_path) {
+            Ok(saved) if saved == active => return Ok(()),
+            Ok(_) => {
+                return Err(anyhow!(
+                    r#"Move.toml has changes that are not in "{}", copy them there or use `--force` to discard them"#,
+                    saved_path.display()
+                ))
+            }
+            Err(_) => (),
+        }
+    }
+
+    Err(anyhow!(
+        "Move.toml does not match any per-network manifest, use `--force` to \
+        discard it"
+    ))
+}
+
+/// Records the addresses of the published package in the manifest of the
+/// network, and in the active Move.toml if it is for the same network.
+///
+/// The original package ID is assigned to the named address of the package,
+/// such that other packages can depend on it, while `published-at` points to
+/// the latest version of the package, which differs after an upgrade.
+///
+/// # Arguments
+/// * `contract_dir` - The directory of the Move package.
+/// * `flavor` - The network the package was published to.
+/// * `original_id` - ID of the first version of the package.
+/// * `published_at` - ID of the latest version of the package.
+pub fn record_publish(
+    contract_dir: &Path,
+    flavor: Flavor,
+    original_id: &Address,
+    published_at: &Address,
+) -> Result<()> {
+    let paths = [
+        manifest_path(contract_dir, flavor),
+        contract_dir.join("Move.toml"),
+    ];
+
+    for path in paths.iter().filter(|path| path.exists()) {
+        let mut document = MoveTomlDocument::read(path)?;
+        if document.flavor().is_some_and(|other| other != flavor) {
+            continue;
+        }
+
+        let name = document.package_address().ok_or_else(|| {
+            anyhow!(r#""{}" has no package name"#, path.display())
+        })?;
+
+        document.set_published_at(published_at)?;
+        document.set_address(&name, original_id)?;
+        document.write(path)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn manifest(flavor: &str, rev: &str) -> String {
+        format!(
+            r#"[package]
+name = "Suimarines"
+version = "1.0.0"
+flavor = "{flavor}"
+
+[dependencies.Sui]
+git = "https://github.com/MystenLabs/sui.git"
+rev = "{rev}"
+
+[addresses]
+suimarines = "0x0"
+"#
+        )
+    }
+
+    fn contract_dir() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join(FLAVOURS_DIR)).unwrap();
+
+        let main = manifest("Mainnet", "main");
+        let test = manifest("Testnet", "test");
+        fs::write(manifest_path(dir.path(), Flavor::Mainnet), &main).unwrap();
+        fs::write(manifest_path(dir.path(), Flavor::Testnet), test).unwrap();
+        fs::write(dir.path().join("Move.toml"), main).unwrap();
+
+        dir
+    }
+
+    fn active(dir: &TempDir) -> String {
+        fs::read_to_string(dir.path().join("Move.toml")).unwrap()
+    }
+
+    #[test]
+    fn switches_between_networks() -> Result<()> {
+        let dir = contract_dir();
+
+        load(dir.path(), Flavor::Testnet, false)?;
+        assert_eq!(active(&dir), manifest("Testnet", "test"));
+
+        load(dir.path(), Flavor::Mainnet, false)?;
+        assert_eq!(active(&dir), manifest("Mainnet", "main"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn refuses_to_lose_manual_edits() -> Result<()> {
+        let dir = contract_dir();
+        let edited = manifest("Mainnet", "edited");
+        fs::write(dir.path().join("Move.toml"), &edited)?;
+
+        let err = load(dir.path(), Flavor::Testnet, false).unwrap_err();
+        assert!(err.to_string().contains("Move-main.toml"));
+        assert_eq!(active(&dir), edited);
+
+        // Edits made through the package manager are kept in sync
+        write_manifest(dir.path(), &edited.parse()?)?;
+        load(dir.path(), Flavor::Testnet, false)?;
+        load(dir.path(), Flavor::Mainnet, false)?;
+        assert_eq!(active(&dir), edited);
+
+        fs::write(dir.path().join("Move.toml"), "[package]\n")?;
+        assert!(load(dir.path(), Flavor::Testnet, false).is_err());
+        load(dir.path(), Flavor::Testnet, true)?;
+        assert_eq!(active(&dir), manifest("Testnet", "test"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn checks_flavor_of_network() -> Result<()> {
+        let move_toml: MoveToml = toml::from_str(&manifest("Mainnet", "main"))?;
+
+        check_flavor(&move_toml, &Network::Mainnet)?;
+
+        let err = check_flavor(&move_toml, &Network::Localnet).unwrap_err();
+        assert!(err.to_string().contains("load-env localnet"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn records_published_addresses() -> Result<()> {
+        let dir = contract_dir();
+        let original_id = Address::new("0xa1")?;
+        let published_at = Address::new("0xb2")?;
+
+        record_publish(
+            dir.path(),
+            Flavor::Testnet,
+            &original_id,
+            &original_id,
+        )?;
+        record_publish(
+            dir.path(),
+            Flavor::Testnet,
+            &original_id,
+            &published_at,
+        )?;
+
+        let test =
+            fs::read_to_string(manifest_path(dir.path(), Flavor::Testnet))?;
+        assert!(test.contains(&format!("published-at = \"{published_at}\"")));
+        assert!(test.contains(&format!("suimarines = \"{original_id}\"")));
+
+        // Active Move.toml is for Mainnet
+        assert_eq!(active(&dir), manifest("Mainnet", "main"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn records_published_ob_addresses() -> Result<()> {
+        let dir = TempDir::new()?;
+        fs::write(
+            dir.path().join("Move.toml"),
+            r#"[package]
+name = "Launchpad"
+
+[addresses]
+ob_launchpad = "0x0"
+"#,
+        )?;
+        let original_id = Address::new("0xa1")?;
+
+        record_publish(
+            dir.path(),
+            Flavor::Mainnet,
+            &original_id,
+            &original_id,
+        )?;
+
+        let manifest = active(&dir);
+        let address = format!("ob_launchpad = \"{original_id}\"");
+        assert!(manifest.contains(&address));
+        assert!(!manifest.contains("\nlaunchpad ="));
+
+        Ok(())
+    }
+}
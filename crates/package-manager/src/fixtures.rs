@@ -0,0 +1,22 @@
+//! Fixtures shared by the tests of the crate
+
+use crate::package::{Flavor, PackageRegistry};
+use std::path::Path;
+
+/// Public registry of mainnet packages
+pub fn registry() -> PackageRegistry {
+    PackageRegistry::read(
+        Path::new("registry/registry-main.json"),
+        Flavor::Mainnet,
+    )
+    .unwrap()
+}
+
+/// Public registry of testnet packages
+pub fn test_registry() -> PackageRegistry {
+    PackageRegistry::read(
+        Path::new("registry/registry-test.json"),
+        Flavor::Testnet,
+    )
+    .unwrap()
+}
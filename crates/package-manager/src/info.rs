@@ -1,7 +1,8 @@
+use anyhow::{anyhow, Result};
 use convert_case::{Case, Casing};
 use gutenberg_types::models::address::Address;
 use serde::Deserialize;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, fs, path::Path};
 
 use crate::OB_PACKAGES;
 
@@ -13,7 +14,7 @@ use crate::OB_PACKAGES;
 /// # Fields
 /// * `packages` - Contains the compiled package information, aliased as "compiled_package_info"
 ///                during deserialization.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct BuildInfo {
     #[serde(alias = "compiled_package_info")]
     pub packages: CompiledPackageInfo,
@@ -28,7 +29,7 @@ pub struct BuildInfo {
 /// * `package_name` - The name of the package.
 /// * `ob_packages` - A `BTreeMap` mapping package names to their blockchain addresses.
 ///                  These names are aliased as "address_alias_instantiation" during deserialization.
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct CompiledPackageInfo {
     pub package_name: String,
     #[serde(alias = "address_alias_instantiation")]
@@ -73,3 +74,68 @@ impl CompiledPackageInfo {
         self.ob_packages = canonical;
     }
 }
+
+/// Represents the contents of a Move.lock file, written by `sui move build`.
+///
+/// # Fields
+/// * `table` - The `[move]` table of the lock file.
+#[derive(Deserialize, Debug, Default)]
+pub struct MoveLock {
+    #[serde(rename = "move", default)]
+    pub table: MoveLockTable,
+}
+
+/// Represents the `[move]` table of a Move.lock file.
+///
+/// # Fields
+/// * `packages` - Every package reachable from the dependencies, as
+///   resolved by the Move compiler.
+#[derive(Deserialize, Debug, Default)]
+pub struct MoveLockTable {
+    #[serde(rename = "package", default)]
+    pub packages: Vec<MoveLockPackage>,
+}
+
+/// Represents a package resolved by the Move compiler.
+///
+/// # Fields
+/// * `name` - The name of the package.
+/// * `source` - Where the sources of the package were fetched from.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MoveLockPackage {
+    pub name: String,
+    pub source: MoveLockSource,
+}
+
+/// Location of a package resolved by the Move compiler, either a git
+/// revision or a local directory.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MoveLockSource {
+    pub git: Option<String>,
+    pub subdir: Option<String>,
+    pub rev: Option<String>,
+    pub local: Option<String>,
+}
+
+impl MoveLock {
+    /// Reads the Move.lock at the given path.
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path).map_err(|err| {
+            anyhow!(r#"Could not read "{}": {err}"#, path.display())
+        })?;
+
+        toml::from_str(&contents).map_err(|err| {
+            anyhow!(r#"Could not parse "{}": {err}"#, path.display())
+        })
+    }
+
+    /// Resolved packages, ordered as written by the Move compiler.
+    pub fn packages(&self) -> &[MoveLockPackage] {
+        &self.table.packages
+    }
+
+    /// Retrieves a resolved package by name.
+    pub fn package(&self, name: &str) -> Option<&MoveLockPackage> {
+        self.packages().iter().find(|package| package.name == name)
+    }
+}
@@ -0,0 +1,483 @@
+//! Registry integrity
+//!
+//! Registry entries are written by hand or by `mpm registry add`, so nothing
+//! guarantees that the IDs they reference point to the packages they
+//! describe. The integrity check reads every referenced object on-chain and
+//! verifies that it is a package, and that the versions of each package form
+//! a lineage of upgrades of its original package.
+//!
+//! Objects are read through a `PackageReader`, which is implemented over the
+//! RPC client by the CLI and over a fixed set of objects in tests.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use gutenberg_types::models::address::Address;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display},
+};
+
+use crate::{package::PackageRegistry, version::Version};
+
+/// Packages of the Sui framework, which are upgraded in place by the system
+/// and thus keep their ID across versions.
+const SYSTEM_PACKAGES: [&str; 4] = ["0x1", "0x2", "0x3", "0xdee9"];
+
+/// Package object as read from chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageObject {
+    /// Version of the object, which is 1 for an original package and
+    /// increases with every upgrade.
+    pub version: u64,
+    /// Names of the modules of the package.
+    pub modules: BTreeSet<String>,
+}
+
+/// Object as read from chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OnChainObject {
+    Package(PackageObject),
+    /// Any object other than a package, along with its type
+    Other(String),
+}
+
+/// Reads objects from chain.
+#[async_trait]
+pub trait PackageReader {
+    /// Reads the object with the given ID, returning `None` if it does not
+    /// exist.
+    async fn read_object(&self, id: &Address) -> Result<Option<OnChainObject>>;
+}
+
+/// Field of a registry entry referencing an object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reference {
+    /// `package.publishedAt`, the ID of the package version
+    PublishedAt,
+    /// `contractRef.objectId`, the ID of the original package
+    ObjectId,
+    /// `objectId` of a dependency, the ID of its original package
+    Dependency(String),
+}
+
+impl Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reference::PublishedAt => f.write_str("publishedAt"),
+            Reference::ObjectId => f.write_str("contractRef.objectId"),
+            Reference::Dependency(name) => {
+                write!(f, "objectId of dependency '{name}'")
+            }
+        }
+    }
+}
+
+/// Inconsistency between the registry and the objects on-chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityIssue {
+    /// Referenced object does not exist
+    Missing {
+        name: String,
+        version: Version,
+        reference: Reference,
+        id: Address,
+    },
+    /// Referenced object is not a package
+    NotAPackage {
+        name: String,
+        version: Version,
+        reference: Reference,
+        id: Address,
+        object_type: String,
+    },
+    /// Version is registered with another original package than the
+    /// previous versions
+    ChangedObjectId {
+        name: String,
+        version: Version,
+        id: Address,
+        original: Address,
+    },
+    /// Referenced original package is an upgrade
+    NotOriginal {
+        name: String,
+        version: Version,
+        reference: Reference,
+        id: Address,
+        object_version: u64,
+    },
+    /// Version is published at an older package than the previous version
+    VersionOrder {
+        name: String,
+        version: Version,
+        previous: Version,
+    },
+    /// Version removes modules of the previous version, which upgrades may
+    /// not do
+    RemovedModules {
+        name: String,
+        version: Version,
+        previous: Version,
+        modules: Vec<String>,
+    },
+}
+
+impl Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntegrityIssue::Missing {
+                name,
+                version,
+                reference,
+                id,
+            } => write!(f, "{name} {version}: {reference} {id} does not exist"),
+            IntegrityIssue::NotAPackage {
+                name,
+                version,
+                reference,
+                id,
+                object_type,
+            } => write!(
+                f,
+                "{name} {version}: {reference} {id} is not a package but a \
+                {object_type}"
+            ),
+            IntegrityIssue::ChangedObjectId {
+                name,
+                version,
+                id,
+                original,
+            } => write!(
+                f,
+                "{name} {version}: contractRef.objectId {id} differs from \
+                {original} of the previous versions"
+            ),
+            IntegrityIssue::NotOriginal {
+                name,
+                version,
+                reference,
+                id,
+                object_version,
+            } => write!(
+                f,
+                "{name} {version}: {reference} {id} is not an original \
+                package but version {object_version} of an upgrade"
+            ),
+            IntegrityIssue::VersionOrder {
+                name,
+                version,
+                previous,
+            } => write!(
+                f,
+                "{name} {version}: published at an older package than \
+                {previous}"
+            ),
+            IntegrityIssue::RemovedModules {
+                name,
+                version,
+                previous,
+                modules,
+            } => write!(
+                f,
+                "{name} {version}: removes modules {} of {previous}",
+                modules.join(", ")
+            ),
+        }
+    }
+}
+
+/// Checks every package version in the registry against the objects
+/// on-chain.
+///
+/// Each referenced object must exist and be a package. Original IDs must
+/// point to original packages, and the versions of a package must share
+/// their original ID, be published at increasingly upgraded packages and
+/// keep the modules of their previous version.
+///
+/// # Arguments
+/// * `registry` - The registry to check.
+/// * `reader` - Reads the referenced objects, each of them once.
+///
+/// # Returns
+/// The issues found, or an error if an object could not be read.
+pub async fn verify_registry(
+    registry: &PackageRegistry,
+    reader: &(dyn PackageReader + Sync),
+) -> Result<Vec<IntegrityIssue>> {
+    let ids: BTreeSet<&Address> = registry
+        .0
+        .values()
+        .flat_map(BTreeMap::values)
+        .flat_map(|info| {
+            info.package
+                .published_at
+                .iter()
+                .chain([&info.contract_ref.object_id])
+                .chain(info.dependencies.values().map(|dep| &dep.object_id))
+        })
+        .collect();
+
+    let mut objects = BTreeMap::new();
+    for id in ids {
+        objects.insert(id, reader.read_object(id).await?);
+    }
+
+    let mut issues = Vec::new();
+
+    for (name, versions) in registry.0.iter() {
+        let mut original: Option<&Address> = None;
+        let mut previous: Option<(&Version, &Address, &PackageObject)> = None;
+
+        for (version, info) in versions.iter() {
+            let mut package = |reference: Reference, id: &Address| {
+                package_object(&objects, name, version, reference, id)
+                    .map_err(|issue| issues.push(*issue))
+                    .ok()
+            };
+
+            // Original packages referenced as such must not be upgrades
+            let mut originals = vec![(
+                Reference::ObjectId,
+                &info.contract_ref.object_id,
+                package(Reference::ObjectId, &info.contract_ref.object_id),
+            )];
+            for (dep_name, dep) in info.dependencies.iter() {
+                let reference = Reference::Dependency(dep_name.clone());
+                let object = package(reference.clone(), &dep.object_id);
+                originals.push((reference, &dep.object_id, object));
+            }
+
+            let published = info.package.published_at.as_ref().and_then(|id| {
+                package(Reference::PublishedAt, id).map(|object| (id, object))
+            });
+
+            for (reference, id, object) in originals {
+                if let Some(object) = object.filter(|object| {
+                    object.version != 1 && !is_system_package(id)
+                }) {
+                    issues.push(IntegrityIssue::NotOriginal {
+                        name: name.clone(),
+                        version: version.clone(),
+                        reference,
+                        id: id.clone(),
+                        object_version: object.version,
+                    });
+                }
+            }
+
+            let object_id = &info.contract_ref.object_id;
+            match original {
+                Some(original) if original != object_id => {
+                    issues.push(IntegrityIssue::ChangedObjectId {
+                        name: name.clone(),
+                        version: version.clone(),
+                        id: object_id.clone(),
+                        original: original.clone(),
+                    })
+                }
+                Some(_) => (),
+                None => original = Some(object_id),
+            }
+
+            let Some((id, object)) = published else {
+                continue;
+            };
+
+            if let Some((prev_version, prev_id, prev_object)) = previous {
+                if prev_id != id && prev_object.version >= object.version {
+                    issues.push(IntegrityIssue::VersionOrder {
+                        name: name.clone(),
+                        version: version.clone(),
+                        previous: prev_version.clone(),
+                    });
+                }
+
+                let removed: Vec<String> = prev_object
+                    .modules
+                    .difference(&object.modules)
+                    .cloned()
+                    .collect();
+
+                if !removed.is_empty() {
+                    issues.push(IntegrityIssue::RemovedModules {
+                        name: name.clone(),
+                        version: version.clone(),
+                        previous: prev_version.clone(),
+                        modules: removed,
+                    });
+                }
+            }
+
+            previous = Some((version, id, object));
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Looks up a referenced object, which must be a package.
+fn package_object<'a>(
+    objects: &'a BTreeMap<&Address, Option<OnChainObject>>,
+    name: &str,
+    version: &Version,
+    reference: Reference,
+    id: &Address,
+) -> Result<&'a PackageObject, Box<IntegrityIssue>> {
+    match objects.get(id).and_then(Option::as_ref) {
+        Some(OnChainObject::Package(package)) => Ok(package),
+        Some(OnChainObject::Other(object_type)) => {
+            Err(Box::new(IntegrityIssue::NotAPackage {
+                name: name.to_string(),
+                version: version.clone(),
+                reference,
+                id: id.clone(),
+                object_type: object_type.clone(),
+            }))
+        }
+        None => Err(Box::new(IntegrityIssue::Missing {
+            name: name.to_string(),
+            version: version.clone(),
+            reference,
+            id: id.clone(),
+        })),
+    }
+}
+
+fn is_system_package(id: &Address) -> bool {
+    SYSTEM_PACKAGES
+        .iter()
+        .any(|system| Address::new(system).ok().as_ref() == Some(id))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::registry;
+
+    /// Serves a fixed set of objects in place of the RPC client
+    struct MockReader(BTreeMap<Address, OnChainObject>);
+
+    #[async_trait]
+    impl PackageReader for MockReader {
+        async fn read_object(
+            &self,
+            id: &Address,
+        ) -> Result<Option<OnChainObject>> {
+            Ok(self.0.get(id).cloned())
+        }
+    }
+
+    fn package(version: u64, modules: &[&str]) -> OnChainObject {
+        OnChainObject::Package(PackageObject {
+            version,
+            modules: modules.iter().map(|module| module.to_string()).collect(),
+        })
+    }
+
+    /// Chain on which every registered version is published as the next
+    /// upgrade of its package
+    fn chain(registry: &PackageRegistry) -> MockReader {
+        let mut objects = BTreeMap::new();
+
+        for versions in registry.0.values() {
+            for (index, info) in versions.values().enumerate() {
+                let id = info.package.published_at.clone().unwrap();
+                objects
+                    .entry(id)
+                    .or_insert_with(|| package(index as u64 + 1, &["module"]));
+            }
+        }
+
+        MockReader(objects)
+    }
+
+    fn permissions(registry: &PackageRegistry, version: Version) -> Address {
+        registry.0["Permissions"][&version]
+            .package
+            .published_at
+            .clone()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn accepts_consistent_registry() -> Result<()> {
+        let registry = registry();
+
+        let issues = verify_registry(&registry, &chain(&registry)).await?;
+        assert_eq!(issues, vec![]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reports_missing_and_foreign_objects() -> Result<()> {
+        let registry = registry();
+        let mut reader = chain(&registry);
+
+        let upgrade = permissions(&registry, Version::new(1, 2, 0));
+        reader.0.remove(&upgrade);
+
+        let original = permissions(&registry, Version::new(1, 0, 0));
+        reader.0.insert(
+            original.clone(),
+            OnChainObject::Other("0x2::coin::Coin".into()),
+        );
+
+        let issues = verify_registry(&registry, &reader).await?;
+
+        assert!(issues.contains(&IntegrityIssue::Missing {
+            name: "Permissions".to_string(),
+            version: Version::new(1, 2, 0),
+            reference: Reference::PublishedAt,
+            id: upgrade,
+        }));
+        // Dependents of Permissions reference its original package
+        assert!(issues.contains(&IntegrityIssue::NotAPackage {
+            name: "Request".to_string(),
+            version: Version::new(1, 0, 0),
+            reference: Reference::Dependency("Permissions".to_string()),
+            id: original,
+            object_type: "0x2::coin::Coin".to_string(),
+        }));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reports_broken_lineage() -> Result<()> {
+        let registry = registry();
+        let mut reader = chain(&registry);
+
+        reader.0.insert(
+            permissions(&registry, Version::new(1, 0, 0)),
+            package(2, &["module"]),
+        );
+        reader.0.insert(
+            permissions(&registry, Version::new(1, 2, 0)),
+            package(1, &["other"]),
+        );
+
+        let issues = verify_registry(&registry, &reader).await?;
+
+        assert!(issues.contains(&IntegrityIssue::NotOriginal {
+            name: "Permissions".to_string(),
+            version: Version::new(1, 0, 0),
+            reference: Reference::ObjectId,
+            id: permissions(&registry, Version::new(1, 0, 0)),
+            object_version: 2,
+        }));
+        assert!(issues.contains(&IntegrityIssue::VersionOrder {
+            name: "Permissions".to_string(),
+            version: Version::new(1, 2, 0),
+            previous: Version::new(1, 0, 0),
+        }));
+        assert!(issues.contains(&IntegrityIssue::RemovedModules {
+            name: "Permissions".to_string(),
+            version: Version::new(1, 2, 0),
+            previous: Version::new(1, 0, 0),
+            modules: vec!["module".to_string()],
+        }));
+
+        Ok(())
+    }
+}
@@ -1,16 +1,27 @@
+pub mod doctor;
+pub mod document;
+pub mod env;
 pub mod info;
+pub mod integrity;
+pub mod lock;
 pub mod package;
+pub mod registry;
+pub mod resolve;
 pub mod toml;
 pub mod version;
 
+#[cfg(test)]
+mod fixtures;
+
 use anyhow::{anyhow, Result};
 use git2::Repository;
 use package::{Flavor, PackageRegistry};
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     fmt::{self, Display},
     fs::File,
-    path::PathBuf,
+    path::{Path, PathBuf},
     str::FromStr,
 };
 use tempfile::TempDir;
@@ -32,31 +43,142 @@ pub const OB_PACKAGES: [&str; 12] = [
 ];
 
 /// Enum representing the different network environments.
-#[derive(Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
 pub enum Network {
     Mainnet,
     Testnet,
+    Devnet,
+    /// Local network started with `sui start`
+    Localnet,
+    /// Network of a custom RPC endpoint, whose packages are resolved against
+    /// the program registry of `registry`
+    Custom {
+        name: String,
+        rpc_url: String,
+        registry: Flavor,
+    },
 }
 
 impl FromStr for Network {
     type Err = ();
 
-    /// Parses a string into a Network type.
+    /// Parses the name of a built-in network into a Network type.
     fn from_str(input: &str) -> Result<Network, Self::Err> {
         match input {
             "mainnet" => Ok(Network::Mainnet),
             "testnet" => Ok(Network::Testnet),
+            "devnet" => Ok(Network::Devnet),
+            "localnet" => Ok(Network::Localnet),
             _ => Err(()),
         }
     }
 }
 
+/// Custom network as declared in a networks file, keyed by its name
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CustomNetwork {
+    rpc_url: String,
+    registry: Flavor,
+}
+
+impl Network {
+    /// Parses the name of a network, looking up names other than the
+    /// built-in networks in a JSON file of custom networks.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the network.
+    /// * `networks_path` - The path of the custom networks, which maps their
+    ///   names to their `rpcUrl` and `registry` flavor.
+    pub fn parse(name: &str, networks_path: &Path) -> Result<Network> {
+        if let Ok(network) = Network::from_str(name) {
+            return Ok(network);
+        }
+
+        let mut networks: BTreeMap<String, CustomNetwork> =
+            match networks_path.exists() {
+                true => serde_json::from_reader(File::open(networks_path)?)
+                    .map_err(|err| {
+                        anyhow!(
+                            r#"Could not parse "{}": {err}"#,
+                            networks_path.display()
+                        )
+                    })?,
+                false => BTreeMap::new(),
+            };
+
+        let network = networks.remove(name).ok_or_else(|| {
+            anyhow!(
+                "Invalid network '{name}', expected mainnet, testnet, devnet, \
+                localnet or a custom network of {}",
+                networks_path.display()
+            )
+        })?;
+
+        Ok(Network::Custom {
+            name: name.to_string(),
+            rpc_url: network.rpc_url,
+            registry: network.registry,
+        })
+    }
+
+    /// Flavor of the manifests built for the network, which also selects the
+    /// program registry its packages are resolved against.
+    ///
+    /// Devnet and localnet are used to rehearse releases, and thus build
+    /// against Testnet.
+    pub fn flavor(&self) -> Flavor {
+        match self {
+            Network::Mainnet => Flavor::Mainnet,
+            Network::Testnet | Network::Devnet | Network::Localnet => {
+                Flavor::Testnet
+            }
+            Network::Custom { registry, .. } => *registry,
+        }
+    }
+
+    /// Flavor under which packages published on the network are recorded,
+    /// in per-network manifests and private registries.
+    ///
+    /// Only releases on mainnet and testnet are recorded, as the packages of
+    /// other networks would overwrite the addresses of the network sharing
+    /// their flavor.
+    ///
+    /// # Returns
+    /// Result containing the Flavor of the network, or an error for networks
+    /// other than mainnet and testnet.
+    pub fn release_flavor(&self) -> Result<Flavor> {
+        match self {
+            Network::Mainnet => Ok(Flavor::Mainnet),
+            Network::Testnet => Ok(Flavor::Testnet),
+            _ => Err(anyhow!(
+                "Packages published on {self} are not recorded, only mainnet \
+                and testnet have their own manifests and registries"
+            )),
+        }
+    }
+
+    /// URL of the fullnode RPC endpoint of the network.
+    pub fn rpc_url(&self) -> &str {
+        match self {
+            Network::Mainnet => "https://fullnode.mainnet.sui.io:443",
+            Network::Testnet => "https://fullnode.testnet.sui.io:443",
+            Network::Devnet => "https://fullnode.devnet.sui.io:443",
+            Network::Localnet => "http://127.0.0.1:9000",
+            Network::Custom { rpc_url, .. } => rpc_url,
+        }
+    }
+}
+
 impl Display for Network {
     /// Implements formatting for displaying the Network type.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let string = match self {
             Network::Mainnet => "mainnet",
             Network::Testnet => "testnet",
+            Network::Devnet => "devnet",
+            Network::Localnet => "localnet",
+            Network::Custom { name, .. } => name,
         };
 
         f.write_str(string)
@@ -68,6 +190,19 @@ impl Display for Network {
 /// # Returns
 /// Result containing a tuple of `PackageRegistry` for Mainnet and Testnet.
 pub fn get_program_registries() -> Result<(PackageRegistry, PackageRegistry)> {
+    let (main_registry, test_registry, _) = get_program_registries_at_head()?;
+
+    Ok((main_registry, test_registry))
+}
+
+/// Retrieves package registries for both Mainnet and Testnet, along with the
+/// commit of the Program Registry they were read from.
+///
+/// # Returns
+/// Result containing a tuple of `PackageRegistry` for Mainnet and Testnet,
+/// and the commit hash.
+pub fn get_program_registries_at_head(
+) -> Result<(PackageRegistry, PackageRegistry, String)> {
     let (temp_dir, mainnet_path, testnet_path) = get_pakage_registry_paths();
 
     let url = "https://github.com/Origin-Byte/program-registry";
@@ -83,6 +218,8 @@ pub fn get_program_registries() -> Result<(PackageRegistry, PackageRegistry)> {
         ));
     }
 
+    let commit = repo.head()?.peel_to_commit()?.id().to_string();
+
     let mut main_registry: PackageRegistry =
         serde_json::from_reader(File::open(mainnet_path)?)?;
     let mut test_registry: PackageRegistry =
@@ -91,7 +228,7 @@ pub fn get_program_registries() -> Result<(PackageRegistry, PackageRegistry)> {
     main_registry.set_flavor(Flavor::Mainnet)?;
     test_registry.set_flavor(Flavor::Testnet)?;
 
-    Ok((main_registry, test_registry))
+    Ok((main_registry, test_registry, commit))
 }
 
 /// Retrieves a program registry for a specified network.
@@ -102,14 +239,87 @@ pub fn get_program_registries() -> Result<(PackageRegistry, PackageRegistry)> {
 /// # Returns
 /// Result containing the `PackageRegistry` for the specified network.
 pub fn get_program_registry(network: &Network) -> Result<PackageRegistry> {
-    let (main_registry, test_registry) = get_program_registries()?;
+    let (registry, _) = get_program_registry_at_head(network)?;
+
+    Ok(registry)
+}
+
+/// Retrieves a program registry for a specified network, along with the
+/// commit of the Program Registry it was read from.
+///
+/// # Arguments
+/// * `network` - The network for which to retrieve the package registry.
+///
+/// # Returns
+/// Result containing the `PackageRegistry` for the specified network and the
+/// commit hash.
+pub fn get_program_registry_at_head(
+    network: &Network,
+) -> Result<(PackageRegistry, String)> {
+    let (main_registry, test_registry, commit) =
+        get_program_registries_at_head()?;
 
-    Ok(match network {
-        Network::Mainnet => main_registry,
-        Network::Testnet => test_registry,
+    Ok(match network.flavor() {
+        Flavor::Mainnet => (main_registry, commit),
+        Flavor::Testnet => (test_registry, commit),
     })
 }
 
+/// Retrieves the program registry for a specified network merged with the
+/// private registry of the network, along with the commit of the Program
+/// Registry it was read from.
+///
+/// # Arguments
+/// * `network` - The network for which to retrieve the package registry.
+/// * `private_dir` - The directory of the private registry, which is skipped
+///   if it has no registry for the network.
+///
+/// # Returns
+/// Result containing the merged `PackageRegistry` for the specified network
+/// and the commit hash of the public Program Registry.
+pub fn get_merged_registry_at_head(
+    network: &Network,
+    private_dir: &Path,
+) -> Result<(PackageRegistry, String)> {
+    let (mut registry, commit) = get_program_registry_at_head(network)?;
+
+    let private_path = registry::registry_path(private_dir, network.flavor());
+    if private_path.exists() {
+        registry
+            .merge(PackageRegistry::read(&private_path, network.flavor())?)?;
+    }
+
+    Ok((registry, commit))
+}
+
+/// Retrieves package registries for both Mainnet and Testnet, each merged
+/// with the private registry of its network.
+///
+/// # Arguments
+/// * `private_dir` - The directory of the private registry, whose registry
+///   of a network is skipped if it does not exist.
+///
+/// # Returns
+/// Result containing a tuple of the merged `PackageRegistry` for Mainnet and
+/// Testnet.
+pub fn get_merged_registries(
+    private_dir: &Path,
+) -> Result<(PackageRegistry, PackageRegistry)> {
+    let (mut main_registry, mut test_registry) = get_program_registries()?;
+
+    for (registry, flavor) in [
+        (&mut main_registry, Flavor::Mainnet),
+        (&mut test_registry, Flavor::Testnet),
+    ] {
+        let private_path = registry::registry_path(private_dir, flavor);
+        if private_path.exists() {
+            registry.merge(PackageRegistry::read(&private_path, flavor)?)?;
+        }
+    }
+
+    Ok((main_registry, test_registry))
+}
+
 /// Generates temporary paths for main and test package registries.
 ///
 /// # Returns
@@ -127,3 +337,49 @@ pub fn get_pakage_registry_paths() -> (TempDir, PathBuf, PathBuf) {
 
     (temp_dir, registry_main_path, registry_test_path)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_custom_networks() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("networks.json");
+        std::fs::write(
+            &path,
+            r#"{ "staging": { "rpcUrl": "http://10.0.0.1:9000", "registry": "Mainnet" } }"#,
+        )?;
+
+        assert_eq!(Network::parse("localnet", &path)?, Network::Localnet);
+
+        let staging = Network::parse("staging", &path)?;
+        assert_eq!(staging.to_string(), "staging");
+        assert_eq!(staging.rpc_url(), "http://10.0.0.1:9000");
+        assert_eq!(staging.flavor(), Flavor::Mainnet);
+
+        assert!(Network::parse("production", &path).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn records_releases_of_mainnet_and_testnet_only() -> Result<()> {
+        assert_eq!(Network::Mainnet.release_flavor()?, Flavor::Mainnet);
+        assert_eq!(Network::Testnet.release_flavor()?, Flavor::Testnet);
+
+        // Rehearsals share the flavor of testnet without overwriting it
+        assert_eq!(Network::Localnet.flavor(), Flavor::Testnet);
+        assert!(Network::Localnet.release_flavor().is_err());
+        assert!(Network::Devnet.release_flavor().is_err());
+
+        let staging = Network::Custom {
+            name: "staging".to_string(),
+            rpc_url: "http://10.0.0.1:9000".to_string(),
+            registry: Flavor::Mainnet,
+        };
+        assert!(staging.release_flavor().is_err());
+
+        Ok(())
+    }
+}
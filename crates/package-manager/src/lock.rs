@@ -0,0 +1,356 @@
+//! `byte.lock`, the record of the dependency set a project was resolved
+//! against
+//!
+//! The lock file is written next to Move.toml whenever dependencies are
+//! resolved. It records every package reachable from the dependencies, along
+//! with the Program Registry commit they were resolved against, such that the
+//! exact dependencies of Move.toml can be restored later.
+
+use anyhow::{anyhow, Result};
+use gutenberg_types::models::address::Address;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+use crate::{
+    package::{Flavor, GitPath, PackageRegistry},
+    resolve::Resolution,
+    toml::MoveToml,
+    version::Version,
+};
+
+/// Name of the lock file, written next to Move.toml
+pub const LOCK_FILE_NAME: &str = "byte.lock";
+
+const LOCK_FILE_HEADER: &str =
+    "# This file is generated by `byte mpm`, do not edit it by hand\n\n";
+
+/// Represents the contents of a `byte.lock` file.
+///
+/// # Fields
+/// * `registry_commit` - Commit of the Program Registry the packages were
+///   resolved against.
+/// * `flavor` - Network flavor of the Move.toml.
+/// * `packages` - Every package reachable from the dependencies, ordered by
+///   name.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Lockfile {
+    pub registry_commit: String,
+    pub flavor: Option<Flavor>,
+    #[serde(rename = "package", default)]
+    pub packages: Vec<LockedPackage>,
+}
+
+/// Represents a package pinned by the lock file.
+///
+/// # Fields
+/// * `name` - The name of the package.
+/// * `version` - Registry version of the package, if the registry knows
+///   about its revision.
+/// * `git`, `subdir`, `rev` - Location of the package sources.
+/// * `published_at` - Address the package version was published at.
+/// * `direct` - Whether the package is a dependency listed in Move.toml.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: Option<Version>,
+    pub git: String,
+    pub subdir: Option<String>,
+    pub rev: String,
+    pub published_at: Option<Address>,
+    pub direct: bool,
+}
+
+impl LockedPackage {
+    /// Location of the package sources, as written in Move.toml
+    pub fn git_path(&self) -> GitPath {
+        let mut path = GitPath::new(
+            self.git.clone(),
+            self.subdir.clone(),
+            self.rev.clone(),
+        );
+        path.sanitize_subdir();
+        path
+    }
+}
+
+/// Difference between a dependency in Move.toml and the lock file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Drift {
+    /// Dependency points to a different location than locked
+    Changed {
+        name: String,
+        locked: GitPath,
+        actual: GitPath,
+    },
+    /// Dependency is not in the lock file
+    Added(String),
+    /// Locked dependency was removed from Move.toml
+    Removed(String),
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Drift::Changed {
+                name,
+                locked,
+                actual,
+            } => write!(
+                f,
+                "{name} is at rev {} but {LOCK_FILE_NAME} has rev {}",
+                actual.rev, locked.rev
+            ),
+            Drift::Added(name) => {
+                write!(f, "{name} is missing from {LOCK_FILE_NAME}")
+            }
+            Drift::Removed(name) => write!(
+                f,
+                "{name} is in {LOCK_FILE_NAME} but missing from Move.toml"
+            ),
+        }
+    }
+}
+
+impl Lockfile {
+    /// Constructs the lock file for the resolved dependencies of a Move.toml.
+    ///
+    /// Dependencies of the Move.toml that are not in the Package Registry are
+    /// locked at their current location.
+    ///
+    /// # Arguments
+    /// * `pkg_registry` - The `PackageRegistry` the dependencies were
+    ///   resolved against.
+    /// * `registry_commit` - Commit of the Program Registry.
+    /// * `resolution` - Resolved revisions of all reachable packages.
+    /// * `move_toml` - The Move.toml the dependencies were resolved for.
+    pub fn new(
+        pkg_registry: &PackageRegistry,
+        registry_commit: String,
+        resolution: &Resolution,
+        move_toml: &MoveToml,
+    ) -> Self {
+        let mut packages: BTreeMap<&String, LockedPackage> = resolution
+            .0
+            .iter()
+            .map(|(name, pin)| {
+                let published_at = pin.version.as_ref().and_then(|version| {
+                    pkg_registry
+                        .0
+                        .get(name)
+                        .and_then(|versions| versions.get(version))
+                        .and_then(|info| info.package.published_at.clone())
+                });
+
+                let package = LockedPackage {
+                    name: name.clone(),
+                    version: pin.version.clone(),
+                    git: pin.path.git.clone(),
+                    subdir: pin.path.subdir.clone(),
+                    rev: pin.path.rev.clone(),
+                    published_at,
+                    direct: move_toml.dependencies.contains_key(name),
+                };
+
+                (name, package)
+            })
+            .collect();
+
+        move_toml.dependencies.iter().for_each(|(name, path)| {
+            packages.entry(name).or_insert_with(|| LockedPackage {
+                name: name.clone(),
+                version: None,
+                git: path.git.clone(),
+                subdir: path.subdir.clone(),
+                rev: path.rev.clone(),
+                published_at: None,
+                direct: true,
+            });
+        });
+
+        Self {
+            registry_commit,
+            flavor: move_toml.package.flavor,
+            packages: packages.into_values().collect(),
+        }
+    }
+
+    /// Reads the lock file at the given path.
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|err| {
+            anyhow!(r#"Could not read "{}": {err}"#, path.display())
+        })?;
+
+        toml::from_str(&content).map_err(|err| {
+            anyhow!(r#"Could not parse "{}": {err}"#, path.display())
+        })
+    }
+
+    /// Writes the lock file to the given path.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string()?).map_err(|err| {
+            anyhow!(r#"Could not write "{}": {err}"#, path.display())
+        })
+    }
+
+    /// Converts the lock file into a TOML string.
+    pub fn to_string(&self) -> Result<String, toml::ser::Error> {
+        Ok(format!("{LOCK_FILE_HEADER}{}", toml::to_string(self)?))
+    }
+
+    /// Packages listed as dependencies in Move.toml.
+    pub fn direct_packages(&self) -> impl Iterator<Item = &LockedPackage> {
+        self.packages.iter().filter(|package| package.direct)
+    }
+
+    /// Restores the dependencies of the Move.toml to the locked ones.
+    ///
+    /// # Returns
+    /// An error if the flavor of the Move.toml differs from the locked one.
+    pub fn apply(&self, move_toml: &mut MoveToml) -> Result<()> {
+        if move_toml.package.flavor != self.flavor {
+            return Err(anyhow!(
+                "Move.toml flavor {:?} does not match {LOCK_FILE_NAME} \
+                flavor {:?}",
+                move_toml.package.flavor,
+                self.flavor
+            ));
+        }
+
+        move_toml.dependencies = self
+            .direct_packages()
+            .map(|package| (package.name.clone(), package.git_path()))
+            .collect();
+
+        Ok(())
+    }
+
+    /// Dependencies of the Move.toml that differ from the locked ones.
+    pub fn drift(&self, move_toml: &MoveToml) -> Vec<Drift> {
+        let locked: BTreeMap<&String, GitPath> = self
+            .direct_packages()
+            .map(|package| (&package.name, package.git_path()))
+            .collect();
+
+        let mut drift = Vec::new();
+        for (name, path) in move_toml.dependencies.iter() {
+            let mut actual = path.clone();
+            actual.sanitize_subdir();
+
+            match locked.get(name) {
+                None => drift.push(Drift::Added(name.clone())),
+                Some(locked) if *locked != actual => {
+                    drift.push(Drift::Changed {
+                        name: name.clone(),
+                        locked: locked.clone(),
+                        actual,
+                    })
+                }
+                Some(_) => (),
+            }
+        }
+
+        drift.extend(
+            locked
+                .keys()
+                .filter(|name| !move_toml.dependencies.contains_key(**name))
+                .map(|name| Drift::Removed((*name).clone())),
+        );
+
+        drift
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures::registry;
+
+    const MOVE_TOML: &str = r#"
+[package]
+name = "Suimarines"
+version = "1.0.0"
+flavor = "Mainnet"
+
+[dependencies.NftProtocol]
+git = "https://github.com/Origin-Byte/nft-protocol.git"
+subdir = "contracts/nft_protocol"
+rev = "95d16538dc7688dd4c4a5e7c3348bf3addf9c310"
+
+[dependencies.Sui]
+git = "https://github.com/MystenLabs/sui.git"
+subdir = "crates/sui-framework/packages/sui-framework"
+rev = "ae1212baf8f0837e25926d941db3d26a61c1bea2"
+
+[dependencies.Local]
+git = "https://github.com/example/local.git"
+rev = "main"
+
+[addresses]
+suimarines = "0x0"
+"#;
+
+    fn lockfile(registry: &PackageRegistry, move_toml: &MoveToml) -> Lockfile {
+        let resolution = move_toml.resolve_current(registry).unwrap();
+        Lockfile::new(registry, "abc123".to_string(), &resolution, move_toml)
+    }
+
+    #[test]
+    fn locks_resolved_packages() -> Result<()> {
+        let registry = registry();
+        let move_toml: MoveToml = toml::from_str(MOVE_TOML)?;
+        let lock = lockfile(&registry, &move_toml);
+
+        let names: Vec<_> = lock
+            .direct_packages()
+            .map(|package| package.name.as_str())
+            .collect();
+        assert_eq!(names, ["Local", "NftProtocol", "Sui"]);
+
+        let permissions = lock
+            .packages
+            .iter()
+            .find(|package| package.name == "Permissions")
+            .unwrap();
+        assert!(!permissions.direct);
+        assert_eq!(permissions.version, Some(Version::new(1, 0, 0)));
+        assert!(permissions.published_at.is_some());
+
+        let reparsed: Lockfile = toml::from_str(&lock.to_string()?)?;
+        assert_eq!(reparsed, lock);
+
+        Ok(())
+    }
+
+    #[test]
+    fn restores_locked_dependencies() -> Result<()> {
+        let registry = registry();
+        let move_toml: MoveToml = toml::from_str(MOVE_TOML)?;
+        let lock = lockfile(&registry, &move_toml);
+        assert!(lock.drift(&move_toml).is_empty());
+
+        let mut edited: MoveToml = toml::from_str(MOVE_TOML)?;
+        edited.dependencies.get_mut("NftProtocol").unwrap().rev =
+            "93f6cd0b8966354b1b00e7d798cbfddaa867a07b".to_string();
+        edited.dependencies.remove("Sui");
+        edited.dependencies.insert(
+            "Extra".to_string(),
+            GitPath::new("https://example.com".to_string(), None, "1".into()),
+        );
+
+        let drift = lock.drift(&edited);
+        assert_eq!(drift.len(), 3);
+        assert!(matches!(&drift[0], Drift::Added(name) if name == "Extra"));
+        assert!(
+            matches!(&drift[1], Drift::Changed { name, .. } if name == "NftProtocol")
+        );
+        assert!(matches!(&drift[2], Drift::Removed(name) if name == "Sui"));
+
+        lock.apply(&mut edited)?;
+        assert!(lock.drift(&edited).is_empty());
+        assert_eq!(edited.to_string()?, move_toml.to_string()?);
+
+        Ok(())
+    }
+}
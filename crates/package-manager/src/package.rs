@@ -2,9 +2,13 @@ use anyhow::{anyhow, Result};
 use convert_case::{Case, Casing};
 use gutenberg_types::models::address::Address;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    ops::Bound,
+};
 
-use crate::version::Version;
+use crate::version::{Version, VersionReq};
 
 /// Represents a Package Registry containing package information.
 #[derive(Deserialize, Debug)]
@@ -178,6 +182,115 @@ impl PackageRegistry {
         (dep.package.version != latest.package.version).then_some(latest)
     }
 
+    /// Resolves the newest version of every dependency allowed by the
+    /// version constraints.
+    ///
+    /// A dependency is only updated to a version newer than its current one
+    /// which satisfies its constraint. Dependencies without a constraint are
+    /// never updated to pre-release versions. The newest version that was
+    /// passed over for each dependency is reported along with the reason.
+    ///
+    /// # Arguments
+    /// * `deps` - A slice of references to `PackageInfo` representing the
+    ///   dependencies.
+    /// * `constraints` - Version requirements by package name, usually the
+    ///   `[byte.constraints]` table of the Move.toml.
+    ///
+    /// # Returns
+    /// The packages that need updating and the updates that were skipped.
+    pub fn resolve_updates(
+        &self,
+        deps: &[&PackageInfo],
+        constraints: &BTreeMap<String, VersionReq>,
+    ) -> Updates<'_> {
+        let mut updates = Updates::default();
+
+        for dep in deps {
+            let Some(versions) = self.0.get(&dep.package.name) else {
+                continue;
+            };
+            let constraint = constraints.get(&dep.package.name);
+
+            let newer = versions
+                .range((
+                    Bound::Excluded(&dep.package.version),
+                    Bound::Unbounded,
+                ))
+                .rev();
+
+            for (version, info) in newer {
+                let Some(reason) = skip_reason(version, constraint) else {
+                    updates.to_update.push(info);
+                    break;
+                };
+
+                // Only report the newest version that was skipped
+                if !updates
+                    .skipped
+                    .iter()
+                    .any(|skipped| skipped.name == dep.package.name)
+                {
+                    updates.skipped.push(SkippedUpdate {
+                        name: dep.package.name.clone(),
+                        version: version.clone(),
+                        reason,
+                    });
+                }
+            }
+        }
+
+        updates
+    }
+
+    /// Versions a dependency may be updated to, newest first, followed by
+    /// its current version.
+    ///
+    /// Versions newer than the current one are subject to the same rules as
+    /// in [`PackageRegistry::resolve_updates`].
+    ///
+    /// # Arguments
+    /// * `dep` - A reference to `PackageInfo` representing the dependency.
+    /// * `constraint` - Optional version requirement of the dependency.
+    pub fn allowed_versions(
+        &self,
+        dep: &PackageInfo,
+        constraint: Option<&VersionReq>,
+    ) -> Vec<&PackageInfo> {
+        let Some(versions) = self.0.get(&dep.package.name) else {
+            return Vec::new();
+        };
+
+        versions
+            .range((Bound::Included(&dep.package.version), Bound::Unbounded))
+            .rev()
+            .filter(|(version, _)| {
+                **version == dep.package.version
+                    || skip_reason(version, constraint).is_none()
+            })
+            .map(|(_, info)| info)
+            .collect()
+    }
+
+    /// Newest version of a package allowed by its constraint, pre-releases
+    /// are only allowed if the constraint mentions them.
+    ///
+    /// # Arguments
+    /// * `dep_name` - The name of the package.
+    /// * `constraint` - Optional version requirement of the package.
+    pub fn newest_allowed(
+        &self,
+        dep_name: &str,
+        constraint: Option<&VersionReq>,
+    ) -> Option<&PackageInfo> {
+        self.0.get(dep_name).and_then(|versions| {
+            versions
+                .iter()
+                .rev()
+                .find(|(version, _)| skip_reason(version, constraint).is_none())
+                .map(|(_, info)| info)
+        })
+    }
+
     /// Fetches the latest version of a specified package.
     ///
     /// Attempts to retrieve the latest version available for a given package
@@ -210,6 +323,35 @@ impl PackageRegistry {
             })
     }
 
+    /// Retrieves the addresses of the latest version of a package.
+    ///
+    /// The types of a package keep the address of its original version, while
+    /// its functions are called at the address its latest version was
+    /// published at.
+    ///
+    /// # Arguments
+    /// * `pkg_name` - The name of the package.
+    ///
+    /// # Returns
+    /// A result containing the original address and the `published-at`
+    /// address of the package, or an error message.
+    pub fn get_latest_addresses(
+        &self,
+        pkg_name: &str,
+    ) -> Result<(&Address, &Address)> {
+        let version = self.get_latest_version(pkg_name)?;
+        let metadata = &self.0[pkg_name][version];
+
+        // The first version of a package is published at its original address
+        let published_at = metadata
+            .package
+            .published_at
+            .as_ref()
+            .unwrap_or(&metadata.contract_ref.object_id);
+
+        Ok((&metadata.contract_ref.object_id, published_at))
+    }
+
     /// Retrieves the version associated with a given object ID.
     ///
     /// This function iterates over all versions in the package map to find a
@@ -238,7 +380,7 @@ impl PackageRegistry {
             });
 
             if let Some(search_result) = search_result {
-                return Ok(*search_result.0);
+                return Ok(search_result.0.clone());
             }
         }
 
@@ -290,6 +432,69 @@ impl PackageRegistry {
     }
 }
 
+/// Reason why the version may not be updated to, if any
+fn skip_reason(
+    version: &Version,
+    constraint: Option<&VersionReq>,
+) -> Option<SkipReason> {
+    match constraint {
+        Some(req) if !req.matches(version) => {
+            Some(SkipReason::Constraint(req.clone()))
+        }
+        None if version.is_prerelease() => Some(SkipReason::PreRelease),
+        _ => None,
+    }
+}
+
+/// Dependency updates resolved by [`PackageRegistry::resolve_updates`].
+#[derive(Debug, Default)]
+pub struct Updates<'a> {
+    /// Newest allowed version of every dependency that can be updated
+    pub to_update: Vec<&'a PackageInfo>,
+    /// Newer versions which were not updated to
+    pub skipped: Vec<SkippedUpdate>,
+}
+
+/// Newer version of a dependency which was passed over during an update.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedUpdate {
+    pub name: String,
+    pub version: Version,
+    pub reason: SkipReason,
+}
+
+/// Reason why a newer version of a dependency was passed over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Version does not satisfy the constraint of the dependency
+    Constraint(VersionReq),
+    /// Version is a pre-release and the dependency has no constraint
+    PreRelease,
+}
+
+impl fmt::Display for SkippedUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let SkippedUpdate {
+            name,
+            version,
+            reason,
+        } = self;
+
+        match reason {
+            SkipReason::Constraint(req) => write!(
+                f,
+                "{name} {version}, it does not satisfy `{req}` in \
+                [byte.constraints]"
+            ),
+            SkipReason::PreRelease => write!(
+                f,
+                "{name} {version}, it is a pre-release, add a constraint to \
+                [byte.constraints] to opt into pre-releases"
+            ),
+        }
+    }
+}
+
 /// Represents package information, including dependencies and package
 /// references.
 ///
@@ -300,8 +505,8 @@ impl PackageRegistry {
 pub struct PackageInfo {
     pub package: Package,
     pub contract_ref: PackagePath,
-    // TODO: Consider making this a self-similar struct, such that
-    // we keep dependency tree's depth in its entirity
+    /// Direct dependencies, transitive dependencies are resolved by walking
+    /// the registry, see [`crate::resolve`]
     pub dependencies: HashMap<String, PackagePath>,
 }
 
@@ -309,8 +514,8 @@ pub struct PackageInfo {
 ///
 /// This struct is used to store the path to a specific package and its
 /// associated object ID.
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
-#[serde(rename_all(deserialize = "camelCase"))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub struct PackagePath {
     pub path: GitPath,
     pub object_id: Address,
@@ -562,6 +767,27 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_get_latest_addresses() -> Result<()> {
+        let registry = crate::fixtures::registry();
+
+        let (original_id, published_at) =
+            registry.get_latest_addresses("Launchpad")?;
+
+        assert_eq!(
+            format!("{}", original_id),
+            "0xc74531639fadfb02d30f05f37de4cf1e1149ed8d23658edd089004830068180b"
+        );
+        assert_eq!(
+            format!("{}", published_at),
+            "0xc0c5ca1e59bbb0e7330c8f182cbad262717faf7d8d0d7f7da4b3146391ecbbe1"
+        );
+
+        assert!(registry.get_latest_addresses("Unknown").is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_get_updated_package_info() -> Result<()> {
         let current_dir =
@@ -627,6 +853,82 @@ mod test {
         Ok(())
     }
 
+    /// Adds a version of the package to the registry, copied from an
+    /// existing version
+    fn add_version(
+        registry: &mut PackageRegistry,
+        name: &str,
+        from: &str,
+        version: &str,
+    ) -> Result<()> {
+        let versions = registry.0.get_mut(name).unwrap();
+        let mut info = versions.get(&Version::from_str(from)?).unwrap().clone();
+        info.package.version = Version::from_str(version)?;
+        versions.insert(info.package.version.clone(), info);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_updates() -> Result<()> {
+        let current_dir =
+            env::current_dir().expect("Failed to retrieve current directory.");
+        let file = File::open(current_dir.join("registry/registry-main.json"))?;
+        let mut registry: PackageRegistry = serde_json::from_reader(file)?;
+
+        add_version(&mut registry, "NftProtocol", "1.2.0", "1.3.0-rc.1")?;
+        add_version(&mut registry, "NftProtocol", "1.2.0", "2.0.0")?;
+
+        let current = registry
+            .0
+            .get("NftProtocol")
+            .unwrap()
+            .get(&Version::from_str("1.0.0")?)
+            .unwrap()
+            .clone();
+
+        // Without constraints, the newest version is picked
+        let updates = registry.resolve_updates(&[&current], &BTreeMap::new());
+        assert_eq!(updates.to_update.len(), 1);
+        assert_eq!(updates.to_update[0].package.version.to_string(), "2.0.0");
+        assert!(updates.skipped.is_empty());
+
+        // Constraint holds back the breaking major bump
+        let constraints = BTreeMap::from([(
+            "NftProtocol".to_string(),
+            VersionReq::from_str("^1.2")?,
+        )]);
+        let updates = registry.resolve_updates(&[&current], &constraints);
+        assert_eq!(updates.to_update[0].package.version.to_string(), "1.2.0");
+        assert_eq!(
+            updates.skipped,
+            vec![SkippedUpdate {
+                name: "NftProtocol".to_string(),
+                version: Version::from_str("2.0.0")?,
+                reason: SkipReason::Constraint(VersionReq::from_str("^1.2")?),
+            }]
+        );
+        assert!(updates.skipped[0].to_string().contains("`^1.2`"));
+
+        // Pre-releases are skipped unless a constraint opts into them
+        add_version(&mut registry, "NftProtocol", "1.2.0", "2.1.0-beta.1")?;
+        let updates = registry.resolve_updates(&[&current], &BTreeMap::new());
+        assert_eq!(updates.to_update[0].package.version.to_string(), "2.0.0");
+        assert_eq!(updates.skipped[0].reason, SkipReason::PreRelease);
+
+        let constraints = BTreeMap::from([(
+            "NftProtocol".to_string(),
+            VersionReq::from_str(">=1.3.0-rc.1, <1.4")?,
+        )]);
+        let updates = registry.resolve_updates(&[&current], &constraints);
+        assert_eq!(
+            updates.to_update[0].package.version.to_string(),
+            "1.3.0-rc.1"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_get_latest_version() -> Result<()> {
         let current_dir =
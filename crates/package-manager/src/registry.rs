@@ -0,0 +1,404 @@
+//! Private program registries
+//!
+//! The public Program Registry only lists OriginByte packages. Teams that
+//! publish their own Move libraries keep them in a private registry, which
+//! uses the same JSON format and is merged into the public registry before
+//! dependencies are resolved, such that the private packages are updated and
+//! checked like any other.
+
+use anyhow::{anyhow, Result};
+use gutenberg_types::models::address::Address;
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    document::write_atomic,
+    info::BuildInfo,
+    package::{
+        Flavor, GitPath, Package, PackageInfo, PackagePath, PackageRegistry,
+    },
+    toml::{named_address, MoveToml},
+    version::Version,
+};
+
+/// Path of the registry of the network within a registry directory, named
+/// like the files of the public Program Registry.
+///
+/// # Arguments
+/// * `registry_dir` - The directory of the registry.
+/// * `flavor` - The network flavor.
+pub fn registry_path(registry_dir: &Path, flavor: Flavor) -> PathBuf {
+    let file_name = match flavor {
+        Flavor::Mainnet => "registry-main.json",
+        Flavor::Testnet => "registry-test.json",
+    };
+
+    registry_dir.join(file_name)
+}
+
+impl PackageRegistry {
+    /// Reads a registry of the network from a JSON file.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the registry.
+    /// * `flavor` - The network flavor of the registry.
+    pub fn read(path: &Path, flavor: Flavor) -> Result<Self> {
+        let file = fs::File::open(path).map_err(|err| {
+            anyhow!(r#"Could not read "{}": {err}"#, path.display())
+        })?;
+
+        let mut registry: PackageRegistry = serde_json::from_reader(file)
+            .map_err(|err| {
+                anyhow!(r#"Could not parse "{}": {err}"#, path.display())
+            })?;
+        registry.set_flavor(flavor)?;
+
+        Ok(registry)
+    }
+
+    /// Writes the registry to a JSON file in the format of the public
+    /// Program Registry.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let mut contents = serde_json::to_string_pretty(self)?;
+        contents.push('\n');
+
+        write_atomic(path, &contents)
+    }
+
+    /// Adds a version of a package to the registry.
+    ///
+    /// Registering a version again is a no-op, but fails if its entry
+    /// differs from the registered one, as published versions never change.
+    ///
+    /// # Arguments
+    /// * `info` - The registry entry of the package version.
+    pub fn insert(&mut self, info: PackageInfo) -> Result<()> {
+        let versions = self.0.entry(info.package.name.clone()).or_default();
+
+        match versions.get(&info.package.version) {
+            Some(registered) if *registered != info => Err(anyhow!(
+                "{} {} is already registered with a different entry",
+                info.package.name,
+                info.package.version
+            )),
+            Some(_) => Ok(()),
+            None => {
+                versions.insert(info.package.version.clone(), info);
+                Ok(())
+            }
+        }
+    }
+
+    /// Adds every package version of another registry, such as a private
+    /// registry merged into the public one.
+    ///
+    /// Fails if both registries contain the same version of a package with
+    /// different entries.
+    ///
+    /// # Arguments
+    /// * `other` - The registry to merge into this one.
+    pub fn merge(&mut self, other: PackageRegistry) -> Result<()> {
+        other
+            .0
+            .into_values()
+            .flat_map(BTreeMap::into_values)
+            .try_for_each(|info| self.insert(info))
+    }
+}
+
+/// Builds the registry entry of a published package.
+///
+/// The dependencies are located through the registry, falling back to the
+/// addresses the package was built against in `BuildInfo.yaml`. The package
+/// keeps the object ID of its first version, which is the named address of
+/// the package in the Move.toml, or the ID of a previously registered
+/// version.
+///
+/// # Arguments
+/// * `registry` - The registry the dependencies are looked up in, usually
+///   the public registry merged with the private one.
+/// * `move_toml` - The Move.toml of the package.
+/// * `build_info` - The build info of the published package.
+/// * `path` - The git location of the package source.
+/// * `published_at` - The ID the package version was published at.
+pub fn package_entry(
+    registry: &PackageRegistry,
+    move_toml: &MoveToml,
+    mut build_info: BuildInfo,
+    path: GitPath,
+    published_at: Address,
+) -> Result<PackageInfo> {
+    let name = move_toml.package.name();
+    let flavor = move_toml.package.flavor.ok_or_else(|| {
+        anyhow!("Move.toml of '{name}' does not declare its network flavor")
+    })?;
+
+    if build_info.packages.package_name.to_lowercase() != name.to_lowercase() {
+        return Err(anyhow!(
+            "BuildInfo.yaml is for package '{}' instead of '{name}'",
+            build_info.packages.package_name
+        ));
+    }
+
+    build_info.packages.make_name_canonical();
+    let built_addresses = build_info.packages.ob_packages;
+
+    let dependencies = move_toml
+        .dependencies
+        .iter()
+        .map(|(dep_name, dep_path)| {
+            let registered = registry.0.get(dep_name).and_then(|versions| {
+                versions
+                    .values()
+                    .find(|info| info.contract_ref.path.rev == dep_path.rev)
+            });
+
+            let object_id = match registered {
+                Some(info) => info.contract_ref.object_id.clone(),
+                None => {
+                    built_addresses.get(dep_name).cloned().ok_or_else(|| {
+                        anyhow!(
+                            "Could not find the address of dependency \
+                            '{dep_name}' in the registry or BuildInfo.yaml"
+                        )
+                    })?
+                }
+            };
+
+            Ok((
+                dep_name.clone(),
+                PackagePath {
+                    path: dep_path.clone(),
+                    object_id,
+                },
+            ))
+        })
+        .collect::<Result<HashMap<_, _>>>()?;
+
+    let unpublished = Address::new("0x0")?;
+    let object_id = move_toml
+        .addresses
+        .get(&named_address(&name, &path.git))
+        .filter(|address| **address != unpublished)
+        .or_else(|| {
+            registry
+                .0
+                .get(&name)
+                .and_then(|versions| versions.values().next())
+                .map(|info| &info.contract_ref.object_id)
+        })
+        .unwrap_or(&published_at)
+        .clone();
+
+    Ok(PackageInfo {
+        package: Package::new(
+            name,
+            move_toml.package.version.clone(),
+            flavor,
+            Some(published_at),
+        ),
+        contract_ref: PackagePath { path, object_id },
+        dependencies,
+    })
+}
+
+impl Serialize for PackageRegistry {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+
+        for (name, versions) in self.0.iter() {
+            let entries: BTreeMap<&Version, Entry> = versions
+                .iter()
+                .map(|(version, info)| (version, Entry::from(info)))
+                .collect();
+
+            map.serialize_entry(name, &entries)?;
+        }
+
+        map.end()
+    }
+}
+
+/// Package version as written in the registry, which differs from the
+/// `[package]` table of a Move.toml by its `publishedAt` key and by leaving
+/// the flavor to the file name
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry<'a> {
+    package: EntryPackage<'a>,
+    contract_ref: &'a PackagePath,
+    dependencies: BTreeMap<&'a String, &'a PackagePath>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EntryPackage<'a> {
+    name: &'a str,
+    version: &'a Version,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    published_at: Option<&'a Address>,
+}
+
+impl<'a> From<&'a PackageInfo> for Entry<'a> {
+    fn from(info: &'a PackageInfo) -> Self {
+        Entry {
+            package: EntryPackage {
+                name: &info.package.name,
+                version: &info.package.version,
+                published_at: info.package.published_at.as_ref(),
+            },
+            contract_ref: &info.contract_ref,
+            dependencies: info.dependencies.iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fixtures;
+    use tempfile::TempDir;
+
+    const MOVE_TOML: &str = r#"[package]
+name = "Gnomes"
+version = "1.1.0"
+flavor = "Mainnet"
+
+[dependencies.Sui]
+git = "https://github.com/MystenLabs/sui.git"
+subdir = "crates/sui-framework/packages/sui-framework"
+rev = "ae1212baf8f0837e25926d941db3d26a61c1bea2"
+
+[dependencies.Shared]
+git = "https://github.com/gnomes/shared.git"
+rev = "2"
+
+[addresses]
+gnomes = "0xa1"
+"#;
+
+    const BUILD_INFO: &str = r#"---
+compiled_package_info:
+  package_name: Gnomes
+  address_alias_instantiation:
+    gnomes: "00000000000000000000000000000000000000000000000000000000000000a1"
+    shared: "00000000000000000000000000000000000000000000000000000000000000c3"
+    sui: "0000000000000000000000000000000000000000000000000000000000000002"
+"#;
+
+    fn entry(published_at: &str) -> Result<PackageInfo> {
+        package_entry(
+            &fixtures::registry(),
+            &toml::from_str(MOVE_TOML)?,
+            serde_yaml::from_str(BUILD_INFO)?,
+            GitPath::new(
+                "https://github.com/gnomes/gnomes.git".into(),
+                None,
+                "1".into(),
+            ),
+            Address::new(published_at)?,
+        )
+    }
+
+    #[test]
+    fn builds_entry_of_published_package() -> Result<()> {
+        let info = entry("0xb2")?;
+
+        assert_eq!(info.package.name, "Gnomes");
+        assert_eq!(info.package.published_at, Some(Address::new("0xb2")?));
+        assert_eq!(info.contract_ref.object_id, Address::new("0xa1")?);
+        assert_eq!(
+            info.dependencies["Shared"].object_id,
+            Address::new("0xc3")?
+        );
+        assert_eq!(info.dependencies["Sui"].object_id, Address::new("0x2")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn builds_entry_of_ob_package() -> Result<()> {
+        let move_toml = MOVE_TOML
+            .replace("Gnomes", "Launchpad")
+            .replace("gnomes = ", "ob_launchpad = ");
+        let build_info = BUILD_INFO
+            .replace("Gnomes", "Launchpad")
+            .replace("gnomes: ", "ob_launchpad: ");
+
+        let info = package_entry(
+            &fixtures::registry(),
+            &toml::from_str(&move_toml)?,
+            serde_yaml::from_str(&build_info)?,
+            GitPath::new(
+                "https://github.com/Origin-Byte/nft-protocol.git".into(),
+                None,
+                "1".into(),
+            ),
+            Address::new("0xb2")?,
+        )?;
+
+        // Address of the new version, not of the registered ones
+        assert_eq!(info.contract_ref.object_id, Address::new("0xa1")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrips_registry_format() -> Result<()> {
+        let registry = fixtures::registry();
+        let dir = TempDir::new()?;
+        let path = registry_path(dir.path(), Flavor::Mainnet);
+
+        registry.write(&path)?;
+        assert_eq!(
+            PackageRegistry::read(&path, Flavor::Mainnet)?.0,
+            registry.0
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path)?)?;
+        let package = &json["Permissions"]["1.0.0"]["package"];
+        assert!(package["publishedAt"].is_string());
+        assert!(package.get("flavor").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn merges_private_packages() -> Result<()> {
+        let mut private = PackageRegistry(BTreeMap::new());
+        private.insert(entry("0xb2")?)?;
+        private.insert(entry("0xb2")?)?;
+        assert!(private.insert(entry("0xb3")?).is_err());
+
+        let mut registry = fixtures::registry();
+        registry.merge(private)?;
+
+        let version = Version::new(1, 1, 0);
+        assert!(registry
+            .get_package_info(&"Gnomes".into(), &version)
+            .is_ok());
+        assert!(registry.0.contains_key("NftProtocol"));
+
+        // Public packages may not be redefined
+        let mut conflicting = fixtures::registry();
+        conflicting
+            .0
+            .get_mut("Permissions")
+            .and_then(|versions| versions.values_mut().next())
+            .unwrap()
+            .contract_ref
+            .path
+            .rev = "1".to_string();
+        assert!(registry.merge(conflicting).is_err());
+
+        Ok(())
+    }
+}
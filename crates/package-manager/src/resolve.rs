@@ -0,0 +1,265 @@
+//! Resolution of one consistent revision per package across the dependency
+//! graph of the Package Registry
+//!
+//! Every package in the registry pins its own dependencies, including the Sui
+//! framework, to exact revisions. A set of direct dependencies is only
+//! consistent if every package reachable from them, directly or
+//! transitively, is required at the same revision, otherwise `sui move build`
+//! fails on the conflicting revisions.
+
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    package::{GitPath, PackageInfo, PackageRegistry},
+    version::Version,
+};
+
+/// Direct dependency along with the versions it may be resolved to, in order
+/// of preference.
+#[derive(Debug)]
+pub struct Root<'a> {
+    pub name: String,
+    pub candidates: Vec<&'a PackageInfo>,
+}
+
+/// Package reached while walking the dependency graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub name: String,
+    /// Registry version of the revision, if the registry knows about it
+    pub version: Option<Version>,
+    pub rev: String,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.version {
+            Some(version) => write!(f, "{} {version}", self.name),
+            None => write!(f, "{} @{}", self.name, self.rev),
+        }
+    }
+}
+
+/// Revision a package was resolved to.
+#[derive(Debug, Clone)]
+pub struct Pin {
+    pub version: Option<Version>,
+    pub path: GitPath,
+    /// Path from a direct dependency to this package, ending with the package
+    pub chain: Vec<Step>,
+}
+
+/// Resolved revision of every package reachable from the direct dependencies.
+#[derive(Debug)]
+pub struct Resolution(pub BTreeMap<String, Pin>);
+
+/// Two paths through the dependency graph requiring the same package at
+/// different revisions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub package: String,
+    pub pinned: Vec<Step>,
+    pub required: Vec<Step>,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let chain = |steps: &[Step]| {
+            steps
+                .iter()
+                .map(Step::to_string)
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        };
+
+        writeln!(
+            f,
+            "Could not find a consistent set of dependencies, `{}` is required \
+            at two revisions:",
+            self.package
+        )?;
+        writeln!(f, "  {}", chain(&self.pinned))?;
+        write!(f, "  {}", chain(&self.required))
+    }
+}
+
+impl std::error::Error for Conflict {}
+
+/// Finds one revision per package such that every package reachable from the
+/// roots is required at the same revision.
+///
+/// Candidates of earlier roots are preferred over candidates of later roots.
+/// If no consistent set exists, the conflict met while trying the most
+/// preferred candidates is returned.
+pub fn resolve(
+    registry: &PackageRegistry,
+    roots: &[Root],
+) -> Result<Resolution, Conflict> {
+    search(registry, roots, &BTreeMap::new()).map(Resolution)
+}
+
+fn search(
+    registry: &PackageRegistry,
+    roots: &[Root],
+    pins: &BTreeMap<String, Pin>,
+) -> Result<BTreeMap<String, Pin>, Conflict> {
+    let Some((root, rest)) = roots.split_first() else {
+        return Ok(pins.clone());
+    };
+
+    let mut first_conflict = None;
+    for candidate in &root.candidates {
+        let mut pins = pins.clone();
+        let path = candidate.contract_ref.path.clone();
+
+        let result = pin(registry, &mut pins, &root.name, path, Vec::new())
+            .and_then(|_| search(registry, rest, &pins));
+
+        match result {
+            Ok(pins) => return Ok(pins),
+            Err(conflict) => {
+                first_conflict.get_or_insert(conflict);
+            }
+        }
+    }
+
+    match first_conflict {
+        Some(conflict) => Err(conflict),
+        // Root without candidates does not constrain the resolution
+        None => search(registry, rest, pins),
+    }
+}
+
+/// Pins the package to the revision and walks its dependencies
+fn pin(
+    registry: &PackageRegistry,
+    pins: &mut BTreeMap<String, Pin>,
+    name: &str,
+    path: GitPath,
+    mut chain: Vec<Step>,
+) -> Result<(), Conflict> {
+    let info = registry.0.get(name).and_then(|versions| {
+        versions
+            .values()
+            .find(|info| info.contract_ref.path.rev == path.rev)
+    });
+
+    chain.push(Step {
+        name: name.to_string(),
+        version: info.map(|info| info.package.version.clone()),
+        rev: path.rev.clone(),
+    });
+
+    if let Some(pinned) = pins.get(name) {
+        if pinned.path.rev == path.rev {
+            return Ok(());
+        }
+
+        return Err(Conflict {
+            package: name.to_string(),
+            pinned: pinned.chain.clone(),
+            required: chain,
+        });
+    }
+
+    pins.insert(
+        name.to_string(),
+        Pin {
+            version: info.map(|info| info.package.version.clone()),
+            path,
+            chain: chain.clone(),
+        },
+    );
+
+    // Revisions unknown to the registry are pinned without walking further
+    let Some(info) = info else {
+        return Ok(());
+    };
+
+    let dependencies: BTreeMap<_, _> = info.dependencies.iter().collect();
+    for (dep_name, dep) in dependencies {
+        pin(registry, pins, dep_name, dep.path.clone(), chain.clone())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{fixtures::registry, package::PackageInfo};
+    use std::str::FromStr;
+
+    fn info<'a>(
+        registry: &'a PackageRegistry,
+        name: &str,
+        version: &str,
+    ) -> &'a PackageInfo {
+        registry
+            .0
+            .get(name)
+            .unwrap()
+            .get(&Version::from_str(version).unwrap())
+            .unwrap()
+    }
+
+    fn root<'a>(
+        registry: &'a PackageRegistry,
+        name: &str,
+        versions: &[&str],
+    ) -> Root<'a> {
+        Root {
+            name: name.to_string(),
+            candidates: versions
+                .iter()
+                .map(|version| info(registry, name, version))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn resolves_transitive_dependencies() {
+        let registry = registry();
+        let roots = [
+            root(&registry, "NftProtocol", &["1.2.0", "1.0.0"]),
+            root(&registry, "Sui", &["1.2.0", "1.1.0", "1.0.0"]),
+        ];
+
+        let resolution = resolve(&registry, &roots).unwrap();
+
+        let permissions = &resolution.0["Permissions"];
+        assert_eq!(permissions.version, Some(Version::new(1, 2, 0)));
+        assert_eq!(permissions.chain[0].name, "NftProtocol");
+        assert_eq!(resolution.0["Sui"].version, Some(Version::new(1, 2, 0)));
+    }
+
+    #[test]
+    fn backtracks_to_consistent_versions() {
+        let registry = registry();
+        // Newest Sui is not used by NftProtocol 1.0.0
+        let roots = [
+            root(&registry, "NftProtocol", &["1.0.0"]),
+            root(&registry, "Sui", &["1.2.0", "1.0.0"]),
+        ];
+
+        let resolution = resolve(&registry, &roots).unwrap();
+        assert_eq!(resolution.0["Sui"].version, Some(Version::new(1, 0, 0)));
+    }
+
+    #[test]
+    fn reports_conflict_chain() {
+        let registry = registry();
+        // LiquidityLayer was only released along NftProtocol 1.0.0
+        let roots = [
+            root(&registry, "NftProtocol", &["1.2.0"]),
+            root(&registry, "LiquidityLayer", &["1.0.0"]),
+        ];
+
+        let conflict = resolve(&registry, &roots).unwrap_err();
+        assert_eq!(conflict.package, "Allowlist");
+
+        let message = conflict.to_string();
+        assert!(message.contains("NftProtocol 1.2.0 -> Allowlist 1.2.0\n"));
+        assert!(message.contains("LiquidityLayer 1.0.0 -> Allowlist 1.0.0"));
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::anyhow;
 use console::style;
+use convert_case::{Boundary, Case, Casing};
 use gutenberg_types::models::address::Address;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -8,8 +9,10 @@ use std::{collections::BTreeMap, str::FromStr};
 use crate::{
     package::{
         Flavor, GitPath, Package, PackageInfo, PackagePath, PackageRegistry,
+        SkippedUpdate, Updates,
     },
-    version::Version,
+    resolve::{resolve, Conflict, Pin, Resolution, Root},
+    version::{Version, VersionReq},
 };
 
 /// Represents the structure of a Move.toml file, which includes package
@@ -26,11 +29,51 @@ use crate::{
 ///   paths.
 /// * `addresses` - A mapping of names to blockchain addresses used in the Move
 ///   package.
+/// * `byte` - Settings of the package manager, kept in the `[byte]` table.
 #[derive(Deserialize, Debug, Serialize)]
 pub struct MoveToml {
     pub package: Package,
     pub dependencies: BTreeMap<String, GitPath>,
     pub addresses: BTreeMap<String, Address>,
+    #[serde(default, skip_serializing_if = "ByteTable::is_empty")]
+    pub byte: ByteTable,
+}
+
+/// Package manager settings kept in the `[byte]` table of a Move.toml file,
+/// which the Move compiler ignores.
+///
+/// # Fields
+/// * `constraints` - Version requirements of OriginByte dependencies by
+///   package name, respected when updating dependencies, e.g.
+///   `NftProtocol = "^1.2"`.
+#[derive(Deserialize, Debug, Serialize, Default)]
+pub struct ByteTable {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub constraints: BTreeMap<String, VersionReq>,
+}
+
+impl ByteTable {
+    pub fn is_empty(&self) -> bool {
+        self.constraints.is_empty()
+    }
+}
+
+/// Versions of a dependency known to the Package Registry, as listed by
+/// [`MoveToml::list_dependencies`].
+///
+/// # Fields
+/// * `name` - The name of the dependency.
+/// * `current` - Version of the declared revision, if the registry knows it.
+/// * `latest` - Newest version in the registry, including pre-releases.
+/// * `allowed` - Newest version allowed by the constraint of the dependency.
+/// * `constraint` - Version requirement of the dependency, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyVersions {
+    pub name: String,
+    pub current: Option<Version>,
+    pub latest: Option<Version>,
+    pub allowed: Option<Version>,
+    pub constraint: Option<VersionReq>,
 }
 
 impl MoveToml {
@@ -49,6 +92,7 @@ impl MoveToml {
             package,
             dependencies,
             addresses,
+            byte: ByteTable::default(),
         }
     }
 
@@ -161,72 +205,343 @@ impl MoveToml {
             .collect()
     }
 
+    /// Resolves one consistent revision for every package reachable from the
+    /// dependencies, including the Sui framework.
+    ///
+    /// Every dependency is resolved to the newest version allowed by its
+    /// `[byte.constraints]` that is consistent with the other dependencies,
+    /// or kept at its current version.
+    ///
+    /// # Arguments
+    /// * `pkg_registry` - A reference to the `PackageRegistry`.
+    ///
+    /// # Returns
+    /// The resolved revisions, or the conflict chain if no consistent set of
+    /// revisions exists.
+    pub fn resolve_dependencies(
+        &self,
+        pkg_registry: &PackageRegistry,
+    ) -> Result<Resolution, Conflict> {
+        let roots = self
+            .dependency_pkg_infos(pkg_registry)
+            .into_iter()
+            .map(|dep| Root {
+                name: dep.package.name.clone(),
+                candidates: pkg_registry.allowed_versions(
+                    dep,
+                    self.byte.constraints.get(&dep.package.name),
+                ),
+            })
+            .collect::<Vec<_>>();
+
+        resolve(pkg_registry, &roots)
+    }
+
+    /// Resolves the revision of every package reachable from the
+    /// dependencies, keeping the dependencies at their current revisions.
+    ///
+    /// # Arguments
+    /// * `pkg_registry` - A reference to the `PackageRegistry`.
+    ///
+    /// # Returns
+    /// The resolved revisions, or the conflict chain if the dependencies
+    /// require different revisions of the same package.
+    pub fn resolve_current(
+        &self,
+        pkg_registry: &PackageRegistry,
+    ) -> Result<Resolution, Conflict> {
+        let roots = self
+            .dependency_pkg_infos(pkg_registry)
+            .into_iter()
+            .map(|dep| Root {
+                name: dep.package.name.clone(),
+                candidates: vec![dep],
+            })
+            .collect::<Vec<_>>();
+
+        resolve(pkg_registry, &roots)
+    }
+
     /// Updates the `MoveToml` file with the latest package versions and
     /// dependencies.
     ///
     /// This function checks for updates in the package registry and updates the
     /// Move.toml file accordingly. It also sanitizes the paths of updated
-    /// dependencies.
+    /// dependencies. Dependencies are only updated to versions satisfying
+    /// their `[byte.constraints]` and consistent with each other, newer
+    /// versions which were skipped are reported.
     ///
     /// # Arguments
     /// * `pkg_registry` - A reference to the `PackageRegistry`.
-    pub fn update_toml(&mut self, pkg_registry: &PackageRegistry) {
+    ///
+    /// # Returns
+    /// The resolved revisions of all packages reachable from the updated
+    /// dependencies, or an error with the conflict chain if the dependencies
+    /// cannot be resolved consistently, in which case the `MoveToml` is left
+    /// untouched.
+    pub fn update_toml(
+        &mut self,
+        pkg_registry: &PackageRegistry,
+    ) -> Result<Resolution, anyhow::Error> {
         let dependencies = self.dependency_pkg_infos(pkg_registry);
 
-        let to_update = pkg_registry.get_packages_to_update(&dependencies);
+        let updates =
+            pkg_registry.resolve_updates(&dependencies, &self.byte.constraints);
+        print_skipped(&updates.skipped);
 
-        let mut updated_deps = to_update
-            .iter()
-            .map(|dep| {
-                (
-                    dep.package.name(),
-                    dep.contract_ref.path.clone(),
-                    dep.package.version().clone(),
-                )
-            })
-            .collect::<Vec<(String, GitPath, Version)>>();
-
-        updated_deps
-            .drain(..)
-            .for_each(|(dep_name, mut dep, dep_version)| {
-                println!(
-                    "{}{}",
-                    style("Updated ").green().bold().on_bright(),
-                    format!("{} to version {}", dep_name, dep_version).as_str()
-                );
+        let resolution = self.resolve_dependencies(pkg_registry)?;
+        print_held_back(&updates, &resolution);
 
-                dep.sanitize_subdir();
+        let updated_deps = self
+            .planned_updates(&resolution)
+            .into_iter()
+            .map(|(dep_name, pin)| (dep_name.clone(), pin.clone()))
+            .collect::<Vec<(String, Pin)>>();
 
-                self.dependencies.insert(dep_name, dep);
-            });
+        updated_deps.into_iter().for_each(|(dep_name, pin)| {
+            println!(
+                "{}{}",
+                style("Updated ").green().bold().on_bright(),
+                format!("{} to version {}", dep_name, pin_version(&pin))
+                    .as_str()
+            );
+
+            let mut dep = pin.path;
+            dep.sanitize_subdir();
+
+            self.dependencies.insert(dep_name, dep);
+        });
+
+        Ok(resolution)
     }
 
     /// Checks for updates to the `MoveToml` file with the latest package
     /// versions and dependencies.
     ///
-    /// This function checks for updates in the package registry and updates.
+    /// This function checks for updates in the package registry and updates,
+    /// respecting the `[byte.constraints]` of the dependencies and keeping
+    /// them consistent with each other.
     ///
     /// # Arguments
     /// * `pkg_registry` - A reference to the `PackageRegistry`.
-    pub fn check_updates(&self, pkg_registry: &PackageRegistry) {
+    ///
+    /// # Returns
+    /// An error with the conflict chain if the dependencies cannot be
+    /// resolved consistently.
+    pub fn check_updates(
+        &self,
+        pkg_registry: &PackageRegistry,
+    ) -> Result<(), anyhow::Error> {
         let dependencies = self.dependency_pkg_infos(pkg_registry);
 
-        let to_update = pkg_registry.get_packages_to_update(&dependencies);
+        let updates =
+            pkg_registry.resolve_updates(&dependencies, &self.byte.constraints);
+        print_skipped(&updates.skipped);
+
+        let resolution = self.resolve_dependencies(pkg_registry)?;
+        print_held_back(&updates, &resolution);
 
+        let to_update = self.planned_updates(&resolution);
         if to_update.is_empty() {
             println!("All packages are up to date.");
-            return;
+            return Ok(());
         }
 
-        to_update.iter().for_each(|pkg_info| {
+        to_update.iter().for_each(|(dep_name, pin)| {
             println!(
                 "{}{}",
                 style("Update ").green().bold().on_bright(),
-                format!(
-                    "{} to version {}",
-                    pkg_info.package.name, pkg_info.package.version
+                format!("{} to version {}", dep_name, pin_version(pin))
+                    .as_str()
+            );
+        });
+
+        Ok(())
+    }
+
+    /// Dependencies whose revision differs from the resolved one.
+    fn planned_updates<'a>(
+        &'a self,
+        resolution: &'a Resolution,
+    ) -> Vec<(&'a String, &'a Pin)> {
+        self.dependencies
+            .iter()
+            .filter_map(|(dep_name, dep)| {
+                resolution
+                    .0
+                    .get(dep_name)
+                    .filter(|pin| pin.path.rev != dep.rev)
+                    .map(|pin| (dep_name, pin))
+            })
+            .collect()
+    }
+
+    /// Adds a package of the registry as a dependency, along with its named
+    /// address.
+    ///
+    /// Without an explicit version, the newest version allowed by the
+    /// `[byte.constraints]` of the package is added. A dependency that is
+    /// already declared is replaced.
+    ///
+    /// # Arguments
+    /// * `pkg_registry` - A reference to the `PackageRegistry`.
+    /// * `dep_name` - The name of the package in the registry.
+    /// * `version` - Optional version of the package to add.
+    ///
+    /// # Returns
+    /// The version of the package that was added along with the resolved
+    /// revisions of all packages reachable from the dependencies, or an
+    /// error if the package cannot be found or is not consistent with the
+    /// other dependencies, in which case the `MoveToml` is left untouched.
+    pub fn add_dependency(
+        &mut self,
+        pkg_registry: &PackageRegistry,
+        dep_name: &str,
+        version: Option<&Version>,
+    ) -> Result<(Version, Resolution), anyhow::Error> {
+        let versions = pkg_registry.0.get(dep_name).ok_or_else(|| {
+            anyhow!("Could not find package '{dep_name}' in Package Registry")
+        })?;
+
+        let constraint = self.byte.constraints.get(dep_name);
+        let info = match version {
+            Some(version) => versions.get(version).ok_or_else(|| {
+                anyhow!(
+                    "Could not find {dep_name} {version} in Package Registry"
                 )
-                .as_str()
+            })?,
+            None => pkg_registry
+                .newest_allowed(dep_name, constraint)
+                .ok_or_else(|| match constraint {
+                    Some(req) => {
+                        anyhow!("No version of {dep_name} satisfies `{req}`")
+                    }
+                    None => anyhow!("{dep_name} has no stable release"),
+                })?,
+        };
+
+        let mut dep = info.contract_ref.path.clone();
+        dep.sanitize_subdir();
+        let address = named_address(dep_name, &dep.git);
+
+        let previous_dep = self.dependencies.insert(dep_name.to_string(), dep);
+        let previous_address = self
+            .addresses
+            .insert(address.clone(), info.contract_ref.object_id.clone());
+
+        let resolution = match self.resolve_current(pkg_registry) {
+            Ok(resolution) => resolution,
+            Err(conflict) => {
+                match previous_dep {
+                    Some(dep) => self.dependencies.insert(dep_name.into(), dep),
+                    None => self.dependencies.remove(dep_name),
+                };
+                match previous_address {
+                    Some(addr) => self.addresses.insert(address, addr),
+                    None => self.addresses.remove(&address),
+                };
+
+                return Err(conflict.into());
+            }
+        };
+
+        Ok((info.package.version.clone(), resolution))
+    }
+
+    /// Removes a dependency along with its named address.
+    ///
+    /// # Arguments
+    /// * `dep_name` - The name of the dependency.
+    ///
+    /// # Returns
+    /// The dependency that was removed, or an error if the dependency is not
+    /// declared.
+    pub fn remove_dependency(
+        &mut self,
+        dep_name: &str,
+    ) -> Result<GitPath, anyhow::Error> {
+        let dep = self.dependencies.remove(dep_name).ok_or_else(|| {
+            anyhow!("{dep_name} is not a dependency of the Move.toml")
+        })?;
+        self.addresses.remove(&named_address(dep_name, &dep.git));
+
+        Ok(dep)
+    }
+
+    /// Versions of the declared dependencies known to the registry.
+    ///
+    /// # Arguments
+    /// * `pkg_registry` - A reference to the `PackageRegistry`.
+    pub fn dependency_versions(
+        &self,
+        pkg_registry: &PackageRegistry,
+    ) -> Vec<DependencyVersions> {
+        self.dependencies
+            .iter()
+            .map(|(name, dep)| {
+                let versions = pkg_registry.0.get(name);
+                let constraint = self.byte.constraints.get(name);
+
+                DependencyVersions {
+                    name: name.clone(),
+                    current: versions.and_then(|versions| {
+                        versions
+                            .iter()
+                            .find(|(_, info)| {
+                                info.contract_ref.path.rev == dep.rev
+                            })
+                            .map(|(version, _)| version.clone())
+                    }),
+                    latest: versions
+                        .and_then(|versions| versions.keys().next_back())
+                        .cloned(),
+                    allowed: pkg_registry
+                        .newest_allowed(name, constraint)
+                        .map(|info| info.package.version.clone()),
+                    constraint: constraint.cloned(),
+                }
+            })
+            .collect()
+    }
+
+    /// Prints a table of the declared dependencies along with their current,
+    /// latest and constraint-allowed versions.
+    ///
+    /// # Arguments
+    /// * `pkg_registry` - A reference to the `PackageRegistry`.
+    pub fn list_dependencies(&self, pkg_registry: &PackageRegistry) {
+        let rows = self.dependency_versions(pkg_registry);
+        let width = rows
+            .iter()
+            .map(|row| row.name.len())
+            .chain(["Package".len()])
+            .max()
+            .unwrap_or_default();
+
+        let column = |version: &Option<Version>| match version {
+            Some(version) => version.to_string(),
+            None => String::from("-"),
+        };
+
+        println!(
+            "{}",
+            style(format!(
+                "{:width$}  {:10}  {:10}  {:10}  Constraint",
+                "Package", "Current", "Latest", "Allowed"
+            ))
+            .bold()
+        );
+        rows.iter().for_each(|row| {
+            println!(
+                "{:width$}  {:10}  {:10}  {:10}  {}",
+                row.name,
+                column(&row.current),
+                column(&row.latest),
+                column(&row.allowed),
+                row.constraint
+                    .as_ref()
+                    .map(VersionReq::to_string)
+                    .unwrap_or_else(|| String::from("-")),
             );
         });
     }
@@ -281,6 +596,7 @@ impl MoveToml {
             ),
             dependencies,
             addresses: BTreeMap::from([(String::from(name), empty_addr)]),
+            byte: ByteTable::default(),
         };
 
         Ok(toml)
@@ -367,6 +683,42 @@ impl MoveToml {
     }
 }
 
+/// Explains why newer versions of dependencies were not updated to.
+fn print_skipped(skipped: &[SkippedUpdate]) {
+    skipped.iter().for_each(|skipped| {
+        println!("{}Skipped {skipped}", style("Warning ").yellow().bold());
+    });
+}
+
+/// Reports dependencies that were resolved to an older version than allowed
+/// by their constraints, in order to stay consistent with other dependencies.
+fn print_held_back(updates: &Updates, resolution: &Resolution) {
+    updates.to_update.iter().for_each(|newest| {
+        let name = &newest.package.name;
+        let Some(pin) = resolution.0.get(name) else {
+            return;
+        };
+
+        if pin.path.rev != newest.contract_ref.path.rev {
+            println!(
+                "{}Held back {name} at {}, {name} {} is not consistent with \
+                the other dependencies",
+                style("Warning ").yellow().bold(),
+                pin_version(pin),
+                newest.package.version,
+            );
+        }
+    });
+}
+
+/// Version of the pinned package, or its revision if the version is unknown.
+fn pin_version(pin: &Pin) -> String {
+    match &pin.version {
+        Some(version) => version.to_string(),
+        None => format!("@{}", pin.path.rev),
+    }
+}
+
 /// Retrieves `PackageInfo` for a given dependency based on its GitPath.
 ///
 /// This function is used to fetch the package information associated with a
@@ -486,6 +838,53 @@ pub fn get_contract_ref(
     }
 }
 
+/// Prefix of the Git repositories of OriginByte packages.
+const OB_GIT: &str = "https://github.com/Origin-Byte/";
+
+/// OriginByte packages whose named address carries an `ob_` prefix.
+const OB_PREFIXED: [&str; 8] = [
+    "Pseudorandom",
+    "Utils",
+    "Permissions",
+    "Request",
+    "Kiosk",
+    "Allowlist",
+    "Authlist",
+    "Launchpad",
+];
+
+/// Named address under which a package of the registry is declared, the
+/// package name in snake case, e.g. `LiquidityLayerV1` is declared as
+/// `liquidity_layer_v1`. Most OriginByte packages are declared with an
+/// `ob_` prefix, e.g. `Kiosk` is declared as `ob_kiosk`, whereas packages
+/// hosted elsewhere are never prefixed, whatever their name.
+///
+/// # Arguments
+/// * `package_name` - The name of the package.
+/// * `git` - The Git repository of the package, which tells OriginByte
+///   packages apart from other packages of the same name.
+pub fn named_address(package_name: &str, git: &str) -> String {
+    let address = snake_case(package_name);
+
+    if git.starts_with(OB_GIT) && OB_PREFIXED.contains(&package_name) {
+        format!("ob_{address}")
+    } else {
+        address
+    }
+}
+
+/// Package name in snake case, e.g. `LiquidityLayerV1` becomes
+/// `liquidity_layer_v1`.
+pub(crate) fn snake_case(package_name: &str) -> String {
+    package_name
+        .with_boundaries(&[
+            Boundary::Underscore,
+            Boundary::LowerUpper,
+            Boundary::DigitUpper,
+        ])
+        .to_case(Case::Snake)
+}
+
 /// This function is here because Toml serializer seems to be
 /// failing to add a vertical space between the tables `package` and
 /// `dependencies`
@@ -506,3 +905,127 @@ pub fn add_vertical_spacing(input: &str) -> String {
         .expect("Failed to read `published-at` field");
     re.replace_all(input, "$1\n").to_string()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{fixtures::registry, OB_PACKAGES};
+
+    const MOVE_TOML: &str = r#"
+[package]
+name = "Suimarines"
+version = "1.0.0"
+
+[dependencies.NftProtocol]
+git = "https://github.com/Origin-Byte/nft-protocol.git"
+subdir = "contracts/nft_protocol"
+rev = "95d16538dc7688dd4c4a5e7c3348bf3addf9c310"
+
+[addresses]
+suimarines = "0x0"
+
+[byte.constraints]
+NftProtocol = "^1.2"
+"#;
+
+    #[test]
+    fn test_constraints_roundtrip() -> Result<(), anyhow::Error> {
+        let move_toml: MoveToml = toml::from_str(MOVE_TOML)?;
+        assert_eq!(
+            move_toml.byte.constraints["NftProtocol"],
+            VersionReq::from_str("^1.2")?
+        );
+
+        let reparsed: MoveToml = toml::from_str(&move_toml.to_string()?)?;
+        assert_eq!(reparsed.byte.constraints, move_toml.byte.constraints);
+
+        let mut move_toml = move_toml;
+        move_toml.byte.constraints.clear();
+        assert!(!move_toml.to_string()?.contains("[byte"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_named_address() {
+        let ob = "https://github.com/Origin-Byte/nft-protocol.git";
+        let sui = "https://github.com/MystenLabs/sui.git";
+        let private = "https://github.com/Suimarines/utils.git";
+
+        assert_eq!(named_address("NftProtocol", ob), "nft_protocol");
+        assert_eq!(named_address("LiquidityLayerV1", ob), "liquidity_layer_v1");
+        assert_eq!(named_address("Sui", sui), "sui");
+        assert_eq!(named_address("joystick_v2", private), "joystick_v2");
+        assert_eq!(named_address("Kiosk", ob), "ob_kiosk");
+        assert_eq!(named_address("Launchpad", ob), "ob_launchpad");
+
+        // Private packages sharing the name of an OriginByte package
+        assert_eq!(named_address("Utils", private), "utils");
+        assert_eq!(named_address("Kiosk", private), "kiosk");
+
+        assert!(OB_PREFIXED.iter().all(|name| OB_PACKAGES.contains(name)));
+    }
+
+    #[test]
+    fn test_add_and_remove_dependency() -> Result<(), anyhow::Error> {
+        let registry = registry();
+        let mut move_toml: MoveToml = toml::from_str(MOVE_TOML)?;
+
+        // Latest Permissions is pinned by NftProtocol 1.2.0, the constraint
+        // of the Move.toml does not apply to other packages
+        move_toml.byte.constraints.clear();
+        move_toml.dependencies.get_mut("NftProtocol").unwrap().rev =
+            "93f6cd0b8966354b1b00e7d798cbfddaa867a07b".to_string();
+
+        let (version, _) =
+            move_toml.add_dependency(&registry, "Kiosk", None)?;
+        assert_eq!(version, Version::new(1, 2, 0));
+        let kiosk = &registry.0["Kiosk"][&version];
+        assert_eq!(
+            move_toml.dependencies["Kiosk"].rev,
+            kiosk.contract_ref.path.rev
+        );
+        assert_eq!(
+            move_toml.addresses["ob_kiosk"],
+            kiosk.contract_ref.object_id
+        );
+
+        // Kiosk 1.0.0 conflicts with NftProtocol 1.2.0
+        let err = move_toml
+            .add_dependency(&registry, "Kiosk", Some(&Version::new(1, 0, 0)))
+            .unwrap_err();
+        assert!(err.to_string().contains("two revisions"));
+        assert_eq!(
+            move_toml.dependencies["Kiosk"].rev,
+            kiosk.contract_ref.path.rev
+        );
+
+        assert!(move_toml
+            .add_dependency(&registry, "Unknown", None)
+            .is_err());
+
+        let removed = move_toml.remove_dependency("Kiosk")?;
+        assert_eq!(removed.rev, kiosk.contract_ref.path.rev);
+        assert!(!move_toml.dependencies.contains_key("Kiosk"));
+        assert!(!move_toml.addresses.contains_key("ob_kiosk"));
+        assert!(move_toml.remove_dependency("Kiosk").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_dependency_versions() -> Result<(), anyhow::Error> {
+        let registry = registry();
+        let move_toml: MoveToml = toml::from_str(MOVE_TOML)?;
+
+        let rows = move_toml.dependency_versions(&registry);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].name, "NftProtocol");
+        assert_eq!(rows[0].current, Some(Version::new(1, 0, 0)));
+        assert_eq!(rows[0].constraint, Some(VersionReq::from_str("^1.2")?));
+        assert!(rows[0].allowed >= Some(Version::new(1, 2, 0)));
+        assert!(rows[0].latest >= rows[0].allowed);
+
+        Ok(())
+    }
+}
@@ -1,39 +1,50 @@
 use anyhow::{anyhow, Result};
-use std::{cmp::Ordering, fmt, marker::PhantomData, str::FromStr};
 use serde::{
     de::{self, Unexpected, Visitor},
     Deserialize, Deserializer, Serialize, Serializer,
 };
+use std::{fmt, marker::PhantomData, str::FromStr};
 
 /// Represents a semantic version number.
 ///
-/// This struct is used to handle versioning in the format major.minor.patch.
-/// Each component of the version number is an unsigned 8-bit integer.
+/// This struct is used to handle versioning in the format
+/// major.minor.patch, optionally followed by a pre-release tag and build
+/// metadata, as defined by [Semantic Versioning](https://semver.org).
 ///
-/// # Fields
-/// * `major` - Major version number.
-/// * `minor` - Minor version number.
-/// * `patch` - Patch version number.
-#[derive(Debug, Eq, PartialEq, Clone, Copy)]
-pub struct Version {
-    major: u8,
-    minor: u8,
-    patch: u8,
-}
+/// Versions are ordered by precedence, such that pre-releases are ordered
+/// before the release they precede.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
+pub struct Version(semver::Version);
 
 impl Version {
-    /// Constructs a new `Version`.
+    /// Constructs a new `Version` without pre-release tag or build metadata.
     ///
     /// # Arguments
     /// * `major` - Major version number.
     /// * `minor` - Minor version number.
     /// * `patch` - Patch version number.
-    pub fn new(major: u8, minor: u8, patch: u8) -> Self {
-        Self {
-            major,
-            minor,
-            patch,
-        }
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self(semver::Version::new(major, minor, patch))
+    }
+
+    /// Major version number.
+    pub fn major(&self) -> u64 {
+        self.0.major
+    }
+
+    /// Minor version number.
+    pub fn minor(&self) -> u64 {
+        self.0.minor
+    }
+
+    /// Patch version number.
+    pub fn patch(&self) -> u64 {
+        self.0.patch
+    }
+
+    /// Whether the version carries a pre-release tag, such as `1.3.0-rc.1`.
+    pub fn is_prerelease(&self) -> bool {
+        !self.0.pre.is_empty()
     }
 }
 
@@ -45,27 +56,9 @@ impl FromStr for Version {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let version: Vec<&str> = s.split('.').collect();
-
-        if version.len() != 3 {
-            return Err(anyhow!("Version semantics is incorrect"));
-        }
-
-        let version = version
-            .iter()
-            .map(|v| {
-                let v_string = v.to_string();
-                v_string
-                    .parse::<u8>()
-                    .expect("Unable to convert version substring to u8")
-            })
-            .collect::<Vec<u8>>();
-
-        Ok(Version {
-            major: version[0],
-            minor: version[1],
-            patch: version[2],
-        })
+        semver::Version::parse(s)
+            .map(Version)
+            .map_err(|err| anyhow!("Invalid version `{s}`: {err}"))
     }
 }
 
@@ -93,27 +86,9 @@ impl<'de> Visitor<'de> for VersionVisitor {
     where
         E: de::Error,
     {
-        let version: Vec<&str> = s.split('.').collect();
-
-        if version.len() != 3 {
-            return Err(de::Error::invalid_value(Unexpected::Str(s), &self));
-        }
-
-        let version = version
-            .iter()
-            .map(|v| {
-                let v_string = v.to_string();
-                v_string
-                    .parse::<u8>()
-                    .expect("Unable to convert version substring to u8")
-            })
-            .collect::<Vec<u8>>();
-
-        Ok(Version {
-            major: version[0],
-            minor: version[1],
-            patch: version[2],
-        })
+        semver::Version::parse(s)
+            .map(Version)
+            .map_err(|_| de::Error::invalid_value(Unexpected::Str(s), &self))
     }
 }
 
@@ -132,49 +107,13 @@ impl<'de> Deserialize<'de> for Version {
     }
 }
 
-/// Implementing custom ordering for `Version`.
-///
-/// This enables comparison between two `Version` instances based on their
-/// major, minor, and patch numbers.
-impl Ord for Version {
-    fn cmp(&self, other: &Self) -> Ordering {
-        let major_ord = self.major.cmp(&other.major);
-        if major_ord != Ordering::Equal {
-            return major_ord;
-        }
-
-        let minor_ord = self.minor.cmp(&other.minor);
-        if minor_ord != Ordering::Equal {
-            return minor_ord;
-        }
-
-        self.patch.cmp(&other.patch)
-    }
-}
-
-impl PartialOrd for Version {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        let major_ord = self.major.partial_cmp(&other.major);
-        if major_ord.unwrap() != Ordering::Equal {
-            return major_ord;
-        }
-
-        let minor_ord = self.minor.partial_cmp(&other.minor);
-        if minor_ord.unwrap() != Ordering::Equal {
-            return minor_ord;
-        }
-
-        self.patch.partial_cmp(&other.patch)
-    }
-}
-
 /// Implementing custom display formatting for `Version`.
 ///
 /// This allows for a `Version` instance to be converted into a string
 /// in the format major.minor.patch.
 impl fmt::Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+        self.0.fmt(f)
     }
 }
 
@@ -187,13 +126,58 @@ impl Serialize for Version {
     where
         S: Serializer,
     {
-        let version_str = self.major.to_string()
-            + "."
-            + self.minor.to_string().as_str()
-            + "."
-            + self.patch.to_string().as_str();
+        serializer.collect_str(self)
+    }
+}
+
+/// Represents a range of semantic versions, such as `^1.2` or
+/// `>=1.0.0, <2.0.0`.
+///
+/// Requirements follow the same syntax as Cargo's, such that a bare version
+/// like `1.2` is a caret requirement. Pre-release versions only match
+/// requirements which mention a pre-release of the same major.minor.patch.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct VersionReq(semver::VersionReq);
+
+impl VersionReq {
+    /// Whether the version satisfies the requirement.
+    pub fn matches(&self, version: &Version) -> bool {
+        self.0.matches(&version.0)
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        semver::VersionReq::parse(s)
+            .map(VersionReq)
+            .map_err(|err| anyhow!("Invalid version requirement `{s}`: {err}"))
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionReq {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let req = String::deserialize(deserializer)?;
+        req.parse().map_err(de::Error::custom)
+    }
+}
 
-        serializer.serialize_str(version_str.as_str())
+impl Serialize for VersionReq {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
     }
 }
 
@@ -224,6 +208,38 @@ mod test_version {
         let version_b = Version::from_str("0.5.0")?;
         assert!(version_a < version_b);
 
+        let version_a = Version::from_str("1.3.0-rc.1")?;
+        let version_b = Version::from_str("1.3.0")?;
+        assert!(version_a < version_b);
+        assert!(version_a.is_prerelease());
+
+        let version_a = Version::from_str("1.0.300")?;
+        let version_b = Version::from_str("1.0.256")?;
+        assert!(version_a > version_b);
+        assert_eq!(version_a.patch(), 300);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(Version::from_str("1.0").is_err());
+        assert!(Version::from_str("1.0.x").is_err());
+        assert!(serde_json::from_str::<Version>(r#""1.a.0""#).is_err());
+    }
+
+    #[test]
+    fn test_requirement() -> Result<()> {
+        let req = VersionReq::from_str("^1.2")?;
+        assert!(req.matches(&Version::from_str("1.2.0")?));
+        assert!(req.matches(&Version::from_str("1.9.3")?));
+        assert!(!req.matches(&Version::from_str("1.1.9")?));
+        assert!(!req.matches(&Version::from_str("2.0.0")?));
+        assert!(!req.matches(&Version::from_str("1.3.0-rc.1")?));
+
+        let req = VersionReq::from_str(">=1.3.0-rc.1, <2")?;
+        assert!(req.matches(&Version::from_str("1.3.0-rc.2")?));
+
         Ok(())
     }
 }
@@ -0,0 +1,488 @@
+//! Scriptable in-memory chain client
+//!
+//! `FakeChainClient` serves objects, coins and transaction responses set up
+//! by the test, and records the transactions submitted to it. Failures can be
+//! scripted as well, e.g. `fail_next(rejected(503))` to exercise the handling
+//! of transient errors.
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use jsonrpsee::{core::Error as RpcError, http_client::transport};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Mutex, MutexGuard},
+};
+use sui_json_rpc_types::{
+    Coin, CoinPage, DryRunTransactionBlockResponse, ObjectsPage, Page,
+    SuiMoveStruct, SuiMoveValue, SuiObjectData, SuiObjectDataFilter,
+    SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
+    SuiParsedData, SuiParsedMoveObject, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions,
+};
+use sui_types::{
+    base_types::{
+        MoveObjectType, ObjectDigest, ObjectID, ObjectType, SequenceNumber,
+        SuiAddress,
+    },
+    digests::TransactionDigest,
+    error::SuiObjectResponseError,
+    object::Owner,
+    parse_sui_struct_tag,
+    quorum_driver_types::ExecuteTransactionRequestType,
+    transaction::{Transaction, TransactionData},
+};
+
+use super::ChainClient;
+use crate::err::RustSdkError;
+
+const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
+
+#[derive(Default)]
+struct FakeState {
+    gas_price: u64,
+    page_size: usize,
+    objects: BTreeMap<ObjectID, SuiObjectData>,
+    coins: BTreeMap<SuiAddress, Vec<Coin>>,
+    transactions: BTreeMap<TransactionDigest, SuiTransactionBlockResponse>,
+    dry_run_responses: VecDeque<DryRunTransactionBlockResponse>,
+    execution_responses: VecDeque<SuiTransactionBlockResponse>,
+    failures: VecDeque<RustSdkError>,
+    dry_runs: Vec<TransactionData>,
+    executed: Vec<Transaction>,
+}
+
+/// In-memory chain client serving scripted state and responses.
+pub struct FakeChainClient {
+    state: Mutex<FakeState>,
+}
+
+impl Default for FakeChainClient {
+    fn default() -> Self {
+        FakeChainClient::new()
+    }
+}
+
+impl FakeChainClient {
+    /// Creates an empty chain with a reference gas price of 1000 MIST and
+    /// pages of 50 entries.
+    pub fn new() -> Self {
+        FakeChainClient {
+            state: Mutex::new(FakeState {
+                gas_price: 1_000,
+                page_size: 50,
+                ..FakeState::default()
+            }),
+        }
+    }
+
+    /// Sets the reference gas price.
+    pub fn with_gas_price(self, gas_price: u64) -> Self {
+        self.state().gas_price = gas_price;
+        self
+    }
+
+    /// Sets the number of entries per page of owned objects and coins.
+    pub fn with_page_size(self, page_size: usize) -> Self {
+        self.state().page_size = page_size.max(1);
+        self
+    }
+
+    /// Adds an object, replacing any object with the same ID.
+    pub fn with_object(self, object: SuiObjectData) -> Self {
+        self.state().objects.insert(object.object_id, object);
+        self
+    }
+
+    /// Adds coins owned by the address, after the ones it already owns.
+    pub fn with_coins(self, owner: SuiAddress, coins: Vec<Coin>) -> Self {
+        self.state().coins.entry(owner).or_default().extend(coins);
+        self
+    }
+
+    /// Adds an executed transaction, which can be looked up by its digest.
+    pub fn with_transaction(
+        self,
+        response: SuiTransactionBlockResponse,
+    ) -> Self {
+        self.state().transactions.insert(response.digest, response);
+        self
+    }
+
+    /// Queues the response of the next dry-run.
+    pub fn push_dry_run(&self, response: DryRunTransactionBlockResponse) {
+        self.state().dry_run_responses.push_back(response);
+    }
+
+    /// Queues the response of the next execution, which is then also
+    /// returned when looking up the digest of the executed transaction.
+    pub fn push_execution(&self, response: SuiTransactionBlockResponse) {
+        self.state().execution_responses.push_back(response);
+    }
+
+    /// Makes the next call fail with the given error, whatever the call.
+    pub fn fail_next(&self, err: RustSdkError) {
+        self.state().failures.push_back(err);
+    }
+
+    /// The transactions dry-run so far, in order.
+    pub fn dry_runs(&self) -> Vec<TransactionData> {
+        self.state().dry_runs.clone()
+    }
+
+    /// The transactions submitted for execution so far, in order.
+    pub fn executed(&self) -> Vec<Transaction> {
+        self.state().executed.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, FakeState> {
+        self.state.lock().unwrap()
+    }
+
+    /// Locks the state, unless a failure is scripted for the call.
+    fn call(&self) -> Result<MutexGuard<'_, FakeState>, RustSdkError> {
+        let mut state = self.state();
+
+        match state.failures.pop_front() {
+            Some(err) => Err(err),
+            None => Ok(state),
+        }
+    }
+}
+
+impl FakeState {
+    fn object(&self, object_id: ObjectID) -> SuiObjectResponse {
+        match self.objects.get(&object_id) {
+            Some(object) => SuiObjectResponse::new_with_data(object.clone()),
+            None => SuiObjectResponse::new_with_error(
+                SuiObjectResponseError::NotExists { object_id },
+            ),
+        }
+    }
+
+    /// Returns the page of entries following the one with the cursor ID.
+    fn page<T>(
+        &self,
+        entries: Vec<(ObjectID, T)>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Page<T, ObjectID> {
+        let start = cursor
+            .and_then(|cursor| entries.iter().position(|(id, _)| *id == cursor))
+            .map(|index| index + 1)
+            .unwrap_or(0);
+        let limit = limit.unwrap_or(self.page_size).min(self.page_size);
+
+        let mut entries: Vec<(ObjectID, T)> =
+            entries.into_iter().skip(start).collect();
+        let has_next_page = entries.len() > limit;
+        entries.truncate(limit);
+
+        Page {
+            next_cursor: entries.last().map(|(id, _)| *id),
+            data: entries.into_iter().map(|(_, entry)| entry).collect(),
+            has_next_page,
+        }
+    }
+}
+
+/// Whether the object matches the filter, supporting struct type filters
+/// only and matching any object otherwise.
+fn matches(
+    object: &SuiObjectData,
+    query: &Option<SuiObjectResponseQuery>,
+) -> bool {
+    let filter = query.as_ref().and_then(|query| query.filter.as_ref());
+
+    match filter {
+        Some(SuiObjectDataFilter::StructType(tag)) => {
+            object.type_
+                == Some(ObjectType::Struct(MoveObjectType::from(tag.clone())))
+        }
+        _ => true,
+    }
+}
+
+/// Builds the error of an RPC endpoint rejecting a request with the given
+/// HTTP status code, such as 503 for an unavailable endpoint.
+pub fn rejected(status_code: u16) -> RustSdkError {
+    RustSdkError::SuiSdkError(sui_sdk::error::Error::RpcError(
+        RpcError::Transport(transport::Error::Rejected { status_code }.into()),
+    ))
+}
+
+/// Builds a SUI coin at version 1.
+pub fn coin(coin_object_id: ObjectID, balance: u64) -> Coin {
+    Coin {
+        coin_type: SUI_COIN_TYPE.to_string(),
+        coin_object_id,
+        version: SequenceNumber::from_u64(1),
+        digest: ObjectDigest::new([0; 32]),
+        balance,
+        previous_transaction: TransactionDigest::new([0; 32]),
+    }
+}
+
+/// Builds an object at version 1, without content.
+pub fn object(
+    object_id: ObjectID,
+    type_: ObjectType,
+    owner: Owner,
+) -> SuiObjectData {
+    SuiObjectData {
+        object_id,
+        version: SequenceNumber::from_u64(1),
+        digest: ObjectDigest::new([0; 32]),
+        type_: Some(type_),
+        owner: Some(owner),
+        previous_transaction: None,
+        storage_rebate: None,
+        display: None,
+        content: None,
+        bcs: None,
+    }
+}
+
+/// Sets the fields of a Move object, as read along with its content.
+pub fn with_fields(
+    mut object: SuiObjectData,
+    fields: Vec<(&str, SuiMoveValue)>,
+) -> SuiObjectData {
+    let Some(ObjectType::Struct(type_)) = object.type_.clone() else {
+        panic!("Only Move objects have fields");
+    };
+
+    object.content = Some(SuiParsedData::MoveObject(SuiParsedMoveObject {
+        type_: type_.into(),
+        has_public_transfer: true,
+        fields: SuiMoveStruct::WithFields(
+            fields
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        ),
+    }));
+
+    object
+}
+
+/// Parses a struct type such as `0x2::coin::Coin<0x2::sui::SUI>`.
+pub fn struct_type(type_: &str) -> ObjectType {
+    ObjectType::Struct(MoveObjectType::from(
+        parse_sui_struct_tag(type_).expect("Invalid struct type"),
+    ))
+}
+
+#[async_trait]
+impl ChainClient for FakeChainClient {
+    async fn get_reference_gas_price(&self) -> Result<u64, RustSdkError> {
+        Ok(self.call()?.gas_price)
+    }
+
+    async fn get_object_with_options(
+        &self,
+        object_id: ObjectID,
+        _options: SuiObjectDataOptions,
+    ) -> Result<SuiObjectResponse, RustSdkError> {
+        Ok(self.call()?.object(object_id))
+    }
+
+    async fn multi_get_object_with_options(
+        &self,
+        object_ids: Vec<ObjectID>,
+        _options: SuiObjectDataOptions,
+    ) -> Result<Vec<SuiObjectResponse>, RustSdkError> {
+        let state = self.call()?;
+
+        Ok(object_ids.into_iter().map(|id| state.object(id)).collect())
+    }
+
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        query: Option<SuiObjectResponseQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<ObjectsPage, RustSdkError> {
+        let state = self.call()?;
+
+        let owned = state
+            .objects
+            .values()
+            .filter(|object| object.owner == Some(Owner::AddressOwner(address)))
+            .filter(|object| matches(object, &query))
+            .map(|object| {
+                (
+                    object.object_id,
+                    SuiObjectResponse::new_with_data(object.clone()),
+                )
+            })
+            .collect();
+
+        Ok(state.page(owned, cursor, limit))
+    }
+
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<CoinPage, RustSdkError> {
+        let state = self.call()?;
+        let coin_type = coin_type.unwrap_or_else(|| SUI_COIN_TYPE.to_string());
+
+        let coins = state
+            .coins
+            .get(&owner)
+            .into_iter()
+            .flatten()
+            .filter(|coin| coin.coin_type == coin_type)
+            .map(|coin| (coin.coin_object_id, coin.clone()))
+            .collect();
+
+        Ok(state.page(coins, cursor, limit))
+    }
+
+    async fn get_transaction_with_options(
+        &self,
+        digest: TransactionDigest,
+        _options: SuiTransactionBlockResponseOptions,
+    ) -> Result<SuiTransactionBlockResponse, RustSdkError> {
+        self.call()?
+            .transactions
+            .get(&digest)
+            .cloned()
+            .ok_or_else(|| {
+                RustSdkError::AnyhowError(anyhow!(
+                    "Could not find the requested transaction {digest}"
+                ))
+            })
+    }
+
+    async fn dry_run_transaction_block(
+        &self,
+        tx_data: TransactionData,
+    ) -> Result<DryRunTransactionBlockResponse, RustSdkError> {
+        let mut state = self.call()?;
+        state.dry_runs.push(tx_data);
+
+        state.dry_run_responses.pop_front().ok_or_else(|| {
+            RustSdkError::AnyhowError(anyhow!("No dry-run response scripted"))
+        })
+    }
+
+    async fn execute_transaction_block(
+        &self,
+        tx: Transaction,
+        _options: SuiTransactionBlockResponseOptions,
+        _request_type: Option<ExecuteTransactionRequestType>,
+    ) -> Result<SuiTransactionBlockResponse, RustSdkError> {
+        let mut state = self.call()?;
+        let digest = *tx.digest();
+        state.executed.push(tx);
+
+        let response =
+            state.execution_responses.pop_front().ok_or_else(|| {
+                RustSdkError::AnyhowError(anyhow!(
+                    "No response scripted for transaction {digest}"
+                ))
+            })?;
+
+        state.transactions.insert(digest, response.clone());
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rpc::is_transient;
+
+    #[tokio::test]
+    async fn pages_through_coins() -> Result<(), RustSdkError> {
+        let owner = SuiAddress::from(ObjectID::from_single_byte(0xa));
+        let coins = (1..=5)
+            .map(|id| coin(ObjectID::from_single_byte(id), id as u64))
+            .collect();
+        let client = FakeChainClient::new()
+            .with_page_size(2)
+            .with_coins(owner, coins);
+
+        let mut balances = vec![];
+        let mut cursor = None;
+
+        loop {
+            let page = client.get_coins(owner, None, cursor, None).await?;
+            balances.extend(page.data.iter().map(|coin| coin.balance));
+
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+
+        assert_eq!(balances, vec![1, 2, 3, 4, 5]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fails_scripted_calls() -> Result<(), RustSdkError> {
+        let client = FakeChainClient::new().with_gas_price(750);
+        client.fail_next(rejected(503));
+        client.fail_next(rejected(400));
+        client.fail_next(RustSdkError::AnyhowError(anyhow!("503")));
+
+        let err = client.get_reference_gas_price().await.unwrap_err();
+        assert!(is_transient(&err));
+
+        // Neither client errors nor messages mentioning a status code are
+        // transient
+        let err = client.get_reference_gas_price().await.unwrap_err();
+        assert!(!is_transient(&err));
+        let err = client.get_reference_gas_price().await.unwrap_err();
+        assert!(!is_transient(&err));
+
+        assert_eq!(client.get_reference_gas_price().await?, 750);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn filters_owned_objects_by_type() -> Result<(), RustSdkError> {
+        let owner = SuiAddress::from(ObjectID::from_single_byte(0xa));
+        let mint_cap = "0x2::mint_cap::MintCap<0x3::suimarines::Suimarine>";
+        let client = FakeChainClient::new()
+            .with_object(object(
+                ObjectID::from_single_byte(1),
+                struct_type(mint_cap),
+                Owner::AddressOwner(owner),
+            ))
+            .with_object(object(
+                ObjectID::from_single_byte(2),
+                struct_type("0x2::coin::Coin<0x2::sui::SUI>"),
+                Owner::AddressOwner(owner),
+            ));
+
+        let query = SuiObjectResponseQuery::new(
+            Some(SuiObjectDataFilter::StructType(
+                parse_sui_struct_tag(mint_cap).unwrap(),
+            )),
+            None,
+        );
+        let page = client
+            .get_owned_objects(owner, Some(query), None, None)
+            .await?;
+
+        let ids: Vec<ObjectID> = page
+            .data
+            .iter()
+            .filter_map(|object| object.data.as_ref())
+            .map(|object| object.object_id)
+            .collect();
+        assert_eq!(ids, vec![ObjectID::from_single_byte(1)]);
+
+        Ok(())
+    }
+}
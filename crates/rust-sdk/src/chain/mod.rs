@@ -0,0 +1,370 @@
+//! Chain client abstraction
+//!
+//! The mint, coin and publish logic only needs a handful of RPC calls, which
+//! are gathered in the `ChainClient` trait. `SuiClient` and `FailoverClient`
+//! implement it against a fullnode, while `fake::FakeChainClient` serves
+//! scripted responses from memory so that the logic can be tested offline.
+
+pub mod fake;
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use sui_json_rpc_types::{
+    CoinPage, DryRunTransactionBlockResponse, ObjectsPage,
+    SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_sdk::SuiClient;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    digests::TransactionDigest,
+    quorum_driver_types::ExecuteTransactionRequestType,
+    transaction::{Transaction, TransactionData},
+};
+
+use crate::{err::RustSdkError, rpc::FailoverClient};
+
+/// The RPC calls made by the SDK, covering the read API, gas price, coin
+/// queries, dry-runs and transaction execution.
+#[async_trait]
+pub trait ChainClient: Send + Sync {
+    /// Retrieves the reference gas price of the current epoch.
+    async fn get_reference_gas_price(&self) -> Result<u64, RustSdkError>;
+
+    /// Retrieves an object along with the requested data.
+    async fn get_object_with_options(
+        &self,
+        object_id: ObjectID,
+        options: SuiObjectDataOptions,
+    ) -> Result<SuiObjectResponse, RustSdkError>;
+
+    /// Retrieves several objects along with the requested data, in the
+    /// order of their IDs.
+    async fn multi_get_object_with_options(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: SuiObjectDataOptions,
+    ) -> Result<Vec<SuiObjectResponse>, RustSdkError>;
+
+    /// Retrieves a page of the objects owned by the address.
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        query: Option<SuiObjectResponseQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<ObjectsPage, RustSdkError>;
+
+    /// Retrieves a page of the coins of the given type owned by the address,
+    /// SUI if no type is given.
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<CoinPage, RustSdkError>;
+
+    /// Retrieves an executed transaction by its digest.
+    async fn get_transaction_with_options(
+        &self,
+        digest: TransactionDigest,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> Result<SuiTransactionBlockResponse, RustSdkError>;
+
+    /// Simulates the transaction without executing it.
+    async fn dry_run_transaction_block(
+        &self,
+        tx_data: TransactionData,
+    ) -> Result<DryRunTransactionBlockResponse, RustSdkError>;
+
+    /// Submits a signed transaction for execution.
+    async fn execute_transaction_block(
+        &self,
+        tx: Transaction,
+        options: SuiTransactionBlockResponseOptions,
+        request_type: Option<ExecuteTransactionRequestType>,
+    ) -> Result<SuiTransactionBlockResponse, RustSdkError>;
+}
+
+#[async_trait]
+impl ChainClient for SuiClient {
+    async fn get_reference_gas_price(&self) -> Result<u64, RustSdkError> {
+        Ok(self.read_api().get_reference_gas_price().await?)
+    }
+
+    async fn get_object_with_options(
+        &self,
+        object_id: ObjectID,
+        options: SuiObjectDataOptions,
+    ) -> Result<SuiObjectResponse, RustSdkError> {
+        Ok(self
+            .read_api()
+            .get_object_with_options(object_id, options)
+            .await?)
+    }
+
+    async fn multi_get_object_with_options(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: SuiObjectDataOptions,
+    ) -> Result<Vec<SuiObjectResponse>, RustSdkError> {
+        Ok(self
+            .read_api()
+            .multi_get_object_with_options(object_ids, options)
+            .await?)
+    }
+
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        query: Option<SuiObjectResponseQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<ObjectsPage, RustSdkError> {
+        Ok(self
+            .read_api()
+            .get_owned_objects(address, query, cursor, limit)
+            .await?)
+    }
+
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<CoinPage, RustSdkError> {
+        Ok(self
+            .coin_read_api()
+            .get_coins(owner, coin_type, cursor, limit)
+            .await?)
+    }
+
+    async fn get_transaction_with_options(
+        &self,
+        digest: TransactionDigest,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> Result<SuiTransactionBlockResponse, RustSdkError> {
+        Ok(self
+            .read_api()
+            .get_transaction_with_options(digest, options)
+            .await?)
+    }
+
+    async fn dry_run_transaction_block(
+        &self,
+        tx_data: TransactionData,
+    ) -> Result<DryRunTransactionBlockResponse, RustSdkError> {
+        Ok(self.read_api().dry_run_transaction_block(tx_data).await?)
+    }
+
+    async fn execute_transaction_block(
+        &self,
+        tx: Transaction,
+        options: SuiTransactionBlockResponseOptions,
+        request_type: Option<ExecuteTransactionRequestType>,
+    ) -> Result<SuiTransactionBlockResponse, RustSdkError> {
+        Ok(self
+            .quorum_driver_api()
+            .execute_transaction_block(tx, options, request_type)
+            .await?)
+    }
+}
+
+/// Reads are retried on the next endpoint on transient errors, and
+/// executions are resubmitted only if their digest is not found on-chain.
+#[async_trait]
+impl<C> ChainClient for FailoverClient<C>
+where
+    C: ChainClient + Clone,
+{
+    async fn get_reference_gas_price(&self) -> Result<u64, RustSdkError> {
+        self.read(
+            |client| async move { client.get_reference_gas_price().await },
+        )
+        .await
+    }
+
+    async fn get_object_with_options(
+        &self,
+        object_id: ObjectID,
+        options: SuiObjectDataOptions,
+    ) -> Result<SuiObjectResponse, RustSdkError> {
+        self.read(|client| {
+            let options = options.clone();
+            async move {
+                client.get_object_with_options(object_id, options).await
+            }
+        })
+        .await
+    }
+
+    async fn multi_get_object_with_options(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: SuiObjectDataOptions,
+    ) -> Result<Vec<SuiObjectResponse>, RustSdkError> {
+        self.read(|client| {
+            let object_ids = object_ids.clone();
+            let options = options.clone();
+            async move {
+                client
+                    .multi_get_object_with_options(object_ids, options)
+                    .await
+            }
+        })
+        .await
+    }
+
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        query: Option<SuiObjectResponseQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<ObjectsPage, RustSdkError> {
+        self.read(|client| {
+            let query = query.clone();
+            async move {
+                client
+                    .get_owned_objects(address, query, cursor, limit)
+                    .await
+            }
+        })
+        .await
+    }
+
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<CoinPage, RustSdkError> {
+        self.read(|client| {
+            let coin_type = coin_type.clone();
+            async move {
+                client.get_coins(owner, coin_type, cursor, limit).await
+            }
+        })
+        .await
+    }
+
+    async fn get_transaction_with_options(
+        &self,
+        digest: TransactionDigest,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> Result<SuiTransactionBlockResponse, RustSdkError> {
+        self.read(|client| {
+            let options = options.clone();
+            async move {
+                client.get_transaction_with_options(digest, options).await
+            }
+        })
+        .await
+    }
+
+    async fn dry_run_transaction_block(
+        &self,
+        tx_data: TransactionData,
+    ) -> Result<DryRunTransactionBlockResponse, RustSdkError> {
+        self.read(|client| {
+            let tx_data = tx_data.clone();
+            async move { client.dry_run_transaction_block(tx_data).await }
+        })
+        .await
+    }
+
+    async fn execute_transaction_block(
+        &self,
+        tx: Transaction,
+        options: SuiTransactionBlockResponseOptions,
+        request_type: Option<ExecuteTransactionRequestType>,
+    ) -> Result<SuiTransactionBlockResponse, RustSdkError> {
+        self.execute_transaction_with_options(tx, options, request_type)
+            .await
+    }
+}
+
+#[async_trait]
+impl<C> ChainClient for Arc<C>
+where
+    C: ChainClient + ?Sized,
+{
+    async fn get_reference_gas_price(&self) -> Result<u64, RustSdkError> {
+        self.as_ref().get_reference_gas_price().await
+    }
+
+    async fn get_object_with_options(
+        &self,
+        object_id: ObjectID,
+        options: SuiObjectDataOptions,
+    ) -> Result<SuiObjectResponse, RustSdkError> {
+        self.as_ref()
+            .get_object_with_options(object_id, options)
+            .await
+    }
+
+    async fn multi_get_object_with_options(
+        &self,
+        object_ids: Vec<ObjectID>,
+        options: SuiObjectDataOptions,
+    ) -> Result<Vec<SuiObjectResponse>, RustSdkError> {
+        self.as_ref()
+            .multi_get_object_with_options(object_ids, options)
+            .await
+    }
+
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        query: Option<SuiObjectResponseQuery>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<ObjectsPage, RustSdkError> {
+        self.as_ref()
+            .get_owned_objects(address, query, cursor, limit)
+            .await
+    }
+
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> Result<CoinPage, RustSdkError> {
+        self.as_ref()
+            .get_coins(owner, coin_type, cursor, limit)
+            .await
+    }
+
+    async fn get_transaction_with_options(
+        &self,
+        digest: TransactionDigest,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> Result<SuiTransactionBlockResponse, RustSdkError> {
+        self.as_ref()
+            .get_transaction_with_options(digest, options)
+            .await
+    }
+
+    async fn dry_run_transaction_block(
+        &self,
+        tx_data: TransactionData,
+    ) -> Result<DryRunTransactionBlockResponse, RustSdkError> {
+        self.as_ref().dry_run_transaction_block(tx_data).await
+    }
+
+    async fn execute_transaction_block(
+        &self,
+        tx: Transaction,
+        options: SuiTransactionBlockResponseOptions,
+        request_type: Option<ExecuteTransactionRequestType>,
+    ) -> Result<SuiTransactionBlockResponse, RustSdkError> {
+        self.as_ref()
+            .execute_transaction_block(tx, options, request_type)
+            .await
+    }
+}
@@ -1,18 +1,14 @@
-use crate::utils::{execute_tx, get_reference_gas_price, CloneClient};
+use crate::chain::ChainClient;
+use crate::utils::{execute_tx, get_coin_ref, get_reference_gas_price};
 use crate::{err::RustSdkError, utils::get_context};
 use anyhow::{anyhow, Result};
 use std::fmt::Write;
-use std::ops::Deref;
 use std::{
     fmt::{Display, Formatter},
     str::FromStr,
 };
 use sui_json_rpc_types::{Coin, Page, SuiTransactionBlockEffects};
-use sui_sdk::{
-    types::base_types::{ObjectID, SuiAddress},
-    wallet_context::WalletContext,
-    SuiClient,
-};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
 use sui_types::transaction::{
     CallArg, ObjectArg, ProgrammableTransaction, TransactionData,
 };
@@ -72,10 +68,13 @@ pub async fn split(
     gas_id: Option<ObjectID>,
 ) -> Result<(), RustSdkError> {
     let wallet_ctx = get_context().await.unwrap();
+    let client = wallet_ctx.get_client().await?;
+    let sender = wallet_ctx.config.active_address.unwrap();
 
-    let data =
-        prepare_split(&wallet_ctx, coin_id, amount, count, gas_budget, gas_id)
-            .await?;
+    let data = prepare_split(
+        &client, sender, coin_id, amount, count, gas_budget, gas_id,
+    )
+    .await?;
 
     let response = execute_tx(&wallet_ctx, data).await?;
 
@@ -88,35 +87,64 @@ pub async fn split(
 
 /// Prepares transaction data for splitting a coin.
 ///
+/// # Type Parameters
+/// * `C` - The chain client type.
+///
 /// # Arguments
-/// * `wallet_ctx` - The wallet context for the transaction.
+/// * `client` - The client used for retrieving coin information.
+/// * `sender` - The Sui address of the coin owner.
 /// * `coin_id` - The ID of the coin to be split.
 /// * `amount` - The amount to split from the coin.
 /// * `count` - The number of coins to split into.
 /// * `gas_budget` - The gas budget for the transaction.
-/// * `gas_id` - Optional ID of the gas coin.
+/// * `gas_id` - Optional ID of the gas coin. If `None`, the biggest other coin
+///   covering the gas budget is used.
 ///
 /// # Returns
 /// A result containing the transaction data or an error.
-pub async fn prepare_split(
-    wallet_ctx: &WalletContext,
+pub async fn prepare_split<C>(
+    client: &C,
+    sender: SuiAddress,
     coin_id: ObjectID,
     amount: Option<u64>,
     count: u64,
     gas_budget: u64,
     gas_id: Option<ObjectID>,
-) -> Result<TransactionData, RustSdkError> {
-    let client = wallet_ctx.get_client().await?;
-    let sender = wallet_ctx.config.active_address.unwrap();
-
-    let coin = select_coin(&client, sender, coin_id).await?;
-
+) -> Result<TransactionData, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
     if count == 0 {
         return Err(RustSdkError::AnyhowError(anyhow!(
             "Coin split count must be greater than 0"
         )));
     }
 
+    let gas_price = get_reference_gas_price(client).await?;
+    let mut coins = get_coins(client, sender).await?;
+
+    let coin_index = coins
+        .iter()
+        .position(|c| c.coin_object_id == coin_id)
+        .ok_or_else(|| anyhow!("Could not find coin {coin_id} of {sender}"))?;
+    let coin = coins.remove(coin_index);
+
+    let gas_coin = match gas_id {
+        Some(gas_id) => coins
+            .iter()
+            .find(|c| c.coin_object_id == gas_id)
+            .ok_or_else(|| {
+                anyhow!("Could not find gas coin {gas_id} of {sender}")
+            })?,
+        None => coins
+            .iter()
+            .filter(|c| c.balance >= gas_budget)
+            .max_by_key(|c| c.balance)
+            .ok_or_else(|| {
+                anyhow!("Could not find another coin to pay {gas_budget} gas")
+            })?,
+    };
+
     // TODO: Improve this flow is confusing
     let (count, split_amount) = if amount.is_some() {
         (count, amount.unwrap() / count)
@@ -126,16 +154,27 @@ pub async fn prepare_split(
 
     let split_amounts = vec![split_amount; count as usize];
 
-    Ok(client
-        .transaction_builder()
-        .split_coin(
-            sender,
-            coin.coin_object_id,
-            split_amounts,
-            gas_id,
-            gas_budget,
-        )
-        .await?)
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.move_call(
+        SUI_FRAMEWORK_PACKAGE_ID,                 // Package ID
+        coin::PAY_MODULE_NAME.to_owned(),         // Module Name
+        coin::PAY_SPLIT_VEC_FUNC_NAME.to_owned(), // Function Name
+        vec![TypeTag::from_str(coin.coin_type.as_str())?], // Type Arguments
+        vec![
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(get_coin_ref(&coin))),
+            CallArg::Pure(bcs::to_bytes(&split_amounts).map_err(|err| {
+                anyhow!("Could not serialize the split amounts: {err}")
+            })?),
+        ], // Call Arguments
+    )?;
+
+    Ok(TransactionData::new_programmable(
+        sender,
+        vec![get_coin_ref(gas_coin)], // Gas Objects
+        builder.finish(),
+        gas_budget,
+        gas_price,
+    ))
 }
 
 /// Combines smaller coins into a larger coin.
@@ -177,15 +216,15 @@ pub async fn combine(
 /// # Returns
 /// A result containing the transaction data or an error.
 pub async fn prepare_combine<C>(
-    client: C,
+    client: &C,
     sender: SuiAddress,
     gas_budget: u64,
     gas_id: ObjectID,
 ) -> Result<TransactionData, RustSdkError>
 where
-    C: Deref<Target = SuiClient> + CloneClient,
+    C: ChainClient + ?Sized,
 {
-    let gas_price = get_reference_gas_price(client.clone_client()).await?;
+    let gas_price = get_reference_gas_price(client).await?;
 
     let (main_coin, gas_coin, coins_to_merge) =
         separate_gas_and_max_coin(client, sender, gas_id).await?;
@@ -208,8 +247,7 @@ where
 /// Selects a specific coin by its ObjectID.
 ///
 /// # Type Parameters
-/// * `C` - The client type, bounded by `Deref` targeting `SuiClient` and
-///   `CloneClient`.
+/// * `C` - The chain client type.
 ///
 /// # Arguments
 /// * `client` - The client used for retrieving coin information.
@@ -219,14 +257,14 @@ where
 /// # Returns
 /// A result containing the selected coin (`Coin`) or a `RustSdkError`.
 pub async fn select_coin<C>(
-    client: C,
+    client: &C,
     sender: SuiAddress,
     coin_id: ObjectID,
 ) -> Result<Coin, RustSdkError>
 where
-    C: Deref<Target = SuiClient> + CloneClient,
+    C: ChainClient + ?Sized,
 {
-    let mut coins = get_coins(client.clone_client(), sender).await?;
+    let mut coins = get_coins(client, sender).await?;
 
     let index = coins
         .iter()
@@ -240,8 +278,7 @@ where
 /// Selects the largest coin owned by the sender.
 ///
 /// # Type Parameters
-/// * `C` - The client type, bounded by `Deref` targeting `SuiClient` and
-///   `CloneClient`.
+/// * `C` - The chain client type.
 ///
 /// # Arguments
 /// * `client` - The client used for retrieving coin information.
@@ -250,11 +287,11 @@ where
 /// # Returns
 /// A result containing the largest coin (`Coin`) or a `RustSdkError`.
 pub async fn select_biggest_coin<C>(
-    client: C,
+    client: &C,
     sender: SuiAddress,
 ) -> Result<Coin, RustSdkError>
 where
-    C: Deref<Target = SuiClient> + CloneClient,
+    C: ChainClient + ?Sized,
 {
     let mut coins = get_coins(client, sender).await?;
 
@@ -269,8 +306,7 @@ where
 /// Separates the specified gas coin from the coin with the largest balance.
 ///
 /// # Type Parameters
-/// * `C` - The client type, bounded by `Deref` targeting `SuiClient` and
-///   `CloneClient`.
+/// * `C` - The chain client type.
 ///
 /// # Arguments
 /// * `client` - The client used for retrieving coin information.
@@ -281,12 +317,12 @@ where
 /// A result containing the separated gas coin, the largest balance coin, and
 /// other coins (`(Coin, Coin, Vec<Coin>)`) or a `RustSdkError`.
 pub async fn separate_gas_and_max_coin<C>(
-    client: C,
+    client: &C,
     sender: SuiAddress,
     gas_id: ObjectID,
 ) -> Result<(Coin, Coin, Vec<Coin>), RustSdkError>
 where
-    C: Deref<Target = SuiClient> + CloneClient,
+    C: ChainClient + ?Sized,
 {
     let mut coins = get_coins(client, sender).await?;
 
@@ -308,8 +344,7 @@ where
 /// Separates the specified gas coin from other coins.
 ///
 /// # Type Parameters
-/// * `C` - The client type, bounded by `Deref` targeting `SuiClient` and
-///   `CloneClient`.
+/// * `C` - The chain client type.
 ///
 /// # Arguments
 /// * `client` - The client used for retrieving coin information.
@@ -320,12 +355,12 @@ where
 /// A result containing the separated gas coin and other coins ((Coin,
 /// Vec<Coin>)) or a RustSdkError.
 pub async fn separate_gas_coin<C>(
-    client: C,
+    client: &C,
     sender: SuiAddress,
     gas_id: ObjectID,
 ) -> Result<(Coin, Vec<Coin>), RustSdkError>
 where
-    C: Deref<Target = SuiClient> + CloneClient,
+    C: ChainClient + ?Sized,
 {
     let mut coins = get_coins(client, sender).await?;
 
@@ -345,8 +380,7 @@ where
 /// including each coin's ID, balance, and its equivalent value in SUI.
 ///
 /// # Type Parameters
-/// * `C` - The client type, bounded by `Deref` targeting `SuiClient` and
-///   `CloneClient`.
+/// * `C` - The chain client type.
 ///
 /// # Arguments
 /// * `client` - The client used for retrieving coin information.
@@ -355,9 +389,9 @@ where
 /// # Returns
 /// A result containing a formatted list of coins (`CoinList`) or a
 /// `RustSdkError`.
-pub async fn list_coins<C>(client: C, sender: SuiAddress) -> Result<CoinList>
+pub async fn list_coins<C>(client: &C, sender: SuiAddress) -> Result<CoinList>
 where
-    C: Deref<Target = SuiClient> + CloneClient,
+    C: ChainClient + ?Sized,
 {
     let coins = get_coins(client, sender).await?;
 
@@ -380,22 +414,27 @@ where
 /// This function fetches a complete list of coins, including their balances,
 /// owned by the sender.
 ///
+/// # Type Parameters
+/// * `C` - The chain client type.
+///
 /// # Arguments
-/// * `client` - An instance of `SuiClient` used for querying the blockchain.
+/// * `client` - The chain client used for querying the blockchain.
 /// * `sender` - The Sui address of the coin owner.
 ///
 /// # Returns
 /// A result containing a vector of `Coin` instances or a `RustSdkError`.
-pub async fn get_coins(
-    client: impl Deref<Target = SuiClient>,
+pub async fn get_coins<C>(
+    client: &C,
     sender: SuiAddress,
-) -> Result<Vec<Coin>, RustSdkError> {
+) -> Result<Vec<Coin>, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
     let mut coins: Vec<Coin> = vec![];
     let mut cursor = None;
 
     loop {
         let coin_page = client
-            .coin_read_api()
             .get_coins(sender, Some("0x2::sui::SUI".into()), cursor, None)
             .await?;
 
@@ -420,17 +459,23 @@ pub async fn get_coins(
 /// Finds and returns the coin with the highest balance owned by the specified
 /// sender.
 ///
+/// # Type Parameters
+/// * `C` - The chain client type.
+///
 /// # Arguments
-/// * `client` - An instance of `SuiClient` used for querying the blockchain.
+/// * `client` - The chain client used for querying the blockchain.
 /// * `sender` - The Sui address of the coin owner.
 ///
 /// # Returns
 /// A result containing the coin with the highest balance (`Coin`) or a
 /// `RustSdkError`.
-pub async fn get_max_coin(
-    client: impl Deref<Target = SuiClient>,
+pub async fn get_max_coin<C>(
+    client: &C,
     sender: SuiAddress,
-) -> Result<Coin, RustSdkError> {
+) -> Result<Coin, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
     let mut coins = get_coins(client, sender).await?;
 
     let max_balance = coins.iter().map(|c| c.balance).max().unwrap();
@@ -496,3 +541,99 @@ pub async fn merge_coins(
 
     Ok(builder.finish())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chain::fake::{coin, FakeChainClient};
+    use sui_types::transaction::{TransactionDataAPI, TransactionKind};
+
+    fn sender() -> SuiAddress {
+        SuiAddress::from(ObjectID::from_single_byte(0xa))
+    }
+
+    /// Chain on which the sender owns coins 1 to 3
+    fn chain() -> FakeChainClient {
+        FakeChainClient::new().with_gas_price(750).with_coins(
+            sender(),
+            vec![
+                coin(ObjectID::from_single_byte(1), 100),
+                coin(ObjectID::from_single_byte(2), 500),
+                coin(ObjectID::from_single_byte(3), 300),
+            ],
+        )
+    }
+
+    #[tokio::test]
+    async fn separates_gas_and_max_coin() -> Result<(), RustSdkError> {
+        let (max_coin, gas_coin, others) = separate_gas_and_max_coin(
+            &chain(),
+            sender(),
+            ObjectID::from_single_byte(3),
+        )
+        .await?;
+
+        assert_eq!(max_coin.coin_object_id, ObjectID::from_single_byte(2));
+        assert_eq!(gas_coin.coin_object_id, ObjectID::from_single_byte(3));
+        assert_eq!(
+            others.iter().map(|c| c.coin_object_id).collect::<Vec<_>>(),
+            vec![ObjectID::from_single_byte(1)]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn prepares_split() -> Result<(), RustSdkError> {
+        let tx_data = prepare_split(
+            &chain(),
+            sender(),
+            ObjectID::from_single_byte(2),
+            Some(300),
+            3,
+            200,
+            None,
+        )
+        .await?;
+
+        assert_eq!(tx_data.gas_price(), 750);
+        // Coin 3 is the biggest coin other than the one being split
+        assert_eq!(tx_data.gas()[0].0, ObjectID::from_single_byte(3));
+
+        let TransactionKind::ProgrammableTransaction(pt) = tx_data.kind()
+        else {
+            panic!("Expected a programmable transaction");
+        };
+
+        assert_eq!(pt.commands.len(), 1);
+        assert!(pt.inputs.contains(&CallArg::Pure(
+            bcs::to_bytes(&vec![100u64; 3]).unwrap()
+        )));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_split_without_gas_coin() {
+        let client = FakeChainClient::new().with_coins(
+            sender(),
+            vec![
+                coin(ObjectID::from_single_byte(1), 1_000),
+                coin(ObjectID::from_single_byte(2), 10),
+            ],
+        );
+
+        let result = prepare_split(
+            &client,
+            sender(),
+            ObjectID::from_single_byte(1),
+            None,
+            2,
+            100,
+            None,
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -5,17 +5,73 @@ use sui_sdk::types::base_types::ObjectID;
 /// Enum representing different types of objects in the system, each associated
 /// with an `ObjectID`. This enumeration helps in differentiating between
 /// various object types like Package, Collection, etc.
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// Policies carry the rules they enforce, and policy caps the `ObjectID` of
+/// the policy they administer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ObjectType {
     Package(ObjectID),
+    UpgradeCap(ObjectID),
+    Publisher(ObjectID),
+    Display(ObjectID),
     Collection(ObjectID),
     MintCap(ObjectID),
     Warehouse(ObjectID),
-    BpsRoyaltyStrategy(ObjectID),
-    PolicyCap(ObjectID),
-    Policy(ObjectID),
-    TransferPolicy(ObjectID),
-    TransferPolicyCap(ObjectID),
+    Orderbook(ObjectID),
+    Allowlist(ObjectID),
+    BpsRoyaltyStrategy { id: ObjectID, bps: u64 },
+    TransferPolicy { id: ObjectID, rules: Vec<String> },
+    TransferPolicyCap { id: ObjectID, policy: ObjectID },
+    WithdrawPolicy { id: ObjectID, rules: Vec<String> },
+    WithdrawPolicyCap { id: ObjectID, policy: ObjectID },
+    BorrowPolicy { id: ObjectID, rules: Vec<String> },
+    BorrowPolicyCap { id: ObjectID, policy: ObjectID },
+}
+
+impl ObjectType {
+    /// The `ObjectID` of the object.
+    pub fn id(&self) -> ObjectID {
+        match self {
+            ObjectType::Package(id)
+            | ObjectType::UpgradeCap(id)
+            | ObjectType::Publisher(id)
+            | ObjectType::Display(id)
+            | ObjectType::Collection(id)
+            | ObjectType::MintCap(id)
+            | ObjectType::Warehouse(id)
+            | ObjectType::Orderbook(id)
+            | ObjectType::Allowlist(id)
+            | ObjectType::BpsRoyaltyStrategy { id, .. }
+            | ObjectType::TransferPolicy { id, .. }
+            | ObjectType::TransferPolicyCap { id, .. }
+            | ObjectType::WithdrawPolicy { id, .. }
+            | ObjectType::WithdrawPolicyCap { id, .. }
+            | ObjectType::BorrowPolicy { id, .. }
+            | ObjectType::BorrowPolicyCap { id, .. } => *id,
+        }
+    }
+
+    /// Human-readable name of the kind of object.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ObjectType::Package(_) => "Package",
+            ObjectType::UpgradeCap(_) => "UpgradeCap",
+            ObjectType::Publisher(_) => "Publisher",
+            ObjectType::Display(_) => "Display",
+            ObjectType::Collection(_) => "Collection",
+            ObjectType::MintCap(_) => "MintCap",
+            ObjectType::Warehouse(_) => "Warehouse",
+            ObjectType::Orderbook(_) => "Orderbook",
+            ObjectType::Allowlist(_) => "Allowlist",
+            ObjectType::BpsRoyaltyStrategy { .. } => "BpsRoyaltyStrategy",
+            ObjectType::TransferPolicy { .. } => "TransferPolicy",
+            ObjectType::TransferPolicyCap { .. } => "TransferPolicyCap",
+            ObjectType::WithdrawPolicy { .. } => "WithdrawPolicy",
+            ObjectType::WithdrawPolicyCap { .. } => "WithdrawPolicyCap",
+            ObjectType::BorrowPolicy { .. } => "BorrowPolicy",
+            ObjectType::BorrowPolicyCap { .. } => "BorrowPolicyCap",
+        }
+    }
 }
 
 /// Implementation of the `Display` trait for `ObjectType`.
@@ -33,16 +89,6 @@ impl fmt::Display for ObjectType {
     /// `ObjectID`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Transparently pass through ObjectID
-        match self {
-            ObjectType::Package(id)
-            | ObjectType::Collection(id)
-            | ObjectType::MintCap(id)
-            | ObjectType::Warehouse(id)
-            | ObjectType::BpsRoyaltyStrategy(id)
-            | ObjectType::PolicyCap(id)
-            | ObjectType::Policy(id)
-            | ObjectType::TransferPolicy(id)
-            | ObjectType::TransferPolicyCap(id) => fmt::Display::fmt(id, f),
-        }
+        fmt::Display::fmt(&self.id(), f)
     }
 }
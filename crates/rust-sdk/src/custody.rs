@@ -0,0 +1,461 @@
+//! Hands over the admin objects of a collection
+//!
+//! The caps of a collection are moved to their new custodian, such as a
+//! client or a multisig address, in a single programmable transaction, which
+//! may also restrict the upgrade policy of the package beforehand.
+
+use anyhow::anyhow;
+use move_core_types::identifier::Identifier;
+use std::{fmt, str::FromStr};
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{ObjectArg, TransactionData},
+    SUI_FRAMEWORK_PACKAGE_ID,
+};
+
+use crate::chain::ChainClient;
+use crate::err::RustSdkError;
+use crate::models::project::{Cap, Project};
+use crate::utils::{get_owned_object_refs, get_reference_gas_price};
+
+/// Kind of the admin objects tracked by the project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdminCap {
+    MintCap,
+    TransferPolicyCap,
+    WithdrawPolicyCap,
+    BorrowPolicyCap,
+    Publisher,
+    UpgradeCap,
+}
+
+impl AdminCap {
+    pub const ALL: [AdminCap; 6] = [
+        AdminCap::MintCap,
+        AdminCap::TransferPolicyCap,
+        AdminCap::WithdrawPolicyCap,
+        AdminCap::BorrowPolicyCap,
+        AdminCap::Publisher,
+        AdminCap::UpgradeCap,
+    ];
+
+    /// The IDs of the admin objects of this kind recorded in the project.
+    pub fn object_ids(&self, project: &Project) -> Vec<ObjectID> {
+        let admin_objects = project.admin_objects.as_ref();
+        let cap_ids = |caps: Option<&Vec<Cap>>| -> Vec<ObjectID> {
+            caps.into_iter().flatten().map(|cap| cap.id).collect()
+        };
+
+        match self {
+            AdminCap::MintCap => admin_objects
+                .into_iter()
+                .flat_map(|objects| objects.mint_caps.iter())
+                .map(|cap| cap.id)
+                .collect(),
+            AdminCap::TransferPolicyCap => cap_ids(
+                admin_objects.map(|objects| &objects.transfer_policy_caps),
+            ),
+            AdminCap::WithdrawPolicyCap => cap_ids(
+                admin_objects.map(|objects| &objects.withdraw_policy_caps),
+            ),
+            AdminCap::BorrowPolicyCap => cap_ids(
+                admin_objects.map(|objects| &objects.borrow_policy_caps),
+            ),
+            AdminCap::Publisher => project.publisher.into_iter().collect(),
+            AdminCap::UpgradeCap => project.upgrade_cap.into_iter().collect(),
+        }
+    }
+}
+
+impl FromStr for AdminCap {
+    type Err = RustSdkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mint-cap" => Ok(AdminCap::MintCap),
+            "transfer-policy-cap" => Ok(AdminCap::TransferPolicyCap),
+            "withdraw-policy-cap" => Ok(AdminCap::WithdrawPolicyCap),
+            "borrow-policy-cap" => Ok(AdminCap::BorrowPolicyCap),
+            "publisher" => Ok(AdminCap::Publisher),
+            "upgrade-cap" => Ok(AdminCap::UpgradeCap),
+            _ => Err(anyhow!(
+                "Unknown admin object '{s}', expected one of 'mint-cap', \
+                'transfer-policy-cap', 'withdraw-policy-cap', \
+                'borrow-policy-cap', 'publisher' or 'upgrade-cap'"
+            )
+            .into()),
+        }
+    }
+}
+
+/// Restriction of the upgrade policy of a package, which can only be
+/// tightened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradePolicy {
+    /// Upgrades may only add new functions and types
+    Additive,
+    /// Upgrades may only change the dependencies of the package
+    DepOnly,
+    /// The package can no longer be upgraded, its `UpgradeCap` is destroyed
+    Immutable,
+}
+
+impl UpgradePolicy {
+    /// Function of the `sui::package` module applying the restriction.
+    fn function(&self) -> &'static str {
+        match self {
+            UpgradePolicy::Additive => "only_additive_upgrades",
+            UpgradePolicy::DepOnly => "only_dep_upgrades",
+            UpgradePolicy::Immutable => "make_immutable",
+        }
+    }
+}
+
+impl FromStr for UpgradePolicy {
+    type Err = RustSdkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "additive" => Ok(UpgradePolicy::Additive),
+            "dep-only" => Ok(UpgradePolicy::DepOnly),
+            "immutable" => Ok(UpgradePolicy::Immutable),
+            _ => Err(anyhow!(
+                "Unknown upgrade policy '{s}', expected one of 'additive', \
+                'dep-only' or 'immutable'"
+            )
+            .into()),
+        }
+    }
+}
+
+impl fmt::Display for UpgradePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpgradePolicy::Additive => write!(f, "additive"),
+            UpgradePolicy::DepOnly => write!(f, "dep-only"),
+            UpgradePolicy::Immutable => write!(f, "immutable"),
+        }
+    }
+}
+
+/// Prepares transaction data for handing over admin objects.
+///
+/// The upgrade policy is restricted before the objects are transferred, and
+/// an `UpgradeCap` made immutable is destroyed rather than transferred.
+///
+/// # Type Parameters
+/// * `C` - The chain client type.
+///
+/// # Arguments
+/// * `client` - The chain client used to read the admin objects.
+/// * `sender` - The address owning the admin objects.
+/// * `recipient` - The address of the new custodian.
+/// * `objects` - The IDs of the admin objects to transfer.
+/// * `upgrade` - The `UpgradeCap` of the package and the restriction applied
+///   to its upgrade policy, if any.
+/// * `gas_coin` - The gas coin object reference.
+/// * `gas_budget` - The gas budget for the transaction.
+///
+/// # Returns
+/// A result containing the transaction data or a `RustSdkError` if any of the
+/// objects is not owned by the sender.
+#[allow(clippy::too_many_arguments)]
+pub async fn prepare_transfer_admin<C>(
+    client: &C,
+    sender: SuiAddress,
+    recipient: SuiAddress,
+    objects: &[ObjectID],
+    upgrade: Option<(ObjectID, UpgradePolicy)>,
+    gas_coin: ObjectRef,
+    gas_budget: u64,
+) -> Result<TransactionData, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
+    let mut ids: Vec<ObjectID> = objects.to_vec();
+    if let Some((upgrade_cap, _)) = upgrade {
+        ids.push(upgrade_cap);
+    }
+    ids.sort();
+    ids.dedup();
+
+    if ids.is_empty() {
+        return Err(anyhow!("There are no admin objects to hand over").into());
+    }
+
+    let gas_price = get_reference_gas_price(client).await?;
+    let object_refs = get_owned_object_refs(client, sender, ids).await?;
+    let object_ref = |id: ObjectID| -> Result<ObjectRef, RustSdkError> {
+        object_refs
+            .iter()
+            .find(|object_ref| object_ref.0 == id)
+            .copied()
+            .ok_or_else(|| {
+                anyhow!("Could not read the admin object {id}").into()
+            })
+    };
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+
+    if let Some((upgrade_cap, policy)) = upgrade {
+        let cap = builder
+            .obj(ObjectArg::ImmOrOwnedObject(object_ref(upgrade_cap)?))?;
+
+        builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            Identifier::new("package")?,
+            Identifier::new(policy.function())?,
+            vec![],
+            vec![cap],
+        );
+    }
+
+    let transferred = transferred_objects(objects, upgrade);
+
+    if !transferred.is_empty() {
+        let args = transferred
+            .iter()
+            .map(|id| {
+                Ok(builder
+                    .obj(ObjectArg::ImmOrOwnedObject(object_ref(*id)?))?)
+            })
+            .collect::<Result<Vec<_>, RustSdkError>>()?;
+
+        builder.transfer_args(recipient, args);
+    }
+
+    Ok(TransactionData::new_programmable(
+        sender,
+        vec![gas_coin], // Gas Objects
+        builder.finish(),
+        gas_budget,
+        gas_price,
+    ))
+}
+
+/// The admin objects of the given kinds still held by the project owner,
+/// leaving out those already handed over to a custodian.
+///
+/// # Arguments
+/// * `project` - The project recording the admin objects.
+/// * `caps` - The kinds of admin objects to select.
+///
+/// # Returns
+/// The IDs of the held objects, and of the objects already handed over along
+/// with their custodian.
+pub fn held_objects(
+    project: &Project,
+    caps: &[AdminCap],
+) -> (Vec<ObjectID>, Vec<(ObjectID, SuiAddress)>) {
+    let mut held: Vec<ObjectID> = Vec::new();
+    let mut handed_over: Vec<(ObjectID, SuiAddress)> = Vec::new();
+
+    for id in caps.iter().flat_map(|cap| cap.object_ids(project)) {
+        match project.custodians.get(&id) {
+            Some(custodian) => handed_over.push((id, *custodian)),
+            None => held.push(id),
+        }
+    }
+
+    (held, handed_over)
+}
+
+/// The admin objects transferred by a handover, which excludes an
+/// `UpgradeCap` made immutable.
+///
+/// # Arguments
+/// * `objects` - The IDs of the admin objects to hand over.
+/// * `upgrade` - The `UpgradeCap` of the package and the restriction applied
+///   to its upgrade policy, if any.
+///
+/// # Returns
+/// The IDs of the transferred objects, without duplicates.
+pub fn transferred_objects(
+    objects: &[ObjectID],
+    upgrade: Option<(ObjectID, UpgradePolicy)>,
+) -> Vec<ObjectID> {
+    let mut transferred: Vec<ObjectID> = Vec::new();
+
+    for id in objects {
+        let destroyed = matches!(
+            upgrade,
+            Some((upgrade_cap, UpgradePolicy::Immutable)) if upgrade_cap == *id
+        );
+
+        if !destroyed && !transferred.contains(id) {
+            transferred.push(*id);
+        }
+    }
+
+    transferred
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chain::fake::{coin, object, struct_type, FakeChainClient};
+    use crate::models::project::{AdminObjects, MintCap};
+    use crate::utils::get_coin_ref;
+    use sui_types::object::Owner;
+    use sui_types::transaction::{
+        Command, TransactionDataAPI, TransactionKind,
+    };
+
+    fn sender() -> SuiAddress {
+        SuiAddress::from(ObjectID::from_single_byte(0xa))
+    }
+
+    fn multisig() -> SuiAddress {
+        SuiAddress::from(ObjectID::from_single_byte(0xb))
+    }
+
+    fn chain() -> FakeChainClient {
+        FakeChainClient::new()
+            .with_object(object(
+                ObjectID::from_single_byte(1),
+                struct_type(
+                    "0x9::mint_cap::MintCap<0x1::suimarines::Suimarine>",
+                ),
+                Owner::AddressOwner(sender()),
+            ))
+            .with_object(object(
+                ObjectID::from_single_byte(2),
+                struct_type("0x2::package::UpgradeCap"),
+                Owner::AddressOwner(sender()),
+            ))
+            .with_object(object(
+                ObjectID::from_single_byte(3),
+                struct_type("0x2::package::Publisher"),
+                Owner::AddressOwner(multisig()),
+            ))
+    }
+
+    fn gas_coin() -> ObjectRef {
+        get_coin_ref(&coin(ObjectID::from_single_byte(9), 1_000_000))
+    }
+
+    #[test]
+    fn selects_admin_objects() {
+        let mut admin_objects = AdminObjects::empty();
+        admin_objects
+            .mint_caps
+            .push(MintCap::new(ObjectID::from_single_byte(1)));
+
+        let mut project = Project::new("Suimarines".to_string(), sender());
+        project.upgrade_cap = Some(ObjectID::from_single_byte(2));
+        project.admin_objects = Some(admin_objects);
+
+        assert_eq!(
+            AdminCap::MintCap.object_ids(&project),
+            vec![ObjectID::from_single_byte(1)]
+        );
+        assert_eq!(
+            AdminCap::UpgradeCap.object_ids(&project),
+            vec![ObjectID::from_single_byte(2)]
+        );
+        assert!(AdminCap::Publisher.object_ids(&project).is_empty());
+    }
+
+    #[test]
+    fn skips_objects_already_handed_over() {
+        let mut admin_objects = AdminObjects::empty();
+        admin_objects
+            .mint_caps
+            .push(MintCap::new(ObjectID::from_single_byte(1)));
+
+        let mut project = Project::new("Suimarines".to_string(), sender());
+        project.upgrade_cap = Some(ObjectID::from_single_byte(2));
+        project.admin_objects = Some(admin_objects);
+        project.record_custodian(&[ObjectID::from_single_byte(1)], multisig());
+
+        let (held, handed_over) = held_objects(&project, &AdminCap::ALL);
+
+        assert_eq!(held, vec![ObjectID::from_single_byte(2)]);
+        assert_eq!(
+            handed_over,
+            vec![(ObjectID::from_single_byte(1), multisig())]
+        );
+    }
+
+    #[tokio::test]
+    async fn prepares_handover() -> Result<(), RustSdkError> {
+        let mint_cap = ObjectID::from_single_byte(1);
+        let upgrade_cap = ObjectID::from_single_byte(2);
+
+        let tx_data = prepare_transfer_admin(
+            &chain(),
+            sender(),
+            multisig(),
+            &[mint_cap, upgrade_cap],
+            Some((upgrade_cap, UpgradePolicy::Additive)),
+            gas_coin(),
+            500_000,
+        )
+        .await?;
+
+        let TransactionKind::ProgrammableTransaction(pt) = tx_data.kind()
+        else {
+            panic!("Expected a programmable transaction");
+        };
+
+        // The policy is restricted before the cap is transferred
+        assert!(matches!(&pt.commands[0], Command::MoveCall(call)
+            if call.function.as_str() == "only_additive_upgrades"));
+        assert!(
+            matches!(&pt.commands[1], Command::TransferObjects(objects, _)
+            if objects.len() == 2)
+        );
+        assert_eq!(pt.commands.len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn destroys_upgrade_cap_made_immutable() -> Result<(), RustSdkError> {
+        let mint_cap = ObjectID::from_single_byte(1);
+        let upgrade_cap = ObjectID::from_single_byte(2);
+
+        let tx_data = prepare_transfer_admin(
+            &chain(),
+            sender(),
+            multisig(),
+            &[mint_cap, upgrade_cap],
+            Some((upgrade_cap, UpgradePolicy::Immutable)),
+            gas_coin(),
+            500_000,
+        )
+        .await?;
+
+        let TransactionKind::ProgrammableTransaction(pt) = tx_data.kind()
+        else {
+            panic!("Expected a programmable transaction");
+        };
+
+        assert!(matches!(&pt.commands[0], Command::MoveCall(call)
+            if call.function.as_str() == "make_immutable"));
+        assert!(
+            matches!(&pt.commands[1], Command::TransferObjects(objects, _)
+            if objects.len() == 1)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_objects_of_others() {
+        let publisher = ObjectID::from_single_byte(3);
+
+        assert!(prepare_transfer_admin(
+            &chain(),
+            sender(),
+            multisig(),
+            &[publisher],
+            None,
+            gas_coin(),
+            500_000,
+        )
+        .await
+        .is_err());
+    }
+}
@@ -0,0 +1,508 @@
+//! Sets up the listings of a collection on the OriginByte launchpad
+//!
+//! A listing is created along with its venues in a single programmable
+//! transaction, which also attaches a warehouse of the collection as the
+//! inventory sold by the venues, instead of being bundled up in the `init`
+//! function of the package.
+
+use anyhow::anyhow;
+use move_core_types::{identifier::Identifier, language_storage::StructTag};
+use sui_json_rpc_types::SuiObjectData;
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, ObjectType, SuiAddress},
+    parse_sui_type_tag,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{ObjectArg, TransactionData},
+    SUI_FRAMEWORK_PACKAGE_ID,
+};
+
+use crate::chain::ChainClient;
+use crate::err::RustSdkError;
+use crate::publish::read_objects;
+use crate::utils::{get_owned_object_refs, get_reference_gas_price};
+
+/// Kind of market selling the NFTs of a venue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketKind {
+    FixedPrice,
+    DutchAuction,
+}
+
+impl MarketKind {
+    /// Launchpad module creating the venues of the market.
+    fn module(&self) -> &'static str {
+        match self {
+            MarketKind::FixedPrice => "fixed_price",
+            MarketKind::DutchAuction => "dutch_auction",
+        }
+    }
+}
+
+/// Addresses of the launchpad package.
+///
+/// # Fields
+/// * `original_id` - The ID of the original package, which defines the types
+///   of the launchpad.
+/// * `published_at` - The ID of its latest version, whose functions are
+///   called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LaunchpadPackage {
+    pub original_id: ObjectID,
+    pub published_at: ObjectID,
+}
+
+/// Configuration of a venue of the listing.
+///
+/// # Fields
+/// * `kind` - The kind of market of the venue.
+/// * `token` - Fully qualified fungible token in which the price is
+///   denominated.
+/// * `price` - The fixed price, or the reserve price of a Dutch auction.
+/// * `is_whitelisted` - Whether buyers need a whitelist certificate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VenueConfig {
+    pub kind: MarketKind,
+    pub token: String,
+    pub price: u64,
+    pub is_whitelisted: bool,
+}
+
+/// Configuration of a listing.
+///
+/// # Fields
+/// * `admin` - The address administering the listing.
+/// * `receiver` - The address receiving the proceeds of the sales.
+/// * `venues` - The venues selling the inventory of the listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListingConfig {
+    pub admin: SuiAddress,
+    pub receiver: SuiAddress,
+    pub venues: Vec<VenueConfig>,
+}
+
+/// Objects created along with a listing.
+///
+/// # Fields
+/// * `listing` - The ID of the shared `Listing`.
+/// * `venues` - The IDs of its venues.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListingObjects {
+    pub listing: ObjectID,
+    pub venues: Vec<ObjectID>,
+}
+
+/// Prepares transaction data creating a listing on the launchpad.
+///
+/// The warehouse is attached to the listing as its inventory, and a venue is
+/// created on it for every market of the listing, before the listing is
+/// shared.
+///
+/// # Type Parameters
+/// * `C` - The chain client type.
+///
+/// # Arguments
+/// * `client` - The chain client used to read the warehouse.
+/// * `sender` - The address sending the transaction.
+/// * `launchpad` - The addresses of the launchpad package.
+/// * `nft_type` - The fully qualified type of the NFTs of the collection.
+/// * `listing` - The configuration of the listing.
+/// * `warehouse_id` - The ID of the warehouse, owned by the sender.
+/// * `start_sale` - Whether to turn the sale on in every venue.
+/// * `gas_coin` - The gas coin object reference.
+/// * `gas_budget` - The gas budget for the transaction.
+///
+/// # Returns
+/// A result containing the transaction data or a `RustSdkError`.
+#[allow(clippy::too_many_arguments)]
+pub async fn prepare_create_listing<C>(
+    client: &C,
+    sender: SuiAddress,
+    launchpad: LaunchpadPackage,
+    nft_type: &str,
+    listing: &ListingConfig,
+    warehouse_id: ObjectID,
+    start_sale: bool,
+    gas_coin: ObjectRef,
+    gas_budget: u64,
+) -> Result<TransactionData, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
+    if listing.venues.is_empty() {
+        return Err(anyhow!("The listing has no markets to sell from").into());
+    }
+
+    let gas_price = get_reference_gas_price(client).await?;
+    let warehouse_ref =
+        get_owned_object_refs(client, sender, vec![warehouse_id])
+            .await?
+            .remove(0);
+
+    let nft_type = parse_sui_type_tag(nft_type)?;
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+
+    let admin = builder.pure(listing.admin)?;
+    let receiver = builder.pure(listing.receiver)?;
+    let listing_arg = builder.programmable_move_call(
+        launchpad.published_at,
+        Identifier::new("listing")?,
+        Identifier::new("new")?,
+        vec![],
+        vec![admin, receiver],
+    );
+
+    let warehouse = builder.obj(ObjectArg::ImmOrOwnedObject(warehouse_ref))?;
+    let inventory_id = builder.programmable_move_call(
+        launchpad.published_at,
+        Identifier::new("listing")?,
+        Identifier::new("insert_warehouse")?,
+        vec![nft_type.clone()],
+        vec![listing_arg, warehouse],
+    );
+
+    for venue in listing.venues.iter() {
+        let token = parse_sui_type_tag(venue.token.as_str())
+            .map_err(|err| anyhow!("Invalid token {}: {err}", venue.token))?;
+
+        let is_whitelisted = builder.pure(venue.is_whitelisted)?;
+        let price = builder.pure(venue.price)?;
+        let venue_id = builder.programmable_move_call(
+            launchpad.published_at,
+            Identifier::new(venue.kind.module())?,
+            Identifier::new("create_venue")?,
+            vec![nft_type.clone(), token],
+            vec![listing_arg, inventory_id, is_whitelisted, price],
+        );
+
+        if start_sale {
+            builder.programmable_move_call(
+                launchpad.published_at,
+                Identifier::new("listing")?,
+                Identifier::new("sale_on")?,
+                vec![],
+                vec![listing_arg, venue_id],
+            );
+        }
+    }
+
+    let listing_type = parse_sui_type_tag(&format!(
+        "{}::listing::Listing",
+        launchpad.original_id.to_hex_literal()
+    ))?;
+    builder.programmable_move_call(
+        SUI_FRAMEWORK_PACKAGE_ID,
+        Identifier::new("transfer")?,
+        Identifier::new("public_share_object")?,
+        vec![listing_type],
+        vec![listing_arg],
+    );
+
+    Ok(TransactionData::new_programmable(
+        sender,
+        vec![gas_coin], // Gas Objects
+        builder.finish(),
+        gas_budget,
+        gas_price,
+    ))
+}
+
+/// Finds the listing and its venues among the objects created by the
+/// transaction setting up the listing.
+///
+/// # Type Parameters
+/// * `C` - The chain client type.
+///
+/// # Arguments
+/// * `client` - The chain client used to read the created objects.
+/// * `launchpad` - The addresses of the launchpad package.
+/// * `created` - The IDs of the objects created by the transaction.
+///
+/// # Returns
+/// A result containing the listing objects or a `RustSdkError` if no listing
+/// was created.
+pub async fn listing_objects<C>(
+    client: &C,
+    launchpad: LaunchpadPackage,
+    created: &[ObjectID],
+) -> Result<ListingObjects, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
+    let objects = read_objects(client, created).await?;
+
+    let listing = objects
+        .iter()
+        .find(|object| {
+            is_launchpad_type(object, launchpad, "listing", "Listing")
+        })
+        .map(|object| object.object_id)
+        .ok_or_else(|| anyhow!("Could not find the created listing"))?;
+
+    let venues = objects
+        .iter()
+        .filter(|object| is_launchpad_type(object, launchpad, "venue", "Venue"))
+        .map(|object| object.object_id)
+        .collect();
+
+    Ok(ListingObjects { listing, venues })
+}
+
+/// Whether the object is of the given type of the launchpad package, whose
+/// types keep the address of the original package across upgrades.
+fn is_launchpad_type(
+    object: &SuiObjectData,
+    launchpad: LaunchpadPackage,
+    module: &str,
+    name: &str,
+) -> bool {
+    let Some(ObjectType::Struct(move_object_type)) = object.type_.clone()
+    else {
+        return false;
+    };
+    let tag: StructTag = move_object_type.into();
+
+    ObjectID::from(tag.address) == launchpad.original_id
+        && tag.module.as_str() == module
+        && tag.name.as_str() == name
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chain::fake::{coin, object, struct_type, FakeChainClient};
+    use crate::utils::get_coin_ref;
+    use sui_types::base_types::SequenceNumber;
+    use sui_types::object::Owner;
+    use sui_types::transaction::{
+        Command, TransactionDataAPI, TransactionKind,
+    };
+
+    const NFT: &str = "0x1::suimarines::Suimarine";
+
+    fn sender() -> SuiAddress {
+        SuiAddress::from(ObjectID::from_single_byte(0xa))
+    }
+
+    // The launchpad was upgraded, its types keep the original address
+    fn launchpad() -> LaunchpadPackage {
+        LaunchpadPackage {
+            original_id: ObjectID::from_single_byte(0x9),
+            published_at: ObjectID::from_single_byte(0x7),
+        }
+    }
+
+    fn chain() -> FakeChainClient {
+        FakeChainClient::new().with_object(object(
+            ObjectID::from_single_byte(1),
+            struct_type(&format!("0x9::warehouse::Warehouse<{NFT}>")),
+            Owner::AddressOwner(sender()),
+        ))
+    }
+
+    fn listing() -> ListingConfig {
+        ListingConfig {
+            admin: sender(),
+            receiver: sender(),
+            venues: vec![
+                VenueConfig {
+                    kind: MarketKind::FixedPrice,
+                    token: "0x2::sui::SUI".to_string(),
+                    price: 100,
+                    is_whitelisted: false,
+                },
+                VenueConfig {
+                    kind: MarketKind::DutchAuction,
+                    token: "0x2::sui::SUI".to_string(),
+                    price: 50,
+                    is_whitelisted: true,
+                },
+            ],
+        }
+    }
+
+    fn gas_coin() -> ObjectRef {
+        get_coin_ref(&coin(ObjectID::from_single_byte(0xf), 1_000_000))
+    }
+
+    fn functions(tx_data: &TransactionData) -> Vec<String> {
+        let TransactionKind::ProgrammableTransaction(pt) = tx_data.kind()
+        else {
+            panic!("Expected a programmable transaction");
+        };
+
+        pt.commands
+            .iter()
+            .map(|command| match command {
+                Command::MoveCall(call) => {
+                    format!("{}::{}", call.module, call.function)
+                }
+                _ => panic!("Expected only move calls"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn prepares_listing() -> Result<(), RustSdkError> {
+        let tx_data = prepare_create_listing(
+            &chain(),
+            sender(),
+            launchpad(),
+            NFT,
+            &listing(),
+            ObjectID::from_single_byte(1),
+            true,
+            gas_coin(),
+            500_000,
+        )
+        .await?;
+
+        assert_eq!(
+            functions(&tx_data),
+            vec![
+                "listing::new",
+                "listing::insert_warehouse",
+                "fixed_price::create_venue",
+                "listing::sale_on",
+                "dutch_auction::create_venue",
+                "listing::sale_on",
+                "transfer::public_share_object",
+            ]
+        );
+
+        let tx_data = prepare_create_listing(
+            &chain(),
+            sender(),
+            launchpad(),
+            NFT,
+            &listing(),
+            ObjectID::from_single_byte(1),
+            false,
+            gas_coin(),
+            500_000,
+        )
+        .await?;
+
+        assert!(!functions(&tx_data).contains(&"listing::sale_on".to_string()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn calls_latest_launchpad_version() -> Result<(), RustSdkError> {
+        let tx_data = prepare_create_listing(
+            &chain(),
+            sender(),
+            launchpad(),
+            NFT,
+            &listing(),
+            ObjectID::from_single_byte(1),
+            true,
+            gas_coin(),
+            500_000,
+        )
+        .await?;
+
+        let TransactionKind::ProgrammableTransaction(pt) = tx_data.kind()
+        else {
+            panic!("Expected a programmable transaction");
+        };
+
+        let (share, calls) = pt.commands.split_last().unwrap();
+
+        for command in calls {
+            assert!(matches!(command, Command::MoveCall(call)
+                if call.package == launchpad().published_at));
+        }
+
+        // The shared listing is of the type of the original package
+        let listing_type = parse_sui_type_tag("0x9::listing::Listing")?;
+        assert!(matches!(share, Command::MoveCall(call)
+            if call.type_arguments == vec![listing_type]));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_warehouse_of_others() {
+        let chain = chain().with_object(object(
+            ObjectID::from_single_byte(2),
+            struct_type(&format!("0x9::warehouse::Warehouse<{NFT}>")),
+            Owner::AddressOwner(SuiAddress::from(ObjectID::from_single_byte(
+                0xb,
+            ))),
+        ));
+
+        assert!(prepare_create_listing(
+            &chain,
+            sender(),
+            launchpad(),
+            NFT,
+            &listing(),
+            ObjectID::from_single_byte(2),
+            false,
+            gas_coin(),
+            500_000,
+        )
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn finds_listing_objects() -> Result<(), RustSdkError> {
+        let id = ObjectID::from_single_byte;
+        let child = Owner::ObjectOwner(SuiAddress::from(id(5)));
+
+        let chain = FakeChainClient::new()
+            .with_object(object(
+                id(2),
+                struct_type("0x9::listing::Listing"),
+                Owner::Shared {
+                    initial_shared_version: SequenceNumber::from_u64(1),
+                },
+            ))
+            .with_object(object(
+                id(3),
+                struct_type("0x9::venue::Venue"),
+                child.clone(),
+            ))
+            .with_object(object(
+                id(4),
+                struct_type("0x9::venue::Venue"),
+                child.clone(),
+            ))
+            // Venues of another launchpad
+            .with_object(object(
+                id(6),
+                struct_type("0x8::venue::Venue"),
+                child.clone(),
+            ))
+            // Types never carry the address of an upgraded package
+            .with_object(object(
+                id(7),
+                struct_type("0x7::venue::Venue"),
+                child,
+            ));
+
+        let objects = listing_objects(
+            &chain,
+            launchpad(),
+            &[id(2), id(3), id(4), id(6), id(7)],
+        )
+        .await?;
+
+        assert_eq!(
+            objects,
+            ListingObjects {
+                listing: id(2),
+                venues: vec![id(3), id(4)],
+            }
+        );
+
+        assert!(listing_objects(&chain, launchpad(), &[id(3)])
+            .await
+            .is_err());
+
+        Ok(())
+    }
+}
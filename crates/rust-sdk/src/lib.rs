@@ -1,10 +1,16 @@
+pub mod chain;
 pub mod coin;
 pub mod collection_state;
 pub mod consts;
+pub mod custody;
 pub mod err;
+pub mod launchpad;
 pub mod metadata;
 pub mod mint;
 pub mod models;
 pub mod pay;
 pub mod publish;
+pub mod rpc;
+pub mod sync;
 pub mod utils;
+pub mod verify;
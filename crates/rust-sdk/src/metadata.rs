@@ -22,7 +22,7 @@ pub struct GlobalMetadata(pub DashMap<u32, Metadata>);
 pub struct StorableMetadata(pub BTreeMap<u32, Metadata>);
 
 /// Defines the metadata associated with an object.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Metadata {
     pub name: Option<String>,
     pub url: Option<Url>,
@@ -31,7 +31,7 @@ pub struct Metadata {
 }
 
 /// Represents a single trait in the metadata.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Trait {
     trait_type: String,
     value: String,
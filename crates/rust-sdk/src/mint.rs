@@ -1,20 +1,20 @@
 use crate::{
+    chain::ChainClient,
     err::{self, RustSdkError},
     metadata::Metadata,
     utils::{
-        execute_tx, gas_objects, get_context, get_reference_gas_price,
-        CloneClient, MoveType,
+        execute_tx, execute_tx_with_chain_client, gas_objects, get_context,
+        get_reference_gas_price, MoveType,
     },
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use move_core_types::identifier::Identifier;
-use std::{ops::Deref, str::FromStr, sync::Arc};
+use std::{str::FromStr, sync::Arc};
 use sui_json_rpc_types::{SuiExecutionStatus, SuiTransactionBlockResponse};
 use sui_json_rpc_types::{SuiObjectDataOptions, SuiTransactionBlockEffects};
 use sui_sdk::{
     types::base_types::{ObjectID, SuiAddress},
     wallet_context::WalletContext,
-    SuiClient,
 };
 use sui_types::{
     base_types::ObjectRef,
@@ -94,6 +94,9 @@ pub async fn create_warehouse(
 
 /// Prepares transaction data for creating a warehouse.
 ///
+/// # Type Parameters
+/// * `C` - The chain client type.
+///
 /// # Arguments
 /// * `client` - Client interface for interacting with Sui.
 /// * `sender` - The address sending the transaction.
@@ -104,14 +107,17 @@ pub async fn create_warehouse(
 ///
 /// # Returns
 /// A result containing the transaction data or an error.
-pub async fn prepare_create_warehouse(
-    client: impl Deref<Target = SuiClient>,
+pub async fn prepare_create_warehouse<C>(
+    client: &C,
     sender: SuiAddress,
     collection_type: MoveType,
     package_id: ObjectID,
     gas_coin: ObjectRef,
     gas_budget: u64,
-) -> Result<TransactionData, RustSdkError> {
+) -> Result<TransactionData, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
     let gas_price = get_reference_gas_price(client).await?;
 
     let collection_type = collection_type.write_type();
@@ -138,8 +144,13 @@ pub async fn prepare_create_warehouse(
 
 /// Handles the asynchronous minting of NFTs to a warehouse.
 ///
+/// # Type Parameters
+/// * `C` - The chain client type.
+///
 /// # Arguments
 /// * `data` - Data for minting NFTs.
+/// * `client` - The chain client the transaction is prepared and submitted
+///   through.
 /// * `wallet_ctx` - Context of the wallet.
 /// * `package_id` - The ID of the package.
 /// * `module_name` - The name of the module.
@@ -152,8 +163,9 @@ pub async fn prepare_create_warehouse(
 /// # Returns
 /// A join handle for the asynchronous task.
 #[allow(clippy::too_many_arguments)]
-pub async fn handle_mint_nfts_to_warehouse(
+pub async fn handle_mint_nfts_to_warehouse<C>(
     data: Vec<(u32, Metadata)>,
+    client: Arc<C>,
     wallet_ctx: Arc<WalletContext>,
     package_id: Arc<String>,
     module_name: Arc<String>,
@@ -162,10 +174,14 @@ pub async fn handle_mint_nfts_to_warehouse(
     sender: SuiAddress,
     warehouse: Arc<String>,
     mint_cap: Arc<String>,
-) -> JoinHandle<Result<MintEffect, RustSdkError>> {
+) -> JoinHandle<Result<MintEffect, RustSdkError>>
+where
+    C: ChainClient + 'static,
+{
     tokio::spawn(async move {
         mint_nfts_to_warehouse(
             data,
+            client,
             wallet_ctx,
             package_id,
             module_name,
@@ -184,8 +200,14 @@ pub async fn handle_mint_nfts_to_warehouse(
 /// This function manages the process of minting NFTs, including transaction
 /// preparation and execution.
 ///
+/// # Type Parameters
+/// * `C` - The chain client type. A `FailoverClient` retries transient RPC
+///   errors on its other endpoints.
+///
 /// # Arguments
 /// * `data` - A vector of tuples containing quantity and metadata for each NFT.
+/// * `client` - The chain client the transaction is prepared and submitted
+///   through.
 /// * `wallet_ctx` - The wallet context for transaction signing and management.
 /// * `package_id` - The ID of the package containing the minting logic.
 /// * `module_name` - The name of the module within the package.
@@ -199,8 +221,9 @@ pub async fn handle_mint_nfts_to_warehouse(
 /// A result containing the minting effect (success or error) or a
 /// `RustSdkError`.
 #[allow(clippy::too_many_arguments)]
-pub async fn mint_nfts_to_warehouse(
+pub async fn mint_nfts_to_warehouse<C>(
     data: Vec<(u32, Metadata)>,
+    client: Arc<C>,
     wallet_ctx: Arc<WalletContext>,
     package_id: Arc<String>,
     module_name: Arc<String>,
@@ -209,12 +232,13 @@ pub async fn mint_nfts_to_warehouse(
     sender: SuiAddress,
     warehouse: Arc<String>,
     mint_cap: Arc<String>,
-) -> Result<MintEffect, RustSdkError> {
-    let client = wallet_ctx.get_client().await?;
-
-    let data = prepare_mint_nfts_to_warehouse(
+) -> Result<MintEffect, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
+    let tx_data = prepare_mint_nfts_to_warehouse(
         data,
-        &client, // TODO: Should this be Arc instead?
+        client.as_ref(),
         package_id,
         module_name,
         gas_budget,
@@ -226,7 +250,9 @@ pub async fn mint_nfts_to_warehouse(
     .await?;
 
     // Execute the transaction.
-    let response = execute_tx(wallet_ctx, data).await?;
+    let response =
+        execute_tx_with_chain_client(client.as_ref(), wallet_ctx, tx_data)
+            .await?;
 
     Ok(handle_mint_effects(response, warehouse)?)
 }
@@ -237,8 +263,7 @@ pub async fn mint_nfts_to_warehouse(
 /// including the necessary arguments and gas settings.
 ///
 /// # Type Parameters
-/// * `C` - The client type, bounded by `Deref` targeting `SuiClient` and
-///   `CloneClient`.
+/// * `C` - The chain client type.
 ///
 /// # Arguments
 /// * `data` - A vector of tuples containing quantity and metadata for each NFT.
@@ -251,7 +276,7 @@ pub async fn mint_nfts_to_warehouse(
 #[allow(clippy::too_many_arguments)]
 pub async fn prepare_mint_nfts_to_warehouse<C>(
     mut data: Vec<(u32, Metadata)>,
-    client: C,
+    client: &C,
     package_id: Arc<String>,
     module_name: Arc<String>,
     gas_budget: u64,
@@ -261,7 +286,7 @@ pub async fn prepare_mint_nfts_to_warehouse<C>(
     mint_cap: Arc<String>,
 ) -> Result<TransactionData, RustSdkError>
 where
-    C: Deref<Target = SuiClient> + CloneClient,
+    C: ChainClient + ?Sized,
 {
     let package_id = ObjectID::from_str(package_id.as_str())
         .map_err(|err| err::object_id(err, package_id.as_str()))?;
@@ -270,28 +295,39 @@ where
     let mint_cap_id = ObjectID::from_str(mint_cap.as_str())
         .map_err(|err| err::object_id(err, mint_cap.as_str()))?;
 
-    let gas_price = get_reference_gas_price(client.clone_client()).await?;
+    let gas_price = get_reference_gas_price(client).await?;
 
     let mut builder = ProgrammableTransactionBuilder::new();
 
     let objs = client
-        .clone_client()
-        .read_api()
         .multi_get_object_with_options(
             vec![mint_cap_id, warehouse_id],
             SuiObjectDataOptions::full_content(),
         )
-        .await
-        .unwrap();
+        .await?;
+
+    let obj_refs = objs
+        .iter()
+        .map(|obj| {
+            obj.data
+                .as_ref()
+                .map(|obj_data| {
+                    (obj_data.object_id, obj_data.version, obj_data.digest)
+                })
+                .ok_or_else(|| {
+                    RustSdkError::AnyhowError(anyhow!(
+                        "Could not read the MintCap or Warehouse: {:?}",
+                        obj.error
+                    ))
+                })
+        })
+        .collect::<Result<Vec<ObjectRef>, RustSdkError>>()?;
 
     // Iterate over the entries and consume them
     while let Some((_index, nft_data)) = data.pop() {
         let mut args = nft_data.into_args()?;
-        objs.iter().for_each(|obj| {
-            let obj_data = obj.data.as_ref().unwrap();
-            let obj_ref: ObjectRef =
-                (obj_data.object_id, obj_data.version, obj_data.digest);
-            args.push(CallArg::Object(ObjectArg::ImmOrOwnedObject(obj_ref)));
+        obj_refs.iter().for_each(|obj_ref| {
+            args.push(CallArg::Object(ObjectArg::ImmOrOwnedObject(*obj_ref)));
         });
 
         builder.move_call(
@@ -476,3 +512,96 @@ pub fn handle_mint_effects(
 
 //     Ok(nfts)
 // }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chain::fake::{coin, object, struct_type, FakeChainClient};
+    use sui_types::transaction::{TransactionDataAPI, TransactionKind};
+
+    const MINT_CAP: &str = "0x2::mint_cap::MintCap<0x3::suimarines::Suimarine>";
+    const WAREHOUSE: &str =
+        "0x4::warehouse::Warehouse<0x3::suimarines::Suimarine>";
+
+    fn sender() -> SuiAddress {
+        SuiAddress::from(ObjectID::from_single_byte(0xa))
+    }
+
+    fn metadata(name: &str) -> Metadata {
+        Metadata {
+            name: Some(name.to_string()),
+            url: None,
+            description: None,
+            attributes: None,
+        }
+    }
+
+    /// Chain on which the sender owns the MintCap 1 and the Warehouse 2
+    fn chain() -> FakeChainClient {
+        FakeChainClient::new()
+            .with_gas_price(750)
+            .with_object(object(
+                ObjectID::from_single_byte(1),
+                struct_type(MINT_CAP),
+                Owner::AddressOwner(sender()),
+            ))
+            .with_object(object(
+                ObjectID::from_single_byte(2),
+                struct_type(WAREHOUSE),
+                Owner::AddressOwner(sender()),
+            ))
+    }
+
+    async fn prepare(
+        client: &FakeChainClient,
+    ) -> Result<TransactionData, RustSdkError> {
+        let gas_coin = coin(ObjectID::from_single_byte(9), 1_000_000);
+
+        prepare_mint_nfts_to_warehouse(
+            vec![(1, metadata("Suimarine #1")), (2, metadata("Suimarine #2"))],
+            client,
+            Arc::new(ObjectID::from_single_byte(3).to_string()),
+            Arc::new("suimarines".to_string()),
+            500_000,
+            Some(Arc::new(crate::utils::get_coin_ref(&gas_coin))),
+            sender(),
+            Arc::new(ObjectID::from_single_byte(2).to_string()),
+            Arc::new(ObjectID::from_single_byte(1).to_string()),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn prepares_mint_to_warehouse() -> Result<(), RustSdkError> {
+        let tx_data = prepare(&chain()).await?;
+
+        assert_eq!(tx_data.gas_price(), 750);
+        assert_eq!(tx_data.gas()[0].0, ObjectID::from_single_byte(9));
+
+        let TransactionKind::ProgrammableTransaction(pt) = tx_data.kind()
+        else {
+            panic!("Expected a programmable transaction");
+        };
+
+        // One call per NFT, each taking the MintCap and Warehouse
+        assert_eq!(pt.commands.len(), 2);
+        assert!(pt.inputs.iter().any(|input| matches!(
+            input,
+            CallArg::Object(ObjectArg::ImmOrOwnedObject((id, _, _)))
+                if *id == ObjectID::from_single_byte(2)
+        )));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_warehouse() {
+        let client = FakeChainClient::new().with_object(object(
+            ObjectID::from_single_byte(1),
+            struct_type(MINT_CAP),
+            Owner::AddressOwner(sender()),
+        ));
+
+        assert!(prepare(&client).await.is_err());
+    }
+}
@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use sui_sdk::types::base_types::{ObjectID, SuiAddress};
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::collection_state::ObjectType;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Project {
     pub name: String,
     pub project_owner: SuiAddress,
@@ -10,6 +13,10 @@ pub struct Project {
     pub upgrade_cap: Option<ObjectID>,
     pub admin_objects: Option<AdminObjects>,
     pub collection_objects: Option<CollectionObjects>,
+    // Admin objects handed over to another address, absent from states
+    // written by earlier versions
+    #[serde(default)]
+    pub custodians: BTreeMap<ObjectID, SuiAddress>,
 }
 
 impl Project {
@@ -22,16 +29,101 @@ impl Project {
             upgrade_cap: None,
             admin_objects: None,
             collection_objects: None,
+            custodians: BTreeMap::new(),
+        }
+    }
+
+    /// Records the new custodian of admin objects which were handed over.
+    ///
+    /// # Arguments
+    /// * `objects` - The IDs of the admin objects.
+    /// * `custodian` - The address now holding them.
+    pub fn record_custodian(
+        &mut self,
+        objects: &[ObjectID],
+        custodian: SuiAddress,
+    ) {
+        for object in objects {
+            self.custodians.insert(*object, custodian);
+        }
+    }
+
+    /// Records an object created when publishing the collection in the
+    /// admin or collection objects it belongs to.
+    ///
+    /// # Arguments
+    /// * `object` - The classified object.
+    pub fn record_object(&mut self, object: &ObjectType) {
+        match object.clone() {
+            ObjectType::Package(id) => self.package_id = Some(id),
+            ObjectType::UpgradeCap(id) => self.upgrade_cap = Some(id),
+            ObjectType::Publisher(id) => self.publisher = Some(id),
+            ObjectType::Display(id) => self.admin_objects().displays.push(id),
+            ObjectType::MintCap(id) => {
+                self.admin_objects().mint_caps.push(MintCap::new(id))
+            }
+            ObjectType::TransferPolicyCap { id, policy } => self
+                .admin_objects()
+                .transfer_policy_caps
+                .push(Cap::new(id, policy)),
+            ObjectType::WithdrawPolicyCap { id, policy } => self
+                .admin_objects()
+                .withdraw_policy_caps
+                .push(Cap::new(id, policy)),
+            ObjectType::BorrowPolicyCap { id, policy } => self
+                .admin_objects()
+                .borrow_policy_caps
+                .push(Cap::new(id, policy)),
+            ObjectType::Collection(id) => {
+                self.collection_objects().collection = Some(id)
+            }
+            ObjectType::Warehouse(id) => {
+                self.collection_objects().warehouses.push(id)
+            }
+            ObjectType::Orderbook(id) => {
+                self.collection_objects().orderbooks.push(id)
+            }
+            ObjectType::Allowlist(id) => {
+                self.collection_objects().allowlists.push(id)
+            }
+            ObjectType::BpsRoyaltyStrategy { id, bps } => {
+                self.collection_objects().royalty_bps =
+                    Some(RoyaltyBPS { id, bps })
+            }
+            ObjectType::TransferPolicy { id, rules } => self
+                .collection_objects()
+                .transfer_policy
+                .push(Policy { id, rules }),
+            ObjectType::WithdrawPolicy { id, rules } => self
+                .collection_objects()
+                .withdraw_policy
+                .push(Policy { id, rules }),
+            ObjectType::BorrowPolicy { id, rules } => self
+                .collection_objects()
+                .borrow_policy
+                .push(Policy { id, rules }),
         }
     }
+
+    fn admin_objects(&mut self) -> &mut AdminObjects {
+        self.admin_objects.get_or_insert(AdminObjects::empty())
+    }
+
+    fn collection_objects(&mut self) -> &mut CollectionObjects {
+        self.collection_objects
+            .get_or_insert(CollectionObjects::empty())
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct AdminObjects {
     pub mint_caps: Vec<MintCap>,
     pub transfer_policy_caps: Vec<Cap>,
     pub withdraw_policy_caps: Vec<Cap>,
     pub borrow_policy_caps: Vec<Cap>,
+    // Absent from states written by earlier versions
+    #[serde(default)]
+    pub displays: Vec<ObjectID>,
 }
 
 impl AdminObjects {
@@ -41,11 +133,12 @@ impl AdminObjects {
             transfer_policy_caps: vec![],
             withdraw_policy_caps: vec![],
             borrow_policy_caps: vec![],
+            displays: vec![],
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct CollectionObjects {
     pub collection: Option<ObjectID>,
     pub royalty_bps: Option<RoyaltyBPS>,
@@ -53,6 +146,9 @@ pub struct CollectionObjects {
     pub listing: Option<ObjectID>,
     pub warehouses: Vec<ObjectID>,
     pub venues: Vec<ObjectID>,
+    // Absent from states written by earlier versions
+    #[serde(default)]
+    pub orderbooks: Vec<ObjectID>,
     pub transfer_policy: Vec<Policy>,
     pub withdraw_policy: Vec<Policy>,
     pub borrow_policy: Vec<Policy>,
@@ -67,6 +163,7 @@ impl CollectionObjects {
             listing: None,
             warehouses: vec![],
             venues: vec![],
+            orderbooks: vec![],
             transfer_policy: vec![],
             withdraw_policy: vec![],
             borrow_policy: vec![],
@@ -74,19 +171,19 @@ impl CollectionObjects {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct RoyaltyBPS {
     pub id: ObjectID,
     pub bps: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Policy {
     pub id: ObjectID,
     pub rules: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct MintCap {
     pub id: ObjectID,
     // TODO: to add
@@ -99,8 +196,14 @@ impl MintCap {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Cap {
     pub id: ObjectID,
     pub object_id: ObjectID,
 }
+
+impl Cap {
+    pub fn new(id: ObjectID, object_id: ObjectID) -> Self {
+        Cap { id, object_id }
+    }
+}
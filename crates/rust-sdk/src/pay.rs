@@ -2,9 +2,7 @@ use crate::{
     coin::select_biggest_coin,
     consts::RECIPIENT_ADDRESS,
     err::RustSdkError,
-    utils::{
-        execute_tx, execute_tx_with_client, get_client_mainnet, get_context,
-    },
+    utils::{execute_tx, execute_tx_with_client, get_context},
 };
 use anyhow::Result;
 use std::str::FromStr;
@@ -12,23 +10,26 @@ use sui_json_rpc_types::SuiTransactionBlockEffects;
 use sui_sdk::{types::base_types::SuiAddress, SuiClient};
 use sui_types::transaction::TransactionData;
 
-/// Asynchronously pays a specified amount of SUI on mainnet
+/// Asynchronously pays a specified amount of SUI on the network of the
+/// client, regardless of the active network of the wallet.
 ///
 /// # Arguments
+/// * `client` - The Sui client of the network to pay on, e.g. built with
+///   `get_client` from the RPC URL of mainnet.
 /// * `amount` - The amount of SUI to be paid.
 /// * `gas_budget` - The budget for gas in the transaction.
+/// * `sender` - The address paying the amount.
 ///
 /// # Returns
 /// A result indicating success or an error (`RustSdkError`).
-pub async fn pay_mainnet(
+pub async fn pay_with_client(
+    client: &SuiClient,
     amount: u64,
     gas_budget: u64,
     sender: SuiAddress,
 ) -> Result<(), RustSdkError> {
-    let client = get_client_mainnet().await?;
-
-    let data = prepare_pay(&client, sender, amount, gas_budget).await?;
-    let response = execute_tx_with_client(&client, data, sender).await?;
+    let data = prepare_pay(client, sender, amount, gas_budget).await?;
+    let response = execute_tx_with_client(client, data, sender).await?;
 
     let SuiTransactionBlockEffects::V1(effects) = response.effects.unwrap();
     assert!(effects.status.is_ok());
@@ -1,23 +1,30 @@
 use anyhow::anyhow;
 use console::style;
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::env;
 use std::path::Path;
-use std::sync::mpsc::Sender;
 
 use sui_json_rpc_types::SuiTransactionBlockResponse;
-use sui_json_rpc_types::{OwnedObjectRef, SuiObjectDataOptions};
-use sui_move_build::BuildConfig;
-use sui_sdk::SuiClient;
+use sui_json_rpc_types::{
+    OwnedObjectRef, SuiObjectData, SuiObjectDataOptions, SuiParsedData,
+};
+use sui_move_build::{BuildConfig, CompiledPackage};
 use sui_types::base_types::{ObjectID, ObjectType, SuiAddress};
 use sui_types::{
     base_types::ObjectRef,
     programmable_transaction_builder::ProgrammableTransactionBuilder,
-    transaction::TransactionData,
+    transaction::TransactionData, SUI_FRAMEWORK_ADDRESS,
 };
 
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{StructTag, TypeTag},
+};
 use move_package::BuildConfig as MoveBuildConfig;
 use sui_move::build::resolve_lock_file_path;
 
+use crate::chain::ChainClient;
 use crate::consts::{PRICE_PUBLISH, RECIPIENT_ADDRESS};
 use crate::utils::{execute_tx, get_context};
 use crate::{collection_state::ObjectType as OBObjectType, err::RustSdkError};
@@ -208,6 +215,31 @@ fn get_dependencies(
     build_config: &MoveBuildConfig,
     package_path: &Path,
 ) -> Result<Vec<ObjectID>, RustSdkError> {
+    let compiled_package = compile_package(build_config, package_path)?;
+
+    let deps = compiled_package
+        .dependency_ids
+        .published
+        .iter()
+        .map(|t| *t.1)
+        .collect();
+
+    Ok(deps)
+}
+
+/// Compiles the package along with its dependencies, resolving them through
+/// the Move.lock of the package.
+///
+/// # Arguments
+/// * `build_config` - The build configuration for the Move package.
+/// * `package_path` - The directory path of the package.
+///
+/// # Returns
+/// A result containing the compiled package or a `RustSdkError`.
+pub(crate) fn compile_package(
+    build_config: &MoveBuildConfig,
+    package_path: &Path,
+) -> Result<CompiledPackage, RustSdkError> {
     let cur_dir = env::current_dir()
         .map_err(|_| anyhow!(r#"This error should be unreachable"#))?;
 
@@ -226,84 +258,481 @@ fn get_dependencies(
     }
     .build(package_path.to_path_buf())?;
 
-    let deps = compiled_package
-        .dependency_ids
-        .published
+    Ok(compiled_package)
+}
+
+/// Maximum number of objects read by a single request, as enforced by
+/// fullnodes.
+const MULTI_GET_LIMIT: usize = 50;
+
+/// Kind of the request policies created when publishing a collection.
+#[derive(Clone, Copy)]
+enum PolicyKind {
+    Transfer,
+    Withdraw,
+    Borrow,
+}
+
+/// Reads the objects created by the publish transaction and classifies them
+/// by their fully-qualified type.
+///
+/// Types which are generic over a collection, such as `MintCap<T>`, are only
+/// recognized if `T` belongs to the published package, and policy caps are
+/// matched with the policy they administer. Other objects are left out.
+///
+/// # Type Parameters
+/// * `C` - The chain client type.
+///
+/// # Arguments
+/// * `client` - The chain client used to read object data.
+/// * `objects` - The objects created by the publish transaction.
+///
+/// # Returns
+/// A result containing the recognized objects, in the order they were
+/// created, or a `RustSdkError` if any of them cannot be read.
+pub async fn classify_created_objects<C>(
+    client: &C,
+    objects: &[OwnedObjectRef],
+) -> Result<Vec<OBObjectType>, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
+    let ids: Vec<ObjectID> = objects
         .iter()
-        .map(|t| *t.1)
+        .map(|object| object.reference.object_id)
         .collect();
 
-    Ok(deps)
+    let data = read_objects(client, &ids).await?;
+
+    let package_id = data
+        .iter()
+        .find(|object| object.type_ == Some(ObjectType::Package))
+        .map(|object| object.object_id);
+
+    let Some(package_id) = package_id else {
+        return Ok(vec![]);
+    };
+
+    Ok(classify_objects(&data, package_id))
 }
 
-/// Prints information about an object.
+/// Reads the full content of the objects, in batches of `MULTI_GET_LIMIT`.
 ///
-/// This function retrieves the details of a specified object and sends its type
-/// to a channel.
+/// # Type Parameters
+/// * `C` - The chain client type.
 ///
 /// # Arguments
-/// * `client` - The Sui client used to read object data.
-/// * `object` - The reference to the owned object.
-/// * `tx` - The sender channel used to transmit the object type.
-pub async fn print_object(
-    client: &SuiClient,
-    object: &OwnedObjectRef,
-    tx: Sender<OBObjectType>,
-) {
-    let object_id = object.reference.object_id;
-
-    // get_owned_objects
-    let object_read = client
-        .read_api()
-        .get_object_with_options(
-            object_id,
-            SuiObjectDataOptions::full_content(),
-        )
-        .await
-        .unwrap();
-
-    // TODO: Add Publisher + UpgradeCap
-    if let Some(object_data) = object_read.data {
-        let obj_type = object_data.type_.unwrap();
-
-        match obj_type {
-            ObjectType::Struct(object_type) => {
-                if object_type.name().as_str() == "MintCap" {
-                    println!("Mint Cap: {}", object_id);
-                    tx.send(OBObjectType::MintCap(object_id)).unwrap();
-                }
-                if object_type.name().as_str() == "Collection" {
-                    println!("Collection: {}", object_id);
-                    tx.send(OBObjectType::Collection(object_id)).unwrap();
-                }
-                if object_type.name().as_str() == "BpsRoyaltyStrategy" {
-                    println!("BpsRoyaltyStrategy: {}", object_id);
-                    tx.send(OBObjectType::BpsRoyaltyStrategy(object_id))
-                        .unwrap();
-                }
-                if object_type.name().as_str() == "PolicyCap" {
-                    println!("PolicyCap: {}", object_id);
-                    tx.send(OBObjectType::PolicyCap(object_id)).unwrap();
-                }
-                if object_type.name().as_str() == "Policy" {
-                    // println!("{:?}", yooooh);
-                    println!("Policy: {}", object_id);
-                    tx.send(OBObjectType::Policy(object_id)).unwrap();
-                }
-                if object_type.name().as_str() == "TransferPolicy" {
-                    println!("TransferPolicy: {}", object_id);
-                    tx.send(OBObjectType::TransferPolicy(object_id)).unwrap();
+/// * `client` - The chain client used to read object data.
+/// * `ids` - The IDs of the objects to read.
+///
+/// # Returns
+/// A result containing the objects, in the order of their IDs, or a
+/// `RustSdkError` if any of them cannot be read.
+pub(crate) async fn read_objects<C>(
+    client: &C,
+    ids: &[ObjectID],
+) -> Result<Vec<SuiObjectData>, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
+    let mut data: Vec<SuiObjectData> = Vec::new();
+
+    for chunk in ids.chunks(MULTI_GET_LIMIT) {
+        let responses = client
+            .multi_get_object_with_options(
+                chunk.to_vec(),
+                SuiObjectDataOptions::full_content(),
+            )
+            .await?;
+
+        for response in responses {
+            data.push(response.data.ok_or_else(|| {
+                anyhow!("Could not read object: {:?}", response.error)
+            })?);
+        }
+    }
+
+    Ok(data)
+}
+
+/// Classifies objects of the package by their fully-qualified type, leaving
+/// out the ones which are not recognized.
+///
+/// # Arguments
+/// * `objects` - The objects, read along with their content.
+/// * `package_id` - The ID of the published package.
+///
+/// # Returns
+/// The recognized objects, in the order they were given.
+pub fn classify_objects(
+    objects: &[SuiObjectData],
+    package_id: ObjectID,
+) -> Vec<OBObjectType> {
+    // Policy caps only reference their policy by ID
+    let mut policies: BTreeMap<ObjectID, PolicyKind> = BTreeMap::new();
+    let mut classified = Vec::new();
+
+    for object in objects.iter() {
+        let object_type = classify_object(object, package_id);
+
+        match &object_type {
+            Some(OBObjectType::TransferPolicy { id, .. }) => {
+                policies.insert(*id, PolicyKind::Transfer);
+            }
+            Some(OBObjectType::WithdrawPolicy { id, .. }) => {
+                policies.insert(*id, PolicyKind::Withdraw);
+            }
+            Some(OBObjectType::BorrowPolicy { id, .. }) => {
+                policies.insert(*id, PolicyKind::Borrow);
+            }
+            _ => {}
+        }
+
+        classified.push((object, object_type));
+    }
+
+    classified
+        .into_iter()
+        .filter_map(|(object, object_type)| {
+            object_type.or_else(|| classify_policy_cap(object, &policies))
+        })
+        .collect()
+}
+
+/// Classifies an object created along with the package, except for the
+/// `PolicyCap` of OriginByte request policies.
+fn classify_object(
+    object: &SuiObjectData,
+    package_id: ObjectID,
+) -> Option<OBObjectType> {
+    let id = object.object_id;
+
+    let tag: StructTag = match object.type_.clone()? {
+        ObjectType::Package => return Some(OBObjectType::Package(id)),
+        ObjectType::Struct(move_object_type) => move_object_type.into(),
+    };
+
+    let is_framework = tag.address == SUI_FRAMEWORK_ADDRESS;
+    let of_package = tag
+        .type_params
+        .iter()
+        .any(|param| references_package(param, package_id));
+
+    let object_type = match (tag.module.as_str(), tag.name.as_str()) {
+        ("package", "UpgradeCap") if is_framework => {
+            OBObjectType::UpgradeCap(id)
+        }
+        ("package", "Publisher") if is_framework => OBObjectType::Publisher(id),
+        ("display", "Display") if is_framework && of_package => {
+            OBObjectType::Display(id)
+        }
+        ("transfer_policy", "TransferPolicy") if is_framework && of_package => {
+            OBObjectType::TransferPolicy {
+                id,
+                rules: rules(object),
+            }
+        }
+        ("transfer_policy", "TransferPolicyCap")
+            if is_framework && of_package =>
+        {
+            OBObjectType::TransferPolicyCap {
+                id,
+                policy: id_field(object, "policy_id")?,
+            }
+        }
+        ("collection", "Collection") if of_package => {
+            OBObjectType::Collection(id)
+        }
+        ("mint_cap", "MintCap") if of_package => OBObjectType::MintCap(id),
+        ("warehouse", "Warehouse") if of_package => OBObjectType::Warehouse(id),
+        ("orderbook", "Orderbook") if of_package => OBObjectType::Orderbook(id),
+        // Allowlists are not generic over the collections they authorize
+        ("allowlist", "Allowlist") => OBObjectType::Allowlist(id),
+        ("royalty_strategy_bps", "BpsRoyaltyStrategy") if of_package => {
+            OBObjectType::BpsRoyaltyStrategy {
+                id,
+                bps: number_field(object, "royalty_fee_bps").unwrap_or(0),
+            }
+        }
+        ("request", "Policy") if of_package => {
+            let rules = rules(object);
+
+            match request_kind(&tag)? {
+                PolicyKind::Transfer => {
+                    OBObjectType::TransferPolicy { id, rules }
                 }
-                if object_type.name().as_str() == "TransferPolicyCap" {
-                    println!("TransferPolicyCap: {}", object_id);
-                    tx.send(OBObjectType::TransferPolicyCap(object_id))
-                        .unwrap();
+                PolicyKind::Withdraw => {
+                    OBObjectType::WithdrawPolicy { id, rules }
                 }
-            }
-            ObjectType::Package => {
-                println!("Package object ID: {}", object_data.object_id);
-                tx.send(OBObjectType::Package(object_id)).unwrap();
+                PolicyKind::Borrow => OBObjectType::BorrowPolicy { id, rules },
             }
         }
+        _ => return None,
+    };
+
+    Some(object_type)
+}
+
+/// Classifies the `PolicyCap` of an OriginByte request policy by the kind of
+/// the policy it administers.
+fn classify_policy_cap(
+    object: &SuiObjectData,
+    policies: &BTreeMap<ObjectID, PolicyKind>,
+) -> Option<OBObjectType> {
+    let tag: StructTag = match object.type_.clone()? {
+        ObjectType::Struct(move_object_type) => move_object_type.into(),
+        ObjectType::Package => return None,
+    };
+
+    if (tag.module.as_str(), tag.name.as_str()) != ("request", "PolicyCap") {
+        return None;
+    }
+
+    let id = object.object_id;
+    let policy = id_field(object, "for")?;
+
+    Some(match policies.get(&policy)? {
+        PolicyKind::Transfer => OBObjectType::TransferPolicyCap { id, policy },
+        PolicyKind::Withdraw => OBObjectType::WithdrawPolicyCap { id, policy },
+        PolicyKind::Borrow => OBObjectType::BorrowPolicyCap { id, policy },
+    })
+}
+
+/// Whether the type is, or is parameterized by, a type of the package.
+fn references_package(type_tag: &TypeTag, package_id: ObjectID) -> bool {
+    match type_tag {
+        TypeTag::Struct(tag) => {
+            tag.address == AccountAddress::from(package_id)
+                || tag
+                    .type_params
+                    .iter()
+                    .any(|param| references_package(param, package_id))
+        }
+        TypeTag::Vector(type_tag) => references_package(type_tag, package_id),
+        _ => false,
+    }
+}
+
+/// Kind of a `Policy<WithNft<T, P>>` by its request marker `P`.
+fn request_kind(tag: &StructTag) -> Option<PolicyKind> {
+    let TypeTag::Struct(with_nft) = tag.type_params.first()? else {
+        return None;
+    };
+    let TypeTag::Struct(marker) = with_nft.type_params.last()? else {
+        return None;
+    };
+
+    match (marker.module.as_str(), marker.name.as_str()) {
+        ("transfer_request", "TRANSFER_REQ") => Some(PolicyKind::Transfer),
+        ("withdraw_request", "WITHDRAW_REQ") => Some(PolicyKind::Withdraw),
+        ("borrow_request", "BORROW_REQ") => Some(PolicyKind::Borrow),
+        _ => None,
+    }
+}
+
+/// Fields of the Move object, as JSON.
+pub(crate) fn fields(object: &SuiObjectData) -> Option<Value> {
+    match object.content.clone()? {
+        SuiParsedData::MoveObject(move_object) => {
+            Some(move_object.fields.to_json_value())
+        }
+        SuiParsedData::Package(_) => None,
+    }
+}
+
+/// Reads an `ID` field of the Move object.
+pub(crate) fn id_field(object: &SuiObjectData, name: &str) -> Option<ObjectID> {
+    let fields = fields(object)?;
+    let value = match &fields[name] {
+        Value::Object(uid) => uid.get("id")?.clone(),
+        value => value.clone(),
+    };
+
+    ObjectID::from_str(value.as_str()?).ok()
+}
+
+/// Reads an integer field of the Move object, which may be rendered as a
+/// string when it does not fit into a JSON number.
+fn number_field(object: &SuiObjectData, name: &str) -> Option<u64> {
+    match &fields(object)?[name] {
+        Value::Number(number) => number.as_u64(),
+        Value::String(number) => number.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Reads the rules of a policy, stored as a `VecSet<TypeName>`.
+fn rules(object: &SuiObjectData) -> Vec<String> {
+    let Some(fields) = fields(object) else {
+        return vec![];
+    };
+
+    fields["rules"]["contents"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|rule| match rule {
+            Value::String(name) => Some(name.clone()),
+            rule => rule["name"].as_str().map(String::from),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chain::fake::{
+        object, struct_type, with_fields, FakeChainClient,
+    };
+    use sui_json_rpc_types::{SuiMoveStruct, SuiMoveValue, SuiObjectRef};
+    use sui_types::{
+        base_types::{ObjectDigest, SequenceNumber},
+        object::Owner,
+    };
+
+    const NFT: &str = "0x1::suimarines::Suimarine";
+    const ROYALTY_RULE: &str =
+        "0x9::royalty_strategy_bps::BpsRoyaltyStrategyRule";
+
+    fn owner() -> SuiAddress {
+        SuiAddress::from(ObjectID::from_single_byte(0xa))
+    }
+
+    fn created(object_id: ObjectID) -> OwnedObjectRef {
+        OwnedObjectRef {
+            owner: Owner::AddressOwner(owner()),
+            reference: SuiObjectRef {
+                object_id,
+                version: SequenceNumber::from_u64(1),
+                digest: ObjectDigest::new([0; 32]),
+            },
+        }
+    }
+
+    fn id_value(id: u8) -> SuiMoveValue {
+        SuiMoveValue::Address(SuiAddress::from(ObjectID::from_single_byte(id)))
+    }
+
+    fn rules_value(rules: &[&str]) -> SuiMoveValue {
+        SuiMoveValue::Struct(SuiMoveStruct::WithFields(
+            [(
+                "contents".to_string(),
+                SuiMoveValue::Vector(
+                    rules
+                        .iter()
+                        .map(|rule| SuiMoveValue::String(rule.to_string()))
+                        .collect(),
+                ),
+            )]
+            .into_iter()
+            .collect(),
+        ))
+    }
+
+    /// Chain with the objects created by publishing the collection 0x1,
+    /// from 0x1 to 0x8
+    fn chain() -> FakeChainClient {
+        let owned = |id: u8, type_: &str| {
+            object(
+                ObjectID::from_single_byte(id),
+                struct_type(type_),
+                Owner::AddressOwner(owner()),
+            )
+        };
+        let withdraw_policy = format!(
+            "0x9::request::Policy<0x9::request::WithNft<{NFT}, {}>>",
+            "0x9::withdraw_request::WITHDRAW_REQ"
+        );
+
+        FakeChainClient::new()
+            .with_object(object(
+                ObjectID::from_single_byte(1),
+                ObjectType::Package,
+                Owner::Immutable,
+            ))
+            .with_object(with_fields(
+                owned(
+                    2,
+                    &format!("0x2::transfer_policy::TransferPolicy<{NFT}>"),
+                ),
+                vec![("rules", rules_value(&[ROYALTY_RULE]))],
+            ))
+            .with_object(with_fields(
+                owned(
+                    3,
+                    &format!("0x2::transfer_policy::TransferPolicyCap<{NFT}>"),
+                ),
+                vec![("policy_id", id_value(2))],
+            ))
+            .with_object(with_fields(
+                owned(4, &withdraw_policy),
+                vec![("rules", rules_value(&[]))],
+            ))
+            .with_object(with_fields(
+                owned(5, "0x9::request::PolicyCap"),
+                vec![("for", id_value(4))],
+            ))
+            .with_object(with_fields(
+                owned(
+                    6,
+                    &format!(
+                        "0x9::royalty_strategy_bps::BpsRoyaltyStrategy<{NFT}>"
+                    ),
+                ),
+                vec![("royalty_fee_bps", SuiMoveValue::Number(250))],
+            ))
+            .with_object(owned(7, "0x9::allowlist::Allowlist"))
+            // Not generic over a type of the published package
+            .with_object(owned(
+                8,
+                "0x9::orderbook::Orderbook<0x8::other::Other, 0x2::sui::SUI>",
+            ))
+    }
+
+    #[tokio::test]
+    async fn classifies_created_objects() -> Result<(), RustSdkError> {
+        let created: Vec<OwnedObjectRef> = (1..=8)
+            .map(|id| created(ObjectID::from_single_byte(id)))
+            .collect();
+
+        let objects = classify_created_objects(&chain(), &created).await?;
+        let id = ObjectID::from_single_byte;
+
+        assert_eq!(
+            objects,
+            vec![
+                OBObjectType::Package(id(1)),
+                OBObjectType::TransferPolicy {
+                    id: id(2),
+                    rules: vec![ROYALTY_RULE.to_string()],
+                },
+                OBObjectType::TransferPolicyCap {
+                    id: id(3),
+                    policy: id(2),
+                },
+                OBObjectType::WithdrawPolicy {
+                    id: id(4),
+                    rules: vec![],
+                },
+                OBObjectType::WithdrawPolicyCap {
+                    id: id(5),
+                    policy: id(4),
+                },
+                OBObjectType::BpsRoyaltyStrategy {
+                    id: id(6),
+                    bps: 250
+                },
+                OBObjectType::Allowlist(id(7)),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_unreadable_objects() {
+        let created = vec![created(ObjectID::from_single_byte(9))];
+
+        assert!(classify_created_objects(&chain(), &created).await.is_err());
     }
 }
@@ -0,0 +1,490 @@
+//! RPC endpoint failover
+//!
+//! A long mint or publish should not be aborted by a single rate limited or
+//! unavailable fullnode. `FailoverClient` sends requests to a list of RPC
+//! endpoints, retrying transient errors with backoff and failing over to the
+//! next endpoint in the list.
+
+use anyhow::anyhow;
+use console::style;
+use jsonrpsee::{core::Error as RpcError, http_client::transport};
+use std::{
+    future::Future,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+use sui_json_rpc_types::{
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_sdk::{error::Error as SuiSdkError, SuiClient};
+use sui_types::{
+    quorum_driver_types::ExecuteTransactionRequestType,
+    transaction::Transaction,
+};
+
+use crate::{chain::ChainClient, err::RustSdkError, utils::get_client};
+
+/// HTTP status codes of rate limited and unavailable endpoints, which are
+/// worth retrying.
+const TRANSIENT_STATUS_CODES: [u16; 4] = [429, 502, 503, 504];
+
+/// Policy for retrying requests that failed with a transient error.
+///
+/// # Fields
+/// * `max_attempts` - Attempts of a request across all endpoints, including
+///   the first one.
+/// * `initial_backoff` - Delay before the first retry, doubled on every
+///   further retry.
+/// * `max_backoff` - Upper bound of the delay between retries.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the given retry, counting from 1.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Whether the error is transient, such that the request may succeed when
+/// retried, possibly on another endpoint.
+///
+/// Timeouts, connection failures and rate limited or unavailable endpoints
+/// are transient, as are transactions whose execution could not be confirmed
+/// in time.
+pub fn is_transient(err: &RustSdkError) -> bool {
+    match err {
+        RustSdkError::SuiSdkError(SuiSdkError::RpcError(err)) => match err {
+            RpcError::RequestTimeout => true,
+            RpcError::Transport(err) => {
+                match err.downcast_ref::<transport::Error>() {
+                    Some(transport::Error::Rejected { status_code }) => {
+                        TRANSIENT_STATUS_CODES.contains(status_code)
+                    }
+                    Some(transport::Error::Http(_)) => true,
+                    _ => false,
+                }
+            }
+            _ => false,
+        },
+        RustSdkError::SuiSdkError(
+            SuiSdkError::FailToConfirmTransactionStatus(..),
+        ) => true,
+        _ => false,
+    }
+}
+
+struct Endpoint<C> {
+    url: String,
+    client: C,
+}
+
+/// Chain client over a list of RPC endpoints, in order of preference.
+///
+/// Requests go to the active endpoint, which is replaced by the next one in
+/// the list whenever it fails with a transient error.
+///
+/// # Type Parameters
+/// * `C` - The client of a single endpoint, `SuiClient` unless testing.
+pub struct FailoverClient<C = SuiClient> {
+    endpoints: Vec<Endpoint<C>>,
+    active: AtomicUsize,
+    policy: RetryPolicy,
+}
+
+impl FailoverClient<SuiClient> {
+    /// Connects to the RPC endpoints, skipping the ones that cannot be
+    /// reached.
+    ///
+    /// # Arguments
+    /// * `urls` - The URLs of the RPC endpoints, in order of preference.
+    /// * `policy` - The policy for retrying transient errors.
+    ///
+    /// # Returns
+    /// A result containing the client, or a `RustSdkError` if none of the
+    /// endpoints could be reached.
+    pub async fn new(
+        urls: &[String],
+        policy: RetryPolicy,
+    ) -> Result<Self, RustSdkError> {
+        let mut clients: Vec<(String, SuiClient)> = Vec::new();
+
+        for url in urls {
+            if clients.iter().any(|(connected, _)| connected == url) {
+                continue;
+            }
+
+            match get_client(url).await {
+                Ok(client) => clients.push((url.clone(), client)),
+                Err(err) => println!(
+                    "{} Skipping RPC endpoint {url}: {err}",
+                    style("WARNING").yellow().bold()
+                ),
+            }
+        }
+
+        if clients.is_empty() {
+            return Err(RustSdkError::AnyhowError(anyhow!(
+                "None of the RPC endpoints could be reached: {}",
+                urls.join(", ")
+            )));
+        }
+
+        Self::from_clients(clients, policy)
+    }
+
+    /// Checks every endpoint by reading its latest checkpoint, and makes the
+    /// first healthy endpoint the active one.
+    ///
+    /// # Returns
+    /// The URL of every endpoint along with its latest checkpoint, or the
+    /// error it failed with.
+    pub async fn check_health(
+        &self,
+    ) -> Vec<(String, Result<u64, RustSdkError>)> {
+        let mut health = Vec::new();
+
+        for endpoint in self.endpoints.iter() {
+            let checkpoint = endpoint
+                .client
+                .read_api()
+                .get_latest_checkpoint_sequence_number()
+                .await
+                .map_err(RustSdkError::from);
+
+            health.push((endpoint.url.clone(), checkpoint));
+        }
+
+        if let Some(index) =
+            health.iter().position(|(_, checkpoint)| checkpoint.is_ok())
+        {
+            self.active.store(index, Ordering::SeqCst);
+        }
+
+        health
+    }
+}
+
+impl<C> FailoverClient<C>
+where
+    C: ChainClient + Clone,
+{
+    /// Creates a client over endpoints that are already connected.
+    ///
+    /// # Arguments
+    /// * `clients` - The URLs of the RPC endpoints along with their clients,
+    ///   in order of preference.
+    /// * `policy` - The policy for retrying transient errors.
+    ///
+    /// # Returns
+    /// A result containing the client, or a `RustSdkError` if no endpoint
+    /// was given.
+    pub fn from_clients(
+        clients: Vec<(String, C)>,
+        policy: RetryPolicy,
+    ) -> Result<Self, RustSdkError> {
+        if clients.is_empty() {
+            return Err(RustSdkError::AnyhowError(anyhow!(
+                "No RPC endpoint was given"
+            )));
+        }
+
+        Ok(FailoverClient {
+            endpoints: clients
+                .into_iter()
+                .map(|(url, client)| Endpoint { url, client })
+                .collect(),
+            active: AtomicUsize::new(0),
+            policy,
+        })
+    }
+
+    /// The client of the active endpoint, for requests that are not retried.
+    pub fn client(&self) -> &C {
+        &self.endpoints[self.active.load(Ordering::SeqCst)].client
+    }
+
+    /// The URL of the active endpoint.
+    pub fn url(&self) -> &str {
+        &self.endpoints[self.active.load(Ordering::SeqCst)].url
+    }
+
+    /// Runs an idempotent read, retrying it with backoff on the next
+    /// endpoint whenever it fails with a transient error.
+    ///
+    /// # Arguments
+    /// * `read` - Builds the request from the client of an endpoint, and may
+    ///   thus be called once per attempt.
+    ///
+    /// # Returns
+    /// The outcome of the last attempt.
+    pub async fn read<T, F, Fut>(&self, read: F) -> Result<T, RustSdkError>
+    where
+        F: Fn(C) -> Fut,
+        Fut: Future<Output = Result<T, RustSdkError>>,
+    {
+        let mut retry = 0;
+
+        loop {
+            let index = self.active.load(Ordering::SeqCst);
+
+            match read(self.endpoints[index].client.clone()).await {
+                Err(err)
+                    if is_transient(&err)
+                        && retry + 1 < self.policy.max_attempts =>
+                {
+                    retry += 1;
+                    self.fail_over(index, &err);
+                    tokio::time::sleep(self.policy.backoff(retry)).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Submits a signed transaction, retrying it with backoff on the next
+    /// endpoint whenever it fails with a transient error.
+    ///
+    /// A transaction may have been executed even though its response was
+    /// lost, so its digest is looked up before it is submitted again, and
+    /// once more before giving up.
+    ///
+    /// # Arguments
+    /// * `tx` - The signed transaction.
+    ///
+    /// # Returns
+    /// A result containing the response of the executed transaction or a
+    /// `RustSdkError`.
+    pub async fn execute_transaction(
+        &self,
+        tx: Transaction,
+    ) -> Result<SuiTransactionBlockResponse, RustSdkError> {
+        self.execute_transaction_with_options(
+            tx,
+            SuiTransactionBlockResponseOptions::full_content(),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await
+    }
+
+    /// Submits a signed transaction like `execute_transaction`, requesting
+    /// the given content of the response.
+    ///
+    /// # Arguments
+    /// * `tx` - The signed transaction.
+    /// * `options` - The content of the response.
+    /// * `request_type` - Whether to wait for the effects certificate or for
+    ///   local execution.
+    ///
+    /// # Returns
+    /// A result containing the response of the executed transaction or a
+    /// `RustSdkError`.
+    pub async fn execute_transaction_with_options(
+        &self,
+        tx: Transaction,
+        options: SuiTransactionBlockResponseOptions,
+        request_type: Option<ExecuteTransactionRequestType>,
+    ) -> Result<SuiTransactionBlockResponse, RustSdkError> {
+        let digest = *tx.digest();
+        let mut retry = 0;
+
+        loop {
+            let index = self.active.load(Ordering::SeqCst);
+
+            let err = match self.endpoints[index]
+                .client
+                .execute_transaction_block(
+                    tx.clone(),
+                    options.clone(),
+                    request_type.clone(),
+                )
+                .await
+            {
+                Err(err) if is_transient(&err) => err,
+                result => return result,
+            };
+
+            retry += 1;
+            self.fail_over(index, &err);
+            tokio::time::sleep(self.policy.backoff(retry)).await;
+
+            if let Ok(response) = self
+                .client()
+                .get_transaction_with_options(digest, options.clone())
+                .await
+            {
+                return Ok(response);
+            }
+
+            if retry >= self.policy.max_attempts {
+                return Err(err);
+            }
+        }
+    }
+
+    /// Replaces the failed endpoint by the next one in the list, unless a
+    /// concurrent request already did.
+    fn fail_over(&self, failed: usize, err: &RustSdkError) {
+        let next = (failed + 1) % self.endpoints.len();
+
+        let switched = self
+            .active
+            .compare_exchange(failed, next, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok();
+
+        if switched && next != failed {
+            println!(
+                "{} RPC endpoint {} failed with \"{err}\", switching to {}",
+                style("WARNING").yellow().bold(),
+                self.endpoints[failed].url,
+                self.endpoints[next].url
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        chain::fake::{coin, rejected, FakeChainClient},
+        utils::get_coin_ref,
+    };
+    use std::sync::Arc;
+    use sui_types::{
+        base_types::{ObjectID, SuiAddress},
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        transaction::TransactionData,
+    };
+
+    /// Client over the fake endpoints, retrying without delay
+    fn failover(
+        endpoints: &[&Arc<FakeChainClient>],
+        max_attempts: u32,
+    ) -> FailoverClient<Arc<FakeChainClient>> {
+        let clients = endpoints
+            .iter()
+            .enumerate()
+            .map(|(index, client)| {
+                (format!("http://fullnode-{index}"), Arc::clone(client))
+            })
+            .collect();
+        let policy = RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        };
+
+        FailoverClient::from_clients(clients, policy).unwrap()
+    }
+
+    fn transaction() -> Transaction {
+        let sender = SuiAddress::from(ObjectID::from_single_byte(0xa));
+        let gas_coin = coin(ObjectID::from_single_byte(9), 1_000_000);
+
+        Transaction::from_data(
+            TransactionData::new_programmable(
+                sender,
+                vec![get_coin_ref(&gas_coin)],
+                ProgrammableTransactionBuilder::new().finish(),
+                500_000,
+                1_000,
+            ),
+            vec![],
+        )
+    }
+
+    async fn execute(
+        client: &FailoverClient<Arc<FakeChainClient>>,
+        tx: Transaction,
+    ) -> Result<SuiTransactionBlockResponse, RustSdkError> {
+        client
+            .execute_transaction_block(
+                tx,
+                SuiTransactionBlockResponseOptions::new(),
+                None,
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn fails_over_on_transient_errors() -> Result<(), RustSdkError> {
+        let first = Arc::new(FakeChainClient::new().with_gas_price(750));
+        let second = Arc::new(FakeChainClient::new().with_gas_price(900));
+        let client = failover(&[&first, &second], 3);
+
+        first.fail_next(rejected(503));
+        assert_eq!(client.get_reference_gas_price().await?, 900);
+        assert_eq!(client.url(), "http://fullnode-1");
+
+        // Other errors are returned as they are
+        second.fail_next(rejected(400));
+        assert!(client.get_reference_gas_price().await.is_err());
+        assert_eq!(client.url(), "http://fullnode-1");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn resubmits_transactions_not_found_on_chain(
+    ) -> Result<(), RustSdkError> {
+        let tx = transaction();
+        let digest = *tx.digest();
+        let first = Arc::new(FakeChainClient::new());
+        let second = Arc::new(FakeChainClient::new());
+        second.push_execution(SuiTransactionBlockResponse::new(digest));
+        let client = failover(&[&first, &second], 3);
+
+        first.fail_next(rejected(502));
+        assert_eq!(execute(&client, tx).await?.digest, digest);
+
+        assert!(first.executed().is_empty());
+        assert_eq!(second.executed().len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn does_not_resubmit_executed_transactions(
+    ) -> Result<(), RustSdkError> {
+        let tx = transaction();
+        let digest = *tx.digest();
+        let first = Arc::new(FakeChainClient::new());
+        // Executed although the response of the first endpoint was lost
+        let second = Arc::new(
+            FakeChainClient::new()
+                .with_transaction(SuiTransactionBlockResponse::new(digest)),
+        );
+        let client = failover(&[&first, &second], 3);
+
+        first.fail_next(rejected(504));
+        assert_eq!(execute(&client, tx.clone()).await?.digest, digest);
+        assert!(second.executed().is_empty());
+
+        // The digest is looked up even after the last attempt failed
+        let client = failover(&[&first, &second], 1);
+        first.fail_next(rejected(504));
+        assert_eq!(execute(&client, tx).await?.digest, digest);
+        assert!(second.executed().is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,372 @@
+//! Rebuilds the state of a project from the chain
+//!
+//! The objects of a collection are either owned by the project owner, such
+//! as the `MintCap` and the policy caps, or shared by the transaction which
+//! published the package, such as the `Collection` and its policies.
+
+use anyhow::anyhow;
+use sui_json_rpc_types::{
+    SuiObjectData, SuiObjectDataOptions, SuiObjectResponseQuery,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponseOptions,
+};
+use sui_types::{
+    base_types::{ObjectID, ObjectType, SuiAddress},
+    object::Owner,
+};
+
+use crate::chain::ChainClient;
+use crate::collection_state::ObjectType as OBObjectType;
+use crate::err::RustSdkError;
+use crate::models::project::{CollectionObjects, Project};
+use crate::publish::{classify_objects, fields, id_field, read_objects};
+
+/// Rebuilds the project of a published package from the objects owned by
+/// the project owner and the shared objects created along with the package.
+///
+/// Admin objects which were handed over are looked up in the addresses of
+/// their custodians.
+///
+/// # Type Parameters
+/// * `C` - The chain client type.
+///
+/// # Arguments
+/// * `client` - The chain client used to read object data.
+/// * `name` - The name of the NFT collection.
+/// * `owner` - The address of the project owner.
+/// * `custodians` - The addresses admin objects were handed over to.
+/// * `package_id` - The ID of the published package.
+///
+/// # Returns
+/// A result containing the rebuilt project or a `RustSdkError`.
+pub async fn sync_project<C>(
+    client: &C,
+    name: String,
+    owner: SuiAddress,
+    custodians: &[SuiAddress],
+    package_id: ObjectID,
+) -> Result<Project, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
+    let package = client
+        .get_object_with_options(
+            package_id,
+            SuiObjectDataOptions::new()
+                .with_type()
+                .with_previous_transaction(),
+        )
+        .await?
+        .data
+        .ok_or_else(|| anyhow!("Could not find package {package_id}"))?;
+
+    if package.type_ != Some(ObjectType::Package) {
+        return Err(anyhow!("Object {package_id} is not a package").into());
+    }
+
+    // Packages are immutable, hence they were last touched when published
+    let publish_digest = package.previous_transaction.ok_or_else(|| {
+        anyhow!("Could not find the transaction publishing {package_id}")
+    })?;
+
+    let publish_tx = client
+        .get_transaction_with_options(
+            publish_digest,
+            SuiTransactionBlockResponseOptions::new().with_effects(),
+        )
+        .await?;
+
+    let effects = publish_tx.effects.ok_or_else(|| {
+        anyhow!("Could not read the effects of transaction {publish_digest}")
+    })?;
+
+    let shared_ids: Vec<ObjectID> = effects
+        .created()
+        .iter()
+        .filter(|object| matches!(object.owner, Owner::Shared { .. }))
+        .map(|object| object.reference.object_id)
+        .collect();
+
+    let mut objects = vec![package];
+    objects.extend(read_objects(client, &shared_ids).await?);
+    objects.extend(owned_objects(client, owner).await?);
+
+    for custodian in custodians.iter().filter(|address| **address != owner) {
+        objects.extend(owned_objects(client, *custodian).await?);
+    }
+
+    Ok(rebuild_project(name, owner, package_id, &objects))
+}
+
+/// Rebuilds the project from the objects of the package, leaving out the
+/// ones which are not recognized. Objects held by another address than the
+/// project owner are recorded along with their custodian.
+///
+/// # Arguments
+/// * `name` - The name of the NFT collection.
+/// * `owner` - The address of the project owner.
+/// * `package_id` - The ID of the published package.
+/// * `objects` - The package and the objects around it, read along with
+///   their content.
+///
+/// # Returns
+/// The rebuilt project.
+pub fn rebuild_project(
+    name: String,
+    owner: SuiAddress,
+    package_id: ObjectID,
+    objects: &[SuiObjectData],
+) -> Project {
+    let mut project = Project::new(name, owner);
+
+    let object_by_id =
+        |id: ObjectID| objects.iter().find(|object| object.object_id == id);
+
+    for object_type in classify_objects(objects, package_id) {
+        // The owner may hold the caps of several packages
+        let of_package = match object_type {
+            OBObjectType::UpgradeCap(id) => object_by_id(id)
+                .and_then(|object| id_field(object, "package"))
+                .map_or(false, |id| id == package_id),
+            OBObjectType::Publisher(id) => object_by_id(id)
+                .and_then(publisher_package)
+                .map_or(false, |id| id == package_id),
+            _ => true,
+        };
+
+        if !of_package {
+            continue;
+        }
+
+        project.record_object(&object_type);
+
+        let holder =
+            object_by_id(object_type.id()).and_then(|object| object.owner);
+
+        match holder {
+            Some(Owner::AddressOwner(holder)) if holder != owner => {
+                project.record_custodian(&[object_type.id()], holder)
+            }
+            _ => {}
+        }
+    }
+
+    project
+}
+
+/// Carries the listing of the collection and its venues over from the
+/// previous state of the project, as the listing is shared after the package
+/// is published and the venues are its children, hence neither is found when
+/// rebuilding the project.
+///
+/// # Arguments
+/// * `project` - The rebuilt project.
+/// * `previous` - The previous state of the project.
+pub fn carry_over_listing(project: &mut Project, previous: &Project) {
+    let Some(previous) = previous.collection_objects.as_ref() else {
+        return;
+    };
+
+    if previous.listing.is_none() && previous.venues.is_empty() {
+        return;
+    }
+
+    let collection_objects = project
+        .collection_objects
+        .get_or_insert(CollectionObjects::empty());
+
+    collection_objects.listing = previous.listing;
+    collection_objects.venues = previous.venues.clone();
+}
+
+/// Reads every object owned by the address, along with its content.
+async fn owned_objects<C>(
+    client: &C,
+    owner: SuiAddress,
+) -> Result<Vec<SuiObjectData>, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
+    let mut objects = Vec::new();
+    let mut cursor = None;
+
+    loop {
+        let page = client
+            .get_owned_objects(
+                owner,
+                Some(SuiObjectResponseQuery::new(
+                    None,
+                    Some(SuiObjectDataOptions::full_content()),
+                )),
+                cursor,
+                None,
+            )
+            .await?;
+
+        objects.extend(page.data.into_iter().filter_map(|object| object.data));
+
+        if page.has_next_page {
+            cursor = page.next_cursor;
+        } else {
+            break;
+        }
+    }
+
+    Ok(objects)
+}
+
+/// Package of a `Publisher`, which stores its address as an ASCII string
+/// without the `0x` prefix.
+fn publisher_package(object: &SuiObjectData) -> Option<ObjectID> {
+    let fields = fields(object)?;
+    let package = fields["package"].as_str()?;
+
+    ObjectID::from_hex_literal(&format!(
+        "0x{}",
+        package.trim_start_matches("0x")
+    ))
+    .ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chain::fake::{object, struct_type, with_fields};
+    use crate::models::project::Cap;
+    use move_core_types::account_address::AccountAddress;
+    use sui_json_rpc_types::SuiMoveValue;
+    use sui_types::base_types::SequenceNumber;
+
+    const NFT: &str = "0x1::suimarines::Suimarine";
+
+    fn owner() -> SuiAddress {
+        SuiAddress::from(ObjectID::from_single_byte(0xa))
+    }
+
+    fn multisig() -> SuiAddress {
+        SuiAddress::from(ObjectID::from_single_byte(0xb))
+    }
+
+    fn owned_by(id: u8, type_: &str, holder: SuiAddress) -> SuiObjectData {
+        object(
+            ObjectID::from_single_byte(id),
+            struct_type(type_),
+            Owner::AddressOwner(holder),
+        )
+    }
+
+    fn owned(id: u8, type_: &str) -> SuiObjectData {
+        owned_by(id, type_, owner())
+    }
+
+    fn shared(id: u8, type_: &str) -> SuiObjectData {
+        object(
+            ObjectID::from_single_byte(id),
+            struct_type(type_),
+            Owner::Shared {
+                initial_shared_version: SequenceNumber::from_u64(1),
+            },
+        )
+    }
+
+    fn id_value(id: u8) -> SuiMoveValue {
+        SuiMoveValue::Address(SuiAddress::from(ObjectID::from_single_byte(id)))
+    }
+
+    #[test]
+    fn rebuilds_project() {
+        let id = ObjectID::from_single_byte;
+
+        let objects = vec![
+            object(id(1), ObjectType::Package, Owner::Immutable),
+            shared(2, &format!("0x9::collection::Collection<{NFT}>")),
+            owned(3, &format!("0x9::mint_cap::MintCap<{NFT}>")),
+            with_fields(
+                owned(4, "0x2::package::UpgradeCap"),
+                vec![("package", id_value(1))],
+            ),
+            with_fields(
+                owned(5, "0x2::package::Publisher"),
+                vec![(
+                    "package",
+                    SuiMoveValue::String(AccountAddress::from(id(1)).to_hex()),
+                )],
+            ),
+            shared(6, &format!("0x2::transfer_policy::TransferPolicy<{NFT}>")),
+            with_fields(
+                owned(
+                    7,
+                    &format!("0x2::transfer_policy::TransferPolicyCap<{NFT}>"),
+                ),
+                vec![("policy_id", id_value(6))],
+            ),
+            // Created after publishing the package
+            owned(8, &format!("0x9::warehouse::Warehouse<{NFT}>")),
+            // Handed over to a multisig
+            owned_by(11, &format!("0x9::mint_cap::MintCap<{NFT}>"), multisig()),
+            // Caps of another package of the owner
+            with_fields(
+                owned(9, "0x2::package::UpgradeCap"),
+                vec![("package", id_value(0xb))],
+            ),
+            owned(10, "0x9::mint_cap::MintCap<0xb::other::Other>"),
+        ];
+
+        let project =
+            rebuild_project("Suimarines".to_string(), owner(), id(1), &objects);
+
+        assert_eq!(project.package_id, Some(id(1)));
+        assert_eq!(project.upgrade_cap, Some(id(4)));
+        assert_eq!(project.publisher, Some(id(5)));
+
+        let admin_objects = project.admin_objects.unwrap();
+        let mint_caps: Vec<ObjectID> =
+            admin_objects.mint_caps.iter().map(|cap| cap.id).collect();
+        assert_eq!(mint_caps, vec![id(3), id(11)]);
+        assert_eq!(
+            admin_objects.transfer_policy_caps,
+            vec![Cap::new(id(7), id(6))]
+        );
+
+        let collection_objects = project.collection_objects.unwrap();
+        assert_eq!(collection_objects.collection, Some(id(2)));
+        assert_eq!(collection_objects.warehouses, vec![id(8)]);
+        assert_eq!(collection_objects.transfer_policy.len(), 1);
+        assert_eq!(collection_objects.transfer_policy[0].id, id(6));
+
+        assert_eq!(
+            project.custodians.into_iter().collect::<Vec<_>>(),
+            vec![(id(11), multisig())]
+        );
+    }
+    #[test]
+    fn carries_over_listing() {
+        let id = ObjectID::from_single_byte;
+
+        let mut previous = Project::new("Suimarines".to_string(), owner());
+        let collection_objects = previous
+            .collection_objects
+            .get_or_insert(CollectionObjects::empty());
+        collection_objects.collection = Some(id(2));
+        collection_objects.listing = Some(id(12));
+        collection_objects.venues = vec![id(13), id(14)];
+
+        let objects = vec![
+            object(id(1), ObjectType::Package, Owner::Immutable),
+            shared(2, &format!("0x9::collection::Collection<{NFT}>")),
+        ];
+
+        let mut project =
+            rebuild_project("Suimarines".to_string(), owner(), id(1), &objects);
+        carry_over_listing(&mut project, &previous);
+
+        assert_eq!(project.collection_objects, previous.collection_objects);
+
+        // Without collection objects rebuilt from the chain
+        let mut project = Project::new("Suimarines".to_string(), owner());
+        carry_over_listing(&mut project, &previous);
+
+        let collection_objects = project.collection_objects.unwrap();
+        assert_eq!(collection_objects.listing, Some(id(12)));
+        assert_eq!(collection_objects.venues, vec![id(13), id(14)]);
+    }
+}
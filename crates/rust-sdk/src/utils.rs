@@ -1,6 +1,5 @@
 use std::ops::Deref;
 use std::str::FromStr;
-use std::sync::Arc;
 use sui_types::gas_coin::GasCoin;
 
 use anyhow::{anyhow, Result};
@@ -18,20 +17,13 @@ use sui_sdk::wallet_context::WalletContext;
 use sui_sdk::{SuiClient, SuiClientBuilder};
 use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
 use sui_types::crypto::Signature;
+use sui_types::object::Owner;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
 use sui_types::transaction::{Transaction, TransactionData};
 
+use crate::chain::ChainClient;
 use crate::err::RustSdkError;
 
-pub async fn get_client_mainnet() -> Result<SuiClient, RustSdkError> {
-    let client_builder = SuiClientBuilder::default();
-    let client = client_builder
-        .build("https://fullnode.mainnet.sui.io:443")
-        .await?;
-
-    Ok(client)
-}
-
 /// Retrieves a Sui client for network interactions.
 ///
 /// # Arguments
@@ -63,14 +55,15 @@ pub async fn get_context() -> Result<WalletContext, RustSdkError> {
 /// Retrieves the reference gas price from the network.
 ///
 /// # Arguments
-/// * `client` - An instance of `SuiClient` for network interaction.
+/// * `client` - The chain client for network interaction.
 ///
 /// # Returns
 /// A result containing the reference gas price or a `RustSdkError`.
-pub async fn get_reference_gas_price(
-    client: impl Deref<Target = SuiClient>,
-) -> Result<u64, RustSdkError> {
-    let gas_price = client.read_api().get_reference_gas_price().await?;
+pub async fn get_reference_gas_price<C>(client: &C) -> Result<u64, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
+    let gas_price = client.get_reference_gas_price().await?;
 
     Ok(gas_price)
 }
@@ -81,8 +74,7 @@ pub async fn get_reference_gas_price(
 /// Retrieves all gas objects and their values for a given address.
 ///
 /// # Type Parameters
-/// * `C` - The client type, bounded by `Deref` targeting `SuiClient` and
-///   `CloneClient`.
+/// * `C` - The chain client type.
 ///
 /// # Arguments
 /// * `client` - The client used for network interactions.
@@ -92,17 +84,16 @@ pub async fn get_reference_gas_price(
 /// A result containing a vector of tuples with gas values and corresponding
 /// object data.
 pub async fn gas_objects<C>(
-    client: C,
+    client: &C,
     address: SuiAddress,
 ) -> Result<Vec<(u64, SuiObjectData)>>
 where
-    C: Deref<Target = SuiClient> + CloneClient,
+    C: ChainClient + ?Sized,
 {
     let mut objects: Vec<SuiObjectResponse> = Vec::new();
     let mut cursor = None;
     loop {
         let response = client
-            .read_api()
             .get_owned_objects(
                 address,
                 Some(SuiObjectResponseQuery::new(
@@ -136,6 +127,54 @@ where
     Ok(values_objects)
 }
 
+/// Reads the references of objects which must all be owned by the address,
+/// such as admin caps or warehouses passed to a transaction.
+///
+/// # Type Parameters
+/// * `C` - The chain client type.
+///
+/// # Arguments
+/// * `client` - The client used for network interactions.
+/// * `owner` - The Sui address expected to own the objects.
+/// * `ids` - The IDs of the objects.
+///
+/// # Returns
+/// A result containing the object references or a `RustSdkError`.
+pub async fn get_owned_object_refs<C>(
+    client: &C,
+    owner: SuiAddress,
+    ids: Vec<ObjectID>,
+) -> Result<Vec<ObjectRef>, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
+    let responses = client
+        .multi_get_object_with_options(
+            ids,
+            SuiObjectDataOptions::new().with_owner(),
+        )
+        .await?;
+
+    responses
+        .into_iter()
+        .map(|response| {
+            let object = response.data.ok_or_else(|| {
+                anyhow!("Could not read object: {:?}", response.error)
+            })?;
+
+            if object.owner != Some(Owner::AddressOwner(owner)) {
+                return Err(anyhow!(
+                    "Object {} is not owned by {owner}",
+                    object.object_id
+                )
+                .into());
+            }
+
+            Ok(object.object_ref())
+        })
+        .collect()
+}
+
 /// Retrieves the keystore from the local configuration.
 ///
 /// # Returns
@@ -278,12 +317,14 @@ pub async fn execute_tx(
     Ok(response)
 }
 
-pub async fn execute_tx_with_client(
-    // This way it works with both Arc<SuiClient> and &SuiClient
-    client: impl Deref<Target = SuiClient>,
+pub async fn execute_tx_with_client<C>(
+    client: &C,
     tx_data: TransactionData,
     sender: SuiAddress,
-) -> Result<SuiTransactionBlockResponse, RustSdkError> {
+) -> Result<SuiTransactionBlockResponse, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
     let keystore = get_keystore()?;
 
     // Sign transaction.
@@ -301,7 +342,6 @@ pub async fn execute_tx_with_client(
     );
 
     let response = client
-        .quorum_driver_api()
         .execute_transaction_block(
             Transaction::from_data(tx_data, signatures),
             SuiTransactionBlockResponseOptions::new(),
@@ -317,24 +357,56 @@ pub async fn execute_tx_with_client(
     Ok(response)
 }
 
-/// A trait to enable cloning of Sui client instances.
+/// Executes a transaction through a chain client, signing it with the
+/// active address of the wallet.
 ///
-/// This trait provides a method to clone instances of Sui clients. It's useful
-/// in contexts where a client instance needs to be reused or passed across
-/// different parts of an application, especially when dealing with `Arc` for
-/// shared ownership or references to the client.
-pub trait CloneClient {
-    fn clone_client(&self) -> Self;
-}
+/// Unlike `execute_tx`, the transaction is submitted through the given
+/// client, such that a `FailoverClient` retries transient RPC errors on the
+/// next endpoint without executing the transaction twice.
+///
+/// # Arguments
+/// * `client` - The chain client the transaction is submitted through.
+/// * `wallet_ctx` - The wallet context whose keystore signs the transaction.
+/// * `tx_data` - The transaction data to be executed.
+///
+/// # Returns
+/// A result containing the transaction block response
+/// (`SuiTransactionBlockResponse`) upon success, or a `RustSdkError` in case of
+/// failure.
+pub async fn execute_tx_with_chain_client<C>(
+    client: &C,
+    // This way it works with both Arc<WalletContext> and &WalletContext
+    wallet_ctx: impl Deref<Target = WalletContext>,
+    tx_data: TransactionData,
+) -> Result<SuiTransactionBlockResponse, RustSdkError>
+where
+    C: ChainClient + ?Sized,
+{
+    let keystore = &wallet_ctx.config.keystore;
+    let sender = wallet_ctx.config.active_address.unwrap();
 
-impl CloneClient for &SuiClient {
-    fn clone_client(&self) -> Self {
-        *self
-    }
-}
+    // Sign transaction.
+    let signature =
+        keystore.sign_secure(&sender, &tx_data, Intent::sui_transaction())?;
 
-impl CloneClient for Arc<SuiClient> {
-    fn clone_client(&self) -> Self {
-        Arc::clone(self)
-    }
+    // Execute the transaction.
+    println!(
+        "{} Sending and executing transaction.",
+        style("WIP").cyan().bold()
+    );
+
+    let response = client
+        .execute_transaction_block(
+            Transaction::from_data(tx_data, vec![signature]),
+            SuiTransactionBlockResponseOptions::full_content(),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+
+    println!(
+        "{} Sending and executing transaction.",
+        style("Done").cyan().bold()
+    );
+
+    Ok(response)
 }
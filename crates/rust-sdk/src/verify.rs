@@ -0,0 +1,160 @@
+use console::style;
+use move_core_types::account_address::AccountAddress;
+use move_package::BuildConfig as MoveBuildConfig;
+use std::collections::BTreeMap;
+use std::path::Path;
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiRawData};
+use sui_sdk::SuiClient;
+use sui_source_validation::BytecodeSourceVerifier;
+use sui_types::base_types::ObjectID;
+
+use crate::{err::RustSdkError, publish::compile_package};
+
+/// Outcome of verifying a package against its on-chain bytecode.
+///
+/// # Fields
+/// * `package_name` - The name of the compiled package.
+/// * `package_digest` - Hex digest of the compiled modules and the IDs of
+///   their dependencies, as computed by Sui for published packages.
+/// * `modules` - Names of the compiled modules.
+/// * `dependencies` - On-chain IDs of the published dependencies by package
+///   name.
+/// * `error` - Reason the verification failed, if it did.
+#[derive(Debug, Clone)]
+pub struct SourceVerification {
+    pub package_name: String,
+    pub package_digest: String,
+    pub modules: Vec<String>,
+    pub dependencies: BTreeMap<String, ObjectID>,
+    pub error: Option<String>,
+}
+
+impl SourceVerification {
+    /// Whether the package and its dependencies match the on-chain bytecode.
+    pub fn is_verified(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Rebuilds the package and verifies it, along with its dependencies,
+/// against the bytecode published on-chain.
+///
+/// A mismatch is not an error, it is recorded in the returned
+/// `SourceVerification` instead, such that it can be reported.
+///
+/// # Arguments
+/// * `client` - The Sui client used to fetch the on-chain packages.
+/// * `package_dir` - The directory path of the package to be verified.
+/// * `package_id` - The ID the package was published at.
+///
+/// # Returns
+/// A result containing the outcome of the verification or a `RustSdkError`
+/// if the package could not be built.
+pub async fn verify_source(
+    client: &SuiClient,
+    package_dir: &Path,
+    package_id: ObjectID,
+) -> Result<SourceVerification, RustSdkError> {
+    println!("{} Compiling contract", style("WIP").cyan().bold());
+
+    let compiled_package =
+        compile_package(&MoveBuildConfig::default(), package_dir)?;
+
+    println!("{} Compiling contract", style("DONE").green().bold());
+    println!("{} Verifying on-chain bytecode", style("WIP").cyan().bold());
+
+    let error = BytecodeSourceVerifier::new(client.read_api(), false)
+        .verify_package_root_and_deps(
+            &compiled_package,
+            AccountAddress::from(package_id),
+        )
+        .await
+        .err()
+        .map(|err| err.to_string());
+
+    println!(
+        "{} Verifying on-chain bytecode",
+        style("DONE").green().bold()
+    );
+
+    let package_digest = compiled_package
+        .get_package_digest(false)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    let modules = compiled_package
+        .get_modules()
+        .map(|module| module.self_id().name().to_string())
+        .collect();
+
+    let dependencies = compiled_package
+        .dependency_ids
+        .published
+        .iter()
+        .map(|(name, id)| (name.to_string(), *id))
+        .collect();
+
+    Ok(SourceVerification {
+        package_name: compiled_package
+            .package
+            .compiled_package_info
+            .package_name
+            .to_string(),
+        package_digest,
+        modules,
+        dependencies,
+        error,
+    })
+}
+
+/// Object as read from chain by `read_object`.
+#[derive(Debug, Clone)]
+pub enum ObjectRead {
+    /// A package, along with its version and the names of its modules
+    Package { version: u64, modules: Vec<String> },
+    /// Any other object, along with its type
+    Other(String),
+}
+
+/// Reads an object from chain, in order to check the packages referenced
+/// by a program registry.
+///
+/// # Arguments
+/// * `client` - The Sui client used to read the object.
+/// * `object_id` - The ID of the object.
+///
+/// # Returns
+/// A result containing the object, `None` if it does not exist, or a
+/// `RustSdkError` if the request failed.
+pub async fn read_object(
+    client: &SuiClient,
+    object_id: ObjectID,
+) -> Result<Option<ObjectRead>, RustSdkError> {
+    let response = client
+        .read_api()
+        .get_object_with_options(
+            object_id,
+            SuiObjectDataOptions::new().with_type().with_bcs(),
+        )
+        .await?;
+
+    // Objects that do not exist, or were deleted, come without data
+    let Some(data) = response.data else {
+        return Ok(None);
+    };
+
+    let object = match data.bcs {
+        Some(SuiRawData::Package(package)) => ObjectRead::Package {
+            version: data.version.value(),
+            modules: package.module_map.into_keys().collect(),
+        },
+        _ => ObjectRead::Other(
+            data.type_
+                .map(|object_type| object_type.to_string())
+                .unwrap_or_default(),
+        ),
+    };
+
+    Ok(Some(object))
+}